@@ -0,0 +1,95 @@
+//! # Address Registry
+//!
+//! Stable handles for addresses that must be used where only a `u64` or a
+//! `Symbol` fits, such as migration key lists. Each address gets the next
+//! handle the first time it is seen and keeps it; both directions are
+//! stored, so a handle always resolves to the exact address it was issued
+//! for:
+//!
+//! ```text
+//! AddressRegistryKey::Next             -> u64, next handle to issue
+//! AddressRegistryKey::Handle(Address)  -> u64
+//! AddressRegistryKey::Address(u64)     -> Address
+//! AddressRegistryKey::Symbol(Symbol)   -> u64
+//! ```
+//!
+//! The symbol form of handle `n` is `a<n>`, e.g. `a0`, `a17`.
+
+use soroban_sdk::{contracttype, Address, Env, Symbol};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AddressRegistryKey {
+    Next,
+    Handle(Address),
+    Address(u64),
+    Symbol(Symbol),
+}
+
+pub struct AddressRegistry;
+
+impl AddressRegistry {
+    /// Handle of `address`, issuing the next one if it has none yet.
+    pub fn to_handle(env: &Env, address: &Address) -> u64 {
+        let storage = env.storage().persistent();
+        let key = AddressRegistryKey::Handle(address.clone());
+        if let Some(handle) = storage.get(&key) {
+            return handle;
+        }
+        let handle: u64 = env.storage().instance().get(&AddressRegistryKey::Next).unwrap_or(0);
+        storage.set(&key, &handle);
+        storage.set(&AddressRegistryKey::Address(handle), address);
+        storage.set(&AddressRegistryKey::Symbol(Self::handle_symbol(env, handle)), &handle);
+        env.storage().instance().set(&AddressRegistryKey::Next, &(handle + 1));
+        handle
+    }
+
+    /// The address `handle` was issued for.
+    pub fn from_handle(env: &Env, handle: u64) -> Option<Address> {
+        env.storage().persistent().get(&AddressRegistryKey::Address(handle))
+    }
+
+    /// Handle of `address` if it was ever issued one; never issues.
+    pub fn lookup(env: &Env, address: &Address) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&AddressRegistryKey::Handle(address.clone()))
+    }
+
+    /// Symbol form of `address`'s handle, issuing one if needed.
+    pub fn to_symbol(env: &Env, address: &Address) -> Symbol {
+        Self::handle_symbol(env, Self::to_handle(env, address))
+    }
+
+    /// The address a `to_symbol` result was issued for.
+    pub fn from_symbol(env: &Env, symbol: &Symbol) -> Option<Address> {
+        let handle: u64 = env
+            .storage()
+            .persistent()
+            .get(&AddressRegistryKey::Symbol(symbol.clone()))?;
+        Self::from_handle(env, handle)
+    }
+
+    /// `a<handle>` as a `Symbol`.
+    pub fn handle_symbol(env: &Env, handle: u64) -> Symbol {
+        // 'a' plus at most 20 decimal digits
+        let mut buf = [0u8; 21];
+        let mut digits = [0u8; 20];
+        let mut len = 0;
+        let mut rest = handle;
+        loop {
+            digits[len] = b'0' + (rest % 10) as u8;
+            len += 1;
+            rest /= 10;
+            if rest == 0 {
+                break;
+            }
+        }
+        buf[0] = b'a';
+        for i in 0..len {
+            buf[1 + i] = digits[len - 1 - i];
+        }
+        // Only ASCII was written
+        Symbol::new(env, core::str::from_utf8(&buf[..=len]).unwrap())
+    }
+}
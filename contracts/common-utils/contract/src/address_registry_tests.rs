@@ -0,0 +1,49 @@
+#![cfg(test)]
+
+use crate::address_registry::AddressRegistry;
+use crate::CommonUtilsContract;
+use soroban_sdk::{testutils::Address as _, Address, Env, Symbol};
+
+#[test]
+fn test_round_trip_yields_identical_address() {
+    let env = Env::default();
+    let id = env.register(CommonUtilsContract, ());
+    env.as_contract(&id, || {
+        let address = Address::generate(&env);
+        let handle = AddressRegistry::to_handle(&env, &address);
+        assert_eq!(AddressRegistry::from_handle(&env, handle), Some(address.clone()));
+
+        let symbol = AddressRegistry::to_symbol(&env, &address);
+        assert_eq!(AddressRegistry::from_symbol(&env, &symbol), Some(address));
+    });
+}
+
+#[test]
+fn test_handles_are_stable_and_distinct() {
+    let env = Env::default();
+    let id = env.register(CommonUtilsContract, ());
+    env.as_contract(&id, || {
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        assert_eq!(AddressRegistry::lookup(&env, &alice), None);
+
+        let first = AddressRegistry::to_handle(&env, &alice);
+        let other = AddressRegistry::to_handle(&env, &bob);
+        assert_ne!(first, other);
+        assert_eq!(AddressRegistry::to_handle(&env, &alice), first);
+        assert_eq!(AddressRegistry::lookup(&env, &alice), Some(first));
+        assert_eq!(AddressRegistry::to_symbol(&env, &bob), AddressRegistry::handle_symbol(&env, other));
+    });
+}
+
+#[test]
+fn test_unknown_handles_resolve_to_none() {
+    let env = Env::default();
+    let id = env.register(CommonUtilsContract, ());
+    env.as_contract(&id, || {
+        assert_eq!(AddressRegistry::from_handle(&env, 7), None);
+        assert_eq!(AddressRegistry::from_symbol(&env, &Symbol::new(&env, "a7")), None);
+        assert_eq!(AddressRegistry::handle_symbol(&env, 0), Symbol::new(&env, "a0"));
+        assert_eq!(AddressRegistry::handle_symbol(&env, 1207), Symbol::new(&env, "a1207"));
+    });
+}
@@ -0,0 +1,71 @@
+//! # Admin Action Log
+//!
+//! Append-only record of privileged calls, for security review. Each entry
+//! names the action, the admin, the time and a hash of the call's
+//! parameters, so tooling can check what was passed without the log storing
+//! it. The newest `ADMIN_LOG_CAPACITY` entries are kept in a `RingBuffer`:
+//!
+//! ```text
+//! ("admin_log", 0)  -> RingBuffer<u32, AdminAction>
+//! AdminLogKey::Seq  -> u64, sequence of the next entry
+//! ```
+//!
+//! `seq` increases by one per entry and is never reused, so a gap or a
+//! repeat in a fetched log shows that entries were dropped or replayed.
+
+use soroban_sdk::{contracttype, symbol_short, xdr::ToXdr, Address, BytesN, Env, Symbol, Val, Vec};
+
+use crate::ring_buffer::RingBuffer;
+
+/// Most entries kept; older ones are overwritten
+pub const ADMIN_LOG_CAPACITY: u32 = 100;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AdminLogKey {
+    Seq,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminAction {
+    pub seq: u64,
+    pub action: Symbol,
+    pub admin: Address,
+    pub timestamp: u64,
+    /// SHA-256 of the XDR of the parameters, see `param_hash`
+    pub param_hash: BytesN<32>,
+}
+
+fn buffer() -> RingBuffer<u32, AdminAction> {
+    RingBuffer::new(symbol_short!("admin_log"), 0, ADMIN_LOG_CAPACITY)
+}
+
+/// Hash recorded for a call with `params`, in call order and excluding the
+/// admin.
+pub fn param_hash(env: &Env, params: &Vec<Val>) -> BytesN<32> {
+    env.crypto().sha256(&params.clone().to_xdr(env)).into()
+}
+
+/// Append an entry for `action`. Callers check authorisation first.
+pub fn record_admin_action(env: &Env, action: Symbol, admin: &Address, params: &Vec<Val>) {
+    let seq: u64 = env.storage().instance().get(&AdminLogKey::Seq).unwrap_or(0);
+    buffer().push(
+        env,
+        &AdminAction {
+            seq,
+            action,
+            admin: admin.clone(),
+            timestamp: env.ledger().timestamp(),
+            param_hash: param_hash(env, params),
+        },
+    );
+    env.storage().instance().set(&AdminLogKey::Seq, &(seq + 1));
+}
+
+/// The newest `limit` entries, oldest first.
+pub fn get_admin_log(env: &Env, limit: u32) -> Vec<AdminAction> {
+    let entries = buffer().iter(env);
+    let skip = entries.len().saturating_sub(limit);
+    entries.slice(skip..)
+}
@@ -0,0 +1,34 @@
+#![cfg(test)]
+
+use crate::admin_log::{get_admin_log, param_hash, record_admin_action, ADMIN_LOG_CAPACITY};
+use crate::CommonUtilsContract;
+use soroban_sdk::{symbol_short, testutils::Address as _, vec, Address, Env, IntoVal};
+
+#[test]
+fn test_entries_keep_sequence_past_capacity() {
+    let env = Env::default();
+    let id = env.register(CommonUtilsContract, ());
+    let admin = Address::generate(&env);
+    env.as_contract(&id, || {
+        for i in 0..(ADMIN_LOG_CAPACITY + 5) {
+            record_admin_action(&env, symbol_short!("set_x"), &admin, &vec![&env, i.into_val(&env)]);
+        }
+        let log = get_admin_log(&env, ADMIN_LOG_CAPACITY * 2);
+        assert_eq!(log.len(), ADMIN_LOG_CAPACITY);
+        assert_eq!(log.get(0).unwrap().seq, 5);
+        assert_eq!(log.last().unwrap().seq, (ADMIN_LOG_CAPACITY + 4) as u64);
+
+        let last = get_admin_log(&env, 1).get(0).unwrap();
+        let params = vec![&env, (ADMIN_LOG_CAPACITY + 4).into_val(&env)];
+        assert_eq!(last.param_hash, param_hash(&env, &params));
+    });
+}
+
+#[test]
+fn test_param_hash_depends_on_values_and_order() {
+    let env = Env::default();
+    let a = param_hash(&env, &vec![&env, 1u32.into_val(&env), 2u32.into_val(&env)]);
+    let b = param_hash(&env, &vec![&env, 2u32.into_val(&env), 1u32.into_val(&env)]);
+    assert_ne!(a, b);
+    assert_eq!(a, param_hash(&env, &vec![&env, 1u32.into_val(&env), 2u32.into_val(&env)]));
+}
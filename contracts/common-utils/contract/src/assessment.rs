@@ -0,0 +1,104 @@
+//! Agent Assessment Aggregator
+//! Combines fraud-detect and risk-eval readings for an agent in one call.
+//! Both dependencies are resolved through the [`ServiceRegistry`]; a missing
+//! registration, a failing call or an empty reading yields the default for
+//! that field instead of aborting the assessment.
+//!
+//! [`ServiceRegistry`]: crate::service_registry::ServiceRegistry
+
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, IntoVal, Symbol, TryFromVal, Val, Vec};
+use crate::dex::fraud_indicators::RiskLevel;
+use crate::error::CommonError;
+use crate::service_registry::{resolve_service, SERVICE_FRAUD_DETECT, SERVICE_RISK_EVAL};
+
+/// Combined view of one agent
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AgentAssessment {
+    /// Latest fraud-detect score, 0 when unavailable
+    pub fraud_score: u32,
+    /// Latest risk-eval level, `None` when unavailable
+    pub risk_level: Option<RiskLevel>,
+    /// Whether fraud-detect has flagged the agent's account
+    pub flagged: bool,
+}
+
+/// Agent to assess: fraud-detect keys reports by symbol, risk-eval by address
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AgentRef {
+    pub agent_id: Symbol,
+    pub agent_addr: Address,
+}
+
+#[contracttype]
+pub enum AssessmentKey {
+    Registry,
+}
+
+#[contract]
+pub struct AgentAssessor;
+
+#[contractimpl]
+impl AgentAssessor {
+    /// Initialize with the registry used to find fraud-detect and risk-eval
+    pub fn initialize(env: Env, registry: Address) -> Result<(), CommonError> {
+        if env.storage().instance().has(&AssessmentKey::Registry) {
+            return Err(CommonError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&AssessmentKey::Registry, &registry);
+        Ok(())
+    }
+
+    pub fn assess_agent(env: Env, agent_id: Symbol, agent_addr: Address) -> AgentAssessment {
+        let registry: Option<Address> = env.storage().instance().get(&AssessmentKey::Registry);
+        let fraud = registry
+            .as_ref()
+            .and_then(|registry| resolve_service(&env, registry, SERVICE_FRAUD_DETECT));
+        let risk = registry
+            .as_ref()
+            .and_then(|registry| resolve_service(&env, registry, SERVICE_RISK_EVAL));
+
+        let fraud_score = fraud
+            .as_ref()
+            .and_then(|fraud| try_call::<u32>(&env, fraud, "get_latest_score", agent_id.into_val(&env)))
+            .unwrap_or(0);
+        let flagged = fraud
+            .as_ref()
+            .and_then(|fraud| try_call::<bool>(&env, fraud, "is_flagged", agent_addr.clone().into_val(&env)))
+            .unwrap_or(false);
+        let risk_level = risk
+            .as_ref()
+            .and_then(|risk| try_call::<Option<RiskLevel>>(&env, risk, "get_risk", agent_addr.into_val(&env)))
+            .flatten();
+
+        AgentAssessment {
+            fraud_score,
+            risk_level,
+            flagged,
+        }
+    }
+
+    /// `assess_agent` for several agents, in input order
+    pub fn assess_agents(env: Env, agents: Vec<AgentRef>) -> Vec<AgentAssessment> {
+        let mut out = Vec::new(&env);
+        for agent in agents.iter() {
+            out.push_back(Self::assess_agent(env.clone(), agent.agent_id, agent.agent_addr));
+        }
+        out
+    }
+}
+
+/// Invoke a single-argument reader, returning `None` on any failure
+fn try_call<T>(env: &Env, contract: &Address, func: &str, arg: Val) -> Option<T>
+where
+    T: TryFromVal<Env, Val>,
+{
+    env.try_invoke_contract::<T, soroban_sdk::Error>(
+        contract,
+        &Symbol::new(env, func),
+        soroban_sdk::vec![env, arg],
+    )
+    .ok()
+    .and_then(|result| result.ok())
+}
@@ -0,0 +1,132 @@
+#![cfg(test)]
+
+use crate::assessment::{AgentAssessment, AgentAssessor, AgentAssessorClient, AgentRef};
+use crate::dex::fraud_indicators::RiskLevel;
+use crate::service_registry::{ServiceRegistry, ServiceRegistryClient, SERVICE_FRAUD_DETECT, SERVICE_RISK_EVAL};
+use soroban_sdk::{contract, contractimpl, symbol_short, testutils::Address as _, vec, Address, Env, Symbol};
+
+#[contract]
+pub struct MockFraud;
+
+#[contractimpl]
+impl MockFraud {
+    pub fn get_latest_score(_env: Env, agent_id: Symbol) -> u32 {
+        if agent_id == symbol_short!("bad") {
+            92
+        } else {
+            5
+        }
+    }
+
+    pub fn is_flagged(env: Env, account: Address) -> bool {
+        env.storage().instance().get(&account).unwrap_or(false)
+    }
+
+    pub fn flag(env: Env, account: Address) {
+        env.storage().instance().set(&account, &true);
+    }
+}
+
+#[contract]
+pub struct MockRisk;
+
+#[contractimpl]
+impl MockRisk {
+    pub fn get_risk(env: Env, agent: Address) -> Option<RiskLevel> {
+        env.storage().instance().get(&agent)
+    }
+
+    pub fn set_risk(env: Env, agent: Address, level: RiskLevel) {
+        env.storage().instance().set(&agent, &level);
+    }
+}
+
+struct Setup<'a> {
+    env: Env,
+    assessor: AgentAssessorClient<'a>,
+    registry: ServiceRegistryClient<'a>,
+    admin: Address,
+}
+
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let registry_id = env.register(ServiceRegistry, ());
+    let registry = ServiceRegistryClient::new(&env, &registry_id);
+    let admin = Address::generate(&env);
+    registry.initialize(&admin);
+
+    let assessor_id = env.register(AgentAssessor, ());
+    let assessor = AgentAssessorClient::new(&env, &assessor_id);
+    assessor.initialize(&registry_id);
+
+    Setup { env, assessor, registry, admin }
+}
+
+#[test]
+fn test_assessment_combines_both_contracts() {
+    let s = setup();
+    let fraud_id = s.env.register(MockFraud, ());
+    let risk_id = s.env.register(MockRisk, ());
+    s.registry
+        .register_service(&s.admin, &Symbol::new(&s.env, SERVICE_FRAUD_DETECT), &fraud_id);
+    s.registry
+        .register_service(&s.admin, &Symbol::new(&s.env, SERVICE_RISK_EVAL), &risk_id);
+
+    let agent = Address::generate(&s.env);
+    MockFraudClient::new(&s.env, &fraud_id).flag(&agent);
+    MockRiskClient::new(&s.env, &risk_id).set_risk(&agent, &RiskLevel::High);
+
+    let assessment = s.assessor.assess_agent(&symbol_short!("bad"), &agent);
+    assert_eq!(
+        assessment,
+        AgentAssessment {
+            fraud_score: 92,
+            risk_level: Some(RiskLevel::High),
+            flagged: true,
+        }
+    );
+}
+
+#[test]
+fn test_missing_dependencies_yield_defaults() {
+    let s = setup();
+    let agent = Address::generate(&s.env);
+
+    let assessment = s.assessor.assess_agent(&symbol_short!("bad"), &agent);
+    assert_eq!(
+        assessment,
+        AgentAssessment {
+            fraud_score: 0,
+            risk_level: None,
+            flagged: false,
+        }
+    );
+}
+
+#[test]
+fn test_partial_configuration_and_batch() {
+    let s = setup();
+    let fraud_id = s.env.register(MockFraud, ());
+    // Risk resolves to a contract that doesn't implement `get_risk`
+    s.registry
+        .register_service(&s.admin, &Symbol::new(&s.env, SERVICE_FRAUD_DETECT), &fraud_id);
+    s.registry
+        .register_service(&s.admin, &Symbol::new(&s.env, SERVICE_RISK_EVAL), &fraud_id);
+
+    let good = AgentRef {
+        agent_id: symbol_short!("good"),
+        agent_addr: Address::generate(&s.env),
+    };
+    let bad = AgentRef {
+        agent_id: symbol_short!("bad"),
+        agent_addr: Address::generate(&s.env),
+    };
+
+    let results = s.assessor.assess_agents(&vec![&s.env, good, bad]);
+    assert_eq!(results.len(), 2);
+    assert_eq!(results.get(0).unwrap().fraud_score, 5);
+    assert_eq!(results.get(1).unwrap().fraud_score, 92);
+    assert!(results.iter().all(|a| a.risk_level.is_none() && !a.flagged));
+}
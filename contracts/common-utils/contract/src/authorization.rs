@@ -1,7 +1,7 @@
 #![no_std]
 
 use soroban_sdk::{
-    contracttype, Address, Env, Bytes, BytesN, Symbol, Vec, Map, panic_with_error
+    contracttype, symbol_short, Address, Env, Bytes, BytesN, Symbol, Vec, Map, panic_with_error
 };
 use crate::error::ContractError;
 
@@ -201,6 +201,36 @@ impl IAuthorizable for RoleBasedAuth {
     }
 }
 
+/// Signature algorithm of a registered verification key
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SignatureScheme {
+    /// 32-byte public key, 64-byte signature over the raw payload
+    Ed25519,
+    /// 65-byte uncompressed SEC1 public key, 64-byte `r || s` (low-S)
+    /// signature over the SHA-256 of the payload
+    Secp256k1,
+}
+
+/// Largest `s` a secp256k1 signature may carry: half the group order
+const SECP256K1_HALF_ORDER: [u8; 32] = [
+    0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xff, 0xff, 0xff, 0xff, 0xff, 0x5d, 0x57, 0x6e, 0x73, 0x57, 0xa4,
+    0x50, 0x1d, 0xdf, 0xe9, 0x2f, 0x46, 0x68, 0x1b, 0x20, 0xa0,
+];
+
+/// Whether `signature`, a low-S `r || s` over the SHA-256 of `payload`,
+/// recovers to `public_key`. For every valid signature `(r, s)` there is a
+/// second one, `(r, n - s)`; only the low-S form is accepted, so a
+/// signature cannot be altered into another valid one.
+pub fn secp256k1_verify(env: &Env, public_key: &BytesN<65>, payload: &Bytes, signature: &BytesN<64>) -> bool {
+    if signature.to_array()[32..] > SECP256K1_HALF_ORDER[..] {
+        return false;
+    }
+    let digest = env.crypto().sha256(payload);
+    (0..2).any(|recovery_id| env.crypto().secp256k1_recover(&digest, signature, recovery_id) == *public_key)
+}
+
 /// Signature-based authorization model
 #[derive(Clone, Debug)]
 #[contracttype]
@@ -213,6 +243,36 @@ impl SignatureBasedAuth {
         Self { public_key_key }
     }
     
+    /// Store `public_key` together with the scheme it verifies.
+    pub fn register_key(&self, env: &Env, scheme: SignatureScheme, public_key: &Bytes) -> Result<(), ContractError> {
+        let storage = env.storage().instance();
+        match scheme {
+            SignatureScheme::Ed25519 => {
+                let key: BytesN<32> = public_key.clone().try_into().map_err(|_| ContractError::InvalidInput)?;
+                storage.set(&self.public_key_key, &key);
+            }
+            SignatureScheme::Secp256k1 => {
+                let key: BytesN<65> = public_key.clone().try_into().map_err(|_| ContractError::InvalidInput)?;
+                storage.set(&self.public_key_key, &key);
+            }
+        }
+        storage.set(&self.scheme_key(), &scheme);
+        Ok(())
+    }
+    
+    /// Scheme of the stored key. Keys stored before schemes existed are
+    /// Ed25519.
+    pub fn get_scheme(&self, env: &Env) -> SignatureScheme {
+        env.storage()
+            .instance()
+            .get(&self.scheme_key())
+            .unwrap_or(SignatureScheme::Ed25519)
+    }
+    
+    fn scheme_key(&self) -> (Symbol, Symbol) {
+        (self.public_key_key.clone(), symbol_short!("scheme"))
+    }
+    
     fn get_public_key(&self, env: &Env) -> Result<BytesN<32>, ContractError> {
         env.storage()
             .instance()
@@ -220,9 +280,24 @@ impl SignatureBasedAuth {
             .ok_or(ContractError::NotInitialized)
     }
     
-    fn verify_signature(&self, env: &Env, payload: &Bytes, signature: &BytesN<64>) -> Result<bool, ContractError> {
-        let public_key = self.get_public_key(env)?;
-        Ok(env.crypto().ed25519_verify(&public_key, payload, signature))
+    /// Verify `signature` over `payload` with the stored key, using the scheme
+    /// it was registered with. An invalid Ed25519 signature traps; a
+    /// secp256k1 one, high-S included, returns `false`.
+    pub fn verify_signature(&self, env: &Env, payload: &Bytes, signature: &BytesN<64>) -> Result<bool, ContractError> {
+        match self.get_scheme(env) {
+            SignatureScheme::Ed25519 => {
+                let public_key = self.get_public_key(env)?;
+                env.crypto().ed25519_verify(&public_key, payload, signature);
+                Ok(true)
+            }
+            SignatureScheme::Secp256k1 => {
+                let public_key: BytesN<65> = env.storage()
+                    .instance()
+                    .get(&self.public_key_key)
+                    .ok_or(ContractError::NotInitialized)?;
+                Ok(secp256k1_verify(env, &public_key, payload, signature))
+            }
+        }
     }
 }
 
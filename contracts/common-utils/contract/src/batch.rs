@@ -6,14 +6,19 @@
 //! a single contract invocation.
 
 use soroban_sdk::{
-    contracterror, contracttype, symbol_short, Address, Bytes, Env, Symbol, Vec,
+    contracterror, contracttype, panic_with_error, symbol_short, xdr::ToXdr, Address, Bytes, BytesN,
+    Env, IntoVal, Symbol, Val, Vec,
 };
 
-use crate::error::{ContractError, ErrorCategory};
+use crate::error::{ContractError, ErrorCategory, ValidationError};
 
 /// Maximum operations per batch
 pub const MAX_BATCH_SIZE: u32 = 100;
 
+/// Batch size limit until `set_max_batch_size` is called, the same as the
+/// ceiling so existing callers keep working
+pub const DEFAULT_MAX_BATCH_SIZE: u32 = MAX_BATCH_SIZE;
+
 /// Estimated base gas cost per operation type
 pub const GAS_COST_REPORT: u64 = 100_000;
 pub const GAS_COST_SCORE: u64 = 80_000;
@@ -313,6 +318,13 @@ impl BatchValidator {
         Ok(())
     }
 
+    /// `validate`, additionally enforcing the contract's `max_batch_size`
+    /// with `require_within_batch_limit`.
+    pub fn validate_for(env: &Env, operations: &Vec<BatchOperation>) -> Result<(), BatchError> {
+        require_within_batch_limit(env, operations.len());
+        Self::validate(operations)
+    }
+
     fn validate_operation(op: &BatchOperation) -> Result<(), BatchError> {
         match op {
             BatchOperation::Report(r) => {
@@ -360,6 +372,138 @@ impl BatchValidator {
     }
 }
 
+// --- Batch size limit ---
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BatchLimitKey {
+    MaxSize,
+}
+
+/// Most items any batch entrypoint of this contract accepts.
+pub fn max_batch_size(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&BatchLimitKey::MaxSize)
+        .unwrap_or(DEFAULT_MAX_BATCH_SIZE)
+}
+
+/// Change `max_batch_size`, between 1 and `MAX_BATCH_SIZE`.
+/// Callers are responsible for authorising the change.
+pub fn set_max_batch_size(env: &Env, n: u32) -> Result<(), BatchError> {
+    if n == 0 || n > MAX_BATCH_SIZE {
+        return Err(BatchError::BatchSizeExceeded);
+    }
+    env.storage().instance().set(&BatchLimitKey::MaxSize, &n);
+    Ok(())
+}
+
+/// Whether a batch of `len` items fits `max_batch_size`.
+pub fn within_batch_limit(env: &Env, len: u32) -> bool {
+    len <= max_batch_size(env)
+}
+
+/// Fail the invocation with `ValidationError::CapacityExceeded` unless a
+/// batch of `len` items fits `max_batch_size`. Entrypoints call this before
+/// processing anything, so an oversized batch is rejected with the same
+/// error code whatever error type the entrypoint declares.
+pub fn require_within_batch_limit(env: &Env, len: u32) {
+    if !within_batch_limit(env, len) {
+        panic_with_error!(env, ValidationError::CapacityExceeded);
+    }
+}
+
+// --- Batch events ---
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BatchEventKey {
+    Verbose,
+}
+
+/// Emit one event per batch item as well as the summary. Off by default;
+/// meant for debugging. Callers are responsible for authorising the change.
+pub fn set_verbose_batch_events(env: &Env, enabled: bool) {
+    env.storage().instance().set(&BatchEventKey::Verbose, &enabled);
+}
+
+pub fn verbose_batch_events_enabled(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&BatchEventKey::Verbose)
+        .unwrap_or(false)
+}
+
+/// Collects per-item outcomes of a bulk operation and publishes a single
+/// summary event when it finishes, instead of one event per item:
+///
+/// ```text
+/// topics: (topic, "summary")
+/// data:   (succeeded, failed, first_id, last_id, digest)
+/// ```
+///
+/// `digest` is the SHA-256 of the XDR of every recorded `(id, ok)` so
+/// indexers can check they reconstructed the same batch. In verbose mode
+/// each item is also published as `(topic, id) -> data`.
+pub struct BatchEventAccumulator<I> {
+    topic: Symbol,
+    verbose: bool,
+    succeeded: u32,
+    failed: u32,
+    first: Option<I>,
+    last: Option<I>,
+    outcomes: Bytes,
+}
+
+impl<I> BatchEventAccumulator<I>
+where
+    I: IntoVal<Env, Val> + Clone,
+{
+    pub fn new(env: &Env, topic: Symbol) -> Self {
+        Self {
+            topic,
+            verbose: verbose_batch_events_enabled(env),
+            succeeded: 0,
+            failed: 0,
+            first: None,
+            last: None,
+            outcomes: Bytes::new(env),
+        }
+    }
+
+    pub fn record<D>(&mut self, env: &Env, id: I, ok: bool, data: D)
+    where
+        D: IntoVal<Env, Val>,
+    {
+        if ok {
+            self.succeeded += 1;
+        } else {
+            self.failed += 1;
+        }
+        self.outcomes.append(&(id.clone(), ok).to_xdr(env));
+        if self.verbose {
+            env.events().publish((self.topic.clone(), id.clone()), data);
+        }
+        if self.first.is_none() {
+            self.first = Some(id.clone());
+        }
+        self.last = Some(id);
+    }
+
+    /// Publish the summary and return its digest. Nothing is emitted for a
+    /// batch with no recorded items.
+    pub fn finish(self, env: &Env) -> BytesN<32> {
+        let digest: BytesN<32> = env.crypto().sha256(&self.outcomes).into();
+        if self.succeeded + self.failed > 0 {
+            env.events().publish(
+                (self.topic, symbol_short!("summary")),
+                (self.succeeded, self.failed, self.first, self.last, digest.clone()),
+            );
+        }
+        digest
+    }
+}
+
 // --- Executor ---
 
 pub struct BatchExecutor;
@@ -475,3 +475,125 @@ fn test_gas_savings_new_types() {
     let savings = GasEstimator::savings_bps(&ops);
     assert!(savings > 3000, "Expected >30% savings, got {}bps", savings);
 }
+
+fn events_with_first_topic(env: &Env, topic: Symbol) -> u32 {
+    use soroban_sdk::{testutils::Events, TryFromVal};
+    let mut count = 0;
+    for (_, topics, _) in env.events().all().iter() {
+        let first = topics.get(0).and_then(|t| Symbol::try_from_val(env, &t).ok());
+        if first == Some(topic.clone()) {
+            count += 1;
+        }
+    }
+    count
+}
+
+#[test]
+fn test_batch_event_summary_aggregates_outcomes() {
+    use soroban_sdk::{testutils::Events, xdr::ToXdr, BytesN, IntoVal, TryFromVal, Val};
+
+    let env = make_env();
+    let id = env.register(crate::CommonUtilsContract, ());
+    let ids = [Address::generate(&env), Address::generate(&env), Address::generate(&env)];
+
+    let digest = env.as_contract(&id, || {
+        let mut events = BatchEventAccumulator::new(&env, symbol_short!("scr_upd"));
+        events.record(&env, ids[0].clone(), true, 1u32);
+        events.record(&env, ids[1].clone(), false, 2u32);
+        events.record(&env, ids[2].clone(), true, 3u32);
+        events.finish(&env)
+    });
+
+    let (_, topics, data) = env.events().all().last().unwrap();
+    let expected_topics: Vec<Val> = soroban_sdk::vec![
+        &env,
+        symbol_short!("scr_upd").into_val(&env),
+        symbol_short!("summary").into_val(&env),
+    ];
+    assert_eq!(topics, expected_topics);
+    assert_eq!(events_with_first_topic(&env, symbol_short!("scr_upd")), 1);
+
+    let (succeeded, failed, first, last, emitted): (u32, u32, Option<Address>, Option<Address>, BytesN<32>) =
+        TryFromVal::try_from_val(&env, &data).unwrap();
+    assert_eq!((succeeded, failed), (2, 1));
+    assert_eq!(first, Some(ids[0].clone()));
+    assert_eq!(last, Some(ids[2].clone()));
+    assert_eq!(emitted, digest);
+
+    let mut outcomes = Bytes::new(&env);
+    for (i, ok) in [(0usize, true), (1, false), (2, true)] {
+        outcomes.append(&(ids[i].clone(), ok).to_xdr(&env));
+    }
+    let recomputed: BytesN<32> = env.crypto().sha256(&outcomes).into();
+    assert_eq!(digest, recomputed);
+}
+
+#[test]
+fn test_batch_event_verbose_mode_emits_items() {
+    let env = make_env();
+    let id = env.register(crate::CommonUtilsContract, ());
+
+    env.as_contract(&id, || {
+        set_verbose_batch_events(&env, true);
+        let mut events = BatchEventAccumulator::new(&env, symbol_short!("fraud_flg"));
+        for _ in 0..3 {
+            events.record(&env, Address::generate(&env), true, 7u32);
+        }
+        events.finish(&env);
+    });
+
+    // Three items plus the summary
+    assert_eq!(events_with_first_topic(&env, symbol_short!("fraud_flg")), 4);
+}
+
+// --- Configurable size limit ---
+
+#[test]
+fn test_configured_batch_limit_boundary() {
+    let env = make_env();
+    let id = env.register(crate::CommonUtilsContract, ());
+    env.as_contract(&id, || {
+        assert_eq!(max_batch_size(&env), DEFAULT_MAX_BATCH_SIZE);
+        set_max_batch_size(&env, 3).unwrap();
+
+        let mut ops = Vec::new(&env);
+        for _ in 0..3 {
+            ops.push_back(score_op(&env, 500));
+        }
+        assert_eq!(BatchValidator::validate_for(&env, &ops), Ok(()));
+        ops.push_back(score_op(&env, 500));
+        // The static check still only enforces the hard ceiling
+        assert_eq!(BatchValidator::validate(&ops), Ok(()));
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1011)")]
+fn test_configured_batch_limit_rejects_with_capacity_exceeded() {
+    let env = make_env();
+    let id = env.register(crate::CommonUtilsContract, ());
+    env.as_contract(&id, || {
+        set_max_batch_size(&env, 3).unwrap();
+        let mut ops = Vec::new(&env);
+        for _ in 0..4 {
+            ops.push_back(score_op(&env, 500));
+        }
+        let _ = BatchValidator::validate_for(&env, &ops);
+    });
+}
+
+#[test]
+fn test_batch_limit_must_stay_within_ceiling() {
+    let env = make_env();
+    let id = env.register(crate::CommonUtilsContract, ());
+    env.as_contract(&id, || {
+        assert_eq!(set_max_batch_size(&env, 0), Err(BatchError::BatchSizeExceeded));
+        assert_eq!(
+            set_max_batch_size(&env, MAX_BATCH_SIZE + 1),
+            Err(BatchError::BatchSizeExceeded)
+        );
+        set_max_batch_size(&env, MAX_BATCH_SIZE).unwrap();
+        assert!(within_batch_limit(&env, MAX_BATCH_SIZE));
+        assert!(!within_batch_limit(&env, MAX_BATCH_SIZE + 1));
+    });
+}
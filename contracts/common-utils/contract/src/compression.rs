@@ -362,6 +362,29 @@ pub struct FraudReport {
     pub timestamp: u64,
 }
 
+/// A compressed payload with the only header it carries: the format
+/// version and the algorithm that produced it. The configured type can
+/// change without breaking reads of older blobs. `data` itself is the bare
+/// compressor output. Data stored before blobs existed is raw `Bytes`, and
+/// it is told apart by its stored type, never by sniffing its bytes.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CompressedBlob {
+    pub version: u32,
+    pub compression_type: CompressionType,
+    pub data: Bytes,
+}
+
+/// Storage keys for per-contract compression settings
+#[contracttype]
+#[derive(Clone, Debug)]
+pub enum CompressionKey {
+    Selected,
+}
+
+/// `CompressedBlob::version` written by `compress_blob`
+pub const BLOB_FORMAT_VERSION: u32 = 1;
+
 /// Compression manager for handling different compression types
 pub struct CompressionManager;
 
@@ -442,6 +465,68 @@ impl CompressionManager {
         }
     }
     
+    /// Whether `decompress` can invert blobs written with `compression_type`
+    pub fn has_decompressor(compression_type: &CompressionType) -> bool {
+        matches!(
+            compression_type,
+            CompressionType::None
+                | CompressionType::BitPacking
+                | CompressionType::DeltaEncoding
+                | CompressionType::RunLength
+        )
+    }
+    
+    /// Select the algorithm used for new writes in this contract
+    pub fn set_selected_type(env: &Env, compression_type: &CompressionType) -> Result<(), ContractError> {
+        if !Self::has_decompressor(compression_type) {
+            return Err(ContractError::UnsupportedOperation);
+        }
+        env.storage().instance().set(&CompressionKey::Selected, compression_type);
+        Ok(())
+    }
+    
+    /// Algorithm selected for this contract, or `default` when none was set
+    pub fn selected_type(env: &Env, default: CompressionType) -> CompressionType {
+        env.storage()
+            .instance()
+            .get(&CompressionKey::Selected)
+            .unwrap_or(default)
+    }
+    
+    /// Compress with the selected algorithm and record which one was used
+    pub fn compress_blob(env: &Env, data: &Bytes, default: CompressionType) -> Result<CompressedBlob, ContractError> {
+        let compression_type = Self::selected_type(env, default);
+        let compressed = Self::compress(data, &compression_type)?;
+        Ok(CompressedBlob {
+            version: BLOB_FORMAT_VERSION,
+            compression_type,
+            data: compressed,
+        })
+    }
+    
+    /// Decompress a blob with the algorithm it was written with, no hint needed
+    pub fn decompress_blob(blob: &CompressedBlob) -> Result<Bytes, ContractError> {
+        if blob.version != BLOB_FORMAT_VERSION {
+            return Err(ContractError::InvalidInput);
+        }
+        Self::decompress(&blob.data, &blob.compression_type)
+    }
+    
+    /// Migration helper: wrap raw `Bytes` stored before blobs existed,
+    /// written with `legacy_type`, in a `CompressedBlob`
+    pub fn wrap_legacy(data: &Bytes, legacy_type: &CompressionType) -> Result<CompressedBlob, ContractError> {
+        if !Self::has_decompressor(legacy_type) {
+            return Err(ContractError::UnsupportedOperation);
+        }
+        // Validate the payload actually decodes before stamping it
+        Self::decompress(data, legacy_type)?;
+        Ok(CompressedBlob {
+            version: BLOB_FORMAT_VERSION,
+            compression_type: legacy_type.clone(),
+            data: data.clone(),
+        })
+    }
+    
     /// Calculate compression ratio
     pub fn calculate_compression_ratio(original_size: u32, compressed_size: u32) -> f32 {
         if original_size == 0 { return 0.0; }
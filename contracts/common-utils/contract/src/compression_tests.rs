@@ -7,7 +7,7 @@ use soroban_sdk::{
 use crate::compression::{
     BitPackingCompressor, DeltaEncodingCompressor, RunLengthCompressor,
     CompressionManager, AdaptiveCompressor, FraudReportCompressor,
-    FraudReport, CompressionType
+    FraudReport, CompressionType, CompressedBlob, BLOB_FORMAT_VERSION
 };
 use crate::storage_optimization::{
     BloomFilter, DataSeparator, CompressedReportStorage, ScoreStorage,
@@ -17,8 +17,9 @@ use crate::data_migration::{
     DataMigrationManager, MigrationConfig, MigrationStatus
 };
 use crate::storage_monitoring::{
-    StorageTracker, PerformanceMonitor, EfficiencyAnalyzer
+    StorageTracker, PerformanceMonitor, EfficiencyAnalyzer, PerfStats
 };
+use soroban_sdk::TryFromVal;
 
 #[test]
 fn test_bit_packing_compression() {
@@ -158,6 +159,49 @@ fn test_compression_manager() {
     }
 }
 
+#[test]
+fn test_blobs_decode_without_hint() {
+    let env = Env::default();
+    let data = Bytes::from_slice(&env, &[5u8, 0, 0, 0, 6, 0, 0, 0, 7, 0, 0, 0, 7, 0, 0, 0]);
+    
+    let types = [
+        CompressionType::None,
+        CompressionType::BitPacking,
+        CompressionType::DeltaEncoding,
+        CompressionType::RunLength,
+    ];
+    for compression_type in types.iter() {
+        CompressionManager::set_selected_type(&env, compression_type).unwrap();
+        let blob = CompressionManager::compress_blob(&env, &data, CompressionType::BitPacking).unwrap();
+        assert_eq!(blob.compression_type, *compression_type);
+        // The payload is the bare compressor output, with no byte header
+        assert_eq!(blob.data, CompressionManager::compress(&data, compression_type).unwrap());
+        assert_eq!(CompressionManager::decompress_blob(&blob).unwrap(), data);
+    }
+}
+
+#[test]
+fn test_untagged_legacy_blobs() {
+    let env = Env::default();
+    let values = Vec::from_array(&env, &[1u8, 1, 1, 1, 2, 2]);
+    let legacy = RunLengthCompressor::compress_u8(&values).unwrap();
+    let original = Bytes::from_slice(&env, &[1u8, 1, 1, 1, 2, 2]);
+    
+    // Raw bytes need the algorithm they were written with
+    assert_eq!(
+        CompressionManager::decompress(&legacy, &CompressionType::RunLength).unwrap(),
+        original
+    );
+    
+    // Wrapping makes them self-describing, checking they decode first
+    let wrapped = CompressionManager::wrap_legacy(&legacy, &CompressionType::RunLength).unwrap();
+    assert_eq!(wrapped.data, legacy);
+    assert_eq!(CompressionManager::decompress_blob(&wrapped).unwrap(), original);
+    
+    // Unknown algorithms cannot be wrapped
+    assert!(CompressionManager::wrap_legacy(&legacy, &CompressionType::Huffman).is_err());
+}
+
 #[test]
 fn test_adaptive_compression() {
     let env = Env::default();
@@ -290,6 +334,72 @@ fn test_score_storage() {
     assert_eq!(history.len(), 3);
 }
 
+#[test]
+fn test_selected_compression_type() {
+    let env = Env::default();
+    
+    // Unset falls back to the caller's default
+    assert_eq!(
+        CompressionManager::selected_type(&env, CompressionType::BitPacking),
+        CompressionType::BitPacking
+    );
+    
+    CompressionManager::set_selected_type(&env, &CompressionType::RunLength).unwrap();
+    assert_eq!(
+        CompressionManager::selected_type(&env, CompressionType::BitPacking),
+        CompressionType::RunLength
+    );
+    
+    // Types without a decompressor are rejected and leave the selection alone
+    assert!(CompressionManager::set_selected_type(&env, &CompressionType::Huffman).is_err());
+    assert!(CompressionManager::set_selected_type(
+        &env,
+        &CompressionType::Custom(Symbol::new(&env, "zstd"))
+    ).is_err());
+    assert_eq!(
+        CompressionManager::selected_type(&env, CompressionType::BitPacking),
+        CompressionType::RunLength
+    );
+}
+
+#[test]
+fn test_switching_compression_type_keeps_old_blobs_readable() {
+    let env = Env::default();
+    let data = Bytes::from_slice(&env, &[7, 0, 0, 0, 7, 0, 0, 0, 9, 0, 0, 0]);
+    
+    let old_blob: CompressedBlob =
+        CompressionManager::compress_blob(&env, &data, CompressionType::BitPacking).unwrap();
+    assert_eq!(old_blob.compression_type, CompressionType::BitPacking);
+    
+    CompressionManager::set_selected_type(&env, &CompressionType::RunLength).unwrap();
+    let new_blob =
+        CompressionManager::compress_blob(&env, &data, CompressionType::BitPacking).unwrap();
+    assert_eq!(new_blob.compression_type, CompressionType::RunLength);
+    assert_ne!(old_blob.data, new_blob.data);
+    
+    assert_eq!(CompressionManager::decompress_blob(&old_blob).unwrap(), data);
+    assert_eq!(CompressionManager::decompress_blob(&new_blob).unwrap(), data);
+    
+    // Only the known format version decodes
+    assert_eq!(new_blob.version, BLOB_FORMAT_VERSION);
+    let future = CompressedBlob { version: BLOB_FORMAT_VERSION + 1, ..new_blob };
+    assert!(CompressionManager::decompress_blob(&future).is_err());
+}
+
+#[test]
+fn test_score_storage_across_compression_switch() {
+    let env = Env::default();
+    let before = TestAddress::generate(&env);
+    let after = TestAddress::generate(&env);
+    
+    ScoreStorage::store_score(&env, &before, 700, 1640995200).unwrap();
+    CompressionManager::set_selected_type(&env, &CompressionType::RunLength).unwrap();
+    ScoreStorage::store_score(&env, &after, 710, 1640995300).unwrap();
+    
+    assert_eq!(ScoreStorage::get_score(&env, &before).unwrap(), 700);
+    assert_eq!(ScoreStorage::get_score(&env, &after).unwrap(), 710);
+}
+
 #[test]
 fn test_data_migration() {
     let env = Env::default();
@@ -376,6 +486,135 @@ fn test_storage_monitoring() {
     assert!(updated_perf.cache_hit_rate > 0.0);
 }
 
+#[test]
+fn test_storage_summary_empty() {
+    let env = Env::default();
+    let summary = StorageTracker::summary(&env);
+    assert_eq!(summary.total_stored, 0);
+    assert_eq!(summary.total_accessed, 0);
+    assert!(summary.by_op.is_empty());
+}
+
+#[test]
+fn test_storage_summary_per_op_totals() {
+    let env = Env::default();
+    let key = Symbol::new(&env, "reports");
+    let store = Symbol::new(&env, "store");
+    let access = Symbol::new(&env, "access");
+    let prune = Symbol::new(&env, "prune");
+    
+    StorageTracker::record_operation(&env, &store, &key, 100, true);
+    StorageTracker::record_operation(&env, &store, &key, 44, true);
+    StorageTracker::record_operation(&env, &access, &key, 0, false);
+    StorageTracker::record_operation(&env, &access, &key, 0, false);
+    StorageTracker::record_operation(&env, &access, &key, 0, false);
+    StorageTracker::record_operation(&env, &prune, &key, 44, true);
+    
+    let summary = StorageTracker::summary(&env);
+    assert_eq!(summary.total_stored, 144);
+    assert_eq!(summary.total_accessed, 3);
+    assert_eq!(summary.by_op.get(store), Some(2));
+    assert_eq!(summary.by_op.get(access), Some(3));
+    assert_eq!(summary.by_op.get(prune), Some(1));
+    
+    // Prune shrinks the live size like remove does
+    assert_eq!(StorageTracker::get_metrics(&env).total_size, 100);
+}
+
+fn advance_time(env: &Env, seconds: u64) {
+    use soroban_sdk::testutils::Ledger;
+    env.ledger().with_mut(|li| li.timestamp += seconds);
+}
+
+#[test]
+fn test_perf_stats_accumulate() {
+    let env = Env::default();
+    let op = Symbol::new(&env, "get_score");
+    
+    assert_eq!(PerformanceMonitor::get_stats(&env, &op), PerfStats::default());
+    
+    for seconds in [2u64, 5, 3] {
+        PerformanceMonitor::start_timer(&env, &op);
+        advance_time(&env, seconds);
+        PerformanceMonitor::end_timer(&env, &op);
+    }
+    
+    let stats = PerformanceMonitor::get_stats(&env, &op);
+    assert_eq!(stats.count, 3);
+    assert_eq!(stats.total, 10);
+    assert_eq!(stats.max, 5);
+    
+    let report = PerformanceMonitor::get_report(&env);
+    assert_eq!(report.len(), 1);
+    let entry = report.get(0).unwrap();
+    assert_eq!(entry.operation, op);
+    assert_eq!(entry.stats, stats);
+    assert_eq!(entry.budget, None);
+}
+
+#[test]
+fn test_perf_budget_exceeded_event() {
+    use soroban_sdk::testutils::Events;
+    
+    let env = Env::default();
+    let op = Symbol::new(&env, "batch_calc");
+    let budget_topic = Symbol::new(&env, "perf_budget_exceeded");
+    let exceeded = |env: &Env| {
+        env.events().all().iter().any(|(_, topics, _)| {
+            topics.get(0).map(|t| Symbol::try_from_val(env, &t).ok() == Some(budget_topic.clone()))
+                == Some(true)
+        })
+    };
+    
+    PerformanceMonitor::set_perf_budget(&env, &op, 1);
+    
+    // Within budget: no event
+    PerformanceMonitor::start_timer(&env, &op);
+    advance_time(&env, 1);
+    PerformanceMonitor::end_timer(&env, &op);
+    assert!(!exceeded(&env));
+    
+    // Over budget: event is emitted
+    PerformanceMonitor::start_timer(&env, &op);
+    advance_time(&env, 4);
+    PerformanceMonitor::end_timer(&env, &op);
+    assert!(exceeded(&env));
+    
+    assert_eq!(PerformanceMonitor::get_report(&env).get(0).unwrap().budget, Some(1));
+}
+
+#[test]
+fn test_strict_perf_budget_fails_overrunning_operation() {
+    use crate::error::StateError;
+    
+    let env = Env::default();
+    let op = Symbol::new(&env, "batch_calc");
+    PerformanceMonitor::set_perf_budget(&env, &op, 2);
+    
+    // Default: overruns are only recorded
+    assert!(!PerformanceMonitor::strict_budgets_enabled(&env));
+    PerformanceMonitor::start_timer(&env, &op);
+    advance_time(&env, 5);
+    assert_eq!(PerformanceMonitor::end_timer_checked(&env, &op), Ok(5));
+    
+    PerformanceMonitor::set_strict_budgets(&env, true);
+    PerformanceMonitor::start_timer(&env, &op);
+    advance_time(&env, 2);
+    assert_eq!(PerformanceMonitor::end_timer_checked(&env, &op), Ok(2));
+    
+    PerformanceMonitor::start_timer(&env, &op);
+    advance_time(&env, 3);
+    assert_eq!(
+        PerformanceMonitor::end_timer_checked(&env, &op),
+        Err(StateError::BudgetExceeded)
+    );
+    
+    // Every run is still folded into the stats
+    let stats = PerformanceMonitor::get_stats(&env, &op);
+    assert_eq!(stats.count, 3);
+    assert_eq!(stats.max, 5);
+}
+
 #[test]
 fn test_efficiency_analysis() {
     let env = Env::default();
@@ -550,3 +789,102 @@ fn test_decompression_accuracy() {
         assert_eq!(orig, decomp, "Mismatch at byte {}: {} != {}", i, orig, decomp);
     }
 }
+
+#[test]
+fn test_storage_tier_policy_routes_new_writes() {
+    use crate::storage_optimization::CATEGORY_LATEST_SCORE;
+    use soroban_sdk::symbol_short;
+
+    let env = Env::default();
+    let id = env.register(crate::CommonUtilsContract, ());
+    let key = symbol_short!("tier_key");
+    env.as_contract(&id, || {
+        DataSeparator::store_in_tier(&env, &CATEGORY_LATEST_SCORE, DataTemperature::Hot, &key, &10u32).unwrap();
+        assert!(env.storage().instance().has(&key));
+
+        DataSeparator::set_storage_tier(&env, CATEGORY_LATEST_SCORE, DataTemperature::Cold).unwrap();
+        // Not yet rewritten, still readable from its old tier
+        let stale: Option<u32> = DataSeparator::load_from_tier(&env, &CATEGORY_LATEST_SCORE, DataTemperature::Hot, &key);
+        assert_eq!(stale, Some(10));
+
+        DataSeparator::store_in_tier(&env, &CATEGORY_LATEST_SCORE, DataTemperature::Hot, &key, &20u32).unwrap();
+        assert!(!env.storage().instance().has(&key));
+        assert_eq!(env.storage().persistent().get::<_, u32>(&key), Some(20));
+        let fresh: Option<u32> = DataSeparator::load_from_tier(&env, &CATEGORY_LATEST_SCORE, DataTemperature::Hot, &key);
+        assert_eq!(fresh, Some(20));
+
+        DataSeparator::set_storage_tier(&env, CATEGORY_LATEST_SCORE, DataTemperature::Warm).unwrap();
+        DataSeparator::store_in_tier(&env, &CATEGORY_LATEST_SCORE, DataTemperature::Hot, &key, &30u32).unwrap();
+        assert_eq!(env.storage().persistent().get::<_, u32>(&key), Some(30));
+
+        // Non-durable categories may expire
+        let misc = symbol_short!("misc");
+        DataSeparator::set_storage_tier(&env, misc.clone(), DataTemperature::Frozen).unwrap();
+        DataSeparator::store_in_tier(&env, &misc, DataTemperature::Hot, &key, &40u32).unwrap();
+        assert!(!env.storage().persistent().has(&key));
+        assert_eq!(env.storage().temporary().get::<_, u32>(&key), Some(40));
+
+        DataSeparator::remove_from_tiers(&env, &key);
+        let gone: Option<u32> = DataSeparator::load_from_tier(&env, &CATEGORY_LATEST_SCORE, DataTemperature::Hot, &key);
+        assert_eq!(gone, None);
+    });
+}
+
+#[test]
+fn test_hot_tier_rejects_oversized_entries() {
+    use crate::storage_optimization::{CATEGORY_REPORTS, MAX_INSTANCE_ENTRY_BYTES};
+    use soroban_sdk::symbol_short;
+
+    let env = Env::default();
+    let id = env.register(crate::CommonUtilsContract, ());
+    let key = symbol_short!("big");
+    env.as_contract(&id, || {
+        let mut big = Bytes::new(&env);
+        for _ in 0..MAX_INSTANCE_ENTRY_BYTES {
+            big.push_back(0xAB);
+        }
+        assert!(DataSeparator::store_in_tier(&env, &CATEGORY_REPORTS, DataTemperature::Hot, &key, &big).is_err());
+        assert!(!env.storage().instance().has(&key));
+
+        DataSeparator::set_storage_tier(&env, CATEGORY_REPORTS, DataTemperature::Cold).unwrap();
+        DataSeparator::store_in_tier(&env, &CATEGORY_REPORTS, DataTemperature::Hot, &key, &big).unwrap();
+        assert!(env.storage().persistent().has(&key));
+    });
+}
+
+#[test]
+fn test_durable_categories_cannot_be_made_temporary() {
+    use crate::storage_optimization::DURABLE_CATEGORIES;
+
+    let env = Env::default();
+    let id = env.register(crate::CommonUtilsContract, ());
+    env.as_contract(&id, || {
+        for category in DURABLE_CATEGORIES {
+            assert!(DataSeparator::set_storage_tier(&env, category.clone(), DataTemperature::Frozen).is_err());
+            let tier = DataSeparator::get_storage_tier(&env, &category, DataTemperature::Cold);
+            assert_eq!(tier, DataTemperature::Cold);
+        }
+    });
+}
+
+#[test]
+fn test_rewrite_in_same_tier_keeps_single_copy() {
+    use crate::storage_optimization::CATEGORY_SCORES;
+    use soroban_sdk::symbol_short;
+
+    let env = Env::default();
+    let id = env.register(crate::CommonUtilsContract, ());
+    let key = symbol_short!("same");
+    env.as_contract(&id, || {
+        DataSeparator::store_in_tier(&env, &CATEGORY_SCORES, DataTemperature::Cold, &key, &1u32).unwrap();
+        DataSeparator::store_in_tier(&env, &CATEGORY_SCORES, DataTemperature::Cold, &key, &2u32).unwrap();
+        assert_eq!(env.storage().persistent().get::<_, u32>(&key), Some(2));
+        assert!(!env.storage().instance().has(&key));
+
+        // A tier change migrates the entry on its next write
+        DataSeparator::set_storage_tier(&env, CATEGORY_SCORES, DataTemperature::Hot).unwrap();
+        DataSeparator::store_in_tier(&env, &CATEGORY_SCORES, DataTemperature::Cold, &key, &3u32).unwrap();
+        assert!(!env.storage().persistent().has(&key));
+        assert_eq!(env.storage().instance().get::<_, u32>(&key), Some(3));
+    });
+}
@@ -0,0 +1,74 @@
+//! # Merkle Proofs
+//!
+//! Binary SHA-256 tree with domain-separated hashing:
+//!
+//! ```text
+//! leaf node   = sha256(0x00 || leaf)
+//! parent node = sha256(0x01 || left || right)
+//! ```
+//!
+//! The prefixes stop an interior node from being passed off as a leaf. A
+//! proof is the list of sibling hashes from leaf to root; the leaf's index
+//! decides at each level whether the sibling sits on the left or the right.
+//! A level with an odd number of nodes carries its last node up unchanged,
+//! so that node has no sibling at that level.
+//!
+//! Leaves are 32-byte hashes supplied by the caller; this module never hashes
+//! raw data into a leaf itself.
+
+use soroban_sdk::{Bytes, BytesN, Env, Vec};
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+/// Hash a caller-supplied leaf into its tree node.
+pub fn hash_leaf(env: &Env, leaf: &BytesN<32>) -> BytesN<32> {
+    let mut combined = Bytes::from_array(env, &[LEAF_PREFIX]);
+    combined.append(&leaf.clone().into());
+    env.crypto().sha256(&combined).into()
+}
+
+/// Hash two nodes into their parent. Order matters.
+pub fn hash_node(env: &Env, left: &BytesN<32>, right: &BytesN<32>) -> BytesN<32> {
+    let mut combined = Bytes::from_array(env, &[NODE_PREFIX]);
+    combined.append(&left.clone().into());
+    combined.append(&right.clone().into());
+    env.crypto().sha256(&combined).into()
+}
+
+/// Recompute the root implied by `leaf` at `index` in a tree of
+/// `leaf_count` leaves, given its sibling path `proof`. `None` when `index`
+/// is out of range or `proof` has the wrong length for that position.
+pub fn root_from_proof(
+    env: &Env,
+    leaf: &BytesN<32>,
+    index: u32,
+    leaf_count: u32,
+    proof: &Vec<BytesN<32>>,
+) -> Option<BytesN<32>> {
+    if index >= leaf_count {
+        return None;
+    }
+    let mut node = hash_leaf(env, leaf);
+    let mut index = index;
+    let mut width = leaf_count;
+    let mut used = 0u32;
+    while width > 1 {
+        // The last node of an odd level has no sibling
+        if index % 2 == 1 || index + 1 < width {
+            let sibling = proof.get(used)?;
+            used += 1;
+            node = if index % 2 == 0 {
+                hash_node(env, &node, &sibling)
+            } else {
+                hash_node(env, &sibling, &node)
+            };
+        }
+        index /= 2;
+        width = width / 2 + width % 2;
+    }
+    if used != proof.len() {
+        return None;
+    }
+    Some(node)
+}
@@ -0,0 +1,114 @@
+//! # Deterministic Message Encoding
+//!
+//! Canonical byte layout for every payload that is signed off-chain and
+//! verified on-chain. Contracts must build the bytes they verify with
+//! [`encode_message`] instead of hand-rolling their own layout, so that
+//! signers only ever have to implement one format.
+//!
+//! ## Layout (version 1)
+//!
+//! ```text
+//! [version u8 = 0x01] [field_count u16 LE]
+//! repeated field_count times:
+//!     [tag u8] [len u32 LE] [payload (len bytes)]
+//! ```
+//!
+//! | Tag  | Field     | Payload                                  |
+//! |------|-----------|------------------------------------------|
+//! | 0x01 | `Bool`    | 1 byte, `0x00` or `0x01`                 |
+//! | 0x02 | `U32`     | 4 bytes LE                               |
+//! | 0x03 | `U64`     | 8 bytes LE                               |
+//! | 0x04 | `I128`    | 16 bytes LE (two's complement)           |
+//! | 0x05 | `Bytes`   | raw bytes                                |
+//! | 0x06 | `Hash`    | 32 raw bytes                             |
+//! | 0x07 | `Address` | Strkey ASCII (`G...` / `C...`, 56 bytes) |
+//!
+//! The full specification, including test vectors, lives in
+//! `contracts/common-utils/MESSAGE_ENCODING.md`. Any change to this layout
+//! must bump [`MESSAGE_FORMAT_VERSION`].
+
+use soroban_sdk::{Address, Bytes, BytesN, Env};
+
+use crate::error::CommonError;
+
+/// Current version of the canonical message layout.
+pub const MESSAGE_FORMAT_VERSION: u8 = 1;
+
+const TAG_BOOL: u8 = 0x01;
+const TAG_U32: u8 = 0x02;
+const TAG_U64: u8 = 0x03;
+const TAG_I128: u8 = 0x04;
+const TAG_BYTES: u8 = 0x05;
+const TAG_HASH: u8 = 0x06;
+const TAG_ADDRESS: u8 = 0x07;
+
+/// Strkey length for account (`G...`) and contract (`C...`) addresses.
+const STRKEY_LEN: usize = 56;
+
+/// A single typed field of a signed message.
+#[derive(Clone, Debug)]
+pub enum MessageField {
+    Bool(bool),
+    U32(u32),
+    U64(u64),
+    I128(i128),
+    Bytes(Bytes),
+    Hash(BytesN<32>),
+    Address(Address),
+}
+
+impl MessageField {
+    fn tag(&self) -> u8 {
+        match self {
+            MessageField::Bool(_) => TAG_BOOL,
+            MessageField::U32(_) => TAG_U32,
+            MessageField::U64(_) => TAG_U64,
+            MessageField::I128(_) => TAG_I128,
+            MessageField::Bytes(_) => TAG_BYTES,
+            MessageField::Hash(_) => TAG_HASH,
+            MessageField::Address(_) => TAG_ADDRESS,
+        }
+    }
+
+    fn payload(&self, env: &Env) -> Bytes {
+        match self {
+            MessageField::Bool(v) => Bytes::from_slice(env, &[*v as u8]),
+            MessageField::U32(v) => Bytes::from_slice(env, &v.to_le_bytes()),
+            MessageField::U64(v) => Bytes::from_slice(env, &v.to_le_bytes()),
+            MessageField::I128(v) => Bytes::from_slice(env, &v.to_le_bytes()),
+            MessageField::Bytes(v) => v.clone(),
+            MessageField::Hash(v) => v.clone().into(),
+            MessageField::Address(v) => address_strkey(env, v),
+        }
+    }
+}
+
+/// Encode `fields` into the canonical, versioned, length-prefixed layout.
+/// Fails with `InvalidLength` for more than `u16::MAX` fields, which the
+/// count prefix cannot hold.
+pub fn encode_message(env: &Env, fields: &[MessageField]) -> Result<Bytes, CommonError> {
+    let count = u16::try_from(fields.len()).map_err(|_| CommonError::InvalidLength)?;
+    let mut buf = Bytes::new(env);
+    buf.push_back(MESSAGE_FORMAT_VERSION);
+    buf.append(&Bytes::from_slice(env, &count.to_le_bytes()));
+
+    for field in fields.iter() {
+        let payload = field.payload(env);
+        buf.push_back(field.tag());
+        buf.append(&Bytes::from_slice(env, &payload.len().to_le_bytes()));
+        buf.append(&payload);
+    }
+
+    Ok(buf)
+}
+
+fn address_strkey(env: &Env, address: &Address) -> Bytes {
+    let strkey: soroban_sdk::String = address.to_string();
+    let len = strkey.len() as usize;
+    if len != STRKEY_LEN {
+        panic!("unsupported address encoding");
+    }
+    let mut buf = [0u8; STRKEY_LEN];
+    strkey.copy_into_slice(&mut buf);
+    Bytes::from_slice(env, &buf)
+}
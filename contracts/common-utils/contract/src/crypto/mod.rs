@@ -0,0 +1,9 @@
+//! # Cryptographic Helpers
+//!
+//! Shared primitives for contracts that verify off-chain signatures.
+
+pub mod merkle;
+pub mod message;
+
+pub use merkle::{hash_leaf, hash_node, root_from_proof};
+pub use message::{encode_message, MessageField, MESSAGE_FORMAT_VERSION};
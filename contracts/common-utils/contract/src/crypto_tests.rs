@@ -0,0 +1,127 @@
+#![cfg(test)]
+
+//! Fixed test vectors for the canonical message encoding. If any of these
+//! fail, the on-chain layout changed and off-chain signers will break:
+//! bump `MESSAGE_FORMAT_VERSION` and update `MESSAGE_ENCODING.md` instead of
+//! editing the expected bytes.
+
+use crate::crypto::{encode_message, MessageField, MESSAGE_FORMAT_VERSION};
+use crate::error::CommonError;
+use soroban_sdk::{Address, Bytes, BytesN, Env, String};
+
+const ZERO_ACCOUNT: &str = "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF";
+
+#[test]
+fn test_format_version_is_one() {
+    assert_eq!(MESSAGE_FORMAT_VERSION, 1);
+}
+
+#[test]
+fn test_empty_message_vector() {
+    let env = Env::default();
+    let encoded = encode_message(&env, &[]).unwrap();
+    assert_eq!(encoded, Bytes::from_slice(&env, &[0x01, 0x00, 0x00]));
+}
+
+#[test]
+fn test_integer_fields_vector() {
+    let env = Env::default();
+    let encoded = encode_message(
+        &env,
+        &[
+            MessageField::U32(7),
+            MessageField::U64(1_700_000_000),
+            MessageField::Bool(true),
+        ],
+    ).unwrap();
+
+    let expected: [u8; 31] = [
+        0x01, 0x03, 0x00, // version, field count
+        0x02, 0x04, 0x00, 0x00, 0x00, 0x07, 0x00, 0x00, 0x00, // U32(7)
+        0x03, 0x08, 0x00, 0x00, 0x00, 0x00, 0xf1, 0x53, 0x65, 0x00, 0x00, 0x00, 0x00, // U64
+        0x01, 0x01, 0x00, 0x00, 0x00, 0x01, // Bool(true)
+    ];
+    assert_eq!(encoded, Bytes::from_slice(&env, &expected));
+}
+
+#[test]
+fn test_i128_and_bytes_vector() {
+    let env = Env::default();
+    let encoded = encode_message(
+        &env,
+        &[
+            MessageField::I128(-2),
+            MessageField::Bytes(Bytes::from_slice(&env, &[0xaa, 0xbb])),
+        ],
+    ).unwrap();
+
+    let expected: [u8; 31] = [
+        0x01, 0x02, 0x00, // version, field count
+        0x04, 0x10, 0x00, 0x00, 0x00, // I128 tag + len
+        0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0x05, 0x02, 0x00, 0x00, 0x00, 0xaa, 0xbb, // Bytes
+    ];
+    assert_eq!(encoded, Bytes::from_slice(&env, &expected));
+}
+
+#[test]
+fn test_hash_and_address_vector() {
+    let env = Env::default();
+    let address = Address::from_string(&String::from_str(&env, ZERO_ACCOUNT));
+    let encoded = encode_message(
+        &env,
+        &[
+            MessageField::Hash(BytesN::from_array(&env, &[0x11; 32])),
+            MessageField::Address(address),
+        ],
+    ).unwrap();
+
+    let mut expected = Bytes::from_slice(&env, &[0x01, 0x02, 0x00, 0x06, 0x20, 0x00, 0x00, 0x00]);
+    expected.append(&Bytes::from_slice(&env, &[0x11; 32]));
+    expected.append(&Bytes::from_slice(&env, &[0x07, 0x38, 0x00, 0x00, 0x00]));
+    expected.append(&Bytes::from_slice(&env, ZERO_ACCOUNT.as_bytes()));
+    assert_eq!(encoded, expected);
+}
+
+#[test]
+fn test_field_order_changes_encoding() {
+    let env = Env::default();
+    let a = encode_message(&env, &[MessageField::U32(1), MessageField::U32(2)]).unwrap();
+    let b = encode_message(&env, &[MessageField::U32(2), MessageField::U32(1)]).unwrap();
+    assert_ne!(a, b);
+}
+
+#[test]
+fn test_length_prefix_prevents_ambiguity() {
+    let env = Env::default();
+    // Without length prefixes these two would concatenate to the same bytes.
+    let a = encode_message(
+        &env,
+        &[
+            MessageField::Bytes(Bytes::from_slice(&env, &[1, 2])),
+            MessageField::Bytes(Bytes::from_slice(&env, &[3])),
+        ],
+    ).unwrap();
+    let b = encode_message(
+        &env,
+        &[
+            MessageField::Bytes(Bytes::from_slice(&env, &[1])),
+            MessageField::Bytes(Bytes::from_slice(&env, &[2, 3])),
+        ],
+    ).unwrap();
+    assert_ne!(a, b);
+}
+
+#[test]
+fn test_field_count_must_fit_prefix() {
+    extern crate std;
+
+    let env = Env::default();
+    let mut fields = std::vec![MessageField::Bool(false); u16::MAX as usize];
+    let encoded = encode_message(&env, &fields).unwrap();
+    assert_eq!(encoded.slice(1..3), Bytes::from_slice(&env, &[0xff, 0xff]));
+
+    fields.push(MessageField::Bool(false));
+    assert_eq!(encode_message(&env, &fields), Err(CommonError::InvalidLength));
+}
@@ -3,6 +3,7 @@ use soroban_sdk::{contracttype, Env, Symbol, Vec};
 use super::adapter::TokenPair;
 use super::liquidity::LiquidityMetrics;
 use super::trading_data::TradingData;
+use crate::fixed_point::{self, FixedPoint};
 
 #[contracttype]
 #[derive(Clone, Debug, PartialEq)]
@@ -255,7 +256,8 @@ impl<'a> SignalAggregator<'a> {
             -((50 - dex_score) as i32)
         };
 
-        let impact = (deviation as f64 * 0.2) as i32;
+        // A fifth of the deviation, truncated toward zero
+        let impact = FixedPoint::from_ratio(deviation as i128, 5, fixed_point::precision(self.env)).trunc() as i32;
 
         let adjusted_base = base_score as i32 + impact;
         adjusted_base.clamp(0, 1000)
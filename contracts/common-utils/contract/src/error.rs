@@ -51,6 +51,74 @@ pub enum ErrorCategory {
     Unknown,
 }
 
+/// Input validation errors (1000-1099)
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ValidationError {
+    /// Invalid input format
+    InvalidFormat = 1001,
+    /// Required field missing
+    MissingRequiredField = 1002,
+    /// Value out of range
+    OutOfRange = 1003,
+    /// Invalid length
+    InvalidLength = 1004,
+    /// Invalid CID format
+    InvalidCidFormat = 1005,
+    /// Invalid hash format
+    InvalidHashFormat = 1006,
+    /// Invalid JSON structure
+    InvalidJsonStructure = 1007,
+    /// Invalid address
+    InvalidAddress = 1008,
+    /// Invalid timestamp
+    InvalidTimestamp = 1009,
+    /// Invalid signature format
+    InvalidSignatureFormat = 1010,
+    /// Batch larger than the configured limit
+    CapacityExceeded = 1011,
+    /// Agent not registered
+    UnknownAgent = 1012,
+    /// Action repeated before its cooldown ended
+    Cooldown = 1013,
+    /// Account younger than the required minimum age
+    AccountTooNew = 1014,
+}
+
+impl ContractError for ValidationError {
+    fn code(&self) -> u32 {
+        *self as u32
+    }
+
+    fn message(&self) -> &'static str {
+        match self {
+            ValidationError::InvalidFormat => "Invalid input format",
+            ValidationError::MissingRequiredField => "Required field missing",
+            ValidationError::OutOfRange => "Value out of range",
+            ValidationError::InvalidLength => "Invalid length",
+            ValidationError::InvalidCidFormat => "Invalid CID format",
+            ValidationError::InvalidHashFormat => "Invalid hash format",
+            ValidationError::InvalidJsonStructure => "Invalid JSON structure",
+            ValidationError::InvalidAddress => "Invalid address",
+            ValidationError::InvalidTimestamp => "Invalid timestamp",
+            ValidationError::InvalidSignatureFormat => "Invalid signature format",
+            ValidationError::CapacityExceeded => "Batch exceeds the configured size limit",
+            ValidationError::UnknownAgent => "Agent not registered",
+            ValidationError::Cooldown => "Cooldown still running",
+            ValidationError::AccountTooNew => "Account younger than the required minimum age",
+        }
+    }
+
+    fn category(&self) -> ErrorCategory {
+        ErrorCategory::Validation
+    }
+
+    fn is_recoverable(&self) -> bool {
+        true
+    }
+}
+
 /// Standardized validation errors (1000-1099)
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -82,6 +150,13 @@ pub enum CommonError {
     OracleNotFound = 1402,
     RequestNotFound = 1403,
     RequestAlreadyFulfilled = 1404,
+    RootAlreadyPosted = 1405,
+    PayloadTooLarge = 1406,
+    BelowMinValue = 1407,
+    AlreadyVoted = 1408,
+
+    // State
+    InvalidState = 1409,
     
     // Unknown
     Unknown = 9999,
@@ -162,6 +237,8 @@ pub enum CryptoError {
     InvalidNonce = 1309,
     /// Key derivation failed
     KeyDerivationFailed = 1310,
+    /// Signature is older than allowed, or dated in the future
+    SignatureExpired = 1311,
 }
 
 impl ContractError for CryptoError {
@@ -181,6 +258,7 @@ impl ContractError for CryptoError {
             CryptoError::DecryptionFailed => "Decryption failed",
             CryptoError::InvalidNonce => "Invalid nonce",
             CryptoError::KeyDerivationFailed => "Key derivation failed",
+            CryptoError::SignatureExpired => "Signature expired",
         }
     }
 
@@ -218,6 +296,12 @@ pub enum StateError {
     MaintenanceMode = 1409,
     /// Invalid version
     InvalidVersion = 1410,
+    /// Data is being migrated
+    MigrationInProgress = 1411,
+    /// Operation overran its strict performance budget
+    BudgetExceeded = 1412,
+    /// Stored value changed since it was read
+    Conflict = 1413,
 }
 
 impl ContractError for StateError {
@@ -237,6 +321,9 @@ impl ContractError for StateError {
             StateError::CircuitBreakerOpen => "Circuit breaker is open",
             StateError::MaintenanceMode => "Contract in maintenance mode",
             StateError::InvalidVersion => "Invalid version",
+            StateError::MigrationInProgress => "Migration in progress",
+            StateError::BudgetExceeded => "Performance budget exceeded",
+            StateError::Conflict => "Stored value does not match the expected value",
         }
     }
 
@@ -250,6 +337,8 @@ impl ContractError for StateError {
             StateError::ContractPaused
                 | StateError::MaintenanceMode
                 | StateError::RateLimitExceeded
+                | StateError::MigrationInProgress
+                | StateError::Conflict
         )
     }
 }
@@ -525,7 +614,7 @@ pub mod registry {
 
     /// Get all validation error codes
     pub const VALIDATION_ERROR_CODES: &[u32] =
-        &[1001, 1002, 1003, 1004, 1005, 1006, 1007, 1008, 1009, 1010];
+        &[1001, 1002, 1003, 1004, 1005, 1006, 1007, 1008, 1009, 1010, 1011, 1012, 1013, 1014];
 
     /// Get all authorization error codes
     pub const AUTHORIZATION_ERROR_CODES: &[u32] =
@@ -537,7 +626,7 @@ pub mod registry {
 
     /// Get all cryptographic error codes
     pub const CRYPTO_ERROR_CODES: &[u32] =
-        &[1301, 1302, 1303, 1304, 1305, 1306, 1307, 1308, 1309, 1310];
+        &[1301, 1302, 1303, 1304, 1305, 1306, 1307, 1308, 1309, 1310, 1311];
 
     /// Get all state error codes
     pub const STATE_ERROR_CODES: &[u32] =
@@ -645,6 +734,10 @@ mod comprehensive_tests {
             ValidationError::InvalidAddress,
             ValidationError::InvalidTimestamp,
             ValidationError::InvalidSignatureFormat,
+            ValidationError::CapacityExceeded,
+            ValidationError::UnknownAgent,
+            ValidationError::Cooldown,
+            ValidationError::AccountTooNew,
         ];
 
         for (i, error) in errors.iter().enumerate() {
@@ -712,6 +805,7 @@ mod comprehensive_tests {
             CryptoError::DecryptionFailed,
             CryptoError::InvalidNonce,
             CryptoError::KeyDerivationFailed,
+            CryptoError::SignatureExpired,
         ];
 
         for (i, error) in errors.iter().enumerate() {
@@ -735,6 +829,9 @@ mod comprehensive_tests {
             StateError::CircuitBreakerOpen,
             StateError::MaintenanceMode,
             StateError::InvalidVersion,
+            StateError::MigrationInProgress,
+            StateError::BudgetExceeded,
+            StateError::Conflict,
         ];
 
         for (i, error) in errors.iter().enumerate() {
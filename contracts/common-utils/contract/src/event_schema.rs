@@ -0,0 +1,109 @@
+//! # Versioned Event Envelope
+//!
+//! Every migrated event is published with its schema version as the second
+//! topic, so indexers can dispatch on `(topic, version)` and keep parsing old
+//! payload shapes after a contract changes them:
+//!
+//! ```text
+//! topics: (topic, version)            or (topic, version, subject)
+//! data:   contract-defined payload for that version
+//! ```
+//!
+//! ## Transition
+//!
+//! While indexers migrate, the pre-envelope event (`(topic,)` or
+//! `(topic, subject)` with the same data) is emitted as well. Legacy emission
+//! is on by default and is turned off per contract with
+//! [`set_legacy_events`] once all consumers read the versioned form.
+//!
+//! ## Namespace
+//!
+//! Deployments that share an indexer (staging, prod) can set a namespace
+//! with [`set_event_namespace`]. It is prepended to the topics of versioned
+//! events and of events published through [`emit`]:
+//!
+//! ```text
+//! topics: (namespace, topic, version, subject)
+//! ```
+//!
+//! Legacy events keep their exact pre-envelope shape. With no namespace set,
+//! nothing changes.
+
+use soroban_sdk::{contracttype, Env, IntoVal, Symbol, Val, Vec};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EventSchemaKey {
+    LegacyEvents,
+    Namespace,
+}
+
+/// Namespace prepended to event topics, if one is set.
+pub fn event_namespace(env: &Env) -> Option<Symbol> {
+    env.storage().instance().get(&EventSchemaKey::Namespace)
+}
+
+/// Set or, with `None`, clear the event namespace.
+/// Callers are responsible for authorising the change.
+pub fn set_event_namespace(env: &Env, namespace: Option<Symbol>) {
+    match namespace {
+        Some(namespace) => env
+            .storage()
+            .instance()
+            .set(&EventSchemaKey::Namespace, &namespace),
+        None => env.storage().instance().remove(&EventSchemaKey::Namespace),
+    }
+}
+
+/// Publish `data` under `topics`, behind the namespace when one is set.
+pub fn emit<T, D>(env: &Env, topics: T, data: D)
+where
+    T: IntoVal<Env, Vec<Val>>,
+    D: IntoVal<Env, Val>,
+{
+    let mut topics: Vec<Val> = topics.into_val(env);
+    if let Some(namespace) = event_namespace(env) {
+        topics.push_front(namespace.into_val(env));
+    }
+    env.events().publish(topics, data);
+}
+
+/// Whether pre-envelope events are still emitted alongside versioned ones.
+pub fn legacy_events_enabled(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&EventSchemaKey::LegacyEvents)
+        .unwrap_or(true)
+}
+
+/// Enable or disable the backward-compatible (unversioned) emission.
+/// Callers are responsible for authorising the change.
+pub fn set_legacy_events(env: &Env, enabled: bool) {
+    env.storage()
+        .instance()
+        .set(&EventSchemaKey::LegacyEvents, &enabled);
+}
+
+/// Publish `data` under `(topic, version)`.
+pub fn emit_versioned<D>(env: &Env, topic: Symbol, version: u32, data: D)
+where
+    D: IntoVal<Env, Val> + Clone,
+{
+    emit(env, (topic.clone(), version), data.clone());
+    if legacy_events_enabled(env) {
+        env.events().publish((topic,), data);
+    }
+}
+
+/// Publish `data` under `(topic, version, subject)` for events that are
+/// keyed by an entity (agent, account, listing).
+pub fn emit_versioned_for<S, D>(env: &Env, topic: Symbol, version: u32, subject: S, data: D)
+where
+    S: IntoVal<Env, Val> + Clone,
+    D: IntoVal<Env, Val> + Clone,
+{
+    emit(env, (topic.clone(), version, subject.clone()), data.clone());
+    if legacy_events_enabled(env) {
+        env.events().publish((topic, subject), data);
+    }
+}
@@ -0,0 +1,199 @@
+#![cfg(test)]
+
+use crate::event_schema::{
+    emit_versioned, emit_versioned_for, legacy_events_enabled, set_event_namespace, set_legacy_events,
+};
+use crate::marketplace::{ListingType, MarketplaceContract, MarketplaceContractClient, MARKETPLACE_EVENT_VERSION};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, Events},
+    token::StellarAssetClient,
+    vec, Address, Env, IntoVal, Symbol, Val, Vec,
+};
+
+#[contract]
+pub struct EventHarness;
+
+#[contractimpl]
+impl EventHarness {
+    pub fn emit(env: Env, version: u32) {
+        emit_versioned(&env, symbol_short!("ping"), version, 42u32);
+    }
+
+    pub fn emit_for(env: Env, version: u32, subject: Address) {
+        emit_versioned_for(&env, symbol_short!("ping"), version, subject, 42u32);
+    }
+
+    pub fn set_legacy(env: Env, enabled: bool) {
+        set_legacy_events(&env, enabled);
+    }
+
+    pub fn legacy(env: Env) -> bool {
+        legacy_events_enabled(&env)
+    }
+
+    pub fn set_namespace(env: Env, namespace: Option<Symbol>) {
+        set_event_namespace(&env, namespace);
+    }
+}
+
+fn topics_emitted(env: &Env) -> Vec<Vec<Val>> {
+    let mut out = Vec::new(env);
+    for (_, topics, _) in env.events().all().iter() {
+        out.push_back(topics);
+    }
+    out
+}
+
+#[test]
+fn test_version_is_second_topic() {
+    let env = Env::default();
+    let id = env.register(EventHarness, ());
+    let client = EventHarnessClient::new(&env, &id);
+
+    client.emit(&3);
+
+    let topics = topics_emitted(&env);
+    let expected: Vec<Val> = vec![&env, symbol_short!("ping").into_val(&env), 3u32.into_val(&env)];
+    assert!(topics.contains(&expected));
+}
+
+#[test]
+fn test_subject_follows_version() {
+    let env = Env::default();
+    let id = env.register(EventHarness, ());
+    let client = EventHarnessClient::new(&env, &id);
+    let subject = Address::generate(&env);
+
+    client.emit_for(&1, &subject);
+
+    let topics = topics_emitted(&env);
+    let expected: Vec<Val> = vec![
+        &env,
+        symbol_short!("ping").into_val(&env),
+        1u32.into_val(&env),
+        subject.into_val(&env),
+    ];
+    assert!(topics.contains(&expected));
+}
+
+#[test]
+fn test_legacy_emission_is_on_by_default() {
+    let env = Env::default();
+    let id = env.register(EventHarness, ());
+    let client = EventHarnessClient::new(&env, &id);
+    let subject = Address::generate(&env);
+
+    assert!(client.legacy());
+    client.emit_for(&1, &subject);
+
+    let topics = topics_emitted(&env);
+    assert_eq!(topics.len(), 2);
+    let legacy: Vec<Val> = vec![&env, symbol_short!("ping").into_val(&env), subject.into_val(&env)];
+    assert!(topics.contains(&legacy));
+}
+
+#[test]
+fn test_legacy_emission_can_be_disabled() {
+    let env = Env::default();
+    let id = env.register(EventHarness, ());
+    let client = EventHarnessClient::new(&env, &id);
+
+    client.set_legacy(&false);
+    client.emit(&2);
+
+    let topics = topics_emitted(&env);
+    assert_eq!(topics.len(), 1);
+    let expected: Vec<Val> = vec![&env, symbol_short!("ping").into_val(&env), 2u32.into_val(&env)];
+    assert_eq!(topics.get(0).unwrap(), expected);
+}
+
+#[test]
+fn test_marketplace_events_carry_version() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let issuer = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let royalty_recipient = Address::generate(&env);
+
+    let asset = env.register_stellar_asset_contract_v2(issuer.clone()).address();
+    let currency = env.register_stellar_asset_contract_v2(issuer).address();
+    StellarAssetClient::new(&env, &asset).mint(&seller, &1);
+    StellarAssetClient::new(&env, &currency).mint(&buyer, &1_000);
+
+    let id = env.register(MarketplaceContract, ());
+    let client = MarketplaceContractClient::new(&env, &id);
+
+    client.list_agent(
+        &seller,
+        &7u64,
+        &asset,
+        &1_000,
+        &currency,
+        &ListingType::FixedPrice,
+        &500,
+        &royalty_recipient,
+    );
+    let listed: Vec<Val> = vec![
+        &env,
+        symbol_short!("listed").into_val(&env),
+        MARKETPLACE_EVENT_VERSION.into_val(&env),
+        7u64.into_val(&env),
+    ];
+    assert!(topics_emitted(&env).contains(&listed));
+
+    client.buy_agent(&buyer, &7u64);
+    let sold: Vec<Val> = vec![
+        &env,
+        symbol_short!("sold").into_val(&env),
+        MARKETPLACE_EVENT_VERSION.into_val(&env),
+        7u64.into_val(&env),
+    ];
+    assert!(topics_emitted(&env).contains(&sold));
+}
+
+#[test]
+fn test_namespace_prefixes_versioned_topics() {
+    let env = Env::default();
+    let id = env.register(EventHarness, ());
+    let client = EventHarnessClient::new(&env, &id);
+    let subject = Address::generate(&env);
+
+    client.set_namespace(&Some(symbol_short!("staging")));
+    client.emit_for(&1, &subject);
+
+    let topics = topics_emitted(&env);
+    let namespaced: Vec<Val> = vec![
+        &env,
+        symbol_short!("staging").into_val(&env),
+        symbol_short!("ping").into_val(&env),
+        1u32.into_val(&env),
+        subject.into_val(&env),
+    ];
+    let legacy: Vec<Val> = vec![
+        &env,
+        symbol_short!("ping").into_val(&env),
+        subject.into_val(&env),
+    ];
+    assert!(topics.contains(&namespaced));
+    assert!(topics.contains(&legacy));
+    assert_eq!(topics.len(), 2);
+}
+
+#[test]
+fn test_cleared_namespace_restores_default_topics() {
+    let env = Env::default();
+    let id = env.register(EventHarness, ());
+    let client = EventHarnessClient::new(&env, &id);
+
+    client.set_namespace(&Some(symbol_short!("staging")));
+    client.set_namespace(&None);
+    client.emit(&3);
+
+    let topics = topics_emitted(&env);
+    let expected: Vec<Val> = vec![&env, symbol_short!("ping").into_val(&env), 3u32.into_val(&env)];
+    assert!(topics.contains(&expected));
+    assert_eq!(topics.len(), 2);
+}
@@ -0,0 +1,226 @@
+#![cfg(test)]
+
+use crate::{AgentProfile, Attestation, Error, EvolutionManager, EvolutionManagerClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, BytesN, Env,
+};
+
+fn setup(env: &Env) -> (EvolutionManagerClient<'_>, Address) {
+    env.mock_all_auths();
+    let id = env.register(EvolutionManager, ());
+    let client = EvolutionManagerClient::new(env, &id);
+    let admin = Address::generate(env);
+    client.initialize_evolution(&admin, &Address::generate(env));
+    (client, admin)
+}
+
+fn attestation(env: &Env, agent: &Address, level: u32, hash: u8, nonce: u64) -> Attestation {
+    Attestation {
+        agent: agent.clone(),
+        new_level: level,
+        stake_amount: 100,
+        attestation_hash: BytesN::from_array(env, &[hash; 32]),
+        nonce,
+    }
+}
+
+#[test]
+fn test_initialize_evolution_requires_admin_and_runs_once() {
+    let env = Env::default();
+    let id = env.register(EvolutionManager, ());
+    let client = EvolutionManagerClient::new(&env, &id);
+    let (admin, bridge) = (Address::generate(&env), Address::generate(&env));
+
+    assert!(client.try_initialize_evolution(&admin, &bridge).is_err());
+    env.mock_all_auths();
+    client.initialize_evolution(&admin, &bridge);
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(
+        client.try_initialize_evolution(&Address::generate(&env), &bridge),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+    assert_eq!(client.get_trusted_bridge(), Some(bridge));
+}
+
+#[test]
+fn test_only_the_trusted_bridge_applies_attestations() {
+    let env = Env::default();
+    let id = env.register(EvolutionManager, ());
+    let client = EvolutionManagerClient::new(&env, &id);
+    let (admin, bridge) = (Address::generate(&env), Address::generate(&env));
+    let agent = Address::generate(&env);
+    env.mock_all_auths();
+    client.initialize_evolution(&admin, &bridge);
+
+    client.apply_attestation(&attestation(&env, &agent, 1, 1, 0));
+    assert_eq!(env.auths()[0].0, bridge);
+
+    // After rotation the new bridge signs instead
+    let rotated = Address::generate(&env);
+    client.set_trusted_bridge(&admin, &rotated);
+    client.apply_attestation(&attestation(&env, &agent, 2, 2, 0));
+    assert_eq!(env.auths()[0].0, rotated);
+    assert_eq!(
+        client.try_set_trusted_bridge(&agent, &agent),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    // Nothing signed: neither an attestation nor a bare event gets through
+    env.mock_auths(&[]);
+    assert!(client.try_apply_attestation(&attestation(&env, &agent, 3, 3, 0)).is_err());
+    assert!(client
+        .try_emit_evolution_completed(&agent, &9, &0, &BytesN::from_array(&env, &[9; 32]))
+        .is_err());
+    assert_eq!(client.get_agent_level(&agent), 2);
+}
+
+#[test]
+fn test_global_scope_rejects_shared_hash() {
+    let env = Env::default();
+    let (client, _) = setup(&env);
+    let (first, second) = (Address::generate(&env), Address::generate(&env));
+
+    client.apply_attestation(&attestation(&env, &first, 1, 7, 0));
+    assert_eq!(
+        client.try_apply_attestation(&attestation(&env, &second, 1, 7, 0)),
+        Err(Ok(Error::AttestationUsed))
+    );
+    assert_eq!(client.get_agent_level(&first), 1);
+    assert_eq!(client.get_agent_stake(&first), 100);
+    assert_eq!(client.get_agent_level(&second), 0);
+}
+
+#[test]
+fn test_scoped_hash_is_unique_per_agent() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    client.set_attestation_scope(&admin, &true);
+    let (first, second) = (Address::generate(&env), Address::generate(&env));
+
+    client.apply_attestation(&attestation(&env, &first, 1, 7, 1));
+    client.apply_attestation(&attestation(&env, &second, 1, 7, 1));
+    assert_eq!(client.get_agent_level(&second), 1);
+    assert_eq!(client.get_evolution_nonce(&first), 1);
+    assert_eq!(client.get_evolution_nonce(&second), 1);
+
+    // Same hash again for the same agent, even with the next nonce
+    assert_eq!(
+        client.try_apply_attestation(&attestation(&env, &first, 2, 7, 2)),
+        Err(Ok(Error::AttestationUsed))
+    );
+}
+
+#[test]
+fn test_scoped_attestations_must_be_in_nonce_order() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    client.set_attestation_scope(&admin, &true);
+    let agent = Address::generate(&env);
+
+    let first = attestation(&env, &agent, 1, 1, 1);
+    client.apply_attestation(&first);
+    assert_eq!(client.try_apply_attestation(&first), Err(Ok(Error::InvalidNonce)));
+    assert_eq!(
+        client.try_apply_attestation(&attestation(&env, &agent, 3, 3, 3)),
+        Err(Ok(Error::InvalidNonce))
+    );
+
+    client.apply_attestation(&attestation(&env, &agent, 2, 2, 2));
+    assert_eq!(client.get_agent_level(&agent), 2);
+    assert_eq!(client.get_agent_stake(&agent), 200);
+    assert_eq!(client.get_evolution_nonce(&agent), 2);
+}
+
+#[test]
+fn test_level_requirement_gates_level_jumps() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let agent = Address::generate(&env);
+    client.set_level_requirement(&admin, &2, &200);
+    client.set_level_requirement(&admin, &3, &301);
+    assert_eq!(client.get_level_requirement(&2), 200);
+    assert_eq!(client.get_level_requirement(&1), 0);
+    assert_eq!(client.try_set_level_requirement(&admin, &4, &-1), Err(Ok(Error::InvalidStake)));
+
+    // 100 staked is short of level 2
+    assert_eq!(
+        client.try_apply_attestation(&attestation(&env, &agent, 2, 1, 0)),
+        Err(Ok(Error::InsufficientStake))
+    );
+    assert_eq!(client.get_agent_stake(&agent), 0);
+    client.apply_attestation(&attestation(&env, &agent, 1, 1, 0));
+
+    // Exactly 200 after this attestation meets the requirement
+    client.apply_attestation(&attestation(&env, &agent, 2, 2, 0));
+    assert_eq!(client.get_agent_level(&agent), 2);
+
+    // 300 is one short of level 3
+    assert_eq!(
+        client.try_apply_attestation(&attestation(&env, &agent, 3, 3, 0)),
+        Err(Ok(Error::InsufficientStake))
+    );
+    assert_eq!(client.get_agent_level(&agent), 2);
+}
+
+#[test]
+fn test_agent_profile_reflects_applied_attestation() {
+    let env = Env::default();
+    let (client, _) = setup(&env);
+    let agent = Address::generate(&env);
+    assert_eq!(
+        client.get_agent_profile(&agent),
+        AgentProfile { level: 0, stake: 0, last_evolved: 0 }
+    );
+
+    env.ledger().set_timestamp(5_000);
+    client.apply_attestation(&attestation(&env, &agent, 1, 1, 0));
+    env.ledger().set_timestamp(6_000);
+    client.apply_attestation(&attestation(&env, &agent, 3, 2, 0));
+
+    assert_eq!(
+        client.get_agent_profile(&agent),
+        AgentProfile { level: 3, stake: 200, last_evolved: 6_000 }
+    );
+}
+
+#[test]
+fn test_stake_overflow_rejects_attestation() {
+    let env = Env::default();
+    let (client, _) = setup(&env);
+    let agent = Address::generate(&env);
+    let mut topped_up = attestation(&env, &agent, 1, 1, 0);
+    topped_up.stake_amount = i128::MAX;
+    client.apply_attestation(&topped_up);
+
+    // i128::MAX + 100 does not fit; the hash stays unused
+    assert_eq!(
+        client.try_apply_attestation(&attestation(&env, &agent, 2, 2, 0)),
+        Err(Ok(Error::InvalidStake))
+    );
+    assert_eq!(client.get_agent_stake(&agent), i128::MAX);
+    let mut empty = attestation(&env, &agent, 2, 2, 0);
+    empty.stake_amount = 0;
+    client.apply_attestation(&empty);
+    assert_eq!(client.get_agent_level(&agent), 2);
+}
+
+#[test]
+fn test_nonce_saturates_at_bound() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    client.set_attestation_scope(&admin, &true);
+    let agent = Address::generate(&env);
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&crate::DataKey::EvolutionNonce(agent.clone()), &u64::MAX);
+    });
+
+    // The next nonce stays at the bound; the per-agent hash check still
+    // stops a replay
+    let last = attestation(&env, &agent, 1, 1, u64::MAX);
+    client.apply_attestation(&last);
+    assert_eq!(client.get_evolution_nonce(&agent), u64::MAX);
+    assert_eq!(client.try_apply_attestation(&last), Err(Ok(Error::AttestationUsed)));
+}
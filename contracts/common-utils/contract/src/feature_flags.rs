@@ -0,0 +1,49 @@
+//! # Feature Flags
+//!
+//! One registry of named on/off toggles per contract, kept in instance
+//! storage as a single map:
+//!
+//! ```text
+//! FeatureFlagKey::Flags -> Map<Symbol, bool>
+//! ```
+//!
+//! Flags that were never set read as disabled, so a contract can gate a new
+//! code path behind a flag without migrating anything. Disabling a flag
+//! removes its entry.
+
+use soroban_sdk::{contracttype, Env, Map, Symbol};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FeatureFlagKey {
+    Flags,
+}
+
+/// Whether `flag` is on. Unknown flags are off.
+pub fn is_feature_enabled(env: &Env, flag: &Symbol) -> bool {
+    get_all_features(env).get(flag.clone()).unwrap_or(false)
+}
+
+/// Turn `flag` on or off.
+/// Callers are responsible for authorising the change.
+pub fn set_feature(env: &Env, flag: Symbol, enabled: bool) {
+    let mut flags = get_all_features(env);
+    if enabled {
+        flags.set(flag, true);
+    } else {
+        flags.remove(flag);
+    }
+    if flags.is_empty() {
+        env.storage().instance().remove(&FeatureFlagKey::Flags);
+    } else {
+        env.storage().instance().set(&FeatureFlagKey::Flags, &flags);
+    }
+}
+
+/// Every flag that is currently on.
+pub fn get_all_features(env: &Env) -> Map<Symbol, bool> {
+    env.storage()
+        .instance()
+        .get(&FeatureFlagKey::Flags)
+        .unwrap_or_else(|| Map::new(env))
+}
@@ -0,0 +1,32 @@
+#![cfg(test)]
+
+use crate::feature_flags::{get_all_features, is_feature_enabled, set_feature};
+use crate::CommonUtilsContract;
+use soroban_sdk::{symbol_short, Env};
+
+#[test]
+fn test_unknown_flags_default_to_disabled() {
+    let env = Env::default();
+    let id = env.register(CommonUtilsContract, ());
+    env.as_contract(&id, || {
+        assert!(!is_feature_enabled(&env, &symbol_short!("decay")));
+        assert!(get_all_features(&env).is_empty());
+    });
+}
+
+#[test]
+fn test_toggling_a_flag_leaves_others_alone() {
+    let env = Env::default();
+    let id = env.register(CommonUtilsContract, ());
+    env.as_contract(&id, || {
+        set_feature(&env, symbol_short!("decay"), true);
+        set_feature(&env, symbol_short!("dedupe"), true);
+        assert!(is_feature_enabled(&env, &symbol_short!("decay")));
+        assert_eq!(get_all_features(&env).len(), 2);
+
+        set_feature(&env, symbol_short!("decay"), false);
+        assert!(!is_feature_enabled(&env, &symbol_short!("decay")));
+        assert!(is_feature_enabled(&env, &symbol_short!("dedupe")));
+        assert_eq!(get_all_features(&env).len(), 1);
+    });
+}
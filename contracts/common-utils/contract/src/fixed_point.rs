@@ -0,0 +1,239 @@
+//! # Fixed-Point Math
+//!
+//! Deterministic fractional arithmetic for `no_std` contracts, where floats
+//! are off limits. A `FixedPoint` is an `i128` scaled by `2^frac_bits`:
+//!
+//! ```text
+//! value = raw / 2^frac_bits
+//! ```
+//!
+//! Every operation is integer-only and saturates at the `i128` bounds
+//! instead of overflowing, so the same inputs give the same bits on every
+//! validator. Products and quotients are computed at 256-bit width, so an
+//! intermediate overflow never saturates a result that fits.
+//!
+//! Contracts choose their precision once with `set_precision`; it is kept in
+//! instance storage under `FixedPointKey::FracBits`.
+
+use soroban_sdk::{contracttype, Env};
+
+use crate::error::CommonError;
+
+/// Fractional bits used until a contract calls `set_precision`
+pub const DEFAULT_FRAC_BITS: u32 = 32;
+
+/// Most fractional bits `set_precision` accepts
+pub const MAX_FRAC_BITS: u32 = 64;
+
+const LOW_MASK: u128 = u64::MAX as u128;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FixedPointKey {
+    FracBits,
+}
+
+/// Fractional bits this contract computes with.
+pub fn precision(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&FixedPointKey::FracBits)
+        .unwrap_or(DEFAULT_FRAC_BITS)
+}
+
+/// Change the fractional bits used by `precision`.
+/// Callers are responsible for authorising the change.
+pub fn set_precision(env: &Env, frac_bits: u32) -> Result<(), CommonError> {
+    if frac_bits > MAX_FRAC_BITS {
+        return Err(CommonError::OutOfRange);
+    }
+    env.storage().instance().set(&FixedPointKey::FracBits, &frac_bits);
+    Ok(())
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FixedPoint {
+    raw: i128,
+    frac_bits: u32,
+}
+
+impl FixedPoint {
+    /// Wrap an already scaled value. `frac_bits` is capped at `MAX_FRAC_BITS`.
+    pub fn from_raw(raw: i128, frac_bits: u32) -> Self {
+        Self { raw, frac_bits: frac_bits.min(MAX_FRAC_BITS) }
+    }
+
+    pub fn from_int(value: i128, frac_bits: u32) -> Self {
+        Self::from_ratio(value, 1, frac_bits)
+    }
+
+    /// `num / den`, truncated toward zero. Division by zero saturates toward
+    /// the sign of `num`; `0 / 0` is zero.
+    pub fn from_ratio(num: i128, den: i128, frac_bits: u32) -> Self {
+        let frac_bits = frac_bits.min(MAX_FRAC_BITS);
+        let (hi, lo) = shl_wide(num.unsigned_abs(), frac_bits);
+        let raw = div_wide(hi, lo, den.unsigned_abs(), negative(num, den));
+        Self { raw, frac_bits }
+    }
+
+    pub fn one(frac_bits: u32) -> Self {
+        Self::from_int(1, frac_bits)
+    }
+
+    pub fn raw(&self) -> i128 {
+        self.raw
+    }
+
+    pub fn frac_bits(&self) -> u32 {
+        self.frac_bits
+    }
+
+    pub fn saturating_add(self, other: Self) -> Self {
+        let other = other.rescale(self.frac_bits);
+        Self { raw: self.raw.saturating_add(other.raw), ..self }
+    }
+
+    pub fn saturating_sub(self, other: Self) -> Self {
+        let other = other.rescale(self.frac_bits);
+        Self { raw: self.raw.saturating_sub(other.raw), ..self }
+    }
+
+    /// Product in `self`'s precision, truncated toward zero.
+    pub fn saturating_mul(self, other: Self) -> Self {
+        let other = other.rescale(self.frac_bits);
+        let (hi, lo) = mul_wide(self.raw.unsigned_abs(), other.raw.unsigned_abs());
+        let raw = div_wide(
+            hi >> self.frac_bits,
+            shr_wide(hi, lo, self.frac_bits),
+            1,
+            negative(self.raw, other.raw),
+        );
+        Self { raw, ..self }
+    }
+
+    /// Quotient in `self`'s precision, truncated toward zero. Division by
+    /// zero saturates like `from_ratio`.
+    pub fn saturating_div(self, other: Self) -> Self {
+        let other = other.rescale(self.frac_bits);
+        let (hi, lo) = shl_wide(self.raw.unsigned_abs(), self.frac_bits);
+        let raw = div_wide(hi, lo, other.raw.unsigned_abs(), negative(self.raw, other.raw));
+        Self { raw, ..self }
+    }
+
+    /// `value * self`, truncated toward zero.
+    pub fn mul_int(self, value: i128) -> i128 {
+        self.saturating_mul(Self::from_int(value, self.frac_bits)).trunc()
+    }
+
+    /// Integer part, truncated toward zero.
+    pub fn trunc(&self) -> i128 {
+        let magnitude = self.raw.unsigned_abs() >> self.frac_bits;
+        from_magnitude(magnitude, self.raw < 0)
+    }
+
+    /// Nearest integer, halves rounded away from zero.
+    pub fn round(&self) -> i128 {
+        let magnitude = self.raw.unsigned_abs();
+        let rounded = if self.frac_bits == 0 {
+            magnitude
+        } else {
+            let half = 1u128 << (self.frac_bits - 1);
+            let fraction = magnitude & ((half << 1) - 1);
+            (magnitude >> self.frac_bits) + (fraction >= half) as u128
+        };
+        from_magnitude(rounded, self.raw < 0)
+    }
+
+    /// Same value at `frac_bits` of precision, truncating dropped bits.
+    pub fn rescale(self, frac_bits: u32) -> Self {
+        let frac_bits = frac_bits.min(MAX_FRAC_BITS);
+        let negative = self.raw < 0;
+        let magnitude = self.raw.unsigned_abs();
+        let raw = if frac_bits >= self.frac_bits {
+            let (hi, lo) = shl_wide(magnitude, frac_bits - self.frac_bits);
+            div_wide(hi, lo, 1, negative)
+        } else {
+            from_magnitude(magnitude >> (self.frac_bits - frac_bits), negative)
+        };
+        Self { raw, frac_bits }
+    }
+}
+
+fn negative(a: i128, b: i128) -> bool {
+    (a < 0) != (b < 0)
+}
+
+/// Signed value of `magnitude`, saturating at the `i128` bounds.
+fn from_magnitude(magnitude: u128, negative: bool) -> i128 {
+    if negative {
+        if magnitude > i128::MAX as u128 {
+            i128::MIN
+        } else {
+            -(magnitude as i128)
+        }
+    } else {
+        magnitude.min(i128::MAX as u128) as i128
+    }
+}
+
+/// Full 256-bit product as `(hi, lo)`.
+fn mul_wide(a: u128, b: u128) -> (u128, u128) {
+    let (a1, a0) = (a >> 64, a & LOW_MASK);
+    let (b1, b0) = (b >> 64, b & LOW_MASK);
+    let p00 = a0 * b0;
+    let p01 = a0 * b1;
+    let p10 = a1 * b0;
+    let p11 = a1 * b1;
+
+    let mid = (p00 >> 64) + (p01 & LOW_MASK) + (p10 & LOW_MASK);
+    let lo = (p00 & LOW_MASK) | (mid << 64);
+    let hi = p11 + (p01 >> 64) + (p10 >> 64) + (mid >> 64);
+    (hi, lo)
+}
+
+/// `value << shift` at 256-bit width, for `shift <= MAX_FRAC_BITS`.
+fn shl_wide(value: u128, shift: u32) -> (u128, u128) {
+    if shift == 0 {
+        return (0, value);
+    }
+    (value >> (128 - shift), value << shift)
+}
+
+/// Low 128 bits of `(hi, lo) >> shift`, for `shift <= MAX_FRAC_BITS`.
+fn shr_wide(hi: u128, lo: u128, shift: u32) -> u128 {
+    if shift == 0 {
+        return lo;
+    }
+    (lo >> shift) | (hi << (128 - shift))
+}
+
+/// Signed `(hi, lo) / divisor`, truncated, saturating when the quotient
+/// does not fit or `divisor` is zero.
+fn div_wide(hi: u128, lo: u128, divisor: u128, negative: bool) -> i128 {
+    if divisor == 0 {
+        if hi == 0 && lo == 0 {
+            return 0;
+        }
+        return if negative { i128::MIN } else { i128::MAX };
+    }
+    if hi >= divisor {
+        return if negative { i128::MIN } else { i128::MAX };
+    }
+    if hi == 0 {
+        return from_magnitude(lo / divisor, negative);
+    }
+
+    // Restoring long division; `hi < divisor` keeps the quotient in 128 bits
+    let mut remainder = hi;
+    let mut quotient = 0u128;
+    for bit in (0..128).rev() {
+        let carry = remainder >> 127;
+        remainder = (remainder << 1) | ((lo >> bit) & 1);
+        quotient <<= 1;
+        if carry == 1 || remainder >= divisor {
+            remainder = remainder.wrapping_sub(divisor);
+            quotient |= 1;
+        }
+    }
+    from_magnitude(quotient, negative)
+}
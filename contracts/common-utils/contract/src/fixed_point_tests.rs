@@ -0,0 +1,84 @@
+#![cfg(test)]
+
+use crate::error::CommonError;
+use crate::fixed_point::{precision, set_precision, FixedPoint, DEFAULT_FRAC_BITS, MAX_FRAC_BITS};
+use crate::CommonUtilsContract;
+use soroban_sdk::Env;
+
+fn fp(num: i128, den: i128) -> FixedPoint {
+    FixedPoint::from_ratio(num, den, DEFAULT_FRAC_BITS)
+}
+
+#[test]
+fn test_precision_is_configurable_and_bounded() {
+    let env = Env::default();
+    let id = env.register(CommonUtilsContract, ());
+    env.as_contract(&id, || {
+        assert_eq!(precision(&env), DEFAULT_FRAC_BITS);
+        set_precision(&env, 16).unwrap();
+        assert_eq!(precision(&env), 16);
+        assert_eq!(set_precision(&env, MAX_FRAC_BITS + 1), Err(CommonError::OutOfRange));
+        assert_eq!(precision(&env), 16);
+    });
+}
+
+#[test]
+fn test_round_and_trunc() {
+    assert_eq!(fp(5, 2).round(), 3);
+    assert_eq!(fp(5, 2).trunc(), 2);
+    assert_eq!(fp(-5, 2).round(), -3);
+    assert_eq!(fp(-5, 2).trunc(), -2);
+    assert_eq!(fp(7, 3).round(), 2);
+    assert_eq!(fp(8, 3).round(), 3);
+    assert_eq!(FixedPoint::from_ratio(5, 2, 0).round(), 2);
+}
+
+#[test]
+fn test_mul_and_div_truncate_toward_zero() {
+    assert_eq!(fp(3, 2).saturating_mul(fp(3, 2)), fp(9, 4));
+    assert_eq!(fp(9, 4).saturating_div(fp(3, 2)), fp(3, 2));
+    assert_eq!(fp(1, 4).mul_int(3), 0);
+    assert_eq!(fp(1, 4).mul_int(-10), -2);
+    assert_eq!(fp(2, 3).saturating_mul(fp(-3, 1)).round(), -2);
+}
+
+#[test]
+fn test_mixed_precision_uses_left_operand() {
+    let coarse = FixedPoint::from_ratio(1, 2, 8);
+    let product = fp(3, 1).saturating_mul(coarse);
+    assert_eq!(product.frac_bits(), DEFAULT_FRAC_BITS);
+    assert_eq!(product, fp(3, 2));
+    assert_eq!(coarse.saturating_add(fp(1, 4)), FixedPoint::from_ratio(3, 4, 8));
+}
+
+#[test]
+fn test_saturates_at_extremes() {
+    let huge = FixedPoint::from_int(i128::MAX, DEFAULT_FRAC_BITS);
+    assert_eq!(huge.raw(), i128::MAX);
+    assert_eq!(huge.saturating_mul(fp(2, 1)).raw(), i128::MAX);
+    assert_eq!(huge.saturating_mul(fp(-2, 1)).raw(), i128::MIN);
+    assert_eq!(huge.saturating_add(huge).raw(), i128::MAX);
+    assert_eq!(fp(1, 1).saturating_div(fp(0, 1)).raw(), i128::MAX);
+    assert_eq!(fp(-1, 1).saturating_div(fp(0, 1)).raw(), i128::MIN);
+    assert_eq!(fp(0, 1).saturating_div(fp(0, 1)).raw(), 0);
+}
+
+#[test]
+fn test_wide_intermediates_do_not_saturate() {
+    // Both raw values overflow i128 when multiplied, the result does not
+    let big = fp(1 << 90, 1);
+    let small = fp(1, 1 << 20);
+    assert_eq!(big.saturating_mul(small), fp(1 << 70, 1));
+    assert_eq!(big.saturating_div(fp(1 << 60, 1)), fp(1 << 30, 1));
+}
+
+#[test]
+fn test_results_are_bit_for_bit_stable() {
+    // Pinned raw values: a change here changes consensus results
+    assert_eq!(fp(1, 3).raw(), 1_431_655_765);
+    assert_eq!(fp(2, 3).saturating_mul(fp(2, 3)).raw(), 1_908_874_352);
+    assert_eq!(FixedPoint::from_ratio(1, 3, MAX_FRAC_BITS).raw(), 6_148_914_691_236_517_205);
+    for _ in 0..3 {
+        assert_eq!(fp(22, 7).saturating_div(fp(3, 1)), fp(22, 21));
+    }
+}
@@ -0,0 +1,44 @@
+//! # Health Probes
+//!
+//! Readiness checks for monitoring: a contract's `health` entrypoint
+//! reports its state and, for each dependency it has configured, whether
+//! a cheap read on it succeeds. Reads go through `try_invoke_contract`, so
+//! an unreachable or trapping dependency is reported as failed instead of
+//! aborting the whole probe.
+
+use soroban_sdk::{contracttype, Address, Env, InvokeError, Symbol, TryFromVal, Val, Vec};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HealthReport {
+    /// See `state_machine::state_label`
+    pub state_label: Symbol,
+    /// Whether the ACL answered a permission check
+    pub acl_ok: bool,
+    /// `(dependency, reachable)` for every other configured dependency
+    pub deps: Vec<(Symbol, bool)>,
+}
+
+/// `function(args)` on `contract`, or `None` when the call fails or returns
+/// something other than a `T`.
+pub fn try_read<T: TryFromVal<Env, Val>>(env: &Env, contract: &Address, function: &str, args: Vec<Val>) -> Option<T> {
+    match env.try_invoke_contract::<T, soroban_sdk::Error>(contract, &Symbol::new(env, function), args) {
+        Ok(Ok(value)) => Some(value),
+        _ => None,
+    }
+}
+
+/// Whether `function(args)` on `contract` returns without failing.
+pub fn probe(env: &Env, contract: &Address, function: &str, args: Vec<Val>) -> bool {
+    try_read::<Val>(env, contract, function, args).is_some()
+}
+
+/// Whether `contract` answers `function(args)`, either with a value or with
+/// one of its own contract errors (say, "not found" for the probed key).
+/// Traps, missing functions and missing contracts count as failed.
+pub fn answers(env: &Env, contract: &Address, function: &str, args: Vec<Val>) -> bool {
+    matches!(
+        env.try_invoke_contract::<Val, InvokeError>(contract, &Symbol::new(env, function), args),
+        Ok(Ok(_)) | Err(Ok(InvokeError::Contract(_)))
+    )
+}
@@ -0,0 +1,52 @@
+#![cfg(test)]
+
+use crate::health::{answers, probe, try_read};
+use soroban_sdk::{contract, contracterror, contractimpl, testutils::Address as _, Address, Env, Vec};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum AnswerError {
+    NotFound = 1,
+}
+
+#[contract]
+pub struct Answering;
+
+#[contractimpl]
+impl Answering {
+    pub fn answer(_env: Env) -> u32 {
+        42
+    }
+
+    pub fn fail(_env: Env) -> u32 {
+        panic!("unavailable")
+    }
+
+    pub fn not_found(_env: Env) -> Result<u32, AnswerError> {
+        Err(AnswerError::NotFound)
+    }
+}
+
+#[test]
+fn test_probe_reports_each_call_independently() {
+    let env = Env::default();
+    let id = env.register(Answering, ());
+
+    assert!(probe(&env, &id, "answer", Vec::new(&env)));
+    assert!(!probe(&env, &id, "fail", Vec::new(&env)));
+    assert!(!probe(&env, &id, "missing", Vec::new(&env)));
+    assert_eq!(try_read::<u32>(&env, &id, "answer", Vec::new(&env)), Some(42));
+    assert_eq!(try_read::<bool>(&env, &id, "answer", Vec::new(&env)), None);
+}
+
+#[test]
+fn test_contract_errors_count_as_answers() {
+    let env = Env::default();
+    let id = env.register(Answering, ());
+
+    assert!(answers(&env, &id, "answer", Vec::new(&env)));
+    assert!(answers(&env, &id, "not_found", Vec::new(&env)));
+    assert!(!answers(&env, &id, "fail", Vec::new(&env)));
+    assert!(!answers(&env, &id, "missing", Vec::new(&env)));
+    assert!(!answers(&env, &Address::generate(&env), "answer", Vec::new(&env)));
+}
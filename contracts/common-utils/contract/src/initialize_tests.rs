@@ -0,0 +1,50 @@
+#![cfg(test)]
+
+//! A second `initialize` must fail with `AlreadyInitialized` and leave the
+//! original admin in place.
+
+use crate::error::CommonError;
+use crate::oracle_bridge::{OracleBridgeContract, OracleBridgeContractClient};
+use crate::{CommonUtilsContract, CommonUtilsContractClient};
+use soroban_sdk::{symbol_short, testutils::Address as _, Address, Env, Symbol};
+
+#[test]
+fn test_oracle_bridge_rejects_second_initialize() {
+    let env = Env::default();
+    let id = env.register(OracleBridgeContract, ());
+    let client = OracleBridgeContractClient::new(&env, &id);
+    let admin = Address::generate(&env);
+
+    client.initialize(&admin);
+    assert_eq!(
+        client.try_initialize(&Address::generate(&env)),
+        Err(Ok(CommonError::AlreadyInitialized))
+    );
+
+    let stored: Address = env.as_contract(&id, || {
+        env.storage().instance().get(&symbol_short!("admin")).unwrap()
+    });
+    assert_eq!(stored, admin);
+}
+
+#[test]
+fn test_common_utils_contract_rejects_second_initialize() {
+    let env = Env::default();
+    let id = env.register(CommonUtilsContract, ());
+    let client = CommonUtilsContractClient::new(&env, &id);
+    let admin = Address::generate(&env);
+
+    client.initialize(&admin);
+    assert_eq!(
+        client.try_initialize(&Address::generate(&env)),
+        Err(Ok(CommonError::AlreadyInitialized))
+    );
+
+    let stored: Address = env.as_contract(&id, || {
+        env.storage()
+            .persistent()
+            .get(&Symbol::new(&env, "admin"))
+            .unwrap()
+    });
+    assert_eq!(stored, admin);
+}
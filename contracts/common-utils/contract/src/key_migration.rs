@@ -0,0 +1,95 @@
+//! # Key Migration
+//!
+//! In-place renames of storage keys, for upgrades that change a `DataKey`
+//! layout without a full `DataMigrationManager` run. Each `KeyMigration`
+//! moves the value under `from` to `to` in one storage tier and removes
+//! `from`:
+//!
+//! ```text
+//! KeyMigrationKey::Cursor(sha256(migrations)) -> u32, next index to run
+//! ```
+//!
+//! A step whose `from` is already gone is skipped, so a list can be re-run
+//! safely, and the cursor lets a long list be finished over several calls:
+//! call `migrate_keys` with the same list until `complete` is set.
+
+use soroban_sdk::{contracttype, xdr::ToXdr, BytesN, Env, Val, Vec};
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum KeyTier {
+    Instance,
+    Persistent,
+    Temporary,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct KeyMigration {
+    pub from: Val,
+    pub to: Val,
+    pub tier: KeyTier,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeyMigrationProgress {
+    /// Keys actually moved by this call
+    pub moved: u32,
+    /// Index of the next step to run
+    pub cursor: u32,
+    pub complete: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum KeyMigrationKey {
+    Cursor(BytesN<32>),
+}
+
+/// Run up to `max_steps` steps of `migrations`, resuming where the last
+/// call with the same list stopped. Callers are responsible for
+/// authorising the migration.
+pub fn migrate_keys(env: &Env, migrations: &Vec<KeyMigration>, max_steps: u32) -> KeyMigrationProgress {
+    let cursor_key = KeyMigrationKey::Cursor(env.crypto().sha256(&migrations.clone().to_xdr(env)).into());
+    let start: u32 = env.storage().instance().get(&cursor_key).unwrap_or(0);
+    let end = start.saturating_add(max_steps).min(migrations.len());
+
+    let mut moved = 0;
+    for index in start..end {
+        if move_key(env, &migrations.get(index).unwrap()) {
+            moved += 1;
+        }
+    }
+    env.storage().instance().set(&cursor_key, &end);
+
+    KeyMigrationProgress {
+        moved,
+        cursor: end,
+        complete: end == migrations.len(),
+    }
+}
+
+/// Move one key; false when `from` holds nothing, e.g. on a re-run.
+/// An existing value under `to` is overwritten, since `from` is only
+/// removed once it has been copied.
+fn move_key(env: &Env, migration: &KeyMigration) -> bool {
+    macro_rules! move_in {
+        ($storage:expr) => {{
+            let storage = $storage;
+            match storage.get::<Val, Val>(&migration.from) {
+                Some(value) => {
+                    storage.set(&migration.to, &value);
+                    storage.remove(&migration.from);
+                    true
+                }
+                None => false,
+            }
+        }};
+    }
+    match migration.tier {
+        KeyTier::Instance => move_in!(env.storage().instance()),
+        KeyTier::Persistent => move_in!(env.storage().persistent()),
+        KeyTier::Temporary => move_in!(env.storage().temporary()),
+    }
+}
@@ -0,0 +1,87 @@
+#![cfg(test)]
+
+use crate::key_migration::{migrate_keys, KeyMigration, KeyTier};
+use crate::CommonUtilsContract;
+use soroban_sdk::{symbol_short, Env, IntoVal, Vec};
+
+fn rename(env: &Env, from: u32, to: u32) -> KeyMigration {
+    KeyMigration {
+        from: (symbol_short!("old"), from).into_val(env),
+        to: (symbol_short!("new"), to).into_val(env),
+        tier: KeyTier::Persistent,
+    }
+}
+
+#[test]
+fn test_migration_preserves_values_and_removes_old_keys() {
+    let env = Env::default();
+    let id = env.register(CommonUtilsContract, ());
+    env.as_contract(&id, || {
+        let storage = env.storage().persistent();
+        let mut migrations = Vec::new(&env);
+        for i in 0..3u32 {
+            storage.set(&(symbol_short!("old"), i), &(i * 100));
+            migrations.push_back(rename(&env, i, i));
+        }
+
+        let progress = migrate_keys(&env, &migrations, 10);
+        assert_eq!(progress.moved, 3);
+        assert!(progress.complete);
+        for i in 0..3u32 {
+            assert!(!storage.has(&(symbol_short!("old"), i)));
+            assert_eq!(storage.get::<_, u32>(&(symbol_short!("new"), i)), Some(i * 100));
+        }
+    });
+}
+
+#[test]
+fn test_migration_resumes_and_reruns_without_duplication() {
+    let env = Env::default();
+    let id = env.register(CommonUtilsContract, ());
+    env.as_contract(&id, || {
+        let storage = env.storage().persistent();
+        let mut migrations = Vec::new(&env);
+        for i in 0..5u32 {
+            storage.set(&(symbol_short!("old"), i), &i);
+            migrations.push_back(rename(&env, i, i));
+        }
+
+        let first = migrate_keys(&env, &migrations, 2);
+        assert_eq!((first.moved, first.cursor, first.complete), (2, 2, false));
+        let second = migrate_keys(&env, &migrations, 10);
+        assert_eq!((second.moved, second.cursor, second.complete), (3, 5, true));
+
+        // Finished lists are a no-op
+        let again = migrate_keys(&env, &migrations, 10);
+        assert_eq!((again.moved, again.complete), (0, true));
+
+        // A new list covering the same keys finds nothing left to move
+        let mut rerun = migrations.clone();
+        rerun.push_back(rename(&env, 9, 9));
+        let rerun = migrate_keys(&env, &rerun, 10);
+        assert_eq!((rerun.moved, rerun.complete), (0, true));
+        for i in 0..5u32 {
+            assert_eq!(storage.get::<_, u32>(&(symbol_short!("new"), i)), Some(i));
+        }
+    });
+}
+
+#[test]
+fn test_migration_in_instance_storage() {
+    let env = Env::default();
+    let id = env.register(CommonUtilsContract, ());
+    env.as_contract(&id, || {
+        env.storage().instance().set(&symbol_short!("cfg_v1"), &7u32);
+        let migrations = soroban_sdk::vec![
+            &env,
+            KeyMigration {
+                from: symbol_short!("cfg_v1").into_val(&env),
+                to: symbol_short!("cfg_v2").into_val(&env),
+                tier: KeyTier::Instance,
+            },
+        ];
+        assert_eq!(migrate_keys(&env, &migrations, 1).moved, 1);
+        assert_eq!(env.storage().instance().get::<_, u32>(&symbol_short!("cfg_v2")), Some(7));
+        assert!(!env.storage().instance().has(&symbol_short!("cfg_v1")));
+    });
+}
@@ -23,6 +23,19 @@ pub mod storage_optimization;
 pub mod storage_monitoring;
 pub mod data_migration;
 pub mod compliance_log;
+pub mod crypto;
+pub mod event_schema;
+pub mod pagination;
+pub mod service_registry;
+pub mod assessment;
+pub mod ring_buffer;
+pub mod feature_flags;
+pub mod fixed_point;
+pub mod admin_log;
+pub mod address_registry;
+pub mod key_migration;
+pub mod health;
+pub mod math;
 
 pub use error::CommonError;
 pub use state_machine::{State, StateMachine, FraudDetectState, RiskEvalState, CreditScoreState, state_guard, transition_to};
@@ -49,6 +62,14 @@ pub enum DataKey {
     AgentLevel(Address),
     AgentStake(Address),
     UsedAttestation(BytesN<32>),
+    /// Whether attestation hashes are unique per agent, see
+    /// `EvolutionManager::set_attestation_scope`
+    AttestationScoped,
+    UsedAgentAttestation(Address, BytesN<32>),
+    EvolutionNonce(Address),
+    /// Least stake an agent needs to hold a level
+    LevelRequirement(u32),
+    LastEvolved(Address),
 }
 
 #[contracttype]
@@ -57,8 +78,21 @@ pub struct Attestation {
     pub agent: Address,
     pub new_level: u32,
     pub stake_amount: i128,
-    pub attestation_hash: BytesN<32>,
     pub attestation_hash: BytesN<32>, // unique ID / replay protection
+    /// Must be the agent's `get_evolution_nonce` plus one when attestations
+    /// are scoped; ignored otherwise
+    pub nonce: u64,
+}
+
+/// An agent's evolution state in one read, see
+/// `EvolutionManager::get_agent_profile`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AgentProfile {
+    pub level: u32,
+    pub stake: i128,
+    /// Ledger time of the latest applied attestation, 0 if none
+    pub last_evolved: u64,
 }
 
 
@@ -97,6 +131,12 @@ pub enum Error {
     ExecutionIdExists = 2,
     RateLimitExceeded = 3,
     Unauthorized = 4,
+    AttestationUsed = 5,
+    InvalidNonce = 6,
+    NotInitialized = 7,
+    InvalidStake = 8,
+    InsufficientStake = 9,
+    AlreadyInitialized = 10,
 }
 
 #[contracttype]
@@ -136,14 +176,17 @@ const RATE_LIMIT_MAX: u32 = 10;
 
 #[contractimpl]
 impl CommonUtilsContract {
-    /// Initialize contract with admin.
-    pub fn initialize(env: Env, admin: Address) {
-        env.storage()
-            .persistent()
-            .set(&Symbol::new(&env, "admin"), &admin);
+    /// Initialize contract with admin. Fails on a second call.
+    pub fn initialize(env: Env, admin: Address) -> Result<(), CommonError> {
+        let admin_key = Symbol::new(&env, "admin");
+        if env.storage().persistent().has(&admin_key) {
+            return Err(CommonError::AlreadyInitialized);
+        }
+        env.storage().persistent().set(&admin_key, &admin);
         env.storage()
             .persistent()
             .set(&Symbol::new(&env, "exec_cnt"), &0u64);
+        Ok(())
     }
 
     /// Submit an agent action.
@@ -239,12 +282,177 @@ pub struct EvolutionManager;
 
 #[contractimpl]
 impl EvolutionManager {
+    /// Set the admin and the bridge allowed to apply attestations. The admin
+    /// must authorise it; fails with `AlreadyInitialized` on a second call.
+    pub fn initialize_evolution(env: Env, admin: Address, trusted_bridge: Address) -> Result<(), Error> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::TrustedBridge, &trusted_bridge);
+        Ok(())
+    }
+
+    /// Replace the bridge whose signature `apply_attestation` requires, for
+    /// key rotation or after a compromise.
+    pub fn set_trusted_bridge(env: Env, admin: Address, trusted_bridge: Address) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        env.storage().instance().set(&DataKey::TrustedBridge, &trusted_bridge);
+        Ok(())
+    }
+
+    pub fn get_trusted_bridge(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::TrustedBridge)
+    }
+
+    /// Make attestation hashes unique per agent instead of globally, and
+    /// require each agent's attestations to carry consecutive nonces. Off
+    /// by default, which keeps the global `UsedAttestation` check.
+    pub fn set_attestation_scope(env: Env, admin: Address, scoped: bool) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        env.storage().instance().set(&DataKey::AttestationScoped, &scoped);
+        Ok(())
+    }
+
+    /// Require agents to hold at least `min_stake`, after the attestation's
+    /// stake is added, to be moved to `level`. 0 removes the requirement.
+    pub fn set_level_requirement(env: Env, admin: Address, level: u32, min_stake: i128) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        if min_stake < 0 {
+            return Err(Error::InvalidStake);
+        }
+        let key = DataKey::LevelRequirement(level);
+        if min_stake == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &min_stake);
+        }
+        Ok(())
+    }
+
+    pub fn get_level_requirement(env: Env, level: u32) -> i128 {
+        env.storage().persistent().get(&DataKey::LevelRequirement(level)).unwrap_or(0)
+    }
+
+    pub fn is_attestation_scoped(env: Env) -> bool {
+        env.storage().instance().get(&DataKey::AttestationScoped).unwrap_or(false)
+    }
+
+    /// Nonce of the agent's latest scoped attestation, 0 before the first
+    pub fn get_evolution_nonce(env: Env, agent: Address) -> u64 {
+        env.storage().persistent().get(&DataKey::EvolutionNonce(agent)).unwrap_or(0)
+    }
+
+    pub fn get_agent_level(env: Env, agent: Address) -> u32 {
+        env.storage().persistent().get(&DataKey::AgentLevel(agent)).unwrap_or(0)
+    }
+
+    pub fn get_agent_stake(env: Env, agent: Address) -> i128 {
+        env.storage().persistent().get(&DataKey::AgentStake(agent)).unwrap_or(0)
+    }
+
+    /// Level, stake and last evolution time of `agent`; all zero for an
+    /// agent never attested
+    pub fn get_agent_profile(env: Env, agent: Address) -> AgentProfile {
+        AgentProfile {
+            level: Self::get_agent_level(env.clone(), agent.clone()),
+            stake: Self::get_agent_stake(env.clone(), agent.clone()),
+            last_evolved: env
+                .storage()
+                .persistent()
+                .get(&DataKey::LastEvolved(agent))
+                .unwrap_or(0),
+        }
+    }
+
+    /// Apply a bridge-signed evolution: add `stake_amount` to the agent's
+    /// stake and move it to `new_level`. Each hash is accepted once, across
+    /// all agents or, when scoped, per agent with the nonce checked too.
+    ///
+    /// The bridge's authorisation covers the whole `Attestation`, so an
+    /// observed call cannot be replayed with a different level, stake or
+    /// agent; the hash check stops it being replayed unchanged.
+    pub fn apply_attestation(env: Env, attestation: Attestation) -> Result<(), Error> {
+        Self::require_bridge(&env)?;
+
+        let agent = attestation.agent.clone();
+        let hash = attestation.attestation_hash.clone();
+        if attestation.stake_amount < 0 {
+            return Err(Error::InvalidStake);
+        }
+        let storage = env.storage().persistent();
+        let used_key = if Self::is_attestation_scoped(env.clone()) {
+            let expected = Self::get_evolution_nonce(env.clone(), agent.clone()).saturating_add(1);
+            if attestation.nonce != expected {
+                return Err(Error::InvalidNonce);
+            }
+            storage.set(&DataKey::EvolutionNonce(agent.clone()), &expected);
+            DataKey::UsedAgentAttestation(agent.clone(), hash.clone())
+        } else {
+            DataKey::UsedAttestation(hash.clone())
+        };
+        if storage.has(&used_key) {
+            return Err(Error::AttestationUsed);
+        }
+        storage.set(&used_key, &true);
+
+        let stake = math::checked_add_i128(Self::get_agent_stake(env.clone(), agent.clone()), attestation.stake_amount)
+            .map_err(|_| Error::InvalidStake)?;
+        if stake < Self::get_level_requirement(env.clone(), attestation.new_level) {
+            return Err(Error::InsufficientStake);
+        }
+        storage.set(&DataKey::AgentStake(agent.clone()), &stake);
+        storage.set(&DataKey::AgentLevel(agent.clone()), &attestation.new_level);
+        storage.set(&DataKey::LastEvolved(agent.clone()), &env.ledger().timestamp());
+
+        Self::publish_evolution_completed(&env, agent, attestation.new_level, stake, hash);
+        Ok(())
+    }
+
+    fn require_bridge(env: &Env) -> Result<(), Error> {
+        let bridge: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::TrustedBridge)
+            .ok_or(Error::NotInitialized)?;
+        bridge.require_auth();
+        Ok(())
+    }
+
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+        let stored: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if stored != *admin {
+            return Err(Error::Unauthorized);
+        }
+        admin.require_auth();
+        Ok(())
+    }
+
+    /// Publish an `EvolutionCompleted` event. Only the trusted bridge may
+    /// call this, so indexers cannot be fed evolutions that never happened.
     pub fn emit_evolution_completed(
         env: Env,
         agent: Address,
         new_level: u32,
         total_stake: i128,
         attestation_hash: BytesN<32>,
+    ) -> Result<(), Error> {
+        Self::require_bridge(&env)?;
+        Self::publish_evolution_completed(&env, agent, new_level, total_stake, attestation_hash);
+        Ok(())
+    }
+
+    fn publish_evolution_completed(
+        env: &Env,
+        agent: Address,
+        new_level: u32,
+        total_stake: i128,
+        attestation_hash: BytesN<32>,
     ) {
         env.events().publish(
             ("EvolutionCompleted",),
@@ -273,3 +481,57 @@ mod fees_tests;
 
 #[cfg(test)]
 mod compliance_log_tests;
+
+#[cfg(test)]
+mod crypto_tests;
+
+#[cfg(test)]
+mod event_schema_tests;
+
+#[cfg(test)]
+mod oracle_bridge_tests;
+
+#[cfg(test)]
+mod pagination_tests;
+
+#[cfg(test)]
+mod service_registry_tests;
+
+#[cfg(test)]
+mod assessment_tests;
+
+#[cfg(test)]
+mod initialize_tests;
+
+#[cfg(test)]
+mod signature_scheme_tests;
+
+#[cfg(test)]
+mod ring_buffer_tests;
+
+#[cfg(test)]
+mod feature_flags_tests;
+
+#[cfg(test)]
+mod upgrade_proxy_tests;
+
+#[cfg(test)]
+mod fixed_point_tests;
+
+#[cfg(test)]
+mod admin_log_tests;
+
+#[cfg(test)]
+mod address_registry_tests;
+
+#[cfg(test)]
+mod key_migration_tests;
+
+#[cfg(test)]
+mod health_tests;
+
+#[cfg(test)]
+mod math_tests;
+
+#[cfg(test)]
+mod evolution_tests;
@@ -1,5 +1,24 @@
-use soroban_sdk::{contract, contractimpl, Address, Env, token, contracttype, symbol_short};
+use soroban_sdk::{contract, contractimpl, Address, Env, IntoVal, token, contracttype, symbol_short, Symbol, Vec};
 use crate::error::CommonError;
+use crate::event_schema::{self, emit, emit_versioned_for};
+use crate::math;
+use crate::oracle_bridge::RATE_SCALE;
+use crate::ring_buffer::RingBuffer;
+
+/// Schema version of the `listed` / `sold` event payloads.
+pub const MARKETPLACE_EVENT_VERSION: u32 = 1;
+
+/// Release of this contract, bumped on every meaningful change
+pub const MARKETPLACE_CONTRACT_VERSION: u32 = 1;
+
+/// Release tag reported by `build_id`
+pub const MARKETPLACE_BUILD_ID: Symbol = symbol_short!("v1_0_0");
+
+/// Sales kept per agent before the oldest is evicted
+pub const SALE_HISTORY_CAPACITY: u32 = 50;
+
+/// Basis points in a whole price
+pub const BPS_DENOMINATOR: u32 = 10_000;
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -20,9 +39,64 @@ pub struct Listing {
     pub royalty_recipient: Address,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SaleRecord {
+    pub buyer: Address,
+    pub seller: Address,
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+/// Seller's floor on what a sale must be worth, see
+/// `set_min_settlement_value`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementGuard {
+    /// Contract answering `get_rate(currency) -> Option<i128>`, such as the
+    /// oracle bridge
+    pub rate_oracle: Address,
+    /// Least `price * rate / RATE_SCALE` the seller accepts
+    pub min_value: i128,
+}
+
+/// Payment of a sale kept by the contract until `release_at`, see
+/// `set_payment_hold`. The agent itself is delivered at purchase.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HeldPayment {
+    pub buyer: Address,
+    pub seller: Address,
+    pub currency: Address,
+    /// Units of the currency the contract actually received, which is less
+    /// than the price for fee-on-transfer tokens
+    pub amount: i128,
+    /// `(recipient, amount)` paid out on release, seller included; sums to
+    /// `amount`
+    pub payouts: Vec<(Address, i128)>,
+    pub release_at: u64,
+}
+
 #[contracttype]
 pub enum DataKey {
     Listing(u64),
+    Admin,
+    /// Units of the asset the contract actually received for a listing,
+    /// which is less than the amount sent for fee-on-transfer tokens.
+    /// Listings made before this was recorded hold the single unit sent.
+    ListingEscrow(u64),
+    /// Sum of `ListingEscrow` over active listings of an asset
+    Escrowed(Address),
+    /// Optional `SettlementGuard` of a listing
+    Settlement(u64),
+    /// `(recipient, bps)` shares of `royalty_bps` from
+    /// `list_agent_with_splits`. Absent means `royalty_recipient` is paid
+    /// the whole royalty.
+    RoyaltySplits(u64),
+    /// Seconds a sale's payment is held before payout, 0 when unset
+    HoldSecs,
+    /// `HeldPayment` of an agent's latest sale while it is held
+    HeldPayment(u64),
 }
 
 #[contract]
@@ -30,7 +104,129 @@ pub struct MarketplaceContract;
 
 #[contractimpl]
 impl MarketplaceContract {
-    
+    /// Set the admin allowed to rescue stranded tokens. Listing and buying do
+    /// not need an admin.
+    pub fn initialize(env: Env, admin: Address) -> Result<(), CommonError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(CommonError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    /// Units of `token` held in escrow for active listings
+    pub fn get_escrowed(env: Env, token: Address) -> i128 {
+        env.storage().persistent().get(&DataKey::Escrowed(token)).unwrap_or(0)
+    }
+
+    pub fn version(_env: Env) -> u32 {
+        MARKETPLACE_CONTRACT_VERSION
+    }
+
+    pub fn build_id(_env: Env) -> Symbol {
+        MARKETPLACE_BUILD_ID
+    }
+
+    /// Move `amount` of `token` that no active listing is escrowing to `to`.
+    /// Listings made before escrow was recorded are not counted until
+    /// `backfill_escrow` has been run for them.
+    pub fn rescue_token(
+        env: Env,
+        admin: Address,
+        token: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), CommonError> {
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(CommonError::NotInitialized)?;
+        if stored_admin != admin {
+            return Err(CommonError::NotAuthorized);
+        }
+        admin.require_auth();
+
+        let token_client = token::Client::new(&env, &token);
+        let balance = token_client.balance(&env.current_contract_address());
+        let free = math::checked_sub_i128(balance, Self::get_escrowed(env.clone(), token.clone()))?;
+        if amount <= 0 || amount > free {
+            return Err(CommonError::OutOfRange);
+        }
+        token_client.transfer(&env.current_contract_address(), &to, &amount);
+
+        emit(&env, (symbol_short!("rescued"), token), (to, amount));
+        Ok(())
+    }
+
+    /// Count the unit held by each listing in `agent_ids` that was made
+    /// before escrow was recorded, so `rescue_token` cannot move it. Ids
+    /// without a listing or already counted are skipped. Returns how many
+    /// listings were counted.
+    pub fn backfill_escrow(env: Env, admin: Address, agent_ids: Vec<u64>) -> Result<u32, CommonError> {
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(CommonError::NotInitialized)?;
+        if stored_admin != admin {
+            return Err(CommonError::NotAuthorized);
+        }
+        admin.require_auth();
+
+        let mut counted = 0u32;
+        for agent_id in agent_ids.iter() {
+            let escrow_key = DataKey::ListingEscrow(agent_id);
+            if env.storage().persistent().has(&escrow_key) {
+                continue;
+            }
+            let Some(listing) = env.storage().persistent().get::<_, Listing>(&DataKey::Listing(agent_id)) else {
+                continue;
+            };
+            env.storage().persistent().set(&escrow_key, &1i128);
+            Self::add_escrowed(&env, &listing.asset_address, 1)?;
+            counted += 1;
+        }
+        Ok(counted)
+    }
+
+    /// Prefix all marketplace event topics with `namespace`, or clear it
+    pub fn set_event_namespace(env: Env, admin: Address, namespace: Option<Symbol>) -> Result<(), CommonError> {
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(CommonError::NotInitialized)?;
+        if stored_admin != admin {
+            return Err(CommonError::NotAuthorized);
+        }
+        admin.require_auth();
+        event_schema::set_event_namespace(&env, namespace);
+        Ok(())
+    }
+
+    /// Hold each sale's payment for `hold_secs` before the seller and
+    /// royalty recipients are paid, leaving time to dispute it. 0 pays out
+    /// at purchase.
+    pub fn set_payment_hold(env: Env, admin: Address, hold_secs: u64) -> Result<(), CommonError> {
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(CommonError::NotInitialized)?;
+        if stored_admin != admin {
+            return Err(CommonError::NotAuthorized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::HoldSecs, &hold_secs);
+        Ok(())
+    }
+
+    pub fn get_payment_hold(env: Env) -> u64 {
+        env.storage().instance().get(&DataKey::HoldSecs).unwrap_or(0)
+    }
+
     pub fn list_agent(
         env: Env,
         seller: Address,
@@ -42,10 +238,39 @@ impl MarketplaceContract {
         royalty_bps: u32,        
         royalty_recipient: Address
     ) -> Result<(), CommonError> {
-        seller.require_auth();
+        let listing = Listing {
+            seller,
+            asset_address,
+            price,
+            currency,
+            listing_type,
+            royalty_bps,
+            royalty_recipient,
+        };
+        Self::create_listing(&env, agent_id, listing)
+    }
 
-        let token_client = token::Client::new(&env, &asset_address);
-        token_client.transfer(&seller, &env.current_contract_address(), &1i128);
+    /// `list_agent` with the royalty shared by several recipients. The
+    /// `(recipient, bps)` entries must sum to exactly `royalty_bps`.
+    pub fn list_agent_with_splits(
+        env: Env,
+        seller: Address,
+        agent_id: u64,
+        asset_address: Address,
+        price: i128,
+        currency: Address,
+        listing_type: ListingType,
+        royalty_bps: u32,
+        royalty_splits: Vec<(Address, u32)>,
+    ) -> Result<(), CommonError> {
+        let mut total: u32 = 0;
+        for (_, bps) in royalty_splits.iter() {
+            total = math::checked_add_u32(total, bps)?;
+        }
+        let (royalty_recipient, _) = royalty_splits.first().ok_or(CommonError::MissingRequiredField)?;
+        if total != royalty_bps {
+            return Err(CommonError::InvalidFormat);
+        }
 
         let listing = Listing {
             seller,
@@ -56,46 +281,336 @@ impl MarketplaceContract {
             royalty_bps,
             royalty_recipient,
         };
+        Self::create_listing(&env, agent_id, listing)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::RoyaltySplits(agent_id), &royalty_splits);
+        Ok(())
+    }
+
+    /// Escrow the agent and store `listing`. `royalty_bps` may not exceed
+    /// `BPS_DENOMINATOR`, so royalties never outgrow the price.
+    fn create_listing(env: &Env, agent_id: u64, listing: Listing) -> Result<(), CommonError> {
+        listing.seller.require_auth();
+        if listing.royalty_bps > BPS_DENOMINATOR {
+            return Err(CommonError::OutOfRange);
+        }
+
+        let token_client = token::Client::new(env, &listing.asset_address);
+        let contract = env.current_contract_address();
+        let balance_before = token_client.balance(&contract);
+        token_client.transfer(&listing.seller, &contract, &1i128);
+        let escrowed_amount = token_client.balance(&contract) - balance_before;
+        if escrowed_amount <= 0 {
+            return Err(CommonError::OutOfRange);
+        }
 
+        Self::add_escrowed(env, &listing.asset_address, escrowed_amount)?;
         let key = DataKey::Listing(agent_id);
         env.storage().persistent().set(&key, &listing);
+        env.storage()
+            .persistent()
+            .set(&DataKey::ListingEscrow(agent_id), &escrowed_amount);
+        env.storage().persistent().remove(&DataKey::RoyaltySplits(agent_id));
 
-        env.events().publish(
-            (symbol_short!("listed"), agent_id),
-            price
+        emit_versioned_for(
+            env,
+            symbol_short!("listed"),
+            MARKETPLACE_EVENT_VERSION,
+            agent_id,
+            listing.price,
         );
         
         Ok(())
     }
 
+    /// Point a live listing's royalty at `new_recipient`. Only the seller or
+    /// the current royalty recipient may do this. Listings with
+    /// royalty splits fail with `InvalidState`; their shares are fixed.
+    pub fn update_royalty_recipient(
+        env: Env,
+        agent_id: u64,
+        caller: Address,
+        new_recipient: Address,
+    ) -> Result<(), CommonError> {
+        caller.require_auth();
+
+        let key = DataKey::Listing(agent_id);
+        let mut listing: Listing = env.storage().persistent().get(&key).ok_or(CommonError::KeyNotFound)?;
+        if caller != listing.seller && caller != listing.royalty_recipient {
+            return Err(CommonError::NotAuthorized);
+        }
+        if env.storage().persistent().has(&DataKey::RoyaltySplits(agent_id)) {
+            return Err(CommonError::InvalidState);
+        }
+
+        let old_recipient = listing.royalty_recipient.clone();
+        listing.royalty_recipient = new_recipient.clone();
+        env.storage().persistent().set(&key, &listing);
+
+        emit(
+            &env,
+            (Symbol::new(&env, "royalty_recipient_updated"), MARKETPLACE_EVENT_VERSION, agent_id),
+            (old_recipient, new_recipient),
+        );
+
+        Ok(())
+    }
+
+    /// Refuse sales of `agent_id` whose price, converted at `rate_oracle`'s
+    /// current rate for the listing currency, is below `min_value`. `None`
+    /// removes the check. Only the seller may do this.
+    pub fn set_min_settlement_value(
+        env: Env,
+        agent_id: u64,
+        seller: Address,
+        guard: Option<SettlementGuard>,
+    ) -> Result<(), CommonError> {
+        seller.require_auth();
+
+        let listing: Listing = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Listing(agent_id))
+            .ok_or(CommonError::KeyNotFound)?;
+        if seller != listing.seller {
+            return Err(CommonError::NotAuthorized);
+        }
+
+        let key = DataKey::Settlement(agent_id);
+        match guard {
+            Some(guard) if guard.min_value <= 0 => return Err(CommonError::OutOfRange),
+            Some(guard) => env.storage().persistent().set(&key, &guard),
+            None => env.storage().persistent().remove(&key),
+        }
+        Ok(())
+    }
+
+    pub fn get_min_settlement_value(env: Env, agent_id: u64) -> Option<SettlementGuard> {
+        env.storage().persistent().get(&DataKey::Settlement(agent_id))
+    }
+
+    /// Buy a listing. The agent is delivered at once; with a payment hold
+    /// set, the payment is kept in escrow until `release_payment`. A sale
+    /// fails with `InvalidState` while the agent's previous payment is
+    /// still held.
     pub fn buy_agent(env: Env, buyer: Address, agent_id: u64) -> Result<(), CommonError> {
         buyer.require_auth();
 
         let key = DataKey::Listing(agent_id);
         let listing: Listing = env.storage().persistent().get(&key).ok_or(CommonError::KeyNotFound)?;
+        Self::check_settlement_value(&env, agent_id, &listing)?;
+        let hold_secs = Self::get_payment_hold(env.clone());
+        if hold_secs > 0 && env.storage().persistent().has(&DataKey::HeldPayment(agent_id)) {
+            return Err(CommonError::InvalidState);
+        }
 
-        let royalty_amount = (listing.price * (listing.royalty_bps as i128)) / 10000;
-        let seller_amount = listing.price - royalty_amount;
-
-        let currency_client = token::Client::new(&env, &listing.currency);
-        
-        if seller_amount > 0 {
-            currency_client.transfer(&buyer, &listing.seller, &seller_amount);
+        let royalties = Self::royalty_payouts(&env, agent_id, &listing)?;
+        let mut seller_amount = listing.price;
+        for (_, amount) in royalties.iter() {
+            seller_amount = math::checked_sub_i128(seller_amount, amount)?;
         }
-        if royalty_amount > 0 {
-            currency_client.transfer(&buyer, &listing.royalty_recipient, &royalty_amount);
+        // Listings stored before the bound was enforced
+        if seller_amount < 0 {
+            return Err(CommonError::OutOfRange);
         }
 
+        let escrowed_amount = Self::take_listing_escrow(&env, agent_id, &listing.asset_address)?;
+        let currency_client = token::Client::new(&env, &listing.currency);
+        if hold_secs > 0 {
+            let contract = env.current_contract_address();
+            let balance_before = currency_client.balance(&contract);
+            currency_client.transfer(&buyer, &contract, &listing.price);
+            let received = currency_client.balance(&contract) - balance_before;
+            // A transfer fee comes out of the seller's share
+            let seller_received = math::checked_sub_i128(seller_amount, listing.price - received)?;
+            if seller_received < 0 {
+                return Err(CommonError::OutOfRange);
+            }
+            let mut payouts = royalties;
+            payouts.push_front((listing.seller.clone(), seller_received));
+
+            Self::add_escrowed(&env, &listing.currency, received)?;
+            env.storage().persistent().set(
+                &DataKey::HeldPayment(agent_id),
+                &HeldPayment {
+                    buyer: buyer.clone(),
+                    seller: listing.seller.clone(),
+                    currency: listing.currency.clone(),
+                    amount: received,
+                    payouts,
+                    release_at: env.ledger().timestamp().saturating_add(hold_secs),
+                },
+            );
+        } else {
+            let mut payouts = royalties;
+            payouts.push_front((listing.seller.clone(), seller_amount));
+            for (recipient, amount) in payouts.iter() {
+                if amount > 0 {
+                    currency_client.transfer(&buyer, &recipient, &amount);
+                }
+            }
+        }
         let agent_token_client = token::Client::new(&env, &listing.asset_address);
-        agent_token_client.transfer(&env.current_contract_address(), &buyer, &1i128);
+        agent_token_client.transfer(&env.current_contract_address(), &buyer, &escrowed_amount);
 
         env.storage().persistent().remove(&key);
+        env.storage().persistent().remove(&DataKey::Settlement(agent_id));
+        env.storage().persistent().remove(&DataKey::RoyaltySplits(agent_id));
+
+        Self::sale_history(agent_id).push(&env, &SaleRecord {
+            buyer: buyer.clone(),
+            seller: listing.seller.clone(),
+            price: listing.price,
+            timestamp: env.ledger().timestamp(),
+        });
 
-        env.events().publish(
-            (symbol_short!("sold"), agent_id),
-            listing.price
+        emit_versioned_for(
+            &env,
+            symbol_short!("sold"),
+            MARKETPLACE_EVENT_VERSION,
+            agent_id,
+            listing.price,
         );
         
         Ok(())
     }
+
+    pub fn get_held_payment(env: Env, agent_id: u64) -> Option<HeldPayment> {
+        env.storage().persistent().get(&DataKey::HeldPayment(agent_id))
+    }
+
+    /// Pay out a held sale payment once the hold has elapsed. Anyone may
+    /// call this. Payouts that do not sum to the held amount are refused
+    /// with `InvalidState`, so a release never spends other sales' funds.
+    pub fn release_payment(env: Env, agent_id: u64) -> Result<(), CommonError> {
+        let held = Self::take_held_payment(&env, agent_id)?;
+        if env.ledger().timestamp() < held.release_at {
+            return Err(CommonError::InvalidState);
+        }
+        let mut total: i128 = 0;
+        for (_, amount) in held.payouts.iter() {
+            if amount < 0 {
+                return Err(CommonError::InvalidState);
+            }
+            total = math::checked_add_i128(total, amount)?;
+        }
+        if total != held.amount {
+            return Err(CommonError::InvalidState);
+        }
+        let currency_client = token::Client::new(&env, &held.currency);
+        for (recipient, amount) in held.payouts.iter() {
+            if amount > 0 {
+                currency_client.transfer(&env.current_contract_address(), &recipient, &amount);
+            }
+        }
+        emit(&env, (symbol_short!("released"), agent_id), held.amount);
+        Ok(())
+    }
+
+    /// Refund a sale's held payment to the buyer while its hold is running,
+    /// to settle a dispute. The agent, delivered at purchase, stays with the
+    /// buyer.
+    pub fn refund_buyer(env: Env, admin: Address, agent_id: u64) -> Result<(), CommonError> {
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(CommonError::NotInitialized)?;
+        if stored_admin != admin {
+            return Err(CommonError::NotAuthorized);
+        }
+        admin.require_auth();
+
+        let held = Self::take_held_payment(&env, agent_id)?;
+        if env.ledger().timestamp() >= held.release_at {
+            return Err(CommonError::InvalidState);
+        }
+        token::Client::new(&env, &held.currency).transfer(
+            &env.current_contract_address(),
+            &held.buyer,
+            &held.amount,
+        );
+        emit(&env, (symbol_short!("refunded"), agent_id), (held.buyer, held.amount));
+        Ok(())
+    }
+
+    /// Completed sales of `agent_id`, oldest first.
+    pub fn get_sale_history(env: Env, agent_id: u64) -> Vec<SaleRecord> {
+        Self::sale_history(agent_id).iter(&env)
+    }
+
+    /// Reject the sale when the listing has a `SettlementGuard` it does not
+    /// meet. A missing rate rejects the sale rather than skipping the check.
+    fn check_settlement_value(env: &Env, agent_id: u64, listing: &Listing) -> Result<(), CommonError> {
+        let Some(guard) = Self::get_min_settlement_value(env.clone(), agent_id) else {
+            return Ok(());
+        };
+        let rate: Option<i128> = env.invoke_contract(
+            &guard.rate_oracle,
+            &symbol_short!("get_rate"),
+            soroban_sdk::vec![env, listing.currency.into_val(env)],
+        );
+        let rate = rate.ok_or(CommonError::OracleNotFound)?;
+        let value = math::checked_mul_div_i128(listing.price, rate, RATE_SCALE)?;
+        if value < guard.min_value {
+            return Err(CommonError::BelowMinValue);
+        }
+        Ok(())
+    }
+
+    /// `(recipient, amount)` royalties of a sale. Each share is
+    /// `price * bps / BPS_DENOMINATOR` rounded down, so the seller, paid
+    /// what is left, receives any rounding remainder.
+    fn royalty_payouts(env: &Env, agent_id: u64, listing: &Listing) -> Result<Vec<(Address, i128)>, CommonError> {
+        let shares: Vec<(Address, u32)> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RoyaltySplits(agent_id))
+            .unwrap_or(soroban_sdk::vec![
+                env,
+                (listing.royalty_recipient.clone(), listing.royalty_bps)
+            ]);
+
+        let mut payouts = Vec::new(env);
+        for (recipient, bps) in shares.iter() {
+            let amount = math::checked_mul_div_i128(listing.price, bps as i128, BPS_DENOMINATOR as i128)?;
+            payouts.push_back((recipient, amount));
+        }
+        Ok(payouts)
+    }
+
+    /// Remove `agent_id`'s held payment and release its escrow counts.
+    /// Errors returned after this roll the removal back with the call.
+    fn take_held_payment(env: &Env, agent_id: u64) -> Result<HeldPayment, CommonError> {
+        let key = DataKey::HeldPayment(agent_id);
+        let held: HeldPayment = env.storage().persistent().get(&key).ok_or(CommonError::KeyNotFound)?;
+        env.storage().persistent().remove(&key);
+        Self::add_escrowed(env, &held.currency, -held.amount)?;
+        Ok(held)
+    }
+
+    /// Remove `agent_id`'s recorded escrow and release it from the asset's
+    /// total. Listings from before escrow was recorded were sent one unit
+    /// and never counted in the total.
+    fn take_listing_escrow(env: &Env, agent_id: u64, asset: &Address) -> Result<i128, CommonError> {
+        let key = DataKey::ListingEscrow(agent_id);
+        let Some(amount) = env.storage().persistent().get::<_, i128>(&key) else {
+            return Ok(1);
+        };
+        env.storage().persistent().remove(&key);
+        Self::add_escrowed(env, asset, -amount)?;
+        Ok(amount)
+    }
+
+    fn sale_history(agent_id: u64) -> RingBuffer<u64, SaleRecord> {
+        RingBuffer::new(symbol_short!("sales"), agent_id, SALE_HISTORY_CAPACITY)
+    }
+
+    fn add_escrowed(env: &Env, token: &Address, delta: i128) -> Result<(), CommonError> {
+        let key = DataKey::Escrowed(token.clone());
+        let total: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage().persistent().set(&key, &math::checked_add_i128(total, delta)?);
+        Ok(())
+    }
 }
@@ -0,0 +1,54 @@
+//! # Checked Arithmetic
+//!
+//! Integer helpers that report overflow as `CommonError::OutOfRange`
+//! instead of panicking or wrapping. They are plain `core` arithmetic and
+//! safe in `no_std` contracts.
+//!
+//! Call sites follow one rule. Amounts of value error, so a bad sum never
+//! moves tokens. Counters and timestamps saturate, so a counter at its
+//! bound cannot block the operation it counts:
+//!
+//! ```text
+//! marketplace  royalty shares, seller remainder, escrow totals   error
+//! marketplace  settlement value (`price * rate / RATE_SCALE`)    error
+//! fraud-detect reporter stake deposits                           error
+//! evolution    agent stake added by an attestation               error
+//! fraud-detect total_reports, model update counts, flag count   saturate
+//! fraud-detect stake cooldown deadline                          saturate
+//! credit-score total_scores                                      saturate
+//! evolution    per-agent attestation nonce                       saturate
+//! ```
+
+use crate::error::CommonError;
+
+pub fn checked_add_u32(a: u32, b: u32) -> Result<u32, CommonError> {
+    a.checked_add(b).ok_or(CommonError::OutOfRange)
+}
+
+pub fn checked_add_u64(a: u64, b: u64) -> Result<u64, CommonError> {
+    a.checked_add(b).ok_or(CommonError::OutOfRange)
+}
+
+pub fn checked_sub_u64(a: u64, b: u64) -> Result<u64, CommonError> {
+    a.checked_sub(b).ok_or(CommonError::OutOfRange)
+}
+
+pub fn checked_add_i128(a: i128, b: i128) -> Result<i128, CommonError> {
+    a.checked_add(b).ok_or(CommonError::OutOfRange)
+}
+
+pub fn checked_sub_i128(a: i128, b: i128) -> Result<i128, CommonError> {
+    a.checked_sub(b).ok_or(CommonError::OutOfRange)
+}
+
+pub fn checked_mul_i128(a: i128, b: i128) -> Result<i128, CommonError> {
+    a.checked_mul(b).ok_or(CommonError::OutOfRange)
+}
+
+/// `a * b / denominator`, truncated toward zero. A zero `denominator`
+/// is `OutOfRange` too.
+pub fn checked_mul_div_i128(a: i128, b: i128, denominator: i128) -> Result<i128, CommonError> {
+    checked_mul_i128(a, b)?
+        .checked_div(denominator)
+        .ok_or(CommonError::OutOfRange)
+}
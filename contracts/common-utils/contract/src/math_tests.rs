@@ -0,0 +1,34 @@
+#![cfg(test)]
+
+use crate::error::CommonError;
+use crate::math::{
+    checked_add_i128, checked_add_u32, checked_add_u64, checked_mul_div_i128, checked_mul_i128,
+    checked_sub_i128, checked_sub_u64,
+};
+
+#[test]
+fn test_unsigned_helpers_error_at_bounds() {
+    assert_eq!(checked_add_u32(u32::MAX - 1, 1), Ok(u32::MAX));
+    assert_eq!(checked_add_u32(u32::MAX, 1), Err(CommonError::OutOfRange));
+    assert_eq!(checked_add_u64(u64::MAX - 1, 1), Ok(u64::MAX));
+    assert_eq!(checked_add_u64(u64::MAX, 1), Err(CommonError::OutOfRange));
+    assert_eq!(checked_sub_u64(1, 1), Ok(0));
+    assert_eq!(checked_sub_u64(0, 1), Err(CommonError::OutOfRange));
+}
+
+#[test]
+fn test_signed_helpers_error_at_bounds() {
+    assert_eq!(checked_add_i128(i128::MAX, 1), Err(CommonError::OutOfRange));
+    assert_eq!(checked_add_i128(i128::MAX, -1), Ok(i128::MAX - 1));
+    assert_eq!(checked_sub_i128(i128::MIN, 1), Err(CommonError::OutOfRange));
+    assert_eq!(checked_mul_i128(i128::MAX, 2), Err(CommonError::OutOfRange));
+    assert_eq!(checked_mul_i128(i128::MAX, -1), Ok(-i128::MAX));
+}
+
+#[test]
+fn test_mul_div_truncates_and_rejects_zero_denominator() {
+    assert_eq!(checked_mul_div_i128(999, 333, 10_000), Ok(33));
+    assert_eq!(checked_mul_div_i128(-999, 333, 10_000), Ok(-33));
+    assert_eq!(checked_mul_div_i128(1, 1, 0), Err(CommonError::OutOfRange));
+    assert_eq!(checked_mul_div_i128(i128::MAX, 500, 10_000), Err(CommonError::OutOfRange));
+}
@@ -1,6 +1,14 @@
 //! # Data Migration Framework
 //!
 //! Provides a standardized way to migrate data between contract versions.
+//!
+//! ## Export guard
+//!
+//! Writes that land on a source after its export are lost. A payload
+//! exported while the source is `Paused` or `Terminated` carries the export
+//! time; one exported live carries `None`, and `import_state` refuses it.
+//! With `set_strict_export` the source also refuses the live export itself.
+//! `export_state` stays a read: the marker goes in the payload only.
 
 use soroban_sdk::{contracttype, Address, Env, Symbol, Vec, Val, IntoVal};
 use crate::error::CommonError;
@@ -8,7 +16,7 @@ use crate::error::CommonError;
 /// Trait to be implemented by contracts that support data migration
 pub trait DataMigration {
     /// Export state for migration
-    fn export_state(env: Env) -> Vec<Val>;
+    fn export_state(env: Env) -> Result<Vec<Val>, CommonError>;
     
     /// Import state from a previous version
     fn import_state(env: Env, data: Vec<Val>) -> Result<(), CommonError>;
@@ -28,7 +36,7 @@ impl MigrationRunner {
         admin.require_auth();
         
         // Export state from current implementation
-        // The contract should have: pub fn export_state(env: Env) -> Vec<Val>
+        // The contract should have: pub fn export_state(env: Env) -> Result<Vec<Val>, CommonError>
         let state: Vec<Val> = env.invoke_contract(
             from,
             &Symbol::new(env, "export_state"),
@@ -53,3 +61,55 @@ pub struct MigrationConfig {
     pub runner: Address,
     pub is_active: bool,
 }
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ExportGuardKey {
+    Strict,
+    SourceExportedAt,
+}
+
+/// Require the contract to be frozen for `export_state`. Callers are
+/// responsible for authorising the change.
+pub fn set_strict_export(env: &Env, strict: bool) {
+    env.storage().instance().set(&ExportGuardKey::Strict, &strict);
+}
+
+pub fn is_strict_export(env: &Env) -> bool {
+    env.storage().instance().get(&ExportGuardKey::Strict).unwrap_or(false)
+}
+
+/// Call at the start of `export_state` with whether the contract is
+/// `Paused` or `Terminated`. Returns the marker to put in the payload:
+/// the export time when frozen, `None` for a live export. Live exports fail
+/// with `InvalidState` in strict mode. Writes nothing.
+pub fn guard_export(env: &Env, frozen: bool) -> Result<Option<u64>, CommonError> {
+    if !frozen {
+        if is_strict_export(env) {
+            return Err(CommonError::InvalidState);
+        }
+        return Ok(None);
+    }
+    Ok(Some(env.ledger().timestamp()))
+}
+
+/// Call from `import_state` with the payload's marker. A live source
+/// (`None`) is rejected as `InvalidState`, a marker from the future as
+/// `InvalidFormat`.
+pub fn record_source_export(env: &Env, marker: Option<u64>) -> Result<(), CommonError> {
+    match marker {
+        None => Err(CommonError::InvalidState),
+        Some(at) if at > env.ledger().timestamp() => Err(CommonError::InvalidFormat),
+        Some(at) => {
+            env.storage().instance().set(&ExportGuardKey::SourceExportedAt, &at);
+            Ok(())
+        }
+    }
+}
+
+/// Export time of the source this contract was imported from, or `None`
+/// if it was never imported or imported from a payload that predates the
+/// marker
+pub fn source_exported_at(env: &Env) -> Option<u64> {
+    env.storage().instance().get(&ExportGuardKey::SourceExportedAt)
+}
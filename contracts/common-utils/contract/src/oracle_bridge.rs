@@ -1,9 +1,25 @@
 use soroban_sdk::{
-    contract, contractimpl, Address, Bytes, Env, 
-    contracttype, symbol_short,
+    contract, contractimpl, Address, Bytes, BytesN, Env, Symbol, Vec,
+    contracttype, panic_with_error, symbol_short,
 };
+use crate::authorization::{secp256k1_verify, SignatureScheme};
+use crate::crypto::{encode_message, root_from_proof, MessageField};
 use crate::error::CommonError;
 
+/// Largest `fulfill_request` result accepted until the admin sets a limit.
+pub const DEFAULT_MAX_PAYLOAD_SIZE: u32 = 4096;
+
+/// Fixed-point scale of `post_rate` / `get_rate` values: a rate of
+/// `RATE_SCALE` means one unit of the asset is worth one reference unit.
+pub const RATE_SCALE: i128 = 10_000_000;
+
+/// Weight of an oracle the admin has not weighted
+pub const DEFAULT_ORACLE_WEIGHT: u32 = 1;
+
+/// Agreeing weight that fulfils a request until the admin sets one, so a
+/// single unweighted oracle is enough
+pub const DEFAULT_WEIGHT_THRESHOLD: u32 = 1;
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct OracleRequest {
@@ -16,14 +32,33 @@ pub struct OracleRequest {
     pub timestamp: u64,
 }
 
+/// A merkle root over a batch of results, signed by the posting oracle.
+/// `signature` is by the key registered with `set_oracle_key` (ed25519) or
+/// `set_oracle_secp256k1_key`, over
+/// `create_root_message(oracle, root, leaf_count, timestamp)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RootAttestation {
+    pub oracle: Address,
+    pub root: BytesN<32>,
+    pub leaf_count: u32,
+    pub timestamp: u64,
+    pub signature: BytesN<64>,
+}
+
 #[contract]
 pub struct OracleBridgeContract;
 
 #[contractimpl]
 impl OracleBridgeContract {
-    pub fn initialize(env: Env, admin: Address) {
+    /// Fails on a second call instead of resetting the admin.
+    pub fn initialize(env: Env, admin: Address) -> Result<(), CommonError> {
+        if env.storage().instance().has(&symbol_short!("admin")) {
+            return Err(CommonError::AlreadyInitialized);
+        }
         env.storage().instance().set(&symbol_short!("admin"), &admin);
         env.storage().instance().set(&symbol_short!("req_cnt"), &0u64);
+        Ok(())
     }
 
     pub fn add_oracle(env: Env, oracle: Address) -> Result<(), CommonError> {
@@ -35,9 +70,187 @@ impl OracleBridgeContract {
             return Err(CommonError::OracleAlreadyExists);
         }
         env.storage().persistent().set(&key, &true);
+        // Freshness is measured from approval until the first post
+        Self::record_post(&env, &oracle);
+        Ok(())
+    }
+
+    /// ed25519 key that `oracle`'s `post_root` attestations must be signed
+    /// with. Replaces any earlier key of either scheme.
+    pub fn set_oracle_key(env: Env, admin: Address, oracle: Address, public_key: BytesN<32>) -> Result<(), CommonError> {
+        Self::require_admin(&env, &admin)?;
+        if !Self::is_approved_oracle(env.clone(), oracle.clone()) {
+            return Err(CommonError::OracleNotFound);
+        }
+        env.storage().persistent().remove(&(symbol_short!("oracle_k1"), oracle.clone()));
+        env.storage().persistent().set(&(symbol_short!("oracle_pk"), oracle), &public_key);
         Ok(())
     }
 
+    pub fn get_oracle_key(env: Env, oracle: Address) -> Option<BytesN<32>> {
+        env.storage().persistent().get(&(symbol_short!("oracle_pk"), oracle))
+    }
+
+    /// Uncompressed secp256k1 key that `oracle`'s `post_root` attestations
+    /// must be signed with, low-S over the SHA-256 of the message. Replaces
+    /// any earlier key of either scheme.
+    pub fn set_oracle_secp256k1_key(
+        env: Env,
+        admin: Address,
+        oracle: Address,
+        public_key: BytesN<65>,
+    ) -> Result<(), CommonError> {
+        Self::require_admin(&env, &admin)?;
+        if !Self::is_approved_oracle(env.clone(), oracle.clone()) {
+            return Err(CommonError::OracleNotFound);
+        }
+        env.storage().persistent().remove(&(symbol_short!("oracle_pk"), oracle.clone()));
+        env.storage().persistent().set(&(symbol_short!("oracle_k1"), oracle), &public_key);
+        Ok(())
+    }
+
+    pub fn get_oracle_secp256k1_key(env: Env, oracle: Address) -> Option<BytesN<65>> {
+        env.storage().persistent().get(&(symbol_short!("oracle_k1"), oracle))
+    }
+
+    /// Scheme of `oracle`'s registered key, if it has one
+    pub fn get_oracle_scheme(env: Env, oracle: Address) -> Option<SignatureScheme> {
+        if env.storage().persistent().has(&(symbol_short!("oracle_k1"), oracle.clone())) {
+            Some(SignatureScheme::Secp256k1)
+        } else if env.storage().persistent().has(&(symbol_short!("oracle_pk"), oracle)) {
+            Some(SignatureScheme::Ed25519)
+        } else {
+            None
+        }
+    }
+
+    /// Longest gap between posts `oracle` is allowed before
+    /// `check_oracle_sla` reports it stale. 0 removes the SLA.
+    pub fn set_oracle_sla(env: Env, admin: Address, oracle: Address, secs: u64) -> Result<(), CommonError> {
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("admin"))
+            .ok_or(CommonError::NotInitialized)?;
+        if stored_admin != admin {
+            return Err(CommonError::NotAuthorized);
+        }
+        admin.require_auth();
+
+        let key = (symbol_short!("sla"), oracle);
+        if secs == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &secs);
+        }
+        Ok(())
+    }
+
+    pub fn get_oracle_sla(env: Env, oracle: Address) -> Option<u64> {
+        env.storage().persistent().get(&(symbol_short!("sla"), oracle))
+    }
+
+    /// Largest result, in bytes, an oracle may post with `fulfill_request`.
+    pub fn set_max_payload_size(env: Env, admin: Address, bytes: u32) -> Result<(), CommonError> {
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("admin"))
+            .ok_or(CommonError::NotInitialized)?;
+        if stored_admin != admin {
+            return Err(CommonError::NotAuthorized);
+        }
+        admin.require_auth();
+
+        if bytes == 0 {
+            return Err(CommonError::OutOfRange);
+        }
+        env.storage().instance().set(&symbol_short!("max_pay"), &bytes);
+        Ok(())
+    }
+
+    pub fn get_max_payload_size(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("max_pay"))
+            .unwrap_or(DEFAULT_MAX_PAYLOAD_SIZE)
+    }
+
+    /// Voting weight of `oracle` in `fulfill_request`. Must be positive.
+    pub fn set_oracle_weight(env: Env, admin: Address, oracle: Address, weight: u32) -> Result<(), CommonError> {
+        Self::require_admin(&env, &admin)?;
+        if weight == 0 {
+            return Err(CommonError::OutOfRange);
+        }
+        env.storage().persistent().set(&(symbol_short!("weight"), oracle), &weight);
+        Ok(())
+    }
+
+    pub fn get_oracle_weight(env: Env, oracle: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("weight"), oracle))
+            .unwrap_or(DEFAULT_ORACLE_WEIGHT)
+    }
+
+    /// Summed weight of oracles posting the same result needed to fulfil
+    /// a request. With every weight at 1 this is a plain oracle count.
+    pub fn set_weight_threshold(env: Env, admin: Address, threshold: u32) -> Result<(), CommonError> {
+        Self::require_admin(&env, &admin)?;
+        if threshold == 0 {
+            return Err(CommonError::OutOfRange);
+        }
+        env.storage().instance().set(&symbol_short!("w_thresh"), &threshold);
+        Ok(())
+    }
+
+    pub fn get_weight_threshold(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("w_thresh"))
+            .unwrap_or(DEFAULT_WEIGHT_THRESHOLD)
+    }
+
+    /// Weight posted so far for `result` on an unfulfilled request
+    pub fn get_result_weight(env: Env, request_id: u64, result: Bytes) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&Self::tally_key(&env, request_id, &result))
+            .unwrap_or(0)
+    }
+
+    /// When `oracle` last fulfilled a request or posted a root
+    pub fn get_last_post(env: Env, oracle: Address) -> Option<u64> {
+        env.storage().persistent().get(&(symbol_short!("last_post"), oracle))
+    }
+
+    /// Whether more than `max_gap_secs` passed since `oracle` last posted.
+    /// Unknown oracles are always stale.
+    pub fn is_oracle_stale(env: Env, oracle: Address, max_gap_secs: u64) -> bool {
+        match Self::get_last_post(env.clone(), oracle) {
+            Some(last) => env.ledger().timestamp().saturating_sub(last) > max_gap_secs,
+            None => true,
+        }
+    }
+
+    /// Permissionless SLA check: emits `oracle_stale` with
+    /// `(last_post, sla)` and returns true when `oracle` is past its SLA.
+    /// Oracles without an SLA are never reported.
+    pub fn check_oracle_sla(env: Env, oracle: Address) -> bool {
+        let Some(sla) = Self::get_oracle_sla(env.clone(), oracle.clone()) else {
+            return false;
+        };
+        if !Self::is_oracle_stale(env.clone(), oracle.clone(), sla) {
+            return false;
+        }
+        let last_post = Self::get_last_post(env.clone(), oracle.clone());
+        env.events().publish(
+            (Symbol::new(&env, "oracle_stale"), oracle),
+            (last_post, sla),
+        );
+        true
+    }
+
     pub fn request_data(env: Env, requester: Address, data_type: u32, params: Bytes) -> u64 {
         requester.require_auth();
 
@@ -63,8 +276,16 @@ impl OracleBridgeContract {
         request_id
     }
 
+    /// Post `oracle`'s result for a request. The request is fulfilled once
+    /// the oracles posting the same result reach `get_weight_threshold`;
+    /// until then each post only adds its weight, emitting `req_vote`.
     pub fn fulfill_request(env: Env, oracle: Address, request_id: u64, result: Bytes) -> Result<(), CommonError> {
         oracle.require_auth();
+
+        // Size is checked first so oversized results cost no storage reads
+        if result.len() > Self::get_max_payload_size(env.clone()) {
+            return Err(CommonError::PayloadTooLarge);
+        }
         
         let oracle_key = (symbol_short!("oracle"), oracle.clone());
         if !env.storage().persistent().has(&oracle_key) {
@@ -78,6 +299,25 @@ impl OracleBridgeContract {
             return Err(CommonError::RequestAlreadyFulfilled);
         }
 
+        let voted_key = (symbol_short!("voted"), request_id, oracle.clone());
+        if env.storage().persistent().has(&voted_key) {
+            return Err(CommonError::AlreadyVoted);
+        }
+        env.storage().persistent().set(&voted_key, &true);
+        Self::record_post(&env, &oracle);
+
+        // Saturating: a sum past u32::MAX is past any threshold too
+        let tally_key = Self::tally_key(&env, request_id, &result);
+        let tally = Self::get_result_weight(env.clone(), request_id, result.clone())
+            .saturating_add(Self::get_oracle_weight(env.clone(), oracle.clone()));
+        if tally < Self::get_weight_threshold(env.clone()) {
+            env.storage().persistent().set(&tally_key, &tally);
+            env.events()
+                .publish((symbol_short!("req_vote"),), (request_id, oracle, tally));
+            return Ok(());
+        }
+        env.storage().persistent().remove(&tally_key);
+
         request.fulfilled = true;
         request.result = result;
         
@@ -87,8 +327,163 @@ impl OracleBridgeContract {
         Ok(())
     }
     
+    /// Publish the value of one unit of `asset` in reference units, scaled
+    /// by `RATE_SCALE`. Each post replaces the previous rate.
+    pub fn post_rate(env: Env, oracle: Address, asset: Address, rate: i128) -> Result<(), CommonError> {
+        oracle.require_auth();
+
+        if !Self::is_approved_oracle(env.clone(), oracle.clone()) {
+            return Err(CommonError::NotAuthorized);
+        }
+        if rate <= 0 {
+            return Err(CommonError::OutOfRange);
+        }
+
+        env.storage().persistent().set(&(symbol_short!("rate"), asset.clone()), &rate);
+        Self::record_post(&env, &oracle);
+        env.events().publish((symbol_short!("rate"), asset), (oracle, rate));
+        Ok(())
+    }
+
+    /// Latest `post_rate` value for `asset`
+    pub fn get_rate(env: Env, asset: Address) -> Option<i128> {
+        env.storage().persistent().get(&(symbol_short!("rate"), asset))
+    }
+
     pub fn is_approved_oracle(env: Env, oracle: Address) -> bool {
          let key = (symbol_short!("oracle"), oracle);
          env.storage().persistent().has(&key)
     }
+
+    /// Store a signed merkle root so individual leaves can later be proven
+    /// with `verify_membership` instead of being posted one by one. The
+    /// signature is checked against the oracle's registered key: an invalid
+    /// ed25519 signature traps, a secp256k1 one fails with `NotAuthorized`.
+    pub fn post_root(env: Env, attestation: RootAttestation) -> Result<(), CommonError> {
+        attestation.oracle.require_auth();
+
+        let oracle_key = (symbol_short!("oracle"), attestation.oracle.clone());
+        if !env.storage().persistent().has(&oracle_key) {
+            return Err(CommonError::NotAuthorized);
+        }
+        let scheme = Self::get_oracle_scheme(env.clone(), attestation.oracle.clone())
+            .ok_or(CommonError::KeyNotFound)?;
+
+        let root_key = (symbol_short!("root"), attestation.oracle.clone(), attestation.root.clone());
+        if env.storage().persistent().has(&root_key) {
+            return Err(CommonError::RootAlreadyPosted);
+        }
+
+        let message = Self::create_root_message(
+            env.clone(),
+            attestation.oracle.clone(),
+            attestation.root.clone(),
+            attestation.leaf_count,
+            attestation.timestamp,
+        );
+        match scheme {
+            SignatureScheme::Ed25519 => {
+                let public_key = Self::get_oracle_key(env.clone(), attestation.oracle.clone())
+                    .ok_or(CommonError::KeyNotFound)?;
+                env.crypto()
+                    .ed25519_verify(&public_key, &message, &attestation.signature);
+            }
+            SignatureScheme::Secp256k1 => {
+                let public_key = Self::get_oracle_secp256k1_key(env.clone(), attestation.oracle.clone())
+                    .ok_or(CommonError::KeyNotFound)?;
+                if !secp256k1_verify(&env, &public_key, &message, &attestation.signature) {
+                    return Err(CommonError::NotAuthorized);
+                }
+            }
+        }
+
+        env.storage().persistent().set(&root_key, &attestation);
+        Self::record_post(&env, &attestation.oracle);
+        env.events().publish(
+            (symbol_short!("root_pst"), attestation.oracle),
+            (attestation.root, attestation.leaf_count),
+        );
+
+        Ok(())
+    }
+
+    /// Check that `leaf` is leaf number `index` of a root previously posted
+    /// by `oracle`. Returns false for unknown roots, for an `index` past the
+    /// root's `leaf_count` and for proofs that do not rebuild `root`.
+    pub fn verify_membership(
+        env: Env,
+        oracle: Address,
+        root: BytesN<32>,
+        leaf: BytesN<32>,
+        index: u32,
+        proof: Vec<BytesN<32>>,
+    ) -> bool {
+        let Some(attestation) = Self::get_root(env.clone(), oracle, root.clone()) else {
+            return false;
+        };
+        root_from_proof(&env, &leaf, index, attestation.leaf_count, &proof) == Some(root)
+    }
+
+    pub fn get_root(env: Env, oracle: Address, root: BytesN<32>) -> Option<RootAttestation> {
+        let root_key = (symbol_short!("root"), oracle, root);
+        env.storage().persistent().get(&root_key)
+    }
+
+    /// Canonical bytes an oracle signs to attest a merkle root.
+    pub fn create_root_message(
+        env: Env,
+        oracle: Address,
+        root: BytesN<32>,
+        leaf_count: u32,
+        timestamp: u64,
+    ) -> Bytes {
+        encode_message(
+            &env,
+            &[
+                MessageField::Address(oracle),
+                MessageField::Hash(root),
+                MessageField::U32(leaf_count),
+                MessageField::U64(timestamp),
+            ],
+        )
+        .unwrap_or_else(|err| panic_with_error!(&env, err))
+    }
+
+    /// Canonical bytes an oracle signs to attest the result of a request.
+    /// See `crypto::message` for the layout.
+    pub fn create_message(env: Env, oracle: Address, request_id: u64, result: Bytes) -> Bytes {
+        encode_message(
+            &env,
+            &[
+                MessageField::Address(oracle),
+                MessageField::U64(request_id),
+                MessageField::Bytes(result),
+            ],
+        )
+        .unwrap_or_else(|err| panic_with_error!(&env, err))
+    }
+
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), CommonError> {
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("admin"))
+            .ok_or(CommonError::NotInitialized)?;
+        if stored_admin != *admin {
+            return Err(CommonError::NotAuthorized);
+        }
+        admin.require_auth();
+        Ok(())
+    }
+
+    fn tally_key(env: &Env, request_id: u64, result: &Bytes) -> (Symbol, u64, BytesN<32>) {
+        let result_hash: BytesN<32> = env.crypto().sha256(result).into();
+        (symbol_short!("tally"), request_id, result_hash)
+    }
+
+    fn record_post(env: &Env, oracle: &Address) {
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("last_post"), oracle.clone()), &env.ledger().timestamp());
+    }
 }
@@ -0,0 +1,483 @@
+#![cfg(test)]
+
+use crate::crypto::{hash_leaf, hash_node};
+use crate::oracle_bridge::{OracleBridgeContract, OracleBridgeContractClient, RootAttestation};
+use soroban_sdk::{
+    testutils::{ed25519::Sign, Address as _},
+    vec, Address, Bytes, BytesN, Env, Vec,
+};
+
+struct Tree {
+    leaves: [BytesN<32>; 4],
+    nodes: [BytesN<32>; 4],
+    n01: BytesN<32>,
+    n23: BytesN<32>,
+    root: BytesN<32>,
+}
+
+fn leaf(env: &Env, data: &[u8]) -> BytesN<32> {
+    env.crypto().sha256(&Bytes::from_slice(env, data)).into()
+}
+
+fn build_tree(env: &Env) -> Tree {
+    let leaves = [
+        leaf(env, b"result-0"),
+        leaf(env, b"result-1"),
+        leaf(env, b"result-2"),
+        leaf(env, b"result-3"),
+    ];
+    let nodes = [
+        hash_leaf(env, &leaves[0]),
+        hash_leaf(env, &leaves[1]),
+        hash_leaf(env, &leaves[2]),
+        hash_leaf(env, &leaves[3]),
+    ];
+    let n01 = hash_node(env, &nodes[0], &nodes[1]);
+    let n23 = hash_node(env, &nodes[2], &nodes[3]);
+    let root = hash_node(env, &n01, &n23);
+    Tree { leaves, nodes, n01, n23, root }
+}
+
+fn setup(env: &Env) -> (OracleBridgeContractClient, Address) {
+    env.mock_all_auths();
+    let id = env.register(OracleBridgeContract, ());
+    let client = OracleBridgeContractClient::new(env, &id);
+    let admin = Address::generate(env);
+    let oracle = Address::generate(env);
+    client.initialize(&admin);
+    client.add_oracle(&oracle);
+    (client, oracle)
+}
+
+/// Registers a fresh key for `oracle` and signs `root` with it.
+fn signed_root_of(
+    env: &Env,
+    client: &OracleBridgeContractClient,
+    oracle: &Address,
+    root: &BytesN<32>,
+    leaf_count: u32,
+) -> RootAttestation {
+    let signer = soroban_sdk::testutils::ed25519::generate(env);
+    let admin = stored_admin(env, client);
+    client.set_oracle_key(&admin, oracle, &BytesN::from_array(env, signer.public_key()));
+
+    let message = client.create_root_message(oracle, root, &leaf_count, &1_000);
+    let mut raw = [0u8; 256];
+    let raw = &mut raw[..message.len() as usize];
+    message.copy_into_slice(raw);
+    let signature: [u8; 64] = signer.sign(&*raw);
+
+    RootAttestation {
+        oracle: oracle.clone(),
+        root: root.clone(),
+        leaf_count,
+        timestamp: 1_000,
+        signature: BytesN::from_array(env, &signature),
+    }
+}
+
+fn signed_root(env: &Env, client: &OracleBridgeContractClient, oracle: &Address, root: &BytesN<32>) -> RootAttestation {
+    signed_root_of(env, client, oracle, root, 4)
+}
+
+#[test]
+fn test_post_root_and_verify_all_leaves() {
+    let env = Env::default();
+    let (client, oracle) = setup(&env);
+    let tree = build_tree(&env);
+
+    client.post_root(&signed_root(&env, &client, &oracle, &tree.root));
+    assert!(client.get_root(&oracle, &tree.root).is_some());
+
+    let proofs: [Vec<BytesN<32>>; 4] = [
+        vec![&env, tree.nodes[1].clone(), tree.n23.clone()],
+        vec![&env, tree.nodes[0].clone(), tree.n23.clone()],
+        vec![&env, tree.nodes[3].clone(), tree.n01.clone()],
+        vec![&env, tree.nodes[2].clone(), tree.n01.clone()],
+    ];
+    for (i, proof) in proofs.iter().enumerate() {
+        assert!(client.verify_membership(&oracle, &tree.root, &tree.leaves[i], &(i as u32), proof));
+    }
+}
+
+#[test]
+fn test_tampered_leaf_rejected() {
+    let env = Env::default();
+    let (client, oracle) = setup(&env);
+    let tree = build_tree(&env);
+    client.post_root(&signed_root(&env, &client, &oracle, &tree.root));
+
+    let proof = vec![&env, tree.nodes[1].clone(), tree.n23.clone()];
+    let forged = leaf(&env, b"result-X");
+    assert!(!client.verify_membership(&oracle, &tree.root, &forged, &0, &proof));
+}
+
+#[test]
+fn test_tampered_proof_rejected() {
+    let env = Env::default();
+    let (client, oracle) = setup(&env);
+    let tree = build_tree(&env);
+    client.post_root(&signed_root(&env, &client, &oracle, &tree.root));
+
+    let bad_proof = vec![&env, tree.nodes[1].clone(), tree.n01.clone()];
+    assert!(!client.verify_membership(&oracle, &tree.root, &tree.leaves[0], &0, &bad_proof));
+
+    let short_proof = vec![&env, tree.nodes[1].clone()];
+    assert!(!client.verify_membership(&oracle, &tree.root, &tree.leaves[0], &0, &short_proof));
+
+    let long_proof = vec![&env, tree.nodes[1].clone(), tree.n23.clone(), tree.n01.clone()];
+    assert!(!client.verify_membership(&oracle, &tree.root, &tree.leaves[0], &0, &long_proof));
+
+    // A valid path claimed for the wrong position
+    let proof = vec![&env, tree.nodes[1].clone(), tree.n23.clone()];
+    assert!(!client.verify_membership(&oracle, &tree.root, &tree.leaves[0], &1, &proof));
+}
+
+#[test]
+fn test_index_bounded_by_leaf_count() {
+    let env = Env::default();
+    let (client, oracle) = setup(&env);
+    let tree = build_tree(&env);
+    client.post_root(&signed_root(&env, &client, &oracle, &tree.root));
+
+    // Index 4 walks the same left/right path as index 0
+    let proof = vec![&env, tree.nodes[1].clone(), tree.n23.clone()];
+    assert!(client.verify_membership(&oracle, &tree.root, &tree.leaves[0], &0, &proof));
+    assert!(!client.verify_membership(&oracle, &tree.root, &tree.leaves[0], &4, &proof));
+    assert!(!client.verify_membership(&oracle, &tree.root, &tree.leaves[0], &u32::MAX, &proof));
+}
+
+#[test]
+fn test_interior_node_is_not_a_leaf() {
+    let env = Env::default();
+    let (client, oracle) = setup(&env);
+    let tree = build_tree(&env);
+    client.post_root(&signed_root(&env, &client, &oracle, &tree.root));
+
+    let proof = vec![&env, tree.n23.clone()];
+    assert!(!client.verify_membership(&oracle, &tree.root, &tree.n01, &0, &proof));
+}
+
+#[test]
+fn test_odd_leaf_count_carries_last_node_up() {
+    let env = Env::default();
+    let (client, oracle) = setup(&env);
+    let tree = build_tree(&env);
+    let root = hash_node(&env, &tree.n01, &tree.nodes[2]);
+    client.post_root(&signed_root_of(&env, &client, &oracle, &root, 3));
+
+    // The third leaf has no sibling on the bottom level
+    let proof = vec![&env, tree.n01.clone()];
+    assert!(client.verify_membership(&oracle, &root, &tree.leaves[2], &2, &proof));
+    let proof = vec![&env, tree.nodes[0].clone(), tree.nodes[2].clone()];
+    assert!(client.verify_membership(&oracle, &root, &tree.leaves[1], &1, &proof));
+    assert!(!client.verify_membership(&oracle, &root, &tree.leaves[3], &3, &proof));
+}
+
+#[test]
+fn test_unposted_root_rejected() {
+    let env = Env::default();
+    let (client, oracle) = setup(&env);
+    let tree = build_tree(&env);
+
+    // Valid proof, but the root was never posted
+    let proof = vec![&env, tree.nodes[1].clone(), tree.n23.clone()];
+    assert!(!client.verify_membership(&oracle, &tree.root, &tree.leaves[0], &0, &proof));
+
+    // Root posted by a different oracle does not count
+    client.post_root(&signed_root(&env, &client, &oracle, &tree.root));
+    let other = Address::generate(&env);
+    assert!(!client.verify_membership(&other, &tree.root, &tree.leaves[0], &0, &proof));
+}
+
+#[test]
+fn test_duplicate_root_rejected() {
+    let env = Env::default();
+    let (client, oracle) = setup(&env);
+    let tree = build_tree(&env);
+    let attestation = signed_root(&env, &client, &oracle, &tree.root);
+
+    client.post_root(&attestation);
+    assert!(client.try_post_root(&attestation).is_err());
+}
+
+#[test]
+#[should_panic]
+fn test_bad_signature_panics() {
+    let env = Env::default();
+    let (client, oracle) = setup(&env);
+    let tree = build_tree(&env);
+    let mut attestation = signed_root(&env, &client, &oracle, &tree.root);
+    attestation.leaf_count = 5;
+
+    client.post_root(&attestation);
+}
+
+#[test]
+#[should_panic]
+fn test_signature_by_unregistered_key_panics() {
+    let env = Env::default();
+    let (client, oracle) = setup(&env);
+    let tree = build_tree(&env);
+    let attestation = signed_root(&env, &client, &oracle, &tree.root);
+
+    // The key the attestation was signed with is no longer the oracle's
+    let other = soroban_sdk::testutils::ed25519::generate(&env);
+    let admin = stored_admin(&env, &client);
+    client.set_oracle_key(&admin, &oracle, &BytesN::from_array(&env, other.public_key()));
+
+    client.post_root(&attestation);
+}
+
+#[test]
+fn test_oracle_without_key_cannot_post() {
+    use crate::error::CommonError;
+    let env = Env::default();
+    let (client, oracle) = setup(&env);
+    let tree = build_tree(&env);
+    let attestation = RootAttestation {
+        oracle: oracle.clone(),
+        root: tree.root.clone(),
+        leaf_count: 4,
+        timestamp: 1_000,
+        signature: BytesN::from_array(&env, &[0u8; 64]),
+    };
+
+    assert_eq!(client.try_post_root(&attestation), Err(Ok(CommonError::KeyNotFound)));
+}
+
+#[test]
+fn test_secp256k1_key_replaces_ed25519_and_rejects_high_s() {
+    use crate::authorization::SignatureScheme;
+    use crate::error::CommonError;
+    let env = Env::default();
+    let (client, oracle) = setup(&env);
+    let tree = build_tree(&env);
+    let admin = stored_admin(&env, &client);
+
+    client.set_oracle_key(&admin, &oracle, &BytesN::from_array(&env, &[1u8; 32]));
+    assert_eq!(client.get_oracle_scheme(&oracle), Some(SignatureScheme::Ed25519));
+    client.set_oracle_secp256k1_key(&admin, &oracle, &BytesN::from_array(&env, &[4u8; 65]));
+    assert_eq!(client.get_oracle_scheme(&oracle), Some(SignatureScheme::Secp256k1));
+    assert_eq!(client.get_oracle_key(&oracle), None);
+
+    // `s` above half the group order is refused before any recovery
+    let mut signature = [0x11u8; 64];
+    signature[32] = 0xff;
+    let attestation = RootAttestation {
+        oracle: oracle.clone(),
+        root: tree.root.clone(),
+        leaf_count: 4,
+        timestamp: 1_000,
+        signature: BytesN::from_array(&env, &signature),
+    };
+    assert_eq!(client.try_post_root(&attestation), Err(Ok(CommonError::NotAuthorized)));
+    assert!(client.get_root(&oracle, &tree.root).is_none());
+}
+
+#[test]
+fn test_unapproved_oracle_cannot_post() {
+    use crate::error::CommonError;
+    let env = Env::default();
+    let (client, oracle) = setup(&env);
+    let tree = build_tree(&env);
+    let stranger = Address::generate(&env);
+    let admin = stored_admin(&env, &client);
+    assert_eq!(
+        client.try_set_oracle_key(&admin, &stranger, &BytesN::from_array(&env, &[1u8; 32])),
+        Err(Ok(CommonError::OracleNotFound))
+    );
+
+    let mut attestation = signed_root(&env, &client, &oracle, &tree.root);
+    attestation.oracle = stranger;
+    assert!(client.try_post_root(&attestation).is_err());
+}
+
+#[test]
+fn test_oracle_freshness_sla() {
+    use soroban_sdk::testutils::{Events, Ledger};
+    use soroban_sdk::{IntoVal, Symbol};
+
+    let env = Env::default();
+    env.ledger().set_timestamp(1_000);
+    let (client, oracle) = setup(&env);
+    let admin = Address::generate(&env);
+    assert_eq!(client.get_last_post(&oracle), Some(1_000));
+    assert!(client.is_oracle_stale(&Address::generate(&env), &3_600));
+
+    // No SLA configured: never reported
+    env.ledger().set_timestamp(10_000);
+    assert!(!client.check_oracle_sla(&oracle));
+
+    let request_id = client.request_data(&admin, &1, &Bytes::new(&env));
+    client.fulfill_request(&oracle, &request_id, &Bytes::from_slice(&env, b"42"));
+    assert_eq!(client.get_last_post(&oracle), Some(10_000));
+
+    env.ledger().set_timestamp(10_300);
+    assert!(!client.is_oracle_stale(&oracle, &300));
+    assert!(client.is_oracle_stale(&oracle, &299));
+
+    let stored_admin: Address = env.as_contract(&client.address, || {
+        env.storage().instance().get(&soroban_sdk::symbol_short!("admin")).unwrap()
+    });
+    client.set_oracle_sla(&stored_admin, &oracle, &600);
+    assert!(!client.check_oracle_sla(&oracle));
+
+    env.ledger().set_timestamp(10_601);
+    assert!(client.check_oracle_sla(&oracle));
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(
+        topics,
+        vec![&env, Symbol::new(&env, "oracle_stale").into_val(&env), oracle.into_val(&env)]
+    );
+    let (last_post, sla): (Option<u64>, u64) = soroban_sdk::TryFromVal::try_from_val(&env, &data).unwrap();
+    assert_eq!((last_post, sla), (Some(10_000), 600));
+}
+
+#[test]
+fn test_payload_size_limit_boundary() {
+    use crate::error::CommonError;
+    use crate::oracle_bridge::DEFAULT_MAX_PAYLOAD_SIZE;
+
+    let env = Env::default();
+    let (client, oracle) = setup(&env);
+    let requester = Address::generate(&env);
+    let stored_admin: Address = env.as_contract(&client.address, || {
+        env.storage().instance().get(&soroban_sdk::symbol_short!("admin")).unwrap()
+    });
+    assert_eq!(client.get_max_payload_size(), DEFAULT_MAX_PAYLOAD_SIZE);
+
+    client.set_max_payload_size(&stored_admin, &8);
+    let first = client.request_data(&requester, &1, &Bytes::new(&env));
+    let second = client.request_data(&requester, &1, &Bytes::new(&env));
+
+    client.fulfill_request(&oracle, &first, &Bytes::from_array(&env, &[7u8; 8]));
+    assert_eq!(
+        client.try_fulfill_request(&oracle, &second, &Bytes::from_array(&env, &[7u8; 9])),
+        Err(Ok(CommonError::PayloadTooLarge))
+    );
+    assert!(client.try_set_max_payload_size(&Address::generate(&env), &64).is_err());
+}
+
+#[test]
+fn test_oversized_payload_rejected_before_other_checks() {
+    use crate::error::CommonError;
+
+    let env = Env::default();
+    let (client, _) = setup(&env);
+    let stranger = Address::generate(&env);
+    let oversized = Bytes::from_slice(&env, &[0u8; 4097]);
+
+    // Neither the oracle nor the request exist; the size check still wins
+    assert_eq!(
+        client.try_fulfill_request(&stranger, &99, &oversized),
+        Err(Ok(CommonError::PayloadTooLarge))
+    );
+}
+
+#[test]
+fn test_approved_oracle_posts_rates() {
+    use crate::error::CommonError;
+    use crate::oracle_bridge::RATE_SCALE;
+
+    let env = Env::default();
+    let (client, oracle) = setup(&env);
+    let asset = Address::generate(&env);
+    assert_eq!(client.get_rate(&asset), None);
+
+    client.post_rate(&oracle, &asset, &(RATE_SCALE / 2));
+    client.post_rate(&oracle, &asset, &RATE_SCALE);
+    assert_eq!(client.get_rate(&asset), Some(RATE_SCALE));
+
+    assert_eq!(
+        client.try_post_rate(&Address::generate(&env), &asset, &1),
+        Err(Ok(CommonError::NotAuthorized))
+    );
+    assert_eq!(client.try_post_rate(&oracle, &asset, &0), Err(Ok(CommonError::OutOfRange)));
+}
+
+fn stored_admin(env: &Env, client: &OracleBridgeContractClient) -> Address {
+    env.as_contract(&client.address, || {
+        env.storage().instance().get(&soroban_sdk::symbol_short!("admin")).unwrap()
+    })
+}
+
+#[test]
+fn test_single_high_weight_oracle_meets_threshold() {
+    let env = Env::default();
+    let (client, oracle) = setup(&env);
+    let admin = stored_admin(&env, &client);
+    let requester = Address::generate(&env);
+    assert_eq!(client.get_oracle_weight(&oracle), 1);
+    assert_eq!(client.get_weight_threshold(), 1);
+
+    client.set_weight_threshold(&admin, &5);
+    client.set_oracle_weight(&admin, &oracle, &5);
+    let request = client.request_data(&requester, &1, &Bytes::new(&env));
+    client.fulfill_request(&oracle, &request, &Bytes::from_array(&env, &[1]));
+
+    let stored: crate::oracle_bridge::OracleRequest = env.as_contract(&client.address, || {
+        env.storage().persistent().get(&(soroban_sdk::symbol_short!("request"), request)).unwrap()
+    });
+    assert!(stored.fulfilled);
+}
+
+#[test]
+fn test_low_weight_oracles_need_more_participants() {
+    use crate::error::CommonError;
+
+    let env = Env::default();
+    let (client, first) = setup(&env);
+    let admin = stored_admin(&env, &client);
+    let requester = Address::generate(&env);
+    let second = Address::generate(&env);
+    let third = Address::generate(&env);
+    let dissenter = Address::generate(&env);
+    for oracle in [&first, &second, &third, &dissenter] {
+        if oracle != &first {
+            client.add_oracle(oracle);
+        }
+        client.set_oracle_weight(&admin, oracle, &2);
+    }
+    client.set_weight_threshold(&admin, &5);
+    assert_eq!(client.try_set_oracle_weight(&admin, &first, &0), Err(Ok(CommonError::OutOfRange)));
+
+    let request = client.request_data(&requester, &1, &Bytes::new(&env));
+    let agreed = Bytes::from_array(&env, &[1]);
+    client.fulfill_request(&first, &request, &agreed);
+    client.fulfill_request(&dissenter, &request, &Bytes::from_array(&env, &[2]));
+    client.fulfill_request(&second, &request, &agreed);
+    assert_eq!(client.get_result_weight(&request, &agreed), 4);
+    assert_eq!(
+        client.try_fulfill_request(&second, &request, &agreed),
+        Err(Ok(CommonError::AlreadyVoted))
+    );
+
+    client.fulfill_request(&third, &request, &agreed);
+    assert_eq!(client.get_result_weight(&request, &agreed), 0);
+    assert_eq!(
+        client.try_fulfill_request(&dissenter, &request, &agreed),
+        Err(Ok(CommonError::RequestAlreadyFulfilled))
+    );
+}
+
+#[test]
+fn test_weight_sum_saturates_instead_of_wrapping() {
+    let env = Env::default();
+    let (client, first) = setup(&env);
+    let admin = stored_admin(&env, &client);
+    let second = Address::generate(&env);
+    client.add_oracle(&second);
+    client.set_weight_threshold(&admin, &u32::MAX);
+    client.set_oracle_weight(&admin, &first, &(u32::MAX - 1));
+    client.set_oracle_weight(&admin, &second, &u32::MAX);
+
+    let request = client.request_data(&Address::generate(&env), &1, &Bytes::new(&env));
+    let result = Bytes::from_array(&env, &[1]);
+    client.fulfill_request(&first, &request, &result);
+    client.fulfill_request(&second, &request, &result);
+    assert_eq!(
+        client.try_fulfill_request(&first, &request, &result),
+        Err(Ok(crate::error::CommonError::RequestAlreadyFulfilled))
+    );
+}
@@ -0,0 +1,82 @@
+//! # Pagination
+//!
+//! Offset/limit paging for getters whose full result can outgrow the
+//! contract return-size limit. Storage types implement [`Paginated`] by
+//! reporting their length and reading an index range, so a page only loads
+//! the entries it returns. Contracts wrap the resulting [`Page`] in their
+//! own `#[contracttype]` (contract types cannot be generic).
+//!
+//! [`paginate`] slices a `Vec` that is already in memory, such as a list
+//! kept under a single instance key.
+
+use soroban_sdk::{Env, IntoVal, TryFromVal, Val, Vec};
+
+/// Upper bound on `limit`; larger requests are clamped.
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// One slice of a larger collection
+#[derive(Clone, Debug)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Size of the whole collection
+    pub total: u32,
+    /// Whether items exist past `offset + items.len()`
+    pub has_more: bool,
+}
+
+/// Index range `[start, end)` of the page at `offset` with at most `limit`
+/// items in a collection of `total`.
+pub fn page_bounds(total: u32, offset: u32, limit: u32) -> (u32, u32) {
+    let start = offset.min(total);
+    let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(total);
+    (start, end)
+}
+
+/// Slice `all` into the page starting at `offset` with at most `limit` items.
+pub fn paginate<T>(env: &Env, all: &Vec<T>, offset: u32, limit: u32) -> Page<T>
+where
+    T: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    let total = all.len();
+    let (start, end) = page_bounds(total, offset, limit);
+
+    let items = if start < end {
+        all.slice(start..end)
+    } else {
+        Vec::new(env)
+    };
+
+    Page {
+        items,
+        total,
+        has_more: end < total,
+    }
+}
+
+/// A keyed collection that can be read one page at a time
+pub trait Paginated {
+    type Key;
+    type Item: IntoVal<Env, Val> + TryFromVal<Env, Val>;
+
+    /// Number of items stored under `key`
+    fn len(env: &Env, key: &Self::Key) -> u32;
+
+    /// Read items `[start, end)` stored under `key`; `end` is at most `len`
+    fn range(env: &Env, key: &Self::Key, start: u32, end: u32) -> Vec<Self::Item>;
+
+    /// Read items `[offset, offset + limit)` stored under `key`
+    fn page(env: &Env, key: &Self::Key, offset: u32, limit: u32) -> Page<Self::Item> {
+        let total = Self::len(env, key);
+        let (start, end) = page_bounds(total, offset, limit);
+        let items = if start < end {
+            Self::range(env, key, start, end)
+        } else {
+            Vec::new(env)
+        };
+        Page {
+            items,
+            total,
+            has_more: end < total,
+        }
+    }
+}
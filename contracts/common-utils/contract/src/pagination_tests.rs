@@ -0,0 +1,80 @@
+#![cfg(test)]
+
+use crate::pagination::{paginate, MAX_PAGE_SIZE};
+use soroban_sdk::{Env, Vec};
+
+fn numbers(env: &Env, n: u32) -> Vec<u32> {
+    let mut v = Vec::new(env);
+    for i in 0..n {
+        v.push_back(i);
+    }
+    v
+}
+
+#[test]
+fn test_pages_cover_all_items_without_gaps_or_overlaps() {
+    let env = Env::default();
+    let all = numbers(&env, 7);
+
+    let mut seen = Vec::new(&env);
+    let mut offset = 0;
+    loop {
+        let page = paginate(&env, &all, offset, 3);
+        assert_eq!(page.total, 7);
+        for item in page.items.iter() {
+            seen.push_back(item);
+        }
+        offset += page.items.len();
+        if !page.has_more {
+            break;
+        }
+    }
+    assert_eq!(seen, all);
+}
+
+#[test]
+fn test_has_more_at_boundaries() {
+    let env = Env::default();
+    let all = numbers(&env, 6);
+
+    // Exactly filling the last page
+    let page = paginate(&env, &all, 3, 3);
+    assert_eq!(page.items.len(), 3);
+    assert!(!page.has_more);
+
+    // One item short of the end
+    let page = paginate(&env, &all, 2, 3);
+    assert!(page.has_more);
+
+    // Offset at and past the end
+    let page = paginate(&env, &all, 6, 3);
+    assert!(page.items.is_empty());
+    assert!(!page.has_more);
+    let page = paginate(&env, &all, 100, 3);
+    assert!(page.items.is_empty());
+    assert!(!page.has_more);
+}
+
+#[test]
+fn test_empty_collection_and_zero_limit() {
+    let env = Env::default();
+    let empty = numbers(&env, 0);
+    let page = paginate(&env, &empty, 0, 10);
+    assert_eq!(page.total, 0);
+    assert!(page.items.is_empty());
+    assert!(!page.has_more);
+
+    let all = numbers(&env, 2);
+    let page = paginate(&env, &all, 0, 0);
+    assert!(page.items.is_empty());
+    assert!(page.has_more);
+}
+
+#[test]
+fn test_limit_is_clamped() {
+    let env = Env::default();
+    let all = numbers(&env, MAX_PAGE_SIZE + 5);
+    let page = paginate(&env, &all, 0, u32::MAX);
+    assert_eq!(page.items.len(), MAX_PAGE_SIZE);
+    assert!(page.has_more);
+}
@@ -27,12 +27,35 @@
 //! // In your contract function:
 //! RateLimiter::check_and_update(&env, &caller, &symbol_short!("submit"), &config)?;
 //! ```
+//!
+//! ## Window jitter
+//!
+//! With [`RateLimiter::set_window_jitter`] enabled, fixed windows are aligned
+//! to `k × window + H(user) % window` rather than to the first request, so
+//! users who were throttled together do not all reset on the same ledger.
+//! The offset depends only on the address and window length, so every user
+//! keeps a full window of quota.
+//!
+//! ## Overrides
+//!
+//! [`RateLimiter::set_limit_override`] replaces the configuration a contract
+//! passes to the macros for one function, without a redeploy.
+//! [`RateLimiter::effective_config`] reports the numbers a user actually gets
+//! once the override, trust tier and network load are applied.
+//!
+//! ## Observation mode
+//!
+//! With [`RateLimiter::set_observation_mode`] enabled, calls over their limit
+//! still succeed: usage is counted as usual and a `would_limit` event with
+//! the user and function is emitted instead of the rejection, so limits can
+//! be tuned from real traffic before they are enforced.
 
 #![allow(unused)]
 
 use soroban_sdk::{
-    contracttype, symbol_short, Address, Env, Symbol,
+    contracttype, symbol_short, xdr::ToXdr, Address, Env, Symbol, Vec,
 };
+use crate::error::ContractError;
 
 // ---------------------------------------------------------------------------
 // Configuration types
@@ -77,6 +100,21 @@ pub struct RateLimitConfig {
     pub scope: RateLimitScope,
 }
 
+/// The limit a user gets on one function, see `RateLimiter::effective_config`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct EffectiveLimit {
+    /// Configuration in force: the override if there is one, else the default
+    pub config: RateLimitConfig,
+    pub overridden: bool,
+    /// Trust-tier multiplier ×1000 (1000 when the limit is not adaptive)
+    pub trust_multiplier: u32,
+    /// Network-load multiplier ×1000 (1000 when the limit is not adaptive)
+    pub load_multiplier: u32,
+    /// `config.max_requests` after both multipliers
+    pub effective_max: u32,
+}
+
 /// Information returned when a rate limit is exceeded.
 #[contracttype]
 #[derive(Clone, Debug, PartialEq)]
@@ -138,6 +176,32 @@ impl RateLimitState {
     }
 }
 
+/// Parameters a token bucket was last checked with, so its state can be
+/// reported without the caller's `RateLimitConfig`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct BucketParams {
+    /// Effective capacity in whole tokens (after adaptive multipliers).
+    pub capacity: u32,
+    /// Time to refill an empty bucket, in seconds.
+    pub window_seconds: u64,
+}
+
+/// Snapshot of a token bucket with pending refills applied.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct BucketState {
+    /// Whole tokens currently available.
+    pub tokens: u32,
+    /// Bucket capacity in whole tokens.
+    pub capacity: u32,
+    /// Refill rate in milli-tokens per second (×1000).
+    pub refill_per_sec: u64,
+    /// Timestamp at which the next whole token becomes available (`now` when
+    /// the bucket is full).
+    pub next_refill_at: u64,
+}
+
 // ---------------------------------------------------------------------------
 // Adaptive limit helpers
 // ---------------------------------------------------------------------------
@@ -208,6 +272,13 @@ pub struct TrustTierKey {
     pub user: Address,
 }
 
+/// Storage key for the tier of users without an explicit trust tier.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DefaultTrustTierKey {
+    pub prefix: Symbol,
+}
+
 /// Storage key for network load snapshot.
 #[contracttype]
 #[derive(Clone, Debug, PartialEq)]
@@ -215,6 +286,35 @@ pub struct NetworkLoadKey {
     pub prefix: Symbol,
 }
 
+/// Storage key for the load → multiplier curve.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct LoadCurveKey {
+    pub prefix: Symbol,
+}
+
+/// Storage key for a per-function configuration override.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RateLimitOverrideKey {
+    pub prefix: Symbol,
+    pub function: Symbol,
+}
+
+/// Storage key for the fixed-window jitter toggle.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct WindowJitterKey {
+    pub prefix: Symbol,
+}
+
+/// Storage key for the observation-mode toggle.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ObservationModeKey {
+    pub prefix: Symbol,
+}
+
 // ---------------------------------------------------------------------------
 // Core RateLimiter implementation
 // ---------------------------------------------------------------------------
@@ -257,9 +357,10 @@ impl RateLimiter {
         let mut state = Self::load_state(env, &key, now);
         let effective_max = adaptive.effective_max(config.max_requests);
 
-        let info = match config.strategy {
+        let mut info = match config.strategy {
             RateLimitStrategy::FixedWindow => {
-                Self::fixed_window(now, &mut state, config.window_seconds, effective_max)
+                let offset = Self::jitter_offset(env, user, config);
+                Self::fixed_window(now, &mut state, config.window_seconds, effective_max, offset)
             }
             RateLimitStrategy::SlidingWindow => {
                 Self::sliding_window(now, &mut state, config.window_seconds, effective_max)
@@ -269,10 +370,31 @@ impl RateLimiter {
             }
         };
 
+        if !info.allowed && Self::observation_mode_enabled(env) {
+            // Count the call the strategy refused, as if it had gone through
+            match config.strategy {
+                RateLimitStrategy::TokenBucket => state.tokens_available = 0,
+                _ => state.current_count = state.current_count.saturating_add(1),
+            }
+            env.events().publish(
+                (Symbol::new(env, "would_limit"), user.clone()),
+                function.clone(),
+            );
+            info.allowed = true;
+        }
+
         // Persist updated state with TTL matching 2× window to survive one
         // full previous-window look-back.
         Self::save_state(env, &key, &state, config.window_seconds);
 
+        if config.strategy == RateLimitStrategy::TokenBucket {
+            let params = BucketParams {
+                capacity: effective_max,
+                window_seconds: config.window_seconds,
+            };
+            Self::save_bucket_params(env, user, function, &params);
+        }
+
         if info.allowed {
             Ok(info)
         } else {
@@ -305,7 +427,8 @@ impl RateLimiter {
 
         match config.strategy {
             RateLimitStrategy::FixedWindow => {
-                Self::peek_fixed_window(now, &state, config.window_seconds, effective_max)
+                let offset = Self::jitter_offset(env, user, config);
+                Self::peek_fixed_window(now, &state, config.window_seconds, effective_max, offset)
             }
             RateLimitStrategy::SlidingWindow => {
                 Self::peek_sliding_window(now, &state, config.window_seconds, effective_max)
@@ -328,6 +451,44 @@ impl RateLimiter {
         env.storage().temporary().remove(&key);
     }
 
+    // -- Token bucket inspection --------------------------------------------
+
+    /// Current state of a (user, function) token bucket, with refills for the
+    /// elapsed ledger time applied.  `None` if the bucket was never used.
+    pub fn bucket_state(env: &Env, user: &Address, function: &Symbol) -> Option<BucketState> {
+        let params = Self::load_bucket_params(env, user, function)?;
+        let now = env.ledger().timestamp();
+        let key = Self::build_key(env, user, function, &RateLimitScope::PerUser);
+        let mut state = Self::load_state(env, &key, now);
+        Self::apply_refill(now, &mut state, &params);
+        Some(Self::describe_bucket(now, &state, &params))
+    }
+
+    /// Add `n` tokens to a bucket (admin/support override), capped at its
+    /// capacity.  Pending refills are applied first.
+    pub fn grant_tokens(
+        env: &Env,
+        user: &Address,
+        function: &Symbol,
+        n: u32,
+    ) -> Result<BucketState, ContractError> {
+        let params = Self::load_bucket_params(env, user, function)
+            .ok_or(ContractError::NotFound)?;
+        let now = env.ledger().timestamp();
+        let key = Self::build_key(env, user, function, &RateLimitScope::PerUser);
+        let mut state = Self::load_state(env, &key, now);
+        Self::apply_refill(now, &mut state, &params);
+
+        let capacity_scaled = (params.capacity as u64).saturating_mul(1000);
+        state.tokens_available = core::cmp::min(
+            state.tokens_available.saturating_add((n as u64).saturating_mul(1000)),
+            capacity_scaled,
+        );
+        Self::save_state(env, &key, &state, params.window_seconds);
+
+        Ok(Self::describe_bucket(now, &state, &params))
+    }
+
     // -- Adaptive helpers ---------------------------------------------------
 
     /// Set a user's trust tier (admin operation).
@@ -339,7 +500,26 @@ impl RateLimiter {
         env.storage().persistent().set(&key, tier);
     }
 
-    /// Get a user's trust tier.
+    /// Whether `user` has an explicit trust tier
+    pub fn has_trust_tier(env: &Env, user: &Address) -> bool {
+        let key = TrustTierKey {
+            prefix: Symbol::new(env, "rl_trust"),
+            user: user.clone(),
+        };
+        env.storage().persistent().has(&key)
+    }
+
+    /// Drop `user`'s explicit trust tier, returning them to the default
+    pub fn clear_trust_tier(env: &Env, user: &Address) {
+        let key = TrustTierKey {
+            prefix: Symbol::new(env, "rl_trust"),
+            user: user.clone(),
+        };
+        env.storage().persistent().remove(&key);
+    }
+
+    /// Get a user's trust tier, falling back to the default tier for users
+    /// without one.
     pub fn get_trust_tier(env: &Env, user: &Address) -> TrustTier {
         let key = TrustTierKey {
             prefix: Symbol::new(env, "rl_trust"),
@@ -348,6 +528,35 @@ impl RateLimiter {
         env.storage()
             .persistent()
             .get(&key)
+            .unwrap_or_else(|| Self::get_default_trust_tier(env))
+    }
+
+    /// Set the tier used for users without an explicit trust tier (admin
+    /// operation).
+    pub fn set_default_trust_tier(env: &Env, tier: &TrustTier) {
+        let key = DefaultTrustTierKey {
+            prefix: Symbol::new(env, "rl_dtier"),
+        };
+        env.storage().instance().set(&key, tier);
+    }
+
+    /// Restore `Standard` as the default tier
+    pub fn clear_default_trust_tier(env: &Env) {
+        let key = DefaultTrustTierKey {
+            prefix: Symbol::new(env, "rl_dtier"),
+        };
+        env.storage().instance().remove(&key);
+    }
+
+    /// Tier applied to users without an explicit trust tier. `Standard`
+    /// unless configured.
+    pub fn get_default_trust_tier(env: &Env) -> TrustTier {
+        let key = DefaultTrustTierKey {
+            prefix: Symbol::new(env, "rl_dtier"),
+        };
+        env.storage()
+            .instance()
+            .get(&key)
             .unwrap_or(TrustTier::Standard)
     }
 
@@ -358,8 +567,73 @@ impl RateLimiter {
         let load = Self::get_network_load(env);
         AdaptiveConfig {
             trust_multiplier: tier.multiplier(),
-            load_multiplier: load,
+            load_multiplier: Self::load_multiplier(env, load),
+        }
+    }
+
+    /// Configure how the reported network load maps to a limit multiplier.
+    /// `points` are `(load, multiplier ×1000)` breakpoints with strictly
+    /// increasing load; values in between are linearly interpolated and
+    /// values outside the range use the nearest endpoint.  An empty curve
+    /// restores the default, where the load value is used as the multiplier.
+    pub fn set_load_curve(env: &Env, points: &Vec<(u32, u32)>) -> Result<(), ContractError> {
+        let key = LoadCurveKey {
+            prefix: Symbol::new(env, "rl_curve"),
+        };
+        if points.is_empty() {
+            env.storage().instance().remove(&key);
+            return Ok(());
+        }
+
+        let mut prev_load: Option<u32> = None;
+        for (load, _) in points.iter() {
+            if let Some(prev) = prev_load {
+                if load <= prev {
+                    return Err(ContractError::InvalidConfiguration);
+                }
+            }
+            prev_load = Some(load);
         }
+
+        env.storage().instance().set(&key, points);
+        Ok(())
+    }
+
+    /// Read the configured load curve (empty when unset).
+    pub fn get_load_curve(env: &Env) -> Vec<(u32, u32)> {
+        let key = LoadCurveKey {
+            prefix: Symbol::new(env, "rl_curve"),
+        };
+        env.storage()
+            .instance()
+            .get(&key)
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    /// Map a network-load value to a multiplier ×1000 through the curve.
+    pub fn load_multiplier(env: &Env, load: u32) -> u32 {
+        let curve = Self::get_load_curve(env);
+        let Some((first_load, first_mult)) = curve.first() else {
+            return load;
+        };
+        if load <= first_load {
+            return first_mult;
+        }
+
+        let mut lower = (first_load, first_mult);
+        for (point_load, point_mult) in curve.iter().skip(1) {
+            if load <= point_load {
+                let (lo_load, lo_mult) = lower;
+                // i128 holds delta * offset for any pair of u32 breakpoints,
+                // and the result stays between the two multipliers
+                let span = (point_load - lo_load) as i128;
+                let offset = (load - lo_load) as i128;
+                let delta = point_mult as i128 - lo_mult as i128;
+                return (lo_mult as i128 + delta * offset / span) as u32;
+            }
+            lower = (point_load, point_mult);
+        }
+        lower.1
     }
 
     /// Record a network-load snapshot (called externally, e.g. by an oracle or
@@ -380,23 +654,154 @@ impl RateLimiter {
         env.storage().instance().get(&key).unwrap_or(1000u32)
     }
 
+    // -- Overrides ----------------------------------------------------------
+
+    /// Use `config` instead of the contract's default for `function`, or go
+    /// back to the default with `None`. Callers are responsible for
+    /// authorising the change.
+    pub fn set_limit_override(env: &Env, function: &Symbol, config: Option<RateLimitConfig>) {
+        let key = RateLimitOverrideKey {
+            prefix: symbol_short!("rl_ovr"),
+            function: function.clone(),
+        };
+        match config {
+            Some(config) => env.storage().instance().set(&key, &config),
+            None => env.storage().instance().remove(&key),
+        }
+    }
+
+    pub fn get_limit_override(env: &Env, function: &Symbol) -> Option<RateLimitConfig> {
+        let key = RateLimitOverrideKey {
+            prefix: symbol_short!("rl_ovr"),
+            function: function.clone(),
+        };
+        env.storage().instance().get(&key)
+    }
+
+    /// The override for `function` if one is set, else `default`.
+    pub fn resolve_config(env: &Env, function: &Symbol, default: RateLimitConfig) -> RateLimitConfig {
+        Self::get_limit_override(env, function).unwrap_or(default)
+    }
+
+    /// What `user` gets on `function`, whose contract default is `default`.
+    /// `adaptive` says whether the function uses `rate_limit_adaptive!`.
+    pub fn effective_config(
+        env: &Env,
+        user: &Address,
+        function: &Symbol,
+        default: RateLimitConfig,
+        adaptive: bool,
+    ) -> EffectiveLimit {
+        let overridden = Self::get_limit_override(env, function);
+        let multipliers = if adaptive {
+            Self::build_adaptive_config(env, user)
+        } else {
+            AdaptiveConfig::default_config()
+        };
+        let config = overridden.clone().unwrap_or(default);
+        EffectiveLimit {
+            effective_max: multipliers.effective_max(config.max_requests),
+            config,
+            overridden: overridden.is_some(),
+            trust_multiplier: multipliers.trust_multiplier,
+            load_multiplier: multipliers.load_multiplier,
+        }
+    }
+
+    // -- Observation mode ---------------------------------------------------
+
+    /// Let over-limit calls through with a `would_limit` event instead of
+    /// rejecting them. Off by default. Callers are responsible for
+    /// authorising the change.
+    pub fn set_observation_mode(env: &Env, enabled: bool) {
+        let key = ObservationModeKey {
+            prefix: symbol_short!("rl_observ"),
+        };
+        env.storage().instance().set(&key, &enabled);
+    }
+
+    pub fn observation_mode_enabled(env: &Env) -> bool {
+        let key = ObservationModeKey {
+            prefix: symbol_short!("rl_observ"),
+        };
+        env.storage().instance().get(&key).unwrap_or(false)
+    }
+
+    // -- Window jitter ------------------------------------------------------
+
+    /// Spread fixed-window resets by a per-user offset. Off by default.
+    /// Callers are responsible for authorising the change.
+    pub fn set_window_jitter(env: &Env, enabled: bool) {
+        let key = WindowJitterKey {
+            prefix: symbol_short!("rl_jitter"),
+        };
+        env.storage().instance().set(&key, &enabled);
+    }
+
+    pub fn window_jitter_enabled(env: &Env) -> bool {
+        let key = WindowJitterKey {
+            prefix: symbol_short!("rl_jitter"),
+        };
+        env.storage().instance().get(&key).unwrap_or(false)
+    }
+
+    /// `H(user) % window_seconds`: where `user`'s fixed windows start
+    /// relative to multiples of the window length.
+    pub fn window_offset(env: &Env, user: &Address, window_seconds: u64) -> u64 {
+        if window_seconds == 0 {
+            return 0;
+        }
+        let digest = env.crypto().sha256(&user.clone().to_xdr(env)).to_array();
+        let mut head = [0u8; 8];
+        head.copy_from_slice(&digest[..8]);
+        u64::from_be_bytes(head) % window_seconds
+    }
+
+    /// Only per-user counters are jittered; a shared counter has no single
+    /// user to derive its offset from.
+    fn jitter_offset(env: &Env, user: &Address, config: &RateLimitConfig) -> Option<u64> {
+        if config.scope == RateLimitScope::PerUser && Self::window_jitter_enabled(env) {
+            Some(Self::window_offset(env, user, config.window_seconds))
+        } else {
+            None
+        }
+    }
+
+    /// Latest start at or before `now` of a window aligned to `offset`
+    fn aligned_window_start(now: u64, window_seconds: u64, offset: u64) -> u64 {
+        if window_seconds == 0 {
+            return now;
+        }
+        let into_window = (now % window_seconds + window_seconds - offset) % window_seconds;
+        now.saturating_sub(into_window)
+    }
+
     // -- Strategy implementations -------------------------------------------
 
     /// Fixed window: if we're still inside the current window, increment.
-    /// Otherwise start a new window.
+    /// Otherwise start a new window, at `now` or, with a jitter `offset`,
+    /// at the aligned boundary before it.
     fn fixed_window(
         now: u64,
         state: &mut RateLimitState,
         window_seconds: u64,
         max_requests: u32,
+        offset: Option<u64>,
     ) -> RateLimitInfo {
         let window_end = state.window_start.saturating_add(window_seconds);
+        let start = match offset {
+            Some(offset) => Self::aligned_window_start(now, window_seconds, offset),
+            None => now,
+        };
 
         if now >= window_end {
             // New window
             state.previous_count = state.current_count;
             state.current_count = 0;
-            state.window_start = now;
+            state.window_start = start;
+        } else if offset.is_some() && state.current_count == 0 {
+            // Nothing counted yet, so the window can still be aligned
+            state.window_start = start;
         }
 
         let reset_at = state.window_start.saturating_add(window_seconds);
@@ -425,11 +830,16 @@ impl RateLimiter {
         state: &RateLimitState,
         window_seconds: u64,
         max_requests: u32,
+        offset: Option<u64>,
     ) -> RateLimitInfo {
         let window_end = state.window_start.saturating_add(window_seconds);
         let current = if now >= window_end { 0 } else { state.current_count };
-        let reset_at = if now >= window_end {
-            now.saturating_add(window_seconds)
+        let reset_at = if now >= window_end || (offset.is_some() && current == 0) {
+            let start = match offset {
+                Some(offset) => Self::aligned_window_start(now, window_seconds, offset),
+                None => now,
+            };
+            start.saturating_add(window_seconds)
         } else {
             window_end
         };
@@ -634,8 +1044,72 @@ impl RateLimiter {
         }
     }
 
+    fn refill_rate_per_sec(capacity_scaled: u64, window_seconds: u64) -> u64 {
+        if window_seconds > 0 {
+            capacity_scaled / window_seconds
+        } else {
+            capacity_scaled
+        }
+    }
+
+    fn apply_refill(now: u64, state: &mut RateLimitState, params: &BucketParams) {
+        let capacity_scaled = (params.capacity as u64).saturating_mul(1000);
+        let rate = Self::refill_rate_per_sec(capacity_scaled, params.window_seconds);
+        let elapsed = now.saturating_sub(state.last_refill);
+        state.tokens_available = core::cmp::min(
+            state.tokens_available.saturating_add(elapsed.saturating_mul(rate)),
+            capacity_scaled,
+        );
+        state.last_refill = now;
+    }
+
+    fn describe_bucket(now: u64, state: &RateLimitState, params: &BucketParams) -> BucketState {
+        let capacity_scaled = (params.capacity as u64).saturating_mul(1000);
+        let rate = Self::refill_rate_per_sec(capacity_scaled, params.window_seconds);
+        let next_refill_at = if state.tokens_available >= capacity_scaled || rate == 0 {
+            now
+        } else {
+            // Time until the fractional token in progress completes
+            let deficit = 1000 - state.tokens_available % 1000;
+            now.saturating_add((deficit + rate - 1) / rate)
+        };
+
+        BucketState {
+            tokens: (state.tokens_available / 1000) as u32,
+            capacity: params.capacity,
+            refill_per_sec: rate,
+            next_refill_at,
+        }
+    }
+
     // -- Storage helpers ----------------------------------------------------
 
+    fn bucket_params_key(env: &Env, user: &Address, function: &Symbol) -> RateLimitKey {
+        RateLimitKey {
+            prefix: Symbol::new(env, "rl_bkt"),
+            function: function.clone(),
+            user: user.clone(),
+        }
+    }
+
+    fn save_bucket_params(env: &Env, user: &Address, function: &Symbol, params: &BucketParams) {
+        let key = Self::bucket_params_key(env, user, function);
+        env.storage().temporary().set(&key, params);
+        let ttl_ledgers = core::cmp::min(
+            Self::seconds_to_ledgers(params.window_seconds.saturating_mul(2)),
+            120_960,
+        );
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, ttl_ledgers / 2, ttl_ledgers);
+    }
+
+    fn load_bucket_params(env: &Env, user: &Address, function: &Symbol) -> Option<BucketParams> {
+        env.storage()
+            .temporary()
+            .get(&Self::bucket_params_key(env, user, function))
+    }
+
     fn build_key(
         env: &Env,
         user: &Address,
@@ -713,13 +1187,17 @@ macro_rules! rate_limit {
      max: $max:expr, window: $window:expr,
      strategy: $strategy:ident, scope: $scope:ident) => {
         {
-            let config = $crate::rate_limit::RateLimitConfig {
-                max_requests: $max,
-                window_seconds: $window,
-                strategy: $crate::rate_limit::RateLimitStrategy::$strategy,
-                scope: $crate::rate_limit::RateLimitScope::$scope,
-            };
             let func_sym = soroban_sdk::Symbol::new(&$env, $func_name);
+            let config = $crate::rate_limit::RateLimiter::resolve_config(
+                &$env,
+                &func_sym,
+                $crate::rate_limit::RateLimitConfig {
+                    max_requests: $max,
+                    window_seconds: $window,
+                    strategy: $crate::rate_limit::RateLimitStrategy::$strategy,
+                    scope: $crate::rate_limit::RateLimitScope::$scope,
+                },
+            );
             if let Err(_info) = $crate::rate_limit::RateLimiter::check_and_update(
                 &$env, &$user, &func_sym, &config,
             ) {
@@ -736,14 +1214,18 @@ macro_rules! rate_limit_adaptive {
      max: $max:expr, window: $window:expr,
      strategy: $strategy:ident, scope: $scope:ident) => {
         {
-            let config = $crate::rate_limit::RateLimitConfig {
-                max_requests: $max,
-                window_seconds: $window,
-                strategy: $crate::rate_limit::RateLimitStrategy::$strategy,
-                scope: $crate::rate_limit::RateLimitScope::$scope,
-            };
-            let adaptive = $crate::rate_limit::RateLimiter::build_adaptive_config(&$env, &$user);
             let func_sym = soroban_sdk::Symbol::new(&$env, $func_name);
+            let config = $crate::rate_limit::RateLimiter::resolve_config(
+                &$env,
+                &func_sym,
+                $crate::rate_limit::RateLimitConfig {
+                    max_requests: $max,
+                    window_seconds: $window,
+                    strategy: $crate::rate_limit::RateLimitStrategy::$strategy,
+                    scope: $crate::rate_limit::RateLimitScope::$scope,
+                },
+            );
+            let adaptive = $crate::rate_limit::RateLimiter::build_adaptive_config(&$env, &$user);
             if let Err(_info) = $crate::rate_limit::RateLimiter::check_and_update_adaptive(
                 &$env, &$user, &func_sym, &config, &adaptive,
             ) {
@@ -268,6 +268,57 @@ fn test_token_bucket_refills_over_time() {
         "Expected ~5 refilled tokens, got {}", allowed);
 }
 
+#[test]
+fn test_bucket_state_refills_lazily() {
+    let (env, user, func) = setup();
+    // 10 tokens per 100s → 1 token every 10s
+    let config = token_bucket_config(10, 100);
+
+    assert!(RateLimiter::bucket_state(&env, &user, &func).is_none());
+
+    // First check creates the (empty) bucket
+    let _ = RateLimiter::check_and_update(&env, &user, &func, &config);
+    let state = RateLimiter::bucket_state(&env, &user, &func).unwrap();
+    assert_eq!(state.tokens, 0);
+    assert_eq!(state.capacity, 10);
+    assert_eq!(state.refill_per_sec, 100);
+
+    advance_time(&env, 50);
+    let now = env.ledger().timestamp();
+    let state = RateLimiter::bucket_state(&env, &user, &func).unwrap();
+    assert_eq!(state.tokens, 5);
+    assert_eq!(state.next_refill_at, now + 10);
+
+    // Refill stops at capacity
+    advance_time(&env, 500);
+    let state = RateLimiter::bucket_state(&env, &user, &func).unwrap();
+    assert_eq!(state.tokens, 10);
+    assert_eq!(state.next_refill_at, env.ledger().timestamp());
+}
+
+#[test]
+fn test_grant_tokens_capped_at_capacity() {
+    let (env, user, func) = setup();
+    let config = token_bucket_config(5, 100);
+
+    // Unknown buckets cannot be topped up
+    assert!(RateLimiter::grant_tokens(&env, &user, &func, 1).is_err());
+
+    let _ = RateLimiter::check_and_update(&env, &user, &func, &config);
+    let state = RateLimiter::grant_tokens(&env, &user, &func, 3).unwrap();
+    assert_eq!(state.tokens, 3);
+
+    // Granted tokens are spendable
+    for _ in 0..3 {
+        assert!(RateLimiter::check_and_update(&env, &user, &func, &config).is_ok());
+    }
+    assert!(RateLimiter::check_and_update(&env, &user, &func, &config).is_err());
+
+    let state = RateLimiter::grant_tokens(&env, &user, &func, 100).unwrap();
+    assert_eq!(state.tokens, 5);
+    assert_eq!(RateLimiter::bucket_state(&env, &user, &func).unwrap().tokens, 5);
+}
+
 #[test]
 fn test_token_bucket_retry_info() {
     let (env, user, func) = setup();
@@ -427,6 +478,88 @@ fn test_adaptive_combined_trust_and_load() {
     assert_eq!(adaptive.effective_max(10), 30);
 }
 
+fn congestion_curve(env: &Env) -> soroban_sdk::Vec<(u32, u32)> {
+    // Full quota up to load 5000, then shrink to a quarter at 10000
+    soroban_sdk::vec![env, (0u32, 1000u32), (5000, 1000), (10000, 250)]
+}
+
+#[test]
+fn test_load_curve_low_load_full_quota() {
+    let (env, user, _) = setup();
+    RateLimiter::set_load_curve(&env, &congestion_curve(&env)).unwrap();
+    RateLimiter::set_network_load(&env, 2000);
+
+    let adaptive = RateLimiter::build_adaptive_config(&env, &user);
+    assert_eq!(adaptive.load_multiplier, 1000);
+    assert_eq!(adaptive.effective_max(10), 10);
+}
+
+#[test]
+fn test_load_curve_high_load_shrinks_cap() {
+    let (env, user, func) = setup();
+    let config = fixed_config(10, 3600);
+    RateLimiter::set_load_curve(&env, &congestion_curve(&env)).unwrap();
+
+    // At and beyond the last breakpoint the endpoint multiplier applies
+    RateLimiter::set_network_load(&env, 10000);
+    let adaptive = RateLimiter::build_adaptive_config(&env, &user);
+    assert_eq!(adaptive.load_multiplier, 250);
+    assert_eq!(RateLimiter::load_multiplier(&env, 20000), 250);
+
+    // effective = 10 * 250/1000 = 2
+    for _ in 0..2 {
+        assert!(RateLimiter::check_and_update_adaptive(&env, &user, &func, &config, &adaptive).is_ok());
+    }
+    assert!(RateLimiter::check_and_update_adaptive(&env, &user, &func, &config, &adaptive).is_err());
+}
+
+#[test]
+fn test_load_curve_interpolates_between_breakpoints() {
+    let (env, user, _) = setup();
+    RateLimiter::set_load_curve(&env, &congestion_curve(&env)).unwrap();
+
+    // Halfway between (5000, 1000) and (10000, 250)
+    RateLimiter::set_network_load(&env, 7500);
+    let adaptive = RateLimiter::build_adaptive_config(&env, &user);
+    assert_eq!(adaptive.load_multiplier, 625);
+    assert_eq!(adaptive.effective_max(10), 6);
+
+    assert_eq!(RateLimiter::load_multiplier(&env, 6000), 850);
+}
+
+#[test]
+fn test_load_curve_unset_keeps_default_behavior() {
+    let (env, user, _) = setup();
+    RateLimiter::set_network_load(&env, 500);
+    assert_eq!(RateLimiter::build_adaptive_config(&env, &user).load_multiplier, 500);
+
+    // Clearing a configured curve also restores the default
+    RateLimiter::set_load_curve(&env, &congestion_curve(&env)).unwrap();
+    RateLimiter::set_load_curve(&env, &soroban_sdk::Vec::new(&env)).unwrap();
+    assert_eq!(RateLimiter::build_adaptive_config(&env, &user).load_multiplier, 500);
+}
+
+#[test]
+fn test_load_curve_interpolates_extreme_breakpoints() {
+    let (env, _, _) = setup();
+    let rising = soroban_sdk::vec![&env, (0u32, 0u32), (u32::MAX, u32::MAX)];
+    RateLimiter::set_load_curve(&env, &rising).unwrap();
+    assert_eq!(RateLimiter::load_multiplier(&env, u32::MAX - 1), u32::MAX - 1);
+
+    let falling = soroban_sdk::vec![&env, (0u32, u32::MAX), (u32::MAX, 0u32)];
+    RateLimiter::set_load_curve(&env, &falling).unwrap();
+    assert_eq!(RateLimiter::load_multiplier(&env, 1), u32::MAX - 1);
+    assert_eq!(RateLimiter::load_multiplier(&env, u32::MAX - 1), 1);
+}
+
+#[test]
+fn test_load_curve_rejects_unsorted_points() {
+    let (env, _, _) = setup();
+    let unsorted = soroban_sdk::vec![&env, (5000u32, 1000u32), (5000, 500)];
+    assert!(RateLimiter::set_load_curve(&env, &unsorted).is_err());
+    assert!(RateLimiter::get_load_curve(&env).is_empty());
+}
+
 #[test]
 fn test_adaptive_default_no_change() {
     let adaptive = AdaptiveConfig::default_config();
@@ -462,6 +595,28 @@ fn test_trust_tier_persistence() {
     assert_eq!(RateLimiter::get_trust_tier(&env, &user), TrustTier::Premium);
 }
 
+#[test]
+fn test_default_trust_tier_applies_to_unset_users() {
+    let (env, user, _) = setup();
+    let other = Address::generate(&env);
+
+    assert_eq!(RateLimiter::get_default_trust_tier(&env), TrustTier::Standard);
+    RateLimiter::set_default_trust_tier(&env, &TrustTier::Verified);
+    assert_eq!(RateLimiter::get_trust_tier(&env, &user), TrustTier::Verified);
+    assert_eq!(
+        RateLimiter::build_adaptive_config(&env, &user).trust_multiplier,
+        1500
+    );
+
+    // An explicit tier wins over the default, even a lower one
+    RateLimiter::set_trust_tier(&env, &other, &TrustTier::Standard);
+    assert_eq!(RateLimiter::get_trust_tier(&env, &other), TrustTier::Standard);
+    assert_eq!(
+        RateLimiter::build_adaptive_config(&env, &other).trust_multiplier,
+        1000
+    );
+}
+
 #[test]
 fn test_trust_tier_multipliers() {
     assert_eq!(TrustTier::Standard.multiplier(), 1000);
@@ -624,3 +779,156 @@ fn test_window_transition_accuracy() {
         assert!(RateLimiter::check_and_update(&env, &user, &func, &config).is_ok());
     }
 }
+
+// ===========================================================================
+// Window Jitter Tests
+// ===========================================================================
+
+#[test]
+fn test_window_jitter_spreads_reset_boundaries() {
+    let (env, alice, func) = setup();
+    env.ledger().set_timestamp(100_000);
+    let config = fixed_config(1, 3600);
+    RateLimiter::set_window_jitter(&env, true);
+
+    let alice_offset = RateLimiter::window_offset(&env, &alice, 3600);
+    let bob = (0..8)
+        .map(|_| Address::generate(&env))
+        .find(|user| RateLimiter::window_offset(&env, user, 3600) != alice_offset)
+        .unwrap();
+
+    let a = RateLimiter::check_and_update(&env, &alice, &func, &config).unwrap();
+    let b = RateLimiter::check_and_update(&env, &bob, &func, &config).unwrap();
+    assert_ne!(a.reset_at, b.reset_at);
+    assert_eq!(a.reset_at % 3600, alice_offset);
+    assert!(a.reset_at > 100_000 && a.reset_at <= 100_000 + 3600);
+}
+
+#[test]
+fn test_window_jitter_boundary_is_stable_per_user() {
+    let (env, user, func) = setup();
+    env.ledger().set_timestamp(50_000);
+    let config = fixed_config(2, 1000);
+    RateLimiter::set_window_jitter(&env, true);
+
+    let peeked = RateLimiter::peek(&env, &user, &func, &config);
+    let first = RateLimiter::check_and_update(&env, &user, &func, &config).unwrap();
+    assert_eq!(peeked.reset_at, first.reset_at);
+    advance_time(&env, 1);
+    let second = RateLimiter::check_and_update(&env, &user, &func, &config).unwrap();
+    assert_eq!(first.reset_at, second.reset_at);
+    let denied = RateLimiter::check_and_update(&env, &user, &func, &config).unwrap_err();
+    assert_eq!(denied.reset_at, first.reset_at);
+
+    // Later windows stay on the same grid
+    env.ledger().set_timestamp(first.reset_at + 2500);
+    let later = RateLimiter::check_and_update(&env, &user, &func, &config).unwrap();
+    assert_eq!(later.reset_at, first.reset_at + 3000);
+    assert_eq!(later.remaining, 1);
+}
+
+#[test]
+fn test_window_jitter_off_by_default() {
+    let (env, user, func) = setup();
+    env.ledger().set_timestamp(1000);
+    assert!(!RateLimiter::window_jitter_enabled(&env));
+
+    let info = RateLimiter::check_and_update(&env, &user, &func, &fixed_config(2, 500)).unwrap();
+    assert_eq!(info.reset_at, 1500);
+}
+
+// ===========================================================================
+// Override / Effective Limit Tests
+// ===========================================================================
+
+#[test]
+fn test_effective_config_uses_default_without_override() {
+    let (env, user, func) = setup();
+    let effective = RateLimiter::effective_config(&env, &user, &func, fixed_config(10, 3600), false);
+    assert!(!effective.overridden);
+    assert_eq!(effective.config, fixed_config(10, 3600));
+    assert_eq!(effective.trust_multiplier, 1000);
+    assert_eq!(effective.effective_max, 10);
+}
+
+#[test]
+fn test_override_replaces_default_until_cleared() {
+    let (env, user, func) = setup();
+    RateLimiter::set_limit_override(&env, &func, Some(sliding_config(4, 60)));
+
+    let effective = RateLimiter::effective_config(&env, &user, &func, fixed_config(10, 3600), false);
+    assert!(effective.overridden);
+    assert_eq!(effective.config, sliding_config(4, 60));
+    assert_eq!(effective.effective_max, 4);
+    assert_eq!(RateLimiter::resolve_config(&env, &func, fixed_config(10, 3600)), sliding_config(4, 60));
+
+    RateLimiter::set_limit_override(&env, &func, None);
+    assert_eq!(RateLimiter::get_limit_override(&env, &func), None);
+    assert_eq!(RateLimiter::resolve_config(&env, &func, fixed_config(10, 3600)), fixed_config(10, 3600));
+}
+
+#[test]
+fn test_effective_config_applies_tier_and_load_when_adaptive() {
+    let (env, user, func) = setup();
+    RateLimiter::set_trust_tier(&env, &user, &TrustTier::Trusted);
+    RateLimiter::set_network_load(&env, 1500);
+    RateLimiter::set_limit_override(&env, &func, Some(fixed_config(20, 3600)));
+
+    let adaptive = RateLimiter::effective_config(&env, &user, &func, fixed_config(10, 3600), true);
+    assert_eq!(adaptive.trust_multiplier, 2000);
+    assert_eq!(adaptive.load_multiplier, 1500);
+    // 20 * 2000/1000 * 1500/1000
+    assert_eq!(adaptive.effective_max, 60);
+
+    // Non-adaptive limits ignore the tier and load
+    let fixed = RateLimiter::effective_config(&env, &user, &func, fixed_config(10, 3600), false);
+    assert_eq!(fixed.effective_max, 20);
+}
+
+// ===========================================================================
+// Observation Mode Tests
+// ===========================================================================
+
+#[test]
+fn test_observation_mode_lets_over_limit_calls_through_with_event() {
+    use soroban_sdk::testutils::Events;
+    use soroban_sdk::{IntoVal, TryFromVal};
+
+    let (env, user, func) = setup();
+    let id = env.register(crate::CommonUtilsContract, ());
+    env.as_contract(&id, || {
+        let config = fixed_config(1, 3600);
+        RateLimiter::set_observation_mode(&env, true);
+        assert!(RateLimiter::observation_mode_enabled(&env));
+
+        RateLimiter::check_and_update(&env, &user, &func, &config).unwrap();
+        let over = RateLimiter::check_and_update(&env, &user, &func, &config).unwrap();
+        assert_eq!(over.remaining, 0);
+
+        let (_, topics, data) = env.events().all().last().unwrap();
+        assert_eq!(topics, (Symbol::new(&env, "would_limit"), user.clone()).into_val(&env));
+        assert_eq!(Symbol::try_from_val(&env, &data).unwrap(), func);
+
+        // Usage kept counting while observed
+        RateLimiter::set_observation_mode(&env, false);
+        let denied = RateLimiter::check_and_update(&env, &user, &func, &config).unwrap_err();
+        assert!(!denied.allowed);
+    });
+}
+
+#[test]
+fn test_observation_mode_counts_token_bucket_usage() {
+    let (env, user, func) = setup();
+    let id = env.register(crate::CommonUtilsContract, ());
+    env.as_contract(&id, || {
+        let config = token_bucket_config(2, 3600);
+        RateLimiter::set_observation_mode(&env, true);
+        for _ in 0..4 {
+            RateLimiter::check_and_update(&env, &user, &func, &config).unwrap();
+        }
+
+        RateLimiter::set_observation_mode(&env, false);
+        assert!(RateLimiter::check_and_update(&env, &user, &func, &config).is_err());
+        assert!(!RateLimiter::observation_mode_enabled(&env));
+    });
+}
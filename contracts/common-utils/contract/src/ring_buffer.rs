@@ -0,0 +1,157 @@
+//! # Ring Buffer
+//!
+//! Fixed-capacity history in persistent storage. Once full, each `push`
+//! overwrites the oldest entry, so a history stays bounded without the whole
+//! list being rewritten on every append.
+//!
+//! ```text
+//! (namespace, id)        -> RingMeta { start, len, capacity }
+//! (namespace, id, slot)  -> T
+//! ```
+//!
+//! Opening a buffer with a different capacity than it was stored with keeps
+//! the newest entries that fit on the next `push`.
+
+use core::marker::PhantomData;
+use soroban_sdk::{contracttype, Env, IntoVal, Symbol, TryFromVal, Val, Vec};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RingMeta {
+    /// Slot holding the oldest entry
+    pub start: u32,
+    pub len: u32,
+    pub capacity: u32,
+}
+
+pub struct RingBuffer<K, T> {
+    namespace: Symbol,
+    id: K,
+    capacity: u32,
+    _item: PhantomData<T>,
+}
+
+impl<K, T> RingBuffer<K, T>
+where
+    K: IntoVal<Env, Val> + Clone,
+    T: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    /// A buffer of at most `capacity` entries (at least one) stored under
+    /// `(namespace, id)`.
+    pub fn new(namespace: Symbol, id: K, capacity: u32) -> Self {
+        Self {
+            namespace,
+            id,
+            capacity: capacity.max(1),
+            _item: PhantomData,
+        }
+    }
+
+    pub fn capacity(&self) -> u32 {
+        self.capacity
+    }
+
+    pub fn len(&self, env: &Env) -> u32 {
+        self.meta(env).len.min(self.capacity)
+    }
+
+    pub fn is_empty(&self, env: &Env) -> bool {
+        self.len(env) == 0
+    }
+
+    /// Append `item`, evicting the oldest entry when full.
+    pub fn push(&self, env: &Env, item: &T) {
+        let mut meta = self.meta(env);
+        if meta.capacity != self.capacity {
+            meta = self.resize(env, &meta);
+        }
+
+        let slot = if meta.len < meta.capacity {
+            meta.len += 1;
+            (meta.start + meta.len - 1) % meta.capacity
+        } else {
+            let oldest = meta.start;
+            meta.start = (meta.start + 1) % meta.capacity;
+            oldest
+        };
+
+        env.storage().persistent().set(&self.slot_key(slot), item);
+        env.storage().persistent().set(&self.meta_key(), &meta);
+    }
+
+    /// Entries from oldest to newest.
+    pub fn iter(&self, env: &Env) -> Vec<T> {
+        let meta = self.meta(env);
+        let mut items = Vec::new(env);
+        // A shrunk capacity only takes effect on the next push, so read
+        // the newest `self.capacity` entries of the stored layout
+        let skip = meta.len.saturating_sub(self.capacity);
+        for i in skip..meta.len {
+            let slot = (meta.start + i) % meta.capacity;
+            if let Some(item) = env.storage().persistent().get(&self.slot_key(slot)) {
+                items.push_back(item);
+            }
+        }
+        items
+    }
+
+    /// Entries `[start, end)`, counted from the oldest. Only the slots in
+    /// range are read.
+    pub fn range(&self, env: &Env, start: u32, end: u32) -> Vec<T> {
+        let meta = self.meta(env);
+        let mut items = Vec::new(env);
+        let skip = meta.len.saturating_sub(self.capacity);
+        let end = end.min(meta.len - skip);
+        for i in start..end {
+            let slot = (meta.start + skip + i) % meta.capacity;
+            if let Some(item) = env.storage().persistent().get(&self.slot_key(slot)) {
+                items.push_back(item);
+            }
+        }
+        items
+    }
+
+    /// Remove every entry and the buffer's metadata.
+    pub fn clear(&self, env: &Env) {
+        let meta = self.meta(env);
+        for slot in 0..meta.capacity {
+            env.storage().persistent().remove(&self.slot_key(slot));
+        }
+        env.storage().persistent().remove(&self.meta_key());
+    }
+
+    fn meta(&self, env: &Env) -> RingMeta {
+        env.storage()
+            .persistent()
+            .get(&self.meta_key())
+            .unwrap_or(RingMeta {
+                start: 0,
+                len: 0,
+                capacity: self.capacity,
+            })
+    }
+
+    /// Rewrite the stored entries into a buffer of `self.capacity` slots
+    fn resize(&self, env: &Env, meta: &RingMeta) -> RingMeta {
+        let items = self.iter(env);
+        for slot in 0..meta.capacity {
+            env.storage().persistent().remove(&self.slot_key(slot));
+        }
+        for (slot, item) in items.iter().enumerate() {
+            env.storage().persistent().set(&self.slot_key(slot as u32), &item);
+        }
+        RingMeta {
+            start: 0,
+            len: items.len(),
+            capacity: self.capacity,
+        }
+    }
+
+    fn meta_key(&self) -> (Symbol, K) {
+        (self.namespace.clone(), self.id.clone())
+    }
+
+    fn slot_key(&self, slot: u32) -> (Symbol, K, u32) {
+        (self.namespace.clone(), self.id.clone(), slot)
+    }
+}
@@ -0,0 +1,140 @@
+#![cfg(test)]
+
+//! `RingBuffer` keeps the newest `capacity` entries in push order, evicting
+//! the oldest once full.
+
+use crate::ring_buffer::RingBuffer;
+use crate::CommonUtilsContract;
+use soroban_sdk::{symbol_short, Env, Vec};
+
+fn ring(capacity: u32) -> RingBuffer<u32, u32> {
+    RingBuffer::new(symbol_short!("test"), 1, capacity)
+}
+
+fn contents(env: &Env, buffer: &RingBuffer<u32, u32>) -> Vec<u32> {
+    buffer.iter(env)
+}
+
+#[test]
+fn test_push_below_capacity_keeps_everything() {
+    let env = Env::default();
+    let id = env.register(CommonUtilsContract, ());
+    env.as_contract(&id, || {
+        let buffer = ring(4);
+        assert!(buffer.is_empty(&env));
+        for v in 1..=3u32 {
+            buffer.push(&env, &v);
+        }
+        assert_eq!(buffer.len(&env), 3);
+        assert_eq!(contents(&env, &buffer), Vec::from_array(&env, [1, 2, 3]));
+    });
+}
+
+#[test]
+fn test_push_past_capacity_evicts_oldest() {
+    let env = Env::default();
+    let id = env.register(CommonUtilsContract, ());
+    env.as_contract(&id, || {
+        let buffer = ring(3);
+        for v in 1..=5u32 {
+            buffer.push(&env, &v);
+        }
+        assert_eq!(buffer.len(&env), 3);
+        assert_eq!(contents(&env, &buffer), Vec::from_array(&env, [3, 4, 5]));
+    });
+}
+
+#[test]
+fn test_order_survives_many_wraps() {
+    let env = Env::default();
+    let id = env.register(CommonUtilsContract, ());
+    env.as_contract(&id, || {
+        let buffer = ring(4);
+        for v in 1..=23u32 {
+            buffer.push(&env, &v);
+        }
+        assert_eq!(contents(&env, &buffer), Vec::from_array(&env, [20, 21, 22, 23]));
+    });
+}
+
+#[test]
+fn test_buffers_are_isolated_by_id() {
+    let env = Env::default();
+    let id = env.register(CommonUtilsContract, ());
+    env.as_contract(&id, || {
+        let a = ring(2);
+        let b: RingBuffer<u32, u32> = RingBuffer::new(symbol_short!("test"), 2, 2);
+        a.push(&env, &1);
+        b.push(&env, &9);
+        assert_eq!(contents(&env, &a), Vec::from_array(&env, [1]));
+        assert_eq!(contents(&env, &b), Vec::from_array(&env, [9]));
+    });
+}
+
+#[test]
+fn test_shrinking_capacity_keeps_newest() {
+    let env = Env::default();
+    let id = env.register(CommonUtilsContract, ());
+    env.as_contract(&id, || {
+        let wide = ring(5);
+        for v in 1..=7u32 {
+            wide.push(&env, &v);
+        }
+
+        let narrow = ring(2);
+        assert_eq!(contents(&env, &narrow), Vec::from_array(&env, [6, 7]));
+        narrow.push(&env, &8);
+        assert_eq!(contents(&env, &narrow), Vec::from_array(&env, [7, 8]));
+    });
+}
+
+#[test]
+fn test_growing_capacity_keeps_existing_entries() {
+    let env = Env::default();
+    let id = env.register(CommonUtilsContract, ());
+    env.as_contract(&id, || {
+        let small = ring(2);
+        for v in 1..=3u32 {
+            small.push(&env, &v);
+        }
+
+        let large = ring(4);
+        large.push(&env, &4);
+        large.push(&env, &5);
+        assert_eq!(contents(&env, &large), Vec::from_array(&env, [2, 3, 4, 5]));
+    });
+}
+
+#[test]
+fn test_clear_empties_buffer() {
+    let env = Env::default();
+    let id = env.register(CommonUtilsContract, ());
+    env.as_contract(&id, || {
+        let buffer = ring(3);
+        buffer.push(&env, &1);
+        buffer.push(&env, &2);
+        buffer.clear(&env);
+        assert!(buffer.is_empty(&env));
+        buffer.push(&env, &3);
+        assert_eq!(contents(&env, &buffer), Vec::from_array(&env, [3]));
+    });
+}
+
+#[test]
+fn test_range_reads_from_oldest_after_wrapping() {
+    let env = Env::default();
+    let id = env.register(CommonUtilsContract, ());
+    env.as_contract(&id, || {
+        let buffer = ring(4);
+        for v in 1..=6u32 {
+            buffer.push(&env, &v);
+        }
+        assert_eq!(buffer.range(&env, 1, 3), Vec::from_array(&env, [4, 5]));
+        assert_eq!(buffer.range(&env, 2, 10), Vec::from_array(&env, [5, 6]));
+        assert!(buffer.range(&env, 4, 6).is_empty());
+
+        // A shrunk capacity reads the newest entries of the stored layout
+        let shrunk = ring(2);
+        assert_eq!(shrunk.range(&env, 0, 2), Vec::from_array(&env, [5, 6]));
+    });
+}
@@ -0,0 +1,93 @@
+//! Service Registry Contract
+//! Maps service names to the deployed contract address, so cross-contract
+//! dependencies can be rotated in one place instead of in every consumer.
+
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, Symbol};
+use crate::error::CommonError;
+
+/// Well-known service names
+pub const SERVICE_ACL: &str = "acl";
+pub const SERVICE_CREDIT_SCORE: &str = "credit";
+pub const SERVICE_FRAUD_DETECT: &str = "fraud";
+pub const SERVICE_MARKETPLACE: &str = "market";
+pub const SERVICE_RISK_EVAL: &str = "risk";
+pub const SERVICE_SCORE_NFT: &str = "score_nft";
+
+#[contracttype]
+pub enum ServiceKey {
+    Admin,
+    Service(Symbol),
+}
+
+#[contract]
+pub struct ServiceRegistry;
+
+#[contractimpl]
+impl ServiceRegistry {
+    /// Initialize the registry with admin address
+    pub fn initialize(env: Env, admin: Address) -> Result<(), CommonError> {
+        if env.storage().instance().has(&ServiceKey::Admin) {
+            return Err(CommonError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&ServiceKey::Admin, &admin);
+        Ok(())
+    }
+
+    /// Point `name` at `address`, replacing any previous registration
+    pub fn register_service(env: Env, admin: Address, name: Symbol, address: Address) -> Result<(), CommonError> {
+        Self::require_admin(&env, &admin)?;
+
+        env.storage()
+            .persistent()
+            .set(&ServiceKey::Service(name.clone()), &address);
+
+        env.events().publish((symbol_short!("svc_reg"), name), address);
+        Ok(())
+    }
+
+    /// Remove `name`; consumers fall back to their own configuration
+    pub fn deregister_service(env: Env, admin: Address, name: Symbol) -> Result<(), CommonError> {
+        Self::require_admin(&env, &admin)?;
+
+        if !env.storage().persistent().has(&ServiceKey::Service(name.clone())) {
+            return Err(CommonError::KeyNotFound);
+        }
+        env.storage()
+            .persistent()
+            .remove(&ServiceKey::Service(name.clone()));
+
+        env.events().publish((symbol_short!("svc_dereg"), name), ());
+        Ok(())
+    }
+
+    /// Current address for `name`, if registered
+    pub fn resolve(env: Env, name: Symbol) -> Option<Address> {
+        env.storage().persistent().get(&ServiceKey::Service(name))
+    }
+
+    /// Get admin address
+    pub fn get_admin(env: Env) -> Result<Address, CommonError> {
+        env.storage()
+            .instance()
+            .get(&ServiceKey::Admin)
+            .ok_or(CommonError::NotInitialized)
+    }
+
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), CommonError> {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&ServiceKey::Admin)
+            .ok_or(CommonError::NotInitialized)?;
+        if stored_admin != *admin {
+            return Err(CommonError::NotAuthorized);
+        }
+        Ok(())
+    }
+}
+
+/// Resolve `name` through the registry at `registry`, for use by consumers.
+pub fn resolve_service(env: &Env, registry: &Address, name: &str) -> Option<Address> {
+    ServiceRegistryClient::new(env, registry).resolve(&Symbol::new(env, name))
+}
@@ -0,0 +1,66 @@
+#![cfg(test)]
+
+use crate::error::CommonError;
+use crate::service_registry::{resolve_service, ServiceRegistry, ServiceRegistryClient, SERVICE_MARKETPLACE};
+use soroban_sdk::{testutils::Address as _, Address, Env, Symbol};
+
+fn setup(env: &Env) -> (ServiceRegistryClient<'_>, Address, Address) {
+    env.mock_all_auths();
+    let id = env.register(ServiceRegistry, ());
+    let client = ServiceRegistryClient::new(env, &id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    (client, id, admin)
+}
+
+#[test]
+fn test_register_and_resolve() {
+    let env = Env::default();
+    let (client, registry, admin) = setup(&env);
+    let marketplace = Address::generate(&env);
+    let name = Symbol::new(&env, SERVICE_MARKETPLACE);
+
+    assert_eq!(client.resolve(&name), None);
+    client.register_service(&admin, &name, &marketplace);
+
+    assert_eq!(client.resolve(&name), Some(marketplace.clone()));
+    assert_eq!(resolve_service(&env, &registry, SERVICE_MARKETPLACE), Some(marketplace));
+}
+
+#[test]
+fn test_rotation_replaces_address() {
+    let env = Env::default();
+    let (client, _, admin) = setup(&env);
+    let name = Symbol::new(&env, SERVICE_MARKETPLACE);
+    let old = Address::generate(&env);
+    let new = Address::generate(&env);
+
+    client.register_service(&admin, &name, &old);
+    client.register_service(&admin, &name, &new);
+    assert_eq!(client.resolve(&name), Some(new));
+}
+
+#[test]
+fn test_deregister_service() {
+    let env = Env::default();
+    let (client, _, admin) = setup(&env);
+    let name = Symbol::new(&env, SERVICE_MARKETPLACE);
+
+    client.register_service(&admin, &name, &Address::generate(&env));
+    client.deregister_service(&admin, &name);
+    assert_eq!(client.resolve(&name), None);
+    assert_eq!(client.try_deregister_service(&admin, &name), Err(Ok(CommonError::KeyNotFound)));
+}
+
+#[test]
+fn test_only_admin_registers() {
+    let env = Env::default();
+    let (client, _, _) = setup(&env);
+    let intruder = Address::generate(&env);
+    let name = Symbol::new(&env, SERVICE_MARKETPLACE);
+
+    assert_eq!(
+        client.try_register_service(&intruder, &name, &intruder),
+        Err(Ok(CommonError::NotAuthorized))
+    );
+}
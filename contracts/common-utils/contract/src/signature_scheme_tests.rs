@@ -0,0 +1,135 @@
+#![cfg(test)]
+
+//! `SignatureBasedAuth` verifies with the scheme its key was registered
+//! under, and a signature from one scheme never passes under the other.
+
+use crate::authorization::{SignatureBasedAuth, SignatureScheme};
+use crate::CommonUtilsContract;
+use soroban_sdk::{
+    symbol_short,
+    testutils::ed25519::{generate, Sign},
+    Bytes, BytesN, Env,
+};
+
+fn sign(signer: &impl Sign<[u8], Signature = [u8; 64]>, message: &Bytes) -> [u8; 64] {
+    let mut raw = [0u8; 256];
+    let raw = &mut raw[..message.len() as usize];
+    message.copy_into_slice(raw);
+    signer.sign(&*raw)
+}
+
+/// Uncompressed secp256k1 key for `SECP_MESSAGE`.
+const SECP_PUBLIC_KEY: [u8; 65] = [
+    0x04, 0x89, 0x51, 0x26, 0x62, 0xb5, 0x90, 0x2e, 0xb4, 0x66, 0x3d,
+    0xbf, 0x42, 0x8c, 0x62, 0xf4, 0x5c, 0xe5, 0x2c, 0xd3, 0x17, 0x24,
+    0xc9, 0x79, 0x20, 0x56, 0xb9, 0x62, 0xd8, 0x6c, 0x3e, 0x25, 0x1a,
+    0x22, 0x55, 0x1d, 0x77, 0x2e, 0x86, 0x10, 0x1f, 0x38, 0x57, 0x14,
+    0xeb, 0x74, 0xab, 0x27, 0x05, 0x7a, 0x63, 0x92, 0xfe, 0x9e, 0x05,
+    0xb9, 0x2d, 0x98, 0x77, 0xec, 0xf3, 0x89, 0x1f, 0xb3, 0x72,
+];
+
+/// Low-S `r || s` over SHA-256(`SECP_MESSAGE`), recovery id 0.
+const SECP_SIGNATURE: [u8; 64] = [
+    0xff, 0x6f, 0x47, 0x35, 0xad, 0x58, 0x53, 0x90, 0x9b, 0x4d, 0x90,
+    0xb2, 0x77, 0x98, 0x57, 0x75, 0x3d, 0xc5, 0x98, 0xe6, 0xc3, 0xad,
+    0x6c, 0xee, 0x55, 0xb6, 0x04, 0xb7, 0x22, 0x2f, 0x48, 0xd4, 0x2b,
+    0xdd, 0xe5, 0x1e, 0x81, 0x8f, 0x31, 0x1c, 0x87, 0x4f, 0xd8, 0x2a,
+    0x38, 0xed, 0x03, 0x9f, 0x77, 0x23, 0xc8, 0x97, 0xbb, 0x38, 0x13,
+    0x2c, 0x1c, 0x74, 0x20, 0xde, 0xbc, 0xb9, 0xf8, 0x53,
+];
+
+/// `SECP_SIGNATURE` with `s` replaced by `n - s`: recovers to the same key
+/// under recovery id 1, but is the malleated form.
+const SECP_HIGH_S_SIGNATURE: [u8; 64] = [
+    0xff, 0x6f, 0x47, 0x35, 0xad, 0x58, 0x53, 0x90, 0x9b, 0x4d, 0x90,
+    0xb2, 0x77, 0x98, 0x57, 0x75, 0x3d, 0xc5, 0x98, 0xe6, 0xc3, 0xad,
+    0x6c, 0xee, 0x55, 0xb6, 0x04, 0xb7, 0x22, 0x2f, 0x48, 0xd4, 0xd4,
+    0x22, 0x1a, 0xe1, 0x7e, 0x70, 0xce, 0xe3, 0x78, 0xb0, 0x27, 0xd5,
+    0xc7, 0x12, 0xfc, 0x5f, 0x43, 0x8b, 0x14, 0x4e, 0xf4, 0x10, 0x8d,
+    0x0f, 0xa3, 0x5e, 0x3d, 0xae, 0x13, 0x7c, 0x48, 0xee,
+];
+
+const SECP_MESSAGE: &[u8] = b"luminarytrade secp256k1 vector";
+
+#[test]
+fn test_ed25519_signature_verifies() {
+    let env = Env::default();
+    let id = env.register(CommonUtilsContract, ());
+    let auth = SignatureBasedAuth::new(symbol_short!("pubkey"));
+    let signer = generate(&env);
+    let payload = Bytes::from_slice(&env, b"ed25519 payload");
+    let signature = BytesN::from_array(&env, &sign(&signer, &payload));
+
+    env.as_contract(&id, || {
+        let key = Bytes::from_array(&env, signer.public_key());
+        auth.register_key(&env, SignatureScheme::Ed25519, &key).unwrap();
+        assert_eq!(auth.get_scheme(&env), SignatureScheme::Ed25519);
+        assert_eq!(auth.verify_signature(&env, &payload, &signature), Ok(true));
+    });
+}
+
+#[test]
+fn test_secp256k1_signature_verifies() {
+    let env = Env::default();
+    let id = env.register(CommonUtilsContract, ());
+    let auth = SignatureBasedAuth::new(symbol_short!("pubkey"));
+    let payload = Bytes::from_slice(&env, SECP_MESSAGE);
+
+    env.as_contract(&id, || {
+        let key = Bytes::from_array(&env, &SECP_PUBLIC_KEY);
+        auth.register_key(&env, SignatureScheme::Secp256k1, &key).unwrap();
+        assert_eq!(auth.get_scheme(&env), SignatureScheme::Secp256k1);
+
+        let signature = BytesN::from_array(&env, &SECP_SIGNATURE);
+        assert_eq!(auth.verify_signature(&env, &payload, &signature), Ok(true));
+
+        let other = Bytes::from_slice(&env, b"some other payload");
+        assert_eq!(auth.verify_signature(&env, &other, &signature), Ok(false));
+    });
+}
+
+#[test]
+fn test_secp256k1_high_s_signature_is_rejected() {
+    let env = Env::default();
+    let id = env.register(CommonUtilsContract, ());
+    let auth = SignatureBasedAuth::new(symbol_short!("pubkey"));
+    let payload = Bytes::from_slice(&env, SECP_MESSAGE);
+
+    env.as_contract(&id, || {
+        let key = Bytes::from_array(&env, &SECP_PUBLIC_KEY);
+        auth.register_key(&env, SignatureScheme::Secp256k1, &key).unwrap();
+        let signature = BytesN::from_array(&env, &SECP_HIGH_S_SIGNATURE);
+        assert_eq!(auth.verify_signature(&env, &payload, &signature), Ok(false));
+    });
+}
+
+#[test]
+fn test_key_length_must_match_scheme() {
+    let env = Env::default();
+    let id = env.register(CommonUtilsContract, ());
+    let auth = SignatureBasedAuth::new(symbol_short!("pubkey"));
+
+    env.as_contract(&id, || {
+        let secp_key = Bytes::from_array(&env, &SECP_PUBLIC_KEY);
+        assert!(auth.register_key(&env, SignatureScheme::Ed25519, &secp_key).is_err());
+        let short_key = Bytes::from_array(&env, &[7u8; 32]);
+        assert!(auth.register_key(&env, SignatureScheme::Secp256k1, &short_key).is_err());
+    });
+}
+
+#[test]
+#[should_panic]
+fn test_secp256k1_signature_rejected_by_ed25519_key() {
+    let env = Env::default();
+    let id = env.register(CommonUtilsContract, ());
+    let auth = SignatureBasedAuth::new(symbol_short!("pubkey"));
+    let signer = generate(&env);
+    let payload = Bytes::from_slice(&env, SECP_MESSAGE);
+
+    env.as_contract(&id, || {
+        let key = Bytes::from_array(&env, signer.public_key());
+        auth.register_key(&env, SignatureScheme::Ed25519, &key).unwrap();
+        let signature = BytesN::from_array(&env, &SECP_SIGNATURE);
+        let _ = auth.verify_signature(&env, &payload, &signature);
+    });
+}
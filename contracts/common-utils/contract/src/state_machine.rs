@@ -12,7 +12,7 @@
 
 #![allow(unused)]
 
-use soroban_sdk::{contracttype, Env, Address, Symbol, symbol_short};
+use soroban_sdk::{contracttype, Env, Address, IntoVal, Symbol, TryFromVal, Val, Vec, symbol_short};
 use crate::error::{StateError, ContractError};
 
 /// Generic state wrapper that tracks the current state of a contract
@@ -120,6 +120,16 @@ pub trait StateMachine<T: Clone> {
         // Validate the transition
         Self::validate_transition(&current_state, &new_state)?;
 
+        if new_state.is_paused() && !current_state.is_paused() {
+            let grace = get_pause_grace(env);
+            if grace > 0 {
+                let eta = env.ledger().timestamp().saturating_add(grace);
+                env.storage().instance().set(&PauseKey::Eta, &eta);
+            }
+        } else if !new_state.is_paused() {
+            env.storage().instance().remove(&PauseKey::Eta);
+        }
+
         // Emit transition event
         Self::emit_transition_event(env, &current_state, &new_state);
 
@@ -179,7 +189,13 @@ pub trait StateMachine<T: Clone> {
         } else if current.is_uninitialized() {
             Err(StateError::NotInitialized)
         } else if current.is_paused() {
-            Err(StateError::ContractPaused)
+            // Writes already in flight may finish until the grace window
+            // ends, and are told the state moved under them after it
+            match get_pause_eta(env) {
+                Some(eta) if env.ledger().timestamp() < eta => Ok(()),
+                Some(_) => Err(StateError::InvalidState),
+                None => Err(StateError::ContractPaused),
+            }
         } else if current.is_terminated() {
             Err(StateError::ContractTerminated)
         } else {
@@ -187,6 +203,20 @@ pub trait StateMachine<T: Clone> {
         }
     }
 
+    /// Require that no migration is in progress. Used by reads whose backing
+    /// data a migration rewrites, so they fail instead of returning a
+    /// half-migrated value.
+    fn require_not_migrating(env: &Env) -> Result<(), StateError> {
+        let current = Self::get_state(env);
+        if current.is_uninitialized() {
+            Err(StateError::NotInitialized)
+        } else if current.is_migrating() {
+            Err(StateError::MigrationInProgress)
+        } else {
+            Ok(())
+        }
+    }
+
     /// Require that the contract is not terminated
     fn require_not_terminated(env: &Env) -> Result<(), StateError> {
         let current = Self::get_state(env);
@@ -198,6 +228,49 @@ pub trait StateMachine<T: Clone> {
     }
 }
 
+/// Storage keys for the pause grace window
+#[contracttype]
+#[derive(Clone, Debug)]
+pub enum PauseKey {
+    Grace,
+    Eta,
+}
+
+/// Seconds `require_active` keeps passing after a pause, after which it
+/// fails with `StateError::InvalidState`. 0, the default, pauses
+/// immediately with `StateError::ContractPaused`.
+pub fn set_pause_grace(env: &Env, secs: u64) {
+    env.storage().instance().set(&PauseKey::Grace, &secs);
+}
+
+pub fn get_pause_grace(env: &Env) -> u64 {
+    env.storage().instance().get(&PauseKey::Grace).unwrap_or(0)
+}
+
+/// When the current pause starts rejecting writes, if it was entered with a
+/// grace window
+pub fn get_pause_eta(env: &Env) -> Option<u64> {
+    env.storage().instance().get(&PauseKey::Eta)
+}
+
+/// Lower-case name of the state stored under `key`: `uninitialized`,
+/// `active`, `paused`, `migrating` or `terminated`. Reads only the variant
+/// tag of the stored enum, so the state data is never decoded.
+pub fn state_label<K: IntoVal<Env, Val>>(env: &Env, key: &K) -> Symbol {
+    let stored: Option<Vec<Val>> = env.storage().instance().get(key);
+    let tag = stored
+        .and_then(|parts| parts.get(0))
+        .and_then(|tag| Symbol::try_from_val(env, &tag).ok());
+    let label = match tag {
+        Some(tag) if tag == symbol_short!("Active") => "active",
+        Some(tag) if tag == symbol_short!("Paused") => "paused",
+        Some(tag) if tag == symbol_short!("Migrating") => "migrating",
+        Some(tag) if tag == Symbol::new(env, "Terminated") => "terminated",
+        _ => "uninitialized",
+    };
+    Symbol::new(env, label)
+}
+
 /// State guard macro - ensures function is only called in specific states
 #[macro_export]
 macro_rules! state_guard {
@@ -210,6 +283,9 @@ macro_rules! state_guard {
     ($contract:ty, $env:expr, not_terminated) => {
         <$contract as StateMachine<_>>::require_not_terminated($env)?
     };
+    ($contract:ty, $env:expr, not_migrating) => {
+        <$contract as StateMachine<_>>::require_not_migrating($env)?
+    };
 }
 
 /// Transition guard macro - validates and performs state transition
@@ -4,7 +4,7 @@
 
 use super::state_machine::*;
 use crate::error::StateError;
-use soroban_sdk::{testutils::Address as _, Address, Env, Symbol, symbol_short};
+use soroban_sdk::{testutils::{Address as _, Ledger}, Address, Env, Symbol, symbol_short};
 
 #[derive(Clone, Debug, PartialEq)]
 struct MockContractState {
@@ -467,3 +467,43 @@ fn test_complete_lifecycle() {
     });
     assert!(MockContract::transition(&env, active4).is_err());
 }
+
+#[test]
+fn test_pause_grace_window_lets_writes_finish() {
+    let env = Env::default();
+    let id = env.register(crate::CommonUtilsContract, ());
+    let admin = Address::generate(&env);
+    let data = MockContractState { admin, counter: 0 };
+
+    env.as_contract(&id, || {
+        env.ledger().set_timestamp(1_000);
+        set_pause_grace(&env, 60);
+        MockContract::transition(&env, State::Active(data.clone())).unwrap();
+        MockContract::transition(&env, State::Paused(data.clone())).unwrap();
+        assert_eq!(get_pause_eta(&env), Some(1_060));
+
+        env.ledger().set_timestamp(1_059);
+        assert!(MockContract::require_active(&env).is_ok());
+
+        env.ledger().set_timestamp(1_060);
+        assert_eq!(MockContract::require_active(&env), Err(StateError::InvalidState));
+
+        MockContract::transition(&env, State::Active(data.clone())).unwrap();
+        assert_eq!(get_pause_eta(&env), None);
+    });
+}
+
+#[test]
+fn test_pause_without_grace_is_immediate() {
+    let env = Env::default();
+    let id = env.register(crate::CommonUtilsContract, ());
+    let admin = Address::generate(&env);
+    let data = MockContractState { admin, counter: 0 };
+
+    env.as_contract(&id, || {
+        MockContract::transition(&env, State::Active(data.clone())).unwrap();
+        MockContract::transition(&env, State::Paused(data)).unwrap();
+        assert_eq!(get_pause_eta(&env), None);
+        assert_eq!(MockContract::require_active(&env), Err(StateError::ContractPaused));
+    });
+}
@@ -4,7 +4,7 @@ use soroban_sdk::{
     contracttype, Address, Env, Bytes, Vec, Symbol, Map, U256, 
     panic_with_error
 };
-use crate::error::ContractError;
+use crate::error::{ContractError, StateError};
 use crate::compression::CompressionType;
 use crate::storage_optimization::{DataTemperature, StorageMetadata};
 
@@ -37,6 +37,53 @@ pub struct PerformanceMetrics {
     pub last_updated: u64,
 }
 
+/// Totals across every operation recorded by `StorageTracker`
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct StorageSummary {
+    /// Bytes written by `store` operations
+    pub total_stored: u64,
+    /// Number of `access` operations
+    pub total_accessed: u64,
+    /// Number of operations per op symbol (`store`, `access`, `prune`, ...)
+    pub by_op: Map<Symbol, u64>,
+}
+
+/// Storage keys for storage tracker aggregates
+#[contracttype]
+#[derive(Clone, Debug)]
+pub enum TrackerKey {
+    Summary,
+}
+
+/// Aggregated timings for a single monitored operation
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PerfStats {
+    pub count: u64,
+    pub total: u64,
+    pub max: u64,
+}
+
+/// One row of a performance report
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PerfReportEntry {
+    pub operation: Symbol,
+    pub stats: PerfStats,
+    pub budget: Option<u64>,
+}
+
+/// Storage keys for per-operation performance data
+#[contracttype]
+#[derive(Clone, Debug)]
+pub enum PerfKey {
+    Stats(Symbol),
+    Budget(Symbol),
+    Operations,
+    StrictBudgets,
+}
+
 /// Storage usage tracker
 pub struct StorageTracker;
 
@@ -72,7 +119,7 @@ impl StorageTracker {
                 }
                 metrics.total_items += 1;
             }
-            "remove" => {
+            "remove" | "prune" => {
                 metrics.total_size = metrics.total_size.saturating_sub(size);
                 if compressed {
                     metrics.compressed_size = metrics.compressed_size.saturating_sub(size);
@@ -94,6 +141,8 @@ impl StorageTracker {
         
         env.storage().instance().set(&metrics_key, &metrics);
         
+        Self::update_summary(env, operation_type, size);
+        
         // Emit operation event
         env.events().publish(
             (Symbol::short("storage_operation"),),
@@ -101,6 +150,31 @@ impl StorageTracker {
         );
     }
     
+    /// Per-operation totals; all zero when nothing has been recorded yet
+    pub fn summary(env: &Env) -> StorageSummary {
+        env.storage().instance().get(&TrackerKey::Summary)
+            .unwrap_or_else(|| StorageSummary {
+                total_stored: 0,
+                total_accessed: 0,
+                by_op: Map::new(env),
+            })
+    }
+    
+    fn update_summary(env: &Env, operation_type: &Symbol, size: u32) {
+        let mut summary = Self::summary(env);
+        
+        if *operation_type == Symbol::new(env, "store") {
+            summary.total_stored = summary.total_stored.saturating_add(size as u64);
+        } else if *operation_type == Symbol::new(env, "access") {
+            summary.total_accessed = summary.total_accessed.saturating_add(1);
+        }
+        
+        let count = summary.by_op.get(operation_type.clone()).unwrap_or(0);
+        summary.by_op.set(operation_type.clone(), count + 1);
+        
+        env.storage().instance().set(&TrackerKey::Summary, &summary);
+    }
+    
     /// Get current storage metrics
     pub fn get_metrics(env: &Env) -> StorageMetrics {
         let metrics_key = Symbol::short("storage_metrics");
@@ -215,9 +289,107 @@ impl PerformanceMonitor {
             (operation, duration),
         );
         
+        Self::record_sample(env, operation, duration);
+        
         duration
     }
     
+    /// Fold a measured duration into the operation's stats and check its budget
+    pub fn record_sample(env: &Env, operation: &Symbol, duration: u64) {
+        let stats_key = PerfKey::Stats(operation.clone());
+        let mut stats: PerfStats = match env.storage().instance().get(&stats_key) {
+            Some(stats) => stats,
+            None => {
+                let mut operations: Vec<Symbol> = env.storage().instance()
+                    .get(&PerfKey::Operations)
+                    .unwrap_or_else(|| Vec::new(env));
+                operations.push_back(operation.clone());
+                env.storage().instance().set(&PerfKey::Operations, &operations);
+                PerfStats::default()
+            }
+        };
+        
+        stats.count += 1;
+        stats.total = stats.total.saturating_add(duration);
+        stats.max = stats.max.max(duration);
+        env.storage().instance().set(&stats_key, &stats);
+        
+        if let Some(budget) = Self::get_perf_budget(env, operation) {
+            if duration > budget {
+                env.events().publish(
+                    (Symbol::new(env, "perf_budget_exceeded"), operation.clone()),
+                    (duration, budget),
+                );
+            }
+        }
+    }
+    
+    /// Aggregated stats for one operation (zeroed if it never ran)
+    pub fn get_stats(env: &Env, operation: &Symbol) -> PerfStats {
+        env.storage().instance()
+            .get(&PerfKey::Stats(operation.clone()))
+            .unwrap_or_default()
+    }
+    
+    /// Set the maximum allowed duration for an operation.
+    /// Callers are responsible for authorising the change.
+    pub fn set_perf_budget(env: &Env, operation: &Symbol, max_allowed: u64) {
+        env.storage().instance().set(&PerfKey::Budget(operation.clone()), &max_allowed);
+    }
+    
+    /// In strict mode `end_timer_checked` fails when an operation overruns
+    /// its budget. Off unless a deployment opts in; callers are responsible
+    /// for authorising the change.
+    pub fn set_strict_budgets(env: &Env, enabled: bool) {
+        env.storage().instance().set(&PerfKey::StrictBudgets, &enabled);
+    }
+    
+    pub fn strict_budgets_enabled(env: &Env) -> bool {
+        env.storage().instance().get(&PerfKey::StrictBudgets).unwrap_or(false)
+    }
+    
+    /// `Err(BudgetExceeded)` if strict mode is on and `elapsed` is over the
+    /// operation's budget. Never fails in the default, non-strict mode.
+    pub fn enforce_budget(env: &Env, operation: &Symbol, elapsed: u64) -> Result<(), StateError> {
+        if !Self::strict_budgets_enabled(env) {
+            return Ok(());
+        }
+        match Self::get_perf_budget(env, operation) {
+            Some(budget) if elapsed > budget => Err(StateError::BudgetExceeded),
+            _ => Ok(()),
+        }
+    }
+    
+    /// `end_timer` followed by `enforce_budget`. Stats are recorded either
+    /// way; returning the error lets the caller abort (and roll back) the call.
+    pub fn end_timer_checked(env: &Env, operation: &Symbol) -> Result<u64, StateError> {
+        let duration = Self::end_timer(env, operation);
+        Self::enforce_budget(env, operation, duration)?;
+        Ok(duration)
+    }
+    
+    /// Budget configured for an operation, if any
+    pub fn get_perf_budget(env: &Env, operation: &Symbol) -> Option<u64> {
+        env.storage().instance().get(&PerfKey::Budget(operation.clone()))
+    }
+    
+    /// Stats and budgets for every operation recorded so far
+    pub fn get_report(env: &Env) -> Vec<PerfReportEntry> {
+        let operations: Vec<Symbol> = env.storage().instance()
+            .get(&PerfKey::Operations)
+            .unwrap_or_else(|| Vec::new(env));
+        
+        let mut report = Vec::new(env);
+        for operation in operations.iter() {
+            report.push_back(PerfReportEntry {
+                stats: Self::get_stats(env, &operation),
+                budget: Self::get_perf_budget(env, &operation),
+                operation,
+            });
+        }
+        report
+    }
+    
     /// Get performance metrics
     pub fn get_metrics(env: &Env) -> PerformanceMetrics {
         let perf_key = Symbol::short("performance_metrics");
@@ -2,13 +2,15 @@
 
 use soroban_sdk::{
     contracttype, Address, Env, Bytes, Vec, Symbol, Map, U256, 
-    panic_with_error
+    panic_with_error, symbol_short, xdr::ToXdr, IntoVal, TryFromVal, Val
 };
 use crate::error::ContractError;
+use crate::pagination::Paginated;
 use crate::compression::{
-    CompressionType, CompressionMetadata, CompressionManager, 
+    CompressionType, CompressionMetadata, CompressionManager, CompressedBlob,
     FraudReportCompressor, FraudReport
 };
+use crate::ring_buffer::RingBuffer;
 
 /// Data temperature for hot/cold separation
 #[contracttype]
@@ -251,6 +253,165 @@ impl DataSeparator {
     }
 }
 
+/// Per-category storage tier set with `DataSeparator::set_storage_tier`
+#[contracttype]
+#[derive(Clone, Debug)]
+pub enum StorageTierKey {
+    Category(Symbol),
+}
+
+/// Largest entry written to instance storage for a `Hot` category. Instance
+/// storage is loaded on every invocation, so Hot data must stay small.
+pub const MAX_INSTANCE_ENTRY_BYTES: u32 = 64 * 1024;
+
+/// Compressed fraud reports, per agent (default `Hot`)
+pub const CATEGORY_REPORTS: Symbol = symbol_short!("reports");
+/// Latest fraud score, per agent (default `Hot`)
+pub const CATEGORY_LATEST_SCORE: Symbol = symbol_short!("latest");
+/// Current credit score, per account (default `Cold`)
+pub const CATEGORY_SCORES: Symbol = symbol_short!("scores");
+
+/// Categories holding records of truth, which must not be routed to
+/// temporary storage where they would expire
+pub const DURABLE_CATEGORIES: [Symbol; 3] = [CATEGORY_REPORTS, CATEGORY_LATEST_SCORE, CATEGORY_SCORES];
+
+/// Where a temperature's entries live
+#[derive(Clone, Copy, PartialEq)]
+enum Backend {
+    Instance,
+    Persistent,
+    Temporary,
+}
+
+impl Backend {
+    const ALL: [Backend; 3] = [Backend::Instance, Backend::Persistent, Backend::Temporary];
+
+    fn of(temperature: &DataTemperature) -> Self {
+        match temperature {
+            DataTemperature::Hot => Backend::Instance,
+            DataTemperature::Warm | DataTemperature::Cold => Backend::Persistent,
+            DataTemperature::Frozen => Backend::Temporary,
+        }
+    }
+
+    fn get<K: IntoVal<Env, Val>, V: TryFromVal<Env, Val>>(self, env: &Env, key: &K) -> Option<V> {
+        match self {
+            Backend::Instance => env.storage().instance().get(key),
+            Backend::Persistent => env.storage().persistent().get(key),
+            Backend::Temporary => env.storage().temporary().get(key),
+        }
+    }
+
+    fn set<K: IntoVal<Env, Val>, V: IntoVal<Env, Val>>(self, env: &Env, key: &K, value: &V) {
+        match self {
+            Backend::Instance => env.storage().instance().set(key, value),
+            Backend::Persistent => env.storage().persistent().set(key, value),
+            Backend::Temporary => env.storage().temporary().set(key, value),
+        }
+    }
+
+    fn has<K: IntoVal<Env, Val>>(self, env: &Env, key: &K) -> bool {
+        match self {
+            Backend::Instance => env.storage().instance().has(key),
+            Backend::Persistent => env.storage().persistent().has(key),
+            Backend::Temporary => env.storage().temporary().has(key),
+        }
+    }
+
+    fn remove<K: IntoVal<Env, Val>>(self, env: &Env, key: &K) {
+        match self {
+            Backend::Instance => env.storage().instance().remove(key),
+            Backend::Persistent => env.storage().persistent().remove(key),
+            Backend::Temporary => env.storage().temporary().remove(key),
+        }
+    }
+}
+
+impl DataSeparator {
+    /// Route future writes of `category` to `temperature`'s storage: Hot to
+    /// instance, Warm and Cold to persistent, Frozen to temporary. Existing
+    /// entries stay where they are until rewritten. `DURABLE_CATEGORIES`
+    /// cannot be made Frozen and fail with `InvalidConfiguration`.
+    /// Callers are responsible for authorising the change.
+    pub fn set_storage_tier(env: &Env, category: Symbol, temperature: DataTemperature) -> Result<(), ContractError> {
+        if temperature == DataTemperature::Frozen && DURABLE_CATEGORIES.contains(&category) {
+            return Err(ContractError::InvalidConfiguration);
+        }
+        env.storage()
+            .instance()
+            .set(&StorageTierKey::Category(category), &temperature);
+        Ok(())
+    }
+
+    /// Configured tier for `category`, or `default` if none was set
+    pub fn get_storage_tier(env: &Env, category: &Symbol, default: DataTemperature) -> DataTemperature {
+        env.storage()
+            .instance()
+            .get(&StorageTierKey::Category(category.clone()))
+            .unwrap_or(default)
+    }
+
+    /// Write `value` to the tier configured for `category`. When the entry
+    /// is not already in that tier, any copy left in another tier by an
+    /// earlier policy is dropped. Hot writes larger than the Hot tier's
+    /// `max_size` or `MAX_INSTANCE_ENTRY_BYTES` fail with `StorageFull`.
+    pub fn store_in_tier<K, V>(
+        env: &Env,
+        category: &Symbol,
+        default: DataTemperature,
+        key: &K,
+        value: &V,
+    ) -> Result<(), ContractError>
+    where
+        K: IntoVal<Env, Val>,
+        V: IntoVal<Env, Val> + Clone,
+    {
+        let temperature = Self::get_storage_tier(env, category, default);
+        if temperature == DataTemperature::Hot {
+            let limit = Self::get_tier_config(env, &DataTemperature::Hot)
+                .max_size
+                .min(MAX_INSTANCE_ENTRY_BYTES);
+            if value.clone().to_xdr(env).len() > limit {
+                return Err(ContractError::StorageFull);
+            }
+        }
+
+        let target = Backend::of(&temperature);
+        if !target.has(env, key) {
+            for backend in Backend::ALL {
+                if backend != target {
+                    backend.remove(env, key);
+                }
+            }
+        }
+        target.set(env, key, value);
+        Ok(())
+    }
+
+    /// Read `key` from the tier configured for `category`, falling back to
+    /// the others so entries written under an earlier policy still resolve.
+    pub fn load_from_tier<K, V>(env: &Env, category: &Symbol, default: DataTemperature, key: &K) -> Option<V>
+    where
+        K: IntoVal<Env, Val>,
+        V: TryFromVal<Env, Val>,
+    {
+        let target = Backend::of(&Self::get_storage_tier(env, category, default));
+        target.get(env, key).or_else(|| {
+            Backend::ALL
+                .into_iter()
+                .filter(|backend| *backend != target)
+                .find_map(|backend| backend.get(env, key))
+        })
+    }
+
+    /// Remove `key` from every tier
+    pub fn remove_from_tiers<K: IntoVal<Env, Val>>(env: &Env, key: &K) {
+        for backend in Backend::ALL {
+            backend.remove(env, key);
+        }
+    }
+}
+
 /// Storage metadata for tracking data
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -411,7 +572,7 @@ impl CompressedReportStorage {
         let storage_key = Symbol::short(&format!("reports_{}", agent_id));
         let metadata_key = Symbol::short(&format!("reports_meta_{}", agent_id));
         
-        env.storage().instance().set(&storage_key, &compressed_data);
+        DataSeparator::store_in_tier(env, &CATEGORY_REPORTS, DataTemperature::Hot, &storage_key, &compressed_data)?;
         
         let metadata = ReportStorageMetadata {
             agent_id: agent_id.clone(),
@@ -433,17 +594,33 @@ impl CompressedReportStorage {
     /// Retrieve and decompress fraud reports
     pub fn get_reports(env: &Env, agent_id: &Symbol) -> Result<Vec<FraudReport>, ContractError> {
         let storage_key = Symbol::short(&format!("reports_{}", agent_id));
-        let compressed_data = env.storage().instance().get(&storage_key)
+        let compressed_data: Bytes = DataSeparator::load_from_tier(env, &CATEGORY_REPORTS, DataTemperature::Hot, &storage_key)
             .ok_or(ContractError::NotFound)?;
         
         FraudReportCompressor::decompress_reports(&compressed_data)
     }
     
+    /// Size and count recorded by the last `store_reports` for `agent_id`
+    pub fn get_metadata(env: &Env, agent_id: &Symbol) -> Option<ReportStorageMetadata> {
+        let metadata_key = Symbol::short(&format!("reports_meta_{}", agent_id));
+        env.storage().instance().get(&metadata_key)
+    }
+    
+    /// Delete every stored report for `agent_id`, including its latest score
+    pub fn remove_reports(env: &Env, agent_id: &Symbol) {
+        let storage_key = Symbol::short(&format!("reports_{}", agent_id));
+        let metadata_key = Symbol::short(&format!("reports_meta_{}", agent_id));
+        let score_key = Symbol::short(&format!("latest_score_{}", agent_id));
+        DataSeparator::remove_from_tiers(env, &storage_key);
+        env.storage().instance().remove(&metadata_key);
+        DataSeparator::remove_from_tiers(env, &score_key);
+    }
+    
     /// Get latest score without decompressing all reports
     pub fn get_latest_score(env: &Env, agent_id: &Symbol) -> Result<u32, ContractError> {
         // Store latest score separately for quick access
         let score_key = Symbol::short(&format!("latest_score_{}", agent_id));
-        env.storage().instance().get(&score_key)
+        DataSeparator::load_from_tier(env, &CATEGORY_LATEST_SCORE, DataTemperature::Hot, &score_key)
             .ok_or(ContractError::NotFound)
     }
     
@@ -454,8 +631,7 @@ impl CompressedReportStorage {
         score: u32,
     ) -> Result<(), ContractError> {
         let score_key = Symbol::short(&format!("latest_score_{}", agent_id));
-        env.storage().instance().set(&score_key, &score);
-        Ok(())
+        DataSeparator::store_in_tier(env, &CATEGORY_LATEST_SCORE, DataTemperature::Hot, &score_key, &score)
     }
     
     fn calculate_original_size(reports: &Vec<FraudReport>) -> u32 {
@@ -497,6 +673,16 @@ pub struct ReportStorageMetadata {
     pub last_updated: u64,
 }
 
+/// Scores kept per address before the oldest is evicted, unless lowered
+/// with `ScoreStorage::set_history_capacity`
+pub const SCORE_HISTORY_CAPACITY: u32 = 100;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ScoreStorageKey {
+    HistoryCapacity,
+}
+
 /// Efficient score storage
 pub struct ScoreStorage;
 
@@ -516,10 +702,10 @@ impl ScoreStorage {
         
         // Compress the score data
         let data_bytes = Self::serialize_score_data(&score_data);
-        let compressed_data = CompressionManager::compress(&data_bytes, &CompressionType::BitPacking)?;
+        let blob = CompressionManager::compress_blob(env, &data_bytes, CompressionType::BitPacking)?;
         
         let storage_key = Symbol::short(&format!("score_{}", address));
-        env.storage().persistent().set(&storage_key, &compressed_data);
+        DataSeparator::store_in_tier(env, &CATEGORY_SCORES, DataTemperature::Cold, &storage_key, &blob)?;
         
         // Update score history (compressed)
         Self::update_score_history(env, address, score, timestamp)?;
@@ -530,17 +716,35 @@ impl ScoreStorage {
     /// Retrieve and decompress score
     pub fn get_score(env: &Env, address: &Address) -> Result<u32, ContractError> {
         let storage_key = Symbol::short(&format!("score_{}", address));
-        let compressed_data = env.storage().persistent().get(&storage_key)
+        let stored: Val = DataSeparator::load_from_tier(env, &CATEGORY_SCORES, DataTemperature::Cold, &storage_key)
             .ok_or(ContractError::NotFound)?;
         
-        let decompressed_data = CompressionManager::decompress(&compressed_data, &CompressionType::BitPacking)?;
+        // Scores written before blobs were tagged are raw BitPacking bytes
+        let decompressed_data = match CompressedBlob::try_from_val(env, &stored) {
+            Ok(blob) => CompressionManager::decompress_blob(&blob)?,
+            Err(_) => {
+                let legacy = Bytes::try_from_val(env, &stored)
+                    .map_err(|_| ContractError::InvalidInput)?;
+                CompressionManager::decompress(&legacy, &CompressionType::BitPacking)?
+            }
+        };
         let score_data = Self::deserialize_score_data(&decompressed_data)?;
         
         Ok(score_data.score)
     }
     
-    /// Get score history
+    /// Get score history, oldest first. Accounts scored before the ring
+    /// buffer existed are read from their compressed history blob.
     pub fn get_score_history(env: &Env, address: &Address, limit: u32) -> Result<Vec<ScoreData>, ContractError> {
+        let ring = Self::history_ring(env, address);
+        if !ring.is_empty(env) {
+            let mut history = Vec::new(env);
+            for entry in ring.iter(env).iter().take(limit as usize) {
+                history.push_back(entry);
+            }
+            return Ok(history);
+        }
+
         let history_key = Symbol::short(&format!("score_hist_{}", address));
         let compressed_history = env.storage().persistent().get(&history_key)
             .ok_or(ContractError::NotFound)?;
@@ -549,6 +753,29 @@ impl ScoreStorage {
         Self::deserialize_score_history(&decompressed_history, limit)
     }
     
+    /// Scores kept per address; at most `SCORE_HISTORY_CAPACITY`.
+    pub fn history_capacity(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&ScoreStorageKey::HistoryCapacity)
+            .unwrap_or(SCORE_HISTORY_CAPACITY)
+    }
+
+    /// Keep only the newest `capacity` scores per address. Reads see the
+    /// new bound at once; stored histories are trimmed on their next write.
+    /// Callers are responsible for authorising the change.
+    pub fn set_history_capacity(env: &Env, capacity: u32) -> Result<(), ContractError> {
+        if capacity == 0 || capacity > SCORE_HISTORY_CAPACITY {
+            return Err(ContractError::InvalidInput);
+        }
+        env.storage().instance().set(&ScoreStorageKey::HistoryCapacity, &capacity);
+        Ok(())
+    }
+
+    fn history_ring(env: &Env, address: &Address) -> RingBuffer<Address, ScoreData> {
+        RingBuffer::new(symbol_short!("score_hst"), address.clone(), Self::history_capacity(env))
+    }
+    
     fn serialize_score_data(data: &ScoreData) -> Bytes {
         let env = Env::default();
         let mut bytes = Vec::new(&env);
@@ -606,38 +833,23 @@ impl ScoreStorage {
         score: u32,
         timestamp: u64,
     ) -> Result<(), ContractError> {
-        let history_key = Symbol::short(&format!("score_hist_{}", address));
+        let ring = Self::history_ring(env, address);
         
-        // Get existing history
-        let existing_history = env.storage().persistent().get(&history_key);
-        let mut history = if let Some(compressed) = existing_history {
-            CompressionManager::decompress(&compressed, &CompressionType::DeltaEncoding)?
-        } else {
-            Bytes::new(&Env::default())
-        };
+        // First write since the ring buffer: carry the legacy blob over
+        let history_key = Symbol::short(&format!("score_hist_{}", address));
+        if ring.is_empty(env) && env.storage().persistent().has(&history_key) {
+            for entry in Self::get_score_history(env, address, u32::MAX)?.iter() {
+                ring.push(env, &entry);
+            }
+            env.storage().persistent().remove(&history_key);
+        }
         
-        // Add new score to history
-        let new_score_data = Self::serialize_score_data(&ScoreData {
+        ring.push(env, &ScoreData {
             score,
             timestamp,
             address: address.clone(),
         });
         
-        // Combine and compress
-        let env_default = Env::default();
-        let mut combined = Vec::new(&env_default);
-        for byte in history.to_array() {
-            combined.push_back(byte);
-        }
-        for byte in new_score_data.to_array() {
-            combined.push_back(byte);
-        }
-        
-        let combined_bytes = Bytes::from_slice(&env_default, &combined.to_array());
-        let compressed_history = CompressionManager::compress(&combined_bytes, &CompressionType::DeltaEncoding)?;
-        
-        env.storage().persistent().set(&history_key, &compressed_history);
-        
         Ok(())
     }
     
@@ -660,6 +872,56 @@ impl ScoreStorage {
     }
 }
 
+/// Reports are one compressed blob per agent, so the count comes from the
+/// stored metadata and only a non-empty page decodes the blob.
+impl Paginated for CompressedReportStorage {
+    type Key = Symbol;
+    type Item = FraudReport;
+    
+    fn len(env: &Env, agent_id: &Symbol) -> u32 {
+        Self::get_metadata(env, agent_id).map_or(0, |meta| meta.report_count)
+    }
+
+    fn range(env: &Env, agent_id: &Symbol, start: u32, end: u32) -> Vec<FraudReport> {
+        let reports = Self::get_reports(env, agent_id).unwrap_or_else(|_| Vec::new(env));
+        let end = end.min(reports.len());
+        if start < end {
+            reports.slice(start..end)
+        } else {
+            Vec::new(env)
+        }
+    }
+}
+
+/// Ring-buffer histories read only the slots on the page. Legacy history
+/// blobs are decoded whole, as there is nothing smaller to read.
+impl Paginated for ScoreStorage {
+    type Key = Address;
+    type Item = ScoreData;
+    
+    fn len(env: &Env, address: &Address) -> u32 {
+        let ring = Self::history_ring(env, address);
+        if !ring.is_empty(env) {
+            return ring.len(env);
+        }
+        Self::get_score_history(env, address, u32::MAX).map_or(0, |history| history.len())
+    }
+
+    fn range(env: &Env, address: &Address, start: u32, end: u32) -> Vec<ScoreData> {
+        let ring = Self::history_ring(env, address);
+        if !ring.is_empty(env) {
+            return ring.range(env, start, end);
+        }
+        let history = Self::get_score_history(env, address, end).unwrap_or_else(|_| Vec::new(env));
+        let end = end.min(history.len());
+        if start < end {
+            history.slice(start..end)
+        } else {
+            Vec::new(env)
+        }
+    }
+}
+
 /// Score data structure
 #[contracttype]
 #[derive(Clone, Debug)]
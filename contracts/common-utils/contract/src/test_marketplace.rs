@@ -0,0 +1,695 @@
+#![cfg(test)]
+
+use crate::error::CommonError;
+use crate::marketplace::{ListingType, MarketplaceContract, MarketplaceContractClient, SettlementGuard};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env, Vec,
+};
+
+struct Setup<'a> {
+    env: Env,
+    client: MarketplaceContractClient<'a>,
+    asset: Address,
+    currency: Address,
+    seller: Address,
+    buyer: Address,
+    royalty_recipient: Address,
+}
+
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let issuer = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let royalty_recipient = Address::generate(&env);
+
+    let asset = env.register_stellar_asset_contract_v2(issuer.clone()).address();
+    let currency = env.register_stellar_asset_contract_v2(issuer).address();
+    StellarAssetClient::new(&env, &asset).mint(&seller, &1);
+    StellarAssetClient::new(&env, &currency).mint(&buyer, &1_000);
+
+    let id = env.register(MarketplaceContract, ());
+    let client = MarketplaceContractClient::new(&env, &id);
+    client.list_agent(
+        &seller,
+        &7u64,
+        &asset,
+        &1_000,
+        &currency,
+        &ListingType::FixedPrice,
+        &500,
+        &royalty_recipient,
+    );
+
+    Setup { env, client, asset, currency, seller, buyer, royalty_recipient }
+}
+
+#[test]
+fn test_updated_royalty_recipient_is_paid_on_sale() {
+    let s = setup();
+    let new_recipient = Address::generate(&s.env);
+
+    s.client.update_royalty_recipient(&7u64, &s.royalty_recipient, &new_recipient);
+    s.client.buy_agent(&s.buyer, &7u64);
+
+    let currency = TokenClient::new(&s.env, &s.currency);
+    assert_eq!(currency.balance(&new_recipient), 50);
+    assert_eq!(currency.balance(&s.royalty_recipient), 0);
+    assert_eq!(currency.balance(&s.seller), 950);
+}
+
+#[test]
+fn test_seller_can_update_royalty_recipient() {
+    let s = setup();
+    let new_recipient = Address::generate(&s.env);
+
+    s.client.update_royalty_recipient(&7u64, &s.seller, &new_recipient);
+    s.client.buy_agent(&s.buyer, &7u64);
+    assert_eq!(TokenClient::new(&s.env, &s.currency).balance(&new_recipient), 50);
+}
+
+#[test]
+fn test_unrelated_caller_cannot_update_royalty_recipient() {
+    let s = setup();
+    let stranger = Address::generate(&s.env);
+
+    assert_eq!(
+        s.client.try_update_royalty_recipient(&7u64, &stranger, &stranger),
+        Err(Ok(CommonError::NotAuthorized))
+    );
+    assert_eq!(
+        s.client.try_update_royalty_recipient(&8u64, &s.seller, &stranger),
+        Err(Ok(CommonError::KeyNotFound))
+    );
+}
+
+mod fee_token {
+    use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
+
+    #[contracttype]
+    pub enum Key {
+        Balance(Address),
+        Fee,
+    }
+
+    /// Token that burns a fixed `fee` out of every transfer
+    #[contract]
+    pub struct FeeToken;
+
+    #[contractimpl]
+    impl FeeToken {
+        pub fn set_fee(env: Env, fee: i128) {
+            env.storage().instance().set(&Key::Fee, &fee);
+        }
+
+        pub fn mint(env: Env, to: Address, amount: i128) {
+            let balance = Self::balance(env.clone(), to.clone());
+            env.storage().instance().set(&Key::Balance(to), &(balance + amount));
+        }
+
+        pub fn balance(env: Env, id: Address) -> i128 {
+            env.storage().instance().get(&Key::Balance(id)).unwrap_or(0)
+        }
+
+        pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+            from.require_auth();
+            let fee: i128 = env.storage().instance().get(&Key::Fee).unwrap_or(0);
+            let from_balance = Self::balance(env.clone(), from.clone());
+            env.storage().instance().set(&Key::Balance(from), &(from_balance - amount));
+            Self::mint(env, to, (amount - fee).max(0));
+        }
+    }
+}
+
+fn list_fee_token(env: &Env, fee: i128) -> (MarketplaceContractClient, fee_token::FeeTokenClient, Address) {
+    let seller = Address::generate(env);
+    let token_id = env.register(fee_token::FeeToken, ());
+    let token = fee_token::FeeTokenClient::new(env, &token_id);
+    token.set_fee(&fee);
+    token.mint(&seller, &1);
+
+    let id = env.register(MarketplaceContract, ());
+    let client = MarketplaceContractClient::new(env, &id);
+    // Unrelated units already held by the marketplace must not count as escrow
+    token.mint(&id, &5);
+    (client, token, seller)
+}
+
+#[test]
+fn test_escrow_records_amount_actually_received() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token, seller) = list_fee_token(&env, 0);
+    let buyer = Address::generate(&env);
+    let issuer = Address::generate(&env);
+    let currency = env.register_stellar_asset_contract_v2(issuer).address();
+    StellarAssetClient::new(&env, &currency).mint(&buyer, &100);
+
+    client.list_agent(
+        &seller,
+        &1u64,
+        &token.address,
+        &100,
+        &currency,
+        &ListingType::FixedPrice,
+        &0,
+        &seller,
+    );
+    let escrowed: i128 = env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .get(&crate::marketplace::DataKey::ListingEscrow(1))
+            .unwrap()
+    });
+    assert_eq!(escrowed, 1);
+
+    client.buy_agent(&buyer, &1u64);
+    assert_eq!(token.balance(&buyer), 1);
+    assert_eq!(token.balance(&client.address), 5);
+}
+
+#[test]
+fn test_delivery_fee_is_not_paid_from_unrelated_units() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token, seller) = list_fee_token(&env, 0);
+    let buyer = Address::generate(&env);
+    let issuer = Address::generate(&env);
+    let currency = env.register_stellar_asset_contract_v2(issuer).address();
+    StellarAssetClient::new(&env, &currency).mint(&buyer, &100);
+
+    client.list_agent(
+        &seller,
+        &1u64,
+        &token.address,
+        &100,
+        &currency,
+        &ListingType::FixedPrice,
+        &0,
+        &seller,
+    );
+
+    // The fee now burns the whole delivery; the contract still sends only
+    // the unit it escrowed and keeps the 5 it held before
+    token.set_fee(&1);
+    client.buy_agent(&buyer, &1u64);
+    assert_eq!(token.balance(&buyer), 0);
+    assert_eq!(token.balance(&client.address), 5);
+    assert_eq!(client.get_escrowed(&token.address), 0);
+}
+
+#[test]
+fn test_listing_stored_before_escrow_tracking_still_sells() {
+    use crate::marketplace::{DataKey, Listing};
+
+    let s = setup();
+    let currency = TokenClient::new(&s.env, &s.currency);
+    StellarAssetClient::new(&s.env, &s.asset).mint(&s.client.address, &1);
+    let listing = Listing {
+        seller: s.seller.clone(),
+        asset_address: s.asset.clone(),
+        price: 100,
+        currency: s.currency.clone(),
+        listing_type: ListingType::FixedPrice,
+        royalty_bps: 0,
+        royalty_recipient: s.seller.clone(),
+    };
+    s.env.as_contract(&s.client.address, || {
+        s.env.storage().persistent().set(&DataKey::Listing(9), &listing);
+    });
+
+    s.client.buy_agent(&s.buyer, &9u64);
+    assert_eq!(TokenClient::new(&s.env, &s.asset).balance(&s.buyer), 1);
+    assert_eq!(currency.balance(&s.seller), 100);
+    // Listing 7's unit is still counted
+    assert_eq!(s.client.get_escrowed(&s.asset), 1);
+}
+
+#[test]
+fn test_listing_rejected_when_fee_swallows_escrow() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token, seller) = list_fee_token(&env, 1);
+    let currency = Address::generate(&env);
+
+    assert_eq!(
+        client.try_list_agent(
+            &seller,
+            &1u64,
+            &token.address,
+            &100,
+            &currency,
+            &ListingType::FixedPrice,
+            &0,
+            &seller,
+        ),
+        Err(Ok(CommonError::OutOfRange))
+    );
+}
+
+#[test]
+fn test_rescue_token_only_moves_unescrowed_units() {
+    let s = setup();
+    let admin = Address::generate(&s.env);
+    let to = Address::generate(&s.env);
+    s.client.initialize(&admin);
+
+    // Sent directly, not tied to any listing
+    StellarAssetClient::new(&s.env, &s.asset).mint(&s.client.address, &3);
+    assert_eq!(s.client.get_escrowed(&s.asset), 1);
+
+    s.client.rescue_token(&admin, &s.asset, &to, &3);
+    let asset = TokenClient::new(&s.env, &s.asset);
+    assert_eq!(asset.balance(&to), 3);
+
+    // The remaining unit backs listing 7
+    assert_eq!(
+        s.client.try_rescue_token(&admin, &s.asset, &to, &1),
+        Err(Ok(CommonError::OutOfRange))
+    );
+    assert_eq!(
+        s.client.try_rescue_token(&s.seller, &s.asset, &to, &1),
+        Err(Ok(CommonError::NotAuthorized))
+    );
+
+    s.client.buy_agent(&s.buyer, &7u64);
+    assert_eq!(asset.balance(&s.buyer), 1);
+    assert_eq!(s.client.get_escrowed(&s.asset), 0);
+}
+
+#[test]
+fn test_backfill_protects_listings_made_before_escrow_tracking() {
+    use crate::marketplace::{DataKey, Listing};
+
+    let s = setup();
+    let admin = Address::generate(&s.env);
+    let to = Address::generate(&s.env);
+    s.client.initialize(&admin);
+
+    // Listing 9 as stored before escrow was recorded, holding one unit
+    StellarAssetClient::new(&s.env, &s.asset).mint(&s.client.address, &1);
+    let listing = Listing {
+        seller: s.seller.clone(),
+        asset_address: s.asset.clone(),
+        price: 100,
+        currency: s.currency.clone(),
+        listing_type: ListingType::FixedPrice,
+        royalty_bps: 0,
+        royalty_recipient: s.seller.clone(),
+    };
+    s.env.as_contract(&s.client.address, || {
+        s.env.storage().persistent().set(&DataKey::Listing(9), &listing);
+    });
+
+    assert_eq!(
+        s.client.try_backfill_escrow(&s.seller, &soroban_sdk::vec![&s.env, 9u64]),
+        Err(Ok(CommonError::NotAuthorized))
+    );
+    // Listing 7 is already counted and 10 does not exist
+    assert_eq!(s.client.backfill_escrow(&admin, &soroban_sdk::vec![&s.env, 7u64, 9, 10]), 1);
+    assert_eq!(s.client.backfill_escrow(&admin, &soroban_sdk::vec![&s.env, 9u64]), 0);
+    assert_eq!(s.client.get_escrowed(&s.asset), 2);
+    assert_eq!(
+        s.client.try_rescue_token(&admin, &s.asset, &to, &1),
+        Err(Ok(CommonError::OutOfRange))
+    );
+
+    s.client.buy_agent(&s.buyer, &9u64);
+    assert_eq!(TokenClient::new(&s.env, &s.asset).balance(&s.buyer), 1);
+    assert_eq!(s.client.get_escrowed(&s.asset), 1);
+}
+
+#[test]
+fn test_initialize_requires_admin_auth() {
+    let env = Env::default();
+    let id = env.register(MarketplaceContract, ());
+    let client = MarketplaceContractClient::new(&env, &id);
+    let admin = Address::generate(&env);
+
+    assert!(client.try_initialize(&admin).is_err());
+    env.mock_all_auths();
+    client.initialize(&admin);
+    assert_eq!(env.auths()[0].0, admin);
+}
+
+#[test]
+fn test_sale_is_recorded_in_history() {
+    let s = setup();
+    assert_eq!(s.client.get_sale_history(&7u64).len(), 0);
+
+    s.client.buy_agent(&s.buyer, &7u64);
+
+    let history = s.client.get_sale_history(&7u64);
+    assert_eq!(history.len(), 1);
+    let sale = history.get(0).unwrap();
+    assert_eq!(sale.buyer, s.buyer);
+    assert_eq!(sale.seller, s.seller);
+    assert_eq!(sale.price, 1_000);
+}
+
+mod rate_oracle {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    /// Answers `get_rate` like the oracle bridge, with a settable rate
+    #[contract]
+    pub struct MockRateOracle;
+
+    #[contractimpl]
+    impl MockRateOracle {
+        pub fn set_rate(env: Env, asset: Address, rate: i128) {
+            env.storage().instance().set(&asset, &rate);
+        }
+
+        pub fn get_rate(env: Env, asset: Address) -> Option<i128> {
+            env.storage().instance().get(&asset)
+        }
+    }
+}
+
+#[test]
+fn test_sale_blocked_below_min_settlement_value() {
+    use crate::oracle_bridge::RATE_SCALE;
+
+    let s = setup();
+    let oracle_id = s.env.register(rate_oracle::MockRateOracle, ());
+    let oracle = rate_oracle::MockRateOracleClient::new(&s.env, &oracle_id);
+    let guard = SettlementGuard { rate_oracle: oracle_id, min_value: 800 };
+
+    assert_eq!(
+        s.client.try_set_min_settlement_value(&7u64, &s.buyer, &Some(guard.clone())),
+        Err(Ok(CommonError::NotAuthorized))
+    );
+    s.client.set_min_settlement_value(&7u64, &s.seller, &Some(guard.clone()));
+    assert_eq!(s.client.get_min_settlement_value(&7u64), Some(guard));
+
+    // No rate yet: the sale is refused rather than let through unchecked
+    assert_eq!(s.client.try_buy_agent(&s.buyer, &7u64), Err(Ok(CommonError::OracleNotFound)));
+
+    // 1_000 * 0.75 = 750 < 800
+    oracle.set_rate(&s.currency, &(RATE_SCALE * 3 / 4));
+    assert_eq!(s.client.try_buy_agent(&s.buyer, &7u64), Err(Ok(CommonError::BelowMinValue)));
+    assert_eq!(TokenClient::new(&s.env, &s.currency).balance(&s.buyer), 1_000);
+
+    // 1_000 * 0.8 = 800
+    oracle.set_rate(&s.currency, &(RATE_SCALE * 4 / 5));
+    s.client.buy_agent(&s.buyer, &7u64);
+    assert_eq!(TokenClient::new(&s.env, &s.asset).balance(&s.buyer), 1);
+    assert_eq!(s.client.get_min_settlement_value(&7u64), None);
+}
+
+#[test]
+fn test_listing_without_guard_ignores_rates() {
+    let s = setup();
+    assert_eq!(s.client.get_min_settlement_value(&7u64), None);
+    s.client.buy_agent(&s.buyer, &7u64);
+    assert_eq!(TokenClient::new(&s.env, &s.asset).balance(&s.buyer), 1);
+}
+
+fn list_with_splits(s: &Setup, agent_id: u64, price: i128, royalty_bps: u32, splits: &Vec<(Address, u32)>) -> Result<(), CommonError> {
+    StellarAssetClient::new(&s.env, &s.asset).mint(&s.seller, &1);
+    match s.client.try_list_agent_with_splits(
+        &s.seller,
+        &agent_id,
+        &s.asset,
+        &price,
+        &s.currency,
+        &ListingType::FixedPrice,
+        &royalty_bps,
+        splits,
+    ) {
+        Ok(_) => Ok(()),
+        Err(err) => Err(err.unwrap()),
+    }
+}
+
+#[test]
+fn test_two_creator_royalty_split_pays_exact_amounts() {
+    let s = setup();
+    let first = Address::generate(&s.env);
+    let second = Address::generate(&s.env);
+    let splits = soroban_sdk::vec![&s.env, (first.clone(), 300u32), (second.clone(), 200u32)];
+    list_with_splits(&s, 8, 1_000, 500, &splits).unwrap();
+
+    s.client.buy_agent(&s.buyer, &8u64);
+    let currency = TokenClient::new(&s.env, &s.currency);
+    assert_eq!(currency.balance(&first), 30);
+    assert_eq!(currency.balance(&second), 20);
+    assert_eq!(currency.balance(&s.seller), 950);
+}
+
+#[test]
+fn test_royalty_split_rounding_remainder_goes_to_seller() {
+    let s = setup();
+    let first = Address::generate(&s.env);
+    let second = Address::generate(&s.env);
+    let splits = soroban_sdk::vec![&s.env, (first.clone(), 333u32), (second.clone(), 334u32)];
+    // 999 * 333 / 10_000 = 33.27 and 999 * 334 / 10_000 = 33.37, both rounded down
+    list_with_splits(&s, 8, 999, 667, &splits).unwrap();
+    assert_eq!(
+        s.client.try_update_royalty_recipient(&8u64, &s.seller, &first),
+        Err(Ok(CommonError::InvalidState))
+    );
+
+    s.client.buy_agent(&s.buyer, &8u64);
+    let currency = TokenClient::new(&s.env, &s.currency);
+    assert_eq!(currency.balance(&first), 33);
+    assert_eq!(currency.balance(&second), 33);
+    assert_eq!(currency.balance(&s.seller), 933);
+}
+
+#[test]
+fn test_royalty_splits_must_sum_to_royalty_bps() {
+    let s = setup();
+    let creator = Address::generate(&s.env);
+    let short = soroban_sdk::vec![&s.env, (creator.clone(), 200u32), (s.seller.clone(), 200u32)];
+    assert_eq!(list_with_splits(&s, 8, 1_000, 500, &short), Err(CommonError::InvalidFormat));
+    let over = soroban_sdk::vec![&s.env, (creator.clone(), 10_001u32)];
+    assert_eq!(list_with_splits(&s, 8, 1_000, 10_001, &over), Err(CommonError::OutOfRange));
+    let overflow = soroban_sdk::vec![&s.env, (creator.clone(), u32::MAX), (creator, 2u32)];
+    assert_eq!(list_with_splits(&s, 8, 1_000, 1, &overflow), Err(CommonError::OutOfRange));
+    assert_eq!(
+        list_with_splits(&s, 8, 1_000, 0, &Vec::new(&s.env)),
+        Err(CommonError::MissingRequiredField)
+    );
+}
+
+fn setup_with_hold<'a>(hold_secs: u64) -> (Setup<'a>, Address) {
+    let s = setup();
+    let admin = Address::generate(&s.env);
+    s.client.initialize(&admin);
+    s.client.set_payment_hold(&admin, &hold_secs);
+    s.env.ledger().set_timestamp(1_000);
+    (s, admin)
+}
+
+#[test]
+fn test_held_payment_is_released_only_after_hold() {
+    let (s, _) = setup_with_hold(600);
+    s.client.buy_agent(&s.buyer, &7u64);
+
+    // The agent is delivered at purchase; only the payment is held
+    let currency = TokenClient::new(&s.env, &s.currency);
+    let asset = TokenClient::new(&s.env, &s.asset);
+    assert_eq!(asset.balance(&s.buyer), 1);
+    assert_eq!(asset.balance(&s.client.address), 0);
+    assert_eq!(s.client.get_escrowed(&s.asset), 0);
+    assert_eq!(currency.balance(&s.client.address), 1_000);
+    assert_eq!(s.client.get_escrowed(&s.currency), 1_000);
+    assert_eq!(s.client.get_held_payment(&7u64).unwrap().release_at, 1_600);
+
+    s.env.ledger().set_timestamp(1_599);
+    assert_eq!(s.client.try_release_payment(&7u64), Err(Ok(CommonError::InvalidState)));
+    assert_eq!(currency.balance(&s.seller), 0);
+
+    s.env.ledger().set_timestamp(1_600);
+    s.client.release_payment(&7u64);
+    assert_eq!(currency.balance(&s.seller), 950);
+    assert_eq!(currency.balance(&s.royalty_recipient), 50);
+    assert_eq!(s.client.get_escrowed(&s.currency), 0);
+    assert_eq!(s.client.get_held_payment(&7u64), None);
+    assert_eq!(s.client.try_release_payment(&7u64), Err(Ok(CommonError::KeyNotFound)));
+}
+
+#[test]
+fn test_admin_can_refund_buyer_during_hold() {
+    let (s, admin) = setup_with_hold(600);
+    s.client.buy_agent(&s.buyer, &7u64);
+
+    assert_eq!(
+        s.client.try_refund_buyer(&s.seller, &7u64),
+        Err(Ok(CommonError::NotAuthorized))
+    );
+    s.client.refund_buyer(&admin, &7u64);
+
+    let currency = TokenClient::new(&s.env, &s.currency);
+    let asset = TokenClient::new(&s.env, &s.asset);
+    assert_eq!(currency.balance(&s.buyer), 1_000);
+    assert_eq!(currency.balance(&s.seller), 0);
+    assert_eq!(asset.balance(&s.buyer), 1);
+    assert_eq!(s.client.get_escrowed(&s.currency), 0);
+    assert_eq!(s.client.try_release_payment(&7u64), Err(Ok(CommonError::KeyNotFound)));
+}
+
+#[test]
+fn test_refund_closes_when_hold_elapses() {
+    let (s, admin) = setup_with_hold(600);
+    s.client.buy_agent(&s.buyer, &7u64);
+
+    s.env.ledger().set_timestamp(1_600);
+    assert_eq!(s.client.try_refund_buyer(&admin, &7u64), Err(Ok(CommonError::InvalidState)));
+    s.client.release_payment(&7u64);
+    assert_eq!(TokenClient::new(&s.env, &s.currency).balance(&s.seller), 950);
+}
+
+#[test]
+fn test_royalty_overflow_rejects_sale() {
+    let s = setup();
+    StellarAssetClient::new(&s.env, &s.asset).mint(&s.seller, &1);
+    s.client.list_agent(
+        &s.seller,
+        &8u64,
+        &s.asset,
+        &i128::MAX,
+        &s.currency,
+        &ListingType::FixedPrice,
+        &500,
+        &s.royalty_recipient,
+    );
+
+    // i128::MAX * 500 does not fit; nothing moves
+    assert_eq!(s.client.try_buy_agent(&s.buyer, &8u64), Err(Ok(CommonError::OutOfRange)));
+    assert_eq!(TokenClient::new(&s.env, &s.currency).balance(&s.buyer), 1_000);
+    assert_eq!(s.client.get_escrowed(&s.asset), 2);
+}
+
+#[test]
+fn test_escrow_total_overflow_rejects_listing() {
+    use crate::marketplace::DataKey;
+
+    let s = setup();
+    s.env.as_contract(&s.client.address, || {
+        s.env.storage().persistent().set(&DataKey::Escrowed(s.asset.clone()), &i128::MAX);
+    });
+    StellarAssetClient::new(&s.env, &s.asset).mint(&s.seller, &1);
+
+    assert_eq!(
+        s.client.try_list_agent(
+            &s.seller,
+            &8u64,
+            &s.asset,
+            &1_000,
+            &s.currency,
+            &ListingType::FixedPrice,
+            &500,
+            &s.royalty_recipient,
+        ),
+        Err(Ok(CommonError::OutOfRange))
+    );
+    assert_eq!(s.client.get_escrowed(&s.asset), i128::MAX);
+    assert_eq!(TokenClient::new(&s.env, &s.asset).balance(&s.seller), 1);
+}
+
+#[test]
+fn test_settlement_value_overflow_rejects_sale() {
+    let s = setup();
+    let oracle_id = s.env.register(rate_oracle::MockRateOracle, ());
+    let oracle = rate_oracle::MockRateOracleClient::new(&s.env, &oracle_id);
+    s.client.set_min_settlement_value(
+        &7u64,
+        &s.seller,
+        &Some(SettlementGuard { rate_oracle: oracle_id, min_value: 800 }),
+    );
+
+    // 1_000 * i128::MAX does not fit
+    oracle.set_rate(&s.currency, &i128::MAX);
+    assert_eq!(s.client.try_buy_agent(&s.buyer, &7u64), Err(Ok(CommonError::OutOfRange)));
+    assert_eq!(TokenClient::new(&s.env, &s.currency).balance(&s.buyer), 1_000);
+}
+
+#[test]
+fn test_royalty_over_full_price_is_rejected() {
+    use crate::marketplace::{DataKey, Listing};
+
+    let s = setup();
+    StellarAssetClient::new(&s.env, &s.asset).mint(&s.seller, &1);
+    assert_eq!(
+        s.client.try_list_agent(
+            &s.seller,
+            &8u64,
+            &s.asset,
+            &1_000,
+            &s.currency,
+            &ListingType::FixedPrice,
+            &10_001,
+            &s.royalty_recipient,
+        ),
+        Err(Ok(CommonError::OutOfRange))
+    );
+    assert_eq!(TokenClient::new(&s.env, &s.asset).balance(&s.seller), 1);
+
+    // A listing stored before the bound cannot charge more than its price
+    s.env.as_contract(&s.client.address, || {
+        let mut listing: Listing = s.env.storage().persistent().get(&DataKey::Listing(7)).unwrap();
+        listing.royalty_bps = 20_000;
+        s.env.storage().persistent().set(&DataKey::Listing(7), &listing);
+    });
+    assert_eq!(s.client.try_buy_agent(&s.buyer, &7u64), Err(Ok(CommonError::OutOfRange)));
+    assert_eq!(TokenClient::new(&s.env, &s.currency).balance(&s.buyer), 1_000);
+}
+
+#[test]
+fn test_held_payment_records_currency_actually_received() {
+    let (s, _) = setup_with_hold(600);
+    let currency_id = s.env.register(fee_token::FeeToken, ());
+    let currency = fee_token::FeeTokenClient::new(&s.env, &currency_id);
+    currency.set_fee(&10);
+    currency.mint(&s.buyer, &100);
+    // Belongs to another held sale
+    currency.mint(&s.client.address, &7);
+    StellarAssetClient::new(&s.env, &s.asset).mint(&s.seller, &1);
+    s.client.list_agent(
+        &s.seller,
+        &8u64,
+        &s.asset,
+        &100,
+        &currency_id,
+        &ListingType::FixedPrice,
+        &500,
+        &s.royalty_recipient,
+    );
+
+    // 90 of the 100 arrive; the fee comes out of the seller's 95
+    s.client.buy_agent(&s.buyer, &8u64);
+    let held = s.client.get_held_payment(&8u64).unwrap();
+    assert_eq!(held.amount, 90);
+    assert_eq!(
+        held.payouts,
+        soroban_sdk::vec![&s.env, (s.seller.clone(), 85), (s.royalty_recipient.clone(), 5)]
+    );
+
+    s.env.ledger().set_timestamp(1_600);
+    s.client.release_payment(&8u64);
+    assert_eq!(currency.balance(&s.client.address), 7);
+}
+
+#[test]
+fn test_release_refuses_payouts_beyond_held_amount() {
+    use crate::marketplace::DataKey;
+
+    let (s, _) = setup_with_hold(600);
+    s.client.buy_agent(&s.buyer, &7u64);
+    s.env.as_contract(&s.client.address, || {
+        let key = DataKey::HeldPayment(7);
+        let mut held: crate::marketplace::HeldPayment = s.env.storage().persistent().get(&key).unwrap();
+        held.payouts.push_back((s.buyer.clone(), 1));
+        s.env.storage().persistent().set(&key, &held);
+    });
+
+    s.env.ledger().set_timestamp(1_600);
+    assert_eq!(s.client.try_release_payment(&7u64), Err(Ok(CommonError::InvalidState)));
+    assert_eq!(TokenClient::new(&s.env, &s.currency).balance(&s.client.address), 1_000);
+}
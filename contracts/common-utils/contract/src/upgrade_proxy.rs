@@ -150,6 +150,13 @@ impl UpgradeableProxy {
         Ok(env.invoke_contract(&implementation, &function, args))
     }
 
+    /// `version()` of the current implementation, i.e. what `forward_call`
+    /// of `version` returns
+    pub fn implementation_version(env: Env) -> Result<u32, CommonError> {
+        let implementation = Self::get_implementation(env.clone())?;
+        Ok(env.invoke_contract(&implementation, &symbol_short!("version"), vec![&env]))
+    }
+
     /// Get current implementation address
     pub fn get_implementation(env: Env) -> Result<Address, CommonError> {
         let config: ProxyConfig = env
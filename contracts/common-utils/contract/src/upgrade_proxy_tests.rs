@@ -0,0 +1,41 @@
+#![cfg(test)]
+
+use crate::marketplace::{
+    MarketplaceContract, MarketplaceContractClient, MARKETPLACE_BUILD_ID, MARKETPLACE_CONTRACT_VERSION,
+};
+use crate::upgrade_proxy::{UpgradeableProxy, UpgradeableProxyClient};
+use crate::upgrade_registry::{UpgradeRegistry, UpgradeRegistryClient};
+use soroban_sdk::{symbol_short, testutils::Address as _, Address, Env, TryFromVal, Vec};
+
+#[test]
+fn test_marketplace_reports_its_version() {
+    let env = Env::default();
+    let id = env.register(MarketplaceContract, ());
+    let client = MarketplaceContractClient::new(&env, &id);
+
+    assert_eq!(client.version(), MARKETPLACE_CONTRACT_VERSION);
+    assert_eq!(client.build_id(), MARKETPLACE_BUILD_ID);
+}
+
+#[test]
+fn test_proxy_surfaces_implementation_version() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let name = symbol_short!("market");
+    let implementation = env.register(MarketplaceContract, ());
+
+    let registry_id = env.register(UpgradeRegistry, ());
+    let registry = UpgradeRegistryClient::new(&env, &registry_id);
+    registry.initialize(&admin);
+    registry.register_implementation(&admin, &name, &implementation, &1, &None);
+
+    let proxy_id = env.register(UpgradeableProxy, ());
+    let proxy = UpgradeableProxyClient::new(&env, &proxy_id);
+    proxy.initialize(&registry_id, &name, &admin);
+
+    assert_eq!(proxy.implementation_version(), MARKETPLACE_CONTRACT_VERSION);
+    let forwarded = proxy.forward_call(&symbol_short!("version"), &Vec::new(&env));
+    assert_eq!(u32::try_from_val(&env, &forwarded).unwrap(), MARKETPLACE_CONTRACT_VERSION);
+}
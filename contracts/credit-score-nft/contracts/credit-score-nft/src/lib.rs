@@ -1,5 +1,8 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, String, Vec};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, vec, Address, Env, InvokeError,
+    IntoVal, String, Symbol, Vec,
+};
 
 // ============================================================================
 // Data Types
@@ -21,6 +24,36 @@ pub struct CreditScoreNFT {
     pub score_updated_at: u64,
 }
 
+/// Royalty owed on secondary sales of a token, fixed at mint
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Royalty {
+    /// Royalty recipient (the minter unless set at mint)
+    pub creator: Address,
+    /// Royalty in basis points of the sale price
+    pub royalty_bps: u32,
+}
+
+/// Upper bound for `royalty_bps` (100%)
+pub const MAX_ROYALTY_BPS: u32 = 10_000;
+
+/// Function called on the transfer hook after every change of owner, as
+/// `on_nft_transfer(from: Address, to: Address, token_id: u64)`
+pub const TRANSFER_HOOK_FN: &str = "on_nft_transfer";
+
+/// Release of this contract, bumped on every meaningful change
+pub const CONTRACT_VERSION: u32 = 1;
+
+/// Release tag reported by `build_id`
+pub const BUILD_ID: Symbol = symbol_short!("v1_0_0");
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum NftError {
+    AlreadyInitialized = 1,
+}
+
 /// Active marketplace listing for a token
 #[contracttype]
 #[derive(Clone)]
@@ -31,6 +64,16 @@ pub struct Listing {
     pub listed_at: u64,
 }
 
+/// Contract notified of ownership changes, see `set_transfer_hook`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransferHook {
+    pub hook: Address,
+    /// If true a failing hook reverts the transfer; otherwise the failure
+    /// is reported with a `hook_fail` event and the transfer stands
+    pub revert_on_failure: bool,
+}
+
 #[contracttype]
 pub enum DataKey {
     Admin,
@@ -44,6 +87,12 @@ pub enum DataKey {
     Listing(u64),
     /// All currently listed token IDs
     ListedTokens,
+    /// Optional `TransferHook`
+    TransferHook,
+    /// Set once a token's metadata and score are frozen
+    Frozen(u64),
+    /// `Royalty` recorded at mint
+    Royalty(u64),
 }
 
 // ============================================================================
@@ -56,9 +105,9 @@ pub struct CreditScoreNFTContract;
 #[contractimpl]
 impl CreditScoreNFTContract {
     /// Initialize the credit score NFT contract.
-    pub fn initialize(env: Env, admin: Address) {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), NftError> {
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("Contract already initialized");
+            return Err(NftError::AlreadyInitialized);
         }
 
         admin.require_auth();
@@ -68,6 +117,7 @@ impl CreditScoreNFTContract {
 
         env.events()
             .publish((symbol_short!("init"), symbol_short!("contract")), admin);
+        Ok(())
     }
 
     /// Add an authorized minter.
@@ -106,6 +156,55 @@ impl CreditScoreNFTContract {
 
     /// Mint a new credit score NFT with a verifiable on-chain credit score.
     pub fn mint(env: Env, minter: Address, to: Address, metadata_cid: String, credit_score: u32) -> u64 {
+        Self::mint_token(env, minter.clone(), to, metadata_cid, credit_score, minter, 0)
+    }
+
+    /// Mint with a royalty on secondary sales, reported by `royalty_info`.
+    pub fn mint_with_royalty(
+        env: Env,
+        minter: Address,
+        to: Address,
+        metadata_cid: String,
+        credit_score: u32,
+        creator: Address,
+        royalty_bps: u32,
+    ) -> u64 {
+        if royalty_bps > MAX_ROYALTY_BPS {
+            panic!("Royalty cannot exceed 10000 bps");
+        }
+        Self::mint_token(env, minter, to, metadata_cid, credit_score, creator, royalty_bps)
+    }
+
+    /// EIP-2981-style royalty lookup: who is owed what on a sale at `sale_price`.
+    ///
+    /// Informational only; `transfer` does not collect it.
+    pub fn royalty_info(env: Env, token_id: u64, sale_price: i128) -> (Address, i128) {
+        if sale_price < 0 {
+            panic!("Sale price cannot be negative");
+        }
+        let nft: CreditScoreNFT = Self::get_nft(env.clone(), token_id);
+        // Tokens minted before royalties were recorded owe nothing
+        let royalty: Royalty = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Royalty(token_id))
+            .unwrap_or(Royalty { creator: nft.owner, royalty_bps: 0 });
+        let amount = match sale_price.checked_mul(royalty.royalty_bps as i128) {
+            Some(scaled) => scaled / MAX_ROYALTY_BPS as i128,
+            None => panic!("Royalty amount overflows"),
+        };
+        (royalty.creator, amount)
+    }
+
+    fn mint_token(
+        env: Env,
+        minter: Address,
+        to: Address,
+        metadata_cid: String,
+        credit_score: u32,
+        creator: Address,
+        royalty_bps: u32,
+    ) -> u64 {
         minter.require_auth();
 
         if credit_score > 1000 {
@@ -137,14 +236,11 @@ impl CreditScoreNFTContract {
         };
 
         env.storage().persistent().set(&DataKey::NFT(token_id), &nft);
-
-        let mut owner_tokens: Vec<u64> = env
-            .storage()
+        env.storage()
             .persistent()
-            .get(&DataKey::OwnerTokens(to.clone()))
-            .unwrap_or(Vec::new(&env));
-        owner_tokens.push_back(token_id);
-        env.storage().persistent().set(&DataKey::OwnerTokens(to.clone()), &owner_tokens);
+            .set(&DataKey::Royalty(token_id), &Royalty { creator, royalty_bps });
+
+        Self::add_owner_token(&env, &to, token_id);
 
         env.events().publish(
             (symbol_short!("mint"), symbol_short!("nft")),
@@ -180,6 +276,10 @@ impl CreditScoreNFTContract {
             panic!("Cannot update score of a revoked NFT");
         }
 
+        if Self::is_frozen(env.clone(), token_id) {
+            panic!("Metadata is frozen");
+        }
+
         nft.credit_score = new_score;
         nft.score_updated_at = env.ledger().timestamp();
         env.storage().persistent().set(&DataKey::NFT(token_id), &nft);
@@ -190,6 +290,75 @@ impl CreditScoreNFTContract {
         );
     }
 
+    /// Replace the metadata CID of an existing NFT (minter or admin only).
+    pub fn update_metadata(env: Env, caller: Address, token_id: u64, metadata_cid: String) {
+        caller.require_auth();
+
+        let is_authorized = Self::is_minter(env.clone(), caller.clone());
+        if !is_authorized {
+            let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+            if caller != admin {
+                panic!("Unauthorized: caller is not an authorized minter");
+            }
+        }
+
+        let mut nft: CreditScoreNFT = env
+            .storage()
+            .persistent()
+            .get(&DataKey::NFT(token_id))
+            .expect("NFT not found");
+
+        if Self::is_frozen(env.clone(), token_id) {
+            panic!("Metadata is frozen");
+        }
+
+        nft.metadata_cid = metadata_cid.clone();
+        env.storage().persistent().set(&DataKey::NFT(token_id), &nft);
+
+        env.events().publish(
+            (symbol_short!("meta_upd"), symbol_short!("nft")),
+            (token_id, metadata_cid),
+        );
+    }
+
+    /// Permanently freeze a token's metadata and score (minter or admin only).
+    ///
+    /// There is no unfreeze; freezing an already frozen token is a no-op.
+    pub fn freeze_metadata(env: Env, caller: Address, token_id: u64) {
+        caller.require_auth();
+
+        let is_authorized = Self::is_minter(env.clone(), caller.clone());
+        if !is_authorized {
+            let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+            if caller != admin {
+                panic!("Unauthorized: caller is not an authorized minter");
+            }
+        }
+
+        if !env.storage().persistent().has(&DataKey::NFT(token_id)) {
+            panic!("NFT not found");
+        }
+
+        if Self::is_frozen(env.clone(), token_id) {
+            return;
+        }
+
+        env.storage().persistent().set(&DataKey::Frozen(token_id), &true);
+
+        env.events().publish(
+            (Symbol::new(&env, "meta_frozen"), symbol_short!("nft")),
+            (token_id, caller),
+        );
+    }
+
+    /// Whether `freeze_metadata` has been called for a token.
+    pub fn is_frozen(env: Env, token_id: u64) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Frozen(token_id))
+            .unwrap_or(false)
+    }
+
     /// Approve an operator to transfer a specific token on behalf of the owner.
     ///
     /// Used by marketplace contracts to facilitate trustless trades.
@@ -262,6 +431,7 @@ impl CreditScoreNFTContract {
         // Caller must be owner or approved operator
         let approved: Option<Address> = env.storage().persistent().get(&DataKey::Approved(token_id));
         let caller_is_owner = nft.owner == from;
+        #[allow(clippy::unnecessary_map_or)]
         let caller_is_approved = approved.as_ref().map_or(false, |a| *a == from);
 
         if !caller_is_owner && !caller_is_approved {
@@ -270,28 +440,9 @@ impl CreditScoreNFTContract {
 
         from.require_auth();
 
-        // Remove token from previous owner's list
-        let mut from_tokens: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::OwnerTokens(nft.owner.clone()))
-            .unwrap_or(Vec::new(&env));
-        let mut updated_from_tokens: Vec<u64> = Vec::new(&env);
-        for t in from_tokens.iter() {
-            if t != token_id {
-                updated_from_tokens.push_back(t);
-            }
-        }
-        env.storage().persistent().set(&DataKey::OwnerTokens(nft.owner.clone()), &updated_from_tokens);
-
-        // Add token to new owner's list
-        let mut to_tokens: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::OwnerTokens(to.clone()))
-            .unwrap_or(Vec::new(&env));
-        to_tokens.push_back(token_id);
-        env.storage().persistent().set(&DataKey::OwnerTokens(to.clone()), &to_tokens);
+        // Move token between the owners' lists
+        Self::remove_owner_token(&env, &nft.owner, token_id);
+        Self::add_owner_token(&env, &to, token_id);
 
         // Clear approval on transfer
         env.storage().persistent().remove(&DataKey::Approved(token_id));
@@ -302,13 +453,15 @@ impl CreditScoreNFTContract {
             Self::remove_from_listed_tokens(&env, token_id);
         }
 
+        let previous_owner = nft.owner.clone();
         nft.owner = to.clone();
         env.storage().persistent().set(&DataKey::NFT(token_id), &nft);
 
         env.events().publish(
             (symbol_short!("transfer"), symbol_short!("nft")),
-            (from, to, token_id),
+            (from, to.clone(), token_id),
         );
+        Self::notify_transfer_hook(&env, &previous_owner, &to, token_id);
     }
 
     /// List a token for sale at a given price.
@@ -416,31 +569,9 @@ impl CreditScoreNFTContract {
             .get(&DataKey::NFT(token_id))
             .unwrap();
 
-        // Update seller's token list
-        let mut seller_tokens: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::OwnerTokens(listing.seller.clone()))
-            .unwrap_or(Vec::new(&env));
-        let mut updated_seller_tokens: Vec<u64> = Vec::new(&env);
-        for t in seller_tokens.iter() {
-            if t != token_id {
-                updated_seller_tokens.push_back(t);
-            }
-        }
-        env.storage().persistent().set(
-            &DataKey::OwnerTokens(listing.seller.clone()),
-            &updated_seller_tokens,
-        );
-
-        // Add to buyer's token list
-        let mut buyer_tokens: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::OwnerTokens(buyer.clone()))
-            .unwrap_or(Vec::new(&env));
-        buyer_tokens.push_back(token_id);
-        env.storage().persistent().set(&DataKey::OwnerTokens(buyer.clone()), &buyer_tokens);
+        // Move token from seller's list to buyer's
+        Self::remove_owner_token(&env, &listing.seller, token_id);
+        Self::add_owner_token(&env, &buyer, token_id);
 
         // Clear approval
         env.storage().persistent().remove(&DataKey::Approved(token_id));
@@ -450,8 +581,9 @@ impl CreditScoreNFTContract {
 
         env.events().publish(
             (symbol_short!("buy"), symbol_short!("nft")),
-            (buyer, listing.seller, token_id, listing.price),
+            (buyer.clone(), listing.seller.clone(), token_id, listing.price),
         );
+        Self::notify_transfer_hook(&env, &listing.seller, &buyer, token_id);
     }
 
     // ========================================================================
@@ -488,6 +620,28 @@ impl CreditScoreNFTContract {
         );
     }
 
+    /// Set or clear (`None`) the contract told about every change of owner
+    /// (admin only). With `revert_on_failure` a failing hook fails the
+    /// transfer; without it the transfer goes through regardless.
+    pub fn set_transfer_hook(env: Env, admin: Address, hook: Option<Address>, revert_on_failure: bool) {
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("Unauthorized: only admin can set the transfer hook");
+        }
+
+        match hook.clone() {
+            Some(hook) => env.storage().instance().set(
+                &DataKey::TransferHook,
+                &TransferHook { hook, revert_on_failure },
+            ),
+            None => env.storage().instance().remove(&DataKey::TransferHook),
+        }
+
+        env.events()
+            .publish((symbol_short!("set_hook"), symbol_short!("nft")), hook);
+    }
+
     // ========================================================================
     // View Functions
     // ========================================================================
@@ -540,10 +694,65 @@ impl CreditScoreNFTContract {
             .unwrap_or(Vec::new(&env))
     }
 
+    pub fn get_transfer_hook(env: Env) -> Option<TransferHook> {
+        env.storage().instance().get(&DataKey::TransferHook)
+    }
+
+    pub fn version(_env: Env) -> u32 {
+        CONTRACT_VERSION
+    }
+
+    pub fn build_id(_env: Env) -> Symbol {
+        BUILD_ID
+    }
+
     // ========================================================================
     // Internal
     // ========================================================================
 
+    /// Append `token_id` to `owner`'s list unless it is already there.
+    fn add_owner_token(env: &Env, owner: &Address, token_id: u64) {
+        let key = DataKey::OwnerTokens(owner.clone());
+        let mut tokens: Vec<u64> = env.storage().persistent().get(&key).unwrap_or(Vec::new(env));
+        if tokens.contains(token_id) {
+            return;
+        }
+        tokens.push_back(token_id);
+        env.storage().persistent().set(&key, &tokens);
+    }
+
+    /// Remove exactly one occurrence of `token_id` from `owner`'s list,
+    /// keeping the order of the remaining tokens.
+    fn remove_owner_token(env: &Env, owner: &Address, token_id: u64) {
+        let key = DataKey::OwnerTokens(owner.clone());
+        let mut tokens: Vec<u64> = env.storage().persistent().get(&key).unwrap_or(Vec::new(env));
+        if let Some(index) = tokens.first_index_of(token_id) {
+            tokens.remove(index);
+            env.storage().persistent().set(&key, &tokens);
+        }
+    }
+
+    /// Call `TRANSFER_HOOK_FN` on the configured hook, if any. Runs after all
+    /// state is written, so the hook sees the new owner.
+    fn notify_transfer_hook(env: &Env, from: &Address, to: &Address, token_id: u64) {
+        let Some(config) = Self::get_transfer_hook(env.clone()) else {
+            return;
+        };
+        let function = Symbol::new(env, TRANSFER_HOOK_FN);
+        let args = vec![env, from.into_val(env), to.into_val(env), token_id.into_val(env)];
+        if config.revert_on_failure {
+            env.invoke_contract::<()>(&config.hook, &function, args);
+            return;
+        }
+        let result = env.try_invoke_contract::<(), InvokeError>(&config.hook, &function, args);
+        if !matches!(result, Ok(Ok(()))) {
+            env.events().publish(
+                (symbol_short!("hook_fail"), symbol_short!("nft")),
+                (config.hook, token_id),
+            );
+        }
+    }
+
     fn remove_from_listed_tokens(env: &Env, token_id: u64) {
         let listed: Vec<u64> = env
             .storage()
@@ -589,6 +798,26 @@ mod test {
         assert_eq!(client.total_supply(), 0);
     }
 
+    #[test]
+    fn test_version_and_build_id() {
+        let (env, contract_id, _, _) = setup();
+        let client = CreditScoreNFTContractClient::new(&env, &contract_id);
+        assert_eq!(client.version(), CONTRACT_VERSION);
+        assert_eq!(client.build_id(), BUILD_ID);
+    }
+
+    #[test]
+    fn test_double_initialize_is_rejected() {
+        let (env, contract_id, admin, _) = setup();
+        let client = CreditScoreNFTContractClient::new(&env, &contract_id);
+        let other = Address::generate(&env);
+        assert_eq!(
+            client.try_initialize(&other),
+            Err(Ok(NftError::AlreadyInitialized))
+        );
+        assert_eq!(client.get_admin(), admin);
+    }
+
     #[test]
     fn test_add_and_remove_minter() {
         let (env, contract_id, admin, minter) = setup();
@@ -596,6 +825,7 @@ mod test {
         assert!(client.is_minter(&minter));
         client.remove_minter(&minter);
         assert!(!client.is_minter(&minter));
+        assert_eq!(client.get_admin(), admin);
     }
 
     #[test]
@@ -776,4 +1006,247 @@ mod test {
         let tokens = client.get_tokens_by_owner(&recipient);
         assert_eq!(tokens.len(), 2);
     }
+
+    #[test]
+    fn test_update_metadata_when_not_frozen() {
+        let (env, contract_id, _, minter) = setup();
+        let client = CreditScoreNFTContractClient::new(&env, &contract_id);
+        let recipient = Address::generate(&env);
+
+        let token_id = client.mint(&minter, &recipient, &String::from_str(&env, "QmOld..."), &650);
+        let updated = String::from_str(&env, "QmNew...");
+        client.update_metadata(&minter, &token_id, &updated);
+
+        assert_eq!(client.get_metadata_cid(&token_id), updated);
+        assert!(!client.is_frozen(&token_id));
+    }
+
+    #[test]
+    #[should_panic(expected = "Metadata is frozen")]
+    fn test_freeze_blocks_metadata_update() {
+        let (env, contract_id, _, minter) = setup();
+        let client = CreditScoreNFTContractClient::new(&env, &contract_id);
+        let recipient = Address::generate(&env);
+
+        let token_id = client.mint(&minter, &recipient, &String::from_str(&env, "QmFrozen..."), &650);
+        client.freeze_metadata(&minter, &token_id);
+        client.update_metadata(&minter, &token_id, &String::from_str(&env, "QmNew..."));
+    }
+
+    #[test]
+    #[should_panic(expected = "Metadata is frozen")]
+    fn test_freeze_blocks_score_update() {
+        let (env, contract_id, admin, minter) = setup();
+        let client = CreditScoreNFTContractClient::new(&env, &contract_id);
+        let recipient = Address::generate(&env);
+
+        let token_id = client.mint(&minter, &recipient, &String::from_str(&env, "QmFrozen..."), &650);
+        client.freeze_metadata(&admin, &token_id);
+        client.update_credit_score(&minter, &token_id, &700);
+    }
+
+    #[test]
+    fn test_freeze_is_idempotent() {
+        use soroban_sdk::{testutils::Events, vec, IntoVal, Val};
+
+        let (env, contract_id, admin, minter) = setup();
+        let client = CreditScoreNFTContractClient::new(&env, &contract_id);
+        let recipient = Address::generate(&env);
+        let metadata = String::from_str(&env, "QmFrozen...");
+
+        let frozen_topics: Vec<Val> = vec![
+            &env,
+            Symbol::new(&env, "meta_frozen").into_val(&env),
+            symbol_short!("nft").into_val(&env),
+        ];
+        let frozen_events = || {
+            env.events()
+                .all()
+                .iter()
+                .filter(|(_, topics, _)| *topics == frozen_topics)
+                .count()
+        };
+
+        let token_id = client.mint(&minter, &recipient, &metadata, &650);
+        client.freeze_metadata(&minter, &token_id);
+        assert_eq!(frozen_events(), 1);
+
+        // Second freeze is a no-op: no further event
+        client.freeze_metadata(&admin, &token_id);
+        assert_eq!(frozen_events(), 1);
+
+        assert!(client.is_frozen(&token_id));
+        assert_eq!(client.get_metadata_cid(&token_id), metadata);
+    }
+
+    #[test]
+    fn test_royalty_info_from_mint() {
+        let (env, contract_id, _, minter) = setup();
+        let client = CreditScoreNFTContractClient::new(&env, &contract_id);
+        let recipient = Address::generate(&env);
+        let creator = Address::generate(&env);
+
+        let metadata = String::from_str(&env, "QmRoyalty...");
+        let token_id = client.mint_with_royalty(&minter, &recipient, &metadata, &720, &creator, &250);
+
+        for (sale_price, expected) in [(0i128, 0i128), (10_000, 250), (1_000_000, 25_000), (399, 9)] {
+            assert_eq!(client.royalty_info(&token_id, &sale_price), (creator.clone(), expected));
+        }
+    }
+
+    #[test]
+    fn test_plain_mint_has_no_royalty() {
+        let (env, contract_id, _, minter) = setup();
+        let client = CreditScoreNFTContractClient::new(&env, &contract_id);
+        let recipient = Address::generate(&env);
+
+        let token_id = client.mint(&minter, &recipient, &String::from_str(&env, "QmPlain..."), &610);
+        assert_eq!(client.royalty_info(&token_id, &5_000_000), (minter, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Royalty cannot exceed 10000 bps")]
+    fn test_royalty_bps_capped() {
+        let (env, contract_id, _, minter) = setup();
+        let client = CreditScoreNFTContractClient::new(&env, &contract_id);
+        let recipient = Address::generate(&env);
+
+        let metadata = String::from_str(&env, "QmGreedy...");
+        client.mint_with_royalty(&minter, &recipient, &metadata, &700, &minter, &10_001);
+    }
+
+    #[test]
+    #[should_panic(expected = "Royalty amount overflows")]
+    fn test_royalty_overflow_is_rejected() {
+        let (env, contract_id, _, minter) = setup();
+        let client = CreditScoreNFTContractClient::new(&env, &contract_id);
+        let recipient = Address::generate(&env);
+
+        let metadata = String::from_str(&env, "QmHuge...");
+        let token_id = client.mint_with_royalty(&minter, &recipient, &metadata, &700, &minter, &MAX_ROYALTY_BPS);
+        client.royalty_info(&token_id, &i128::MAX);
+    }
+
+    #[test]
+    fn test_transfer_removes_exactly_one_token() {
+        let (env, contract_id, _, minter) = setup();
+        let client = CreditScoreNFTContractClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+        let recipient = Address::generate(&env);
+
+        let metadata = String::from_str(&env, "QmMany...");
+        let first = client.mint(&minter, &owner, &metadata, &600);
+        let second = client.mint(&minter, &owner, &metadata, &650);
+        let third = client.mint(&minter, &owner, &metadata, &700);
+
+        client.transfer(&owner, &recipient, &second);
+
+        assert_eq!(client.get_tokens_by_owner(&owner), Vec::from_array(&env, [first, third]));
+        assert_eq!(client.get_tokens_by_owner(&recipient), Vec::from_array(&env, [second]));
+
+        // Round trip back keeps each id listed once
+        client.transfer(&recipient, &owner, &second);
+        assert_eq!(
+            client.get_tokens_by_owner(&owner),
+            Vec::from_array(&env, [first, third, second])
+        );
+        assert_eq!(client.get_tokens_by_owner(&recipient).len(), 0);
+    }
+
+    mod recording_hook {
+        use soroban_sdk::{contract, contractimpl, Address, Env, Vec};
+
+        #[contract]
+        pub struct RecordingHook;
+
+        #[contractimpl]
+        impl RecordingHook {
+            pub fn on_nft_transfer(env: Env, from: Address, to: Address, token_id: u64) {
+                let mut calls: Vec<(Address, Address, u64)> =
+                    env.storage().instance().get(&0u32).unwrap_or(Vec::new(&env));
+                calls.push_back((from, to, token_id));
+                env.storage().instance().set(&0u32, &calls);
+            }
+
+            pub fn calls(env: Env) -> Vec<(Address, Address, u64)> {
+                env.storage().instance().get(&0u32).unwrap_or(Vec::new(&env))
+            }
+        }
+    }
+
+    mod failing_hook {
+        use soroban_sdk::{contract, contractimpl, Address, Env};
+
+        #[contract]
+        pub struct FailingHook;
+
+        #[contractimpl]
+        impl FailingHook {
+            pub fn on_nft_transfer(_env: Env, _from: Address, _to: Address, _token_id: u64) {
+                panic!("hook rejected the transfer");
+            }
+        }
+    }
+
+    #[test]
+    fn test_transfer_hook_records_transfers_and_sales() {
+        let (env, contract_id, admin, minter) = setup();
+        let client = CreditScoreNFTContractClient::new(&env, &contract_id);
+        let hook_id = env.register_contract(None, recording_hook::RecordingHook);
+        let hook = recording_hook::RecordingHookClient::new(&env, &hook_id);
+        client.set_transfer_hook(&admin, &Some(hook_id.clone()), &true);
+        assert_eq!(
+            client.get_transfer_hook(),
+            Some(TransferHook { hook: hook_id, revert_on_failure: true })
+        );
+
+        let owner = Address::generate(&env);
+        let buyer = Address::generate(&env);
+        let token_id = client.mint(&minter, &owner, &String::from_str(&env, "QmHook..."), &700);
+        // An approved operator moves the token on the owner's behalf
+        let operator = Address::generate(&env);
+        client.approve(&owner, &operator, &token_id);
+        client.transfer(&operator, &buyer, &token_id);
+        client.list_for_sale(&buyer, &token_id, &500);
+        client.buy(&owner, &token_id);
+
+        assert_eq!(
+            hook.calls(),
+            Vec::from_array(&env, [(owner.clone(), buyer.clone(), token_id), (buyer, owner, token_id)])
+        );
+    }
+
+    #[test]
+    fn test_absent_hook_is_a_no_op() {
+        let (env, contract_id, admin, minter) = setup();
+        let client = CreditScoreNFTContractClient::new(&env, &contract_id);
+        let hook_id = env.register_contract(None, recording_hook::RecordingHook);
+        client.set_transfer_hook(&admin, &Some(hook_id), &true);
+        client.set_transfer_hook(&admin, &None, &true);
+        assert_eq!(client.get_transfer_hook(), None);
+
+        let owner = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        let token_id = client.mint(&minter, &owner, &String::from_str(&env, "QmNoHook..."), &700);
+        client.transfer(&owner, &recipient, &token_id);
+        assert_eq!(client.get_owner(&token_id), recipient);
+    }
+
+    #[test]
+    fn test_failing_hook_reverts_only_when_configured() {
+        let (env, contract_id, admin, minter) = setup();
+        let client = CreditScoreNFTContractClient::new(&env, &contract_id);
+        let hook_id = env.register_contract(None, failing_hook::FailingHook);
+        let owner = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        let token_id = client.mint(&minter, &owner, &String::from_str(&env, "QmFail..."), &700);
+
+        client.set_transfer_hook(&admin, &Some(hook_id.clone()), &true);
+        assert!(client.try_transfer(&owner, &recipient, &token_id).is_err());
+        assert_eq!(client.get_owner(&token_id), owner);
+
+        client.set_transfer_hook(&admin, &Some(hook_id), &false);
+        client.transfer(&owner, &recipient, &token_id);
+        assert_eq!(client.get_owner(&token_id), recipient);
+    }
 }
@@ -1,46 +1,68 @@
-use soroban_sdk::{symbol_short, Address, Env, Vec};
+use soroban_sdk::{symbol_short, Env, Vec};
 
 use common_utils::batch::{
-    BatchError, BatchExecutor, BatchOperation, BatchResult, BatchValidator, RollbackStrategy,
-    ScoreUpdate,
+    BatchError, BatchEventAccumulator, BatchExecutor, BatchOperation, BatchResult, BatchValidator,
+    OperationResult, OperationStatus, RollbackStrategy, ScoreUpdate,
 };
+use common_utils::error::ValidationError;
 use common_utils::storage_optimization::ScoreStorage;
 
+use crate::CreditScoreContract;
+
 /// Batch update credit scores atomically (all succeed or all roll back).
-/// Requires admin authorization.
+/// Accounts younger than `min_account_age` are the exception: they are
+/// skipped and reported as failed with `ValidationError::AccountTooNew`.
+/// The caller checks admin authorization.
 pub fn batch_update_scores(
     env: &Env,
-    admin: &Address,
     updates: Vec<ScoreUpdate>,
 ) -> Result<BatchResult, BatchError> {
-    admin.require_auth();
-
     let mut ops = Vec::new(env);
     for u in updates.iter() {
         ops.push_back(BatchOperation::UpdateScore(u));
     }
 
     // Pre-validate before touching storage
-    BatchValidator::validate(&ops)?;
+    BatchValidator::validate_for(env, &ops)?;
 
-    let result = BatchExecutor::execute(env, ops, RollbackStrategy::AllOrNothing)?;
+    let mut result = BatchExecutor::execute(env, ops, RollbackStrategy::AllOrNothing)?;
 
     // Commit to persistent storage only when all ops succeeded
     if !result.rolled_back {
-        for update in updates.iter() {
-            ScoreStorage::store_score(
+        let mut events = BatchEventAccumulator::new(env, symbol_short!("scr_upd"));
+        for (index, update) in updates.iter().enumerate() {
+            let index = index as u32;
+            let ok = CreditScoreContract::account_old_enough(env, &update.account_id);
+            if ok {
+                ScoreStorage::store_score(
+                    env,
+                    &update.account_id,
+                    update.score,
+                    env.ledger().timestamp(),
+                )
+                .map_err(|_| BatchError::OperationFailed)?;
+                CreditScoreContract::record_first_seen(env, &update.account_id);
+            } else {
+                result.results.set(
+                    index,
+                    OperationResult {
+                        index,
+                        status: OperationStatus::Failed,
+                        error_code: ValidationError::AccountTooNew as u32,
+                    },
+                );
+                result.succeeded -= 1;
+                result.failed += 1;
+            }
+
+            events.record(
                 env,
-                &update.account_id,
-                update.score,
-                env.ledger().timestamp(),
-            )
-            .map_err(|_| BatchError::OperationFailed)?;
-
-            env.events().publish(
-                (symbol_short!("scr_upd"), update.account_id.clone()),
+                update.account_id.clone(),
+                ok,
                 (update.score, env.ledger().timestamp()),
             );
         }
+        events.finish(env);
     }
 
     Ok(result)
@@ -1,11 +1,20 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, String, Symbol, Vec, Bytes, BytesN};
-use common_utils::error::{AuthorizationError, StateError, ValidationError, ContractError};
+use soroban_sdk::{contract, contractimpl, contracttype, panic_with_error, symbol_short, vec, xdr::ToXdr, Address, Env, Map, String, Symbol, Vec, Bytes, BytesN, IntoVal, TryFromVal, Val};
+use common_utils::error::{AuthorizationError, StateError, ValidationError, ContractError, CommonError};
+use common_utils::admin_log::{self, AdminAction};
+use common_utils::batch::require_within_batch_limit;
+use common_utils::migration::{self, DataMigration};
+use common_utils::address_registry::AddressRegistry;
 use common_utils::compliance_log::{ComplianceLogger, ComplianceAction};
+use common_utils::event_schema;
 use common_utils::{rate_limit, rate_limit_adaptive};
-use common_utils::rate_limit::{RateLimiter, TrustTier};
-use common_utils::storage_optimization::{ScoreStorage, DataSeparator, DataTemperature};
-use common_utils::storage_monitoring::{StorageTracker, PerformanceMonitor};
+use common_utils::rate_limit::{
+    BucketState, EffectiveLimit, RateLimitConfig, RateLimitScope, RateLimitStrategy, RateLimiter, TrustTier,
+};
+use common_utils::storage_optimization::{ScoreStorage, ScoreData, DataSeparator, DataTemperature};
+use common_utils::pagination::{self, Paginated};
+use common_utils::service_registry::{resolve_service, SERVICE_SCORE_NFT};
+use common_utils::storage_monitoring::{StorageTracker, StorageSummary, PerformanceMonitor, PerfReportEntry};
 use common_utils::data_migration::{DataMigrationManager, MigrationConfig, CompressionType};
 use common_utils::compression::{CompressionManager, CompressionType};
 use common_utils::dex::{DexAdapter, StellarDexAdapter, TokenPair, DexConfig};
@@ -13,9 +22,10 @@ use common_utils::dex::trading_data::{TradingData, TradingVolume, PriceData};
 use common_utils::dex::liquidity::{LiquidityMetrics, PoolInfo};
 use common_utils::dex::scoring_signals::{SignalAggregator, ScoringSignal, SignalType, SignalWeight};
 use common_utils::dex::cache::{DexDataCache, CacheConfig};
-use common_utils::state_machine::{State, StateMachine, CreditScoreState};
+use common_utils::state_machine::{self, State, StateMachine, CreditScoreState};
 use common_utils::{state_guard, transition_to};
 use common_utils::fees::FeeModule;
+use common_utils::health::{self, HealthReport};
 use common_utils::treasury::TreasuryModule;
 
 mod validation;
@@ -23,6 +33,50 @@ mod penalties;
 mod scoring;
 mod batch;
 
+use scoring::ScoreInputs;
+pub use validation::ValidationDetail;
+
+/// Schema version of the `score_set` event payload.
+pub const SCORE_EVENT_VERSION: u32 = 1;
+
+/// Version of the scoring model, carried across migrations.
+pub const SCORING_MODEL_VERSION: u32 = 1;
+
+/// Layout version of the `export_state` payload.
+pub const STATE_EXPORT_VERSION: u32 = 3;
+
+/// Release of this contract, bumped on every meaningful change
+pub const CONTRACT_VERSION: u32 = 1;
+
+/// Release tag reported by `build_id`
+pub const BUILD_ID: Symbol = symbol_short!("v1_0_0");
+
+/// `[version, admin, total_scores, model_version, exported_accounts,
+/// score_digest, exported_at]`
+const STATE_EXPORT_LEN: u32 = 7;
+
+/// Version 2 payloads, which predate `exported_at`
+const STATE_EXPORT_LEN_V2: u32 = 6;
+
+/// Version 1 payloads: `[version, admin, total_scores, model_version,
+/// indexed_accounts]`
+const STATE_EXPORT_LEN_V1: u32 = 5;
+
+/// Seconds in every rate-limit window of this contract
+pub const RATE_LIMIT_WINDOW: u64 = 3600;
+
+/// `get_score` calls per account per `RATE_LIMIT_WINDOW`
+pub const GET_SCORE_RATE_MAX: u32 = 60;
+
+/// `update_factors` calls per `RATE_LIMIT_WINDOW`, shared by all callers
+pub const UPDATE_FACTORS_RATE_MAX: u32 = 20;
+
+/// `set_score` calls per account per `RATE_LIMIT_WINDOW`
+pub const SET_SCORE_RATE_MAX: u32 = 30;
+
+/// Seconds a score read from the fallback contract is served from cache
+pub const FALLBACK_CACHE_SECS: u64 = 300;
+
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct ScoreFactors {
@@ -33,6 +87,28 @@ pub struct ScoreFactors {
     pub new_inquiries: u32,
 }
 
+/// One page of an account's score history, see `get_score_history_page`
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ScoreHistoryPage {
+    pub items: Vec<ScoreData>,
+    pub total: u32,
+    pub has_more: bool,
+}
+
+/// One slice of the score index with each account's latest score,
+/// see `export_state_chunk`
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ScoreExportChunk {
+    pub entries: Vec<ScoreData>,
+    pub total: u32,
+    pub has_more: bool,
+    /// Offset of the next chunk. Accounts without a score are skipped, so
+    /// this can be more than `offset + entries.len()`.
+    pub next_offset: u32,
+}
+
 #[contracttype]
 pub enum DataKey {
     Admin,
@@ -45,6 +121,51 @@ pub enum DataKey {
     ScoreSignals(Address),
     DexEnabled,
     ContractState,
+    Config,
+    ScoreIndex,
+    TotalScores,
+    ModelVersion,
+    ServiceRegistry,
+    FirstSeen(Address),
+    FallbackScore(Address),
+    /// Score digest of the source contract, set by `import_state`
+    ImportedScoreDigest,
+}
+
+/// A score resolved through `CreditConfig::fallback_contract`, kept in
+/// temporary storage
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct FallbackScore {
+    pub score: u32,
+    pub cached_at: u64,
+}
+
+/// All operator-tunable settings, stored under `DataKey::Config` and
+/// returned in one read by `get_config`. A config stored before a field was
+/// added still loads, with that field at its default. `network_load` and
+/// `compression_type` belong to the rate limiter and compression manager
+/// and are read from them on every load.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CreditConfig {
+    pub dex_config: DexConfig,
+    pub dex_enabled: bool,
+    /// Network load reported to the adaptive rate limiter (×1000)
+    pub network_load: u32,
+    /// Compression applied to newly written factors and scores
+    pub compression_type: CompressionType,
+    /// Fail `sync_score_nft` when this contract may not update the NFT;
+    /// otherwise the update is skipped with a `mint_skipped` event
+    pub nft_mint_required: bool,
+    /// Seconds since an account was first seen before it can be scored
+    pub min_account_age: u64,
+    /// Partner credit-score contract `get_score` falls back to on a miss
+    pub fallback_contract: Option<Address>,
+    /// Least change from the stored score `set_score` writes; 0 writes all
+    pub min_score_delta: u32,
+    /// Emit `data_accessed` from `get_score_audited`
+    pub read_audit: bool,
 }
 
 #[contract]
@@ -82,27 +203,123 @@ impl CreditScoreContract {
         
         // Store admin for backward compatibility
         env.storage().instance().set(&DataKey::Admin, &admin);
-        env.storage().instance().set(&DataKey::DexEnabled, &true);
-        
-        let dex_config = DexConfig::default();
-        env.storage().instance().set(&DataKey::DexConfig, &dex_config);
+
+        let mut config = Self::load_config(&env);
+        config.dex_config = DexConfig::default();
+        config.dex_enabled = true;
+        Self::save_config(&env, &config);
         
         Ok(())
     }
 
+    /// `uninitialized`, `active`, `paused`, `migrating` or `terminated`
+    pub fn get_state_label(env: Env) -> Symbol {
+        state_machine::state_label(&env, &DataKey::ContractState)
+    }
+
+    /// Freeze the contract so `export_state` can mark its payload as taken
+    /// from a source that no longer changes.
+    pub fn pause(env: Env, admin: Address) -> Result<(), ContractError> {
+        Self::require_admin_action(&env, &admin, "pause", vec![&env])?;
+        let data = Self::get_state(&env)
+            .get_data()
+            .cloned()
+            .ok_or(ContractError::NotInitialized)?;
+        Self::transition(&env, State::Paused(data)).map_err(|_| ContractError::InvalidState)?;
+        Ok(())
+    }
+
+    pub fn resume(env: Env, admin: Address) -> Result<(), ContractError> {
+        Self::require_admin_action(&env, &admin, "resume", vec![&env])?;
+        let state = Self::get_state(&env);
+        if !state.is_paused() {
+            return Err(ContractError::InvalidState);
+        }
+        let data = state.get_data().cloned().ok_or(ContractError::NotInitialized)?;
+        Self::transition(&env, State::Active(data)).map_err(|_| ContractError::InvalidState)?;
+        Ok(())
+    }
+
+    /// Only allow `export_state` while paused or terminated. `import_state`
+    /// refuses live exports either way; this fails them at the source.
+    pub fn set_strict_export(env: Env, admin: Address, strict: bool) -> Result<(), ContractError> {
+        Self::require_admin_action(&env, &admin, "set_strict_export", vec![&env, strict.into_val(&env)])?;
+        migration::set_strict_export(&env, strict);
+        Ok(())
+    }
+
+    pub fn is_strict_export(env: Env) -> bool {
+        migration::is_strict_export(&env)
+    }
+
+    /// Export time of the source this contract was imported from; `None`
+    /// when never imported or imported from a version 1 or 2 payload
+    pub fn get_source_exported_at(env: Env) -> Option<u64> {
+        migration::source_exported_at(&env)
+    }
+
+    /// Readiness probe: the state label and whether the service registry,
+    /// the score NFT it names and the fallback contract answer a cheap
+    /// read. This contract consults no ACL, so `acl_ok` is always true. A
+    /// failing dependency is reported, never raised.
+    pub fn health(env: Env) -> HealthReport {
+        let mut deps = Vec::new(&env);
+        if let Some(registry) = env.storage().instance().get::<_, Address>(&DataKey::ServiceRegistry) {
+            let resolved = health::try_read::<Option<Address>>(
+                &env,
+                &registry,
+                "resolve",
+                soroban_sdk::vec![&env, Symbol::new(&env, SERVICE_SCORE_NFT).into_val(&env)],
+            );
+            deps.push_back((symbol_short!("registry"), resolved.is_some()));
+            if let Some(Some(nft)) = resolved {
+                let args = soroban_sdk::vec![&env, env.current_contract_address().into_val(&env)];
+                let reachable = health::try_read::<bool>(&env, &nft, "is_minter", args).is_some();
+                deps.push_back((symbol_short!("score_nft"), reachable));
+            }
+        }
+        if let Some(fallback) = Self::load_config(&env).fallback_contract {
+            // A partner that has no score for this contract still answered
+            let args = soroban_sdk::vec![&env, env.current_contract_address().into_val(&env)];
+            let reachable = health::answers(&env, &fallback, "get_score", args);
+            deps.push_back((symbol_short!("fallback"), reachable));
+        }
+
+        HealthReport {
+            state_label: Self::get_state_label(env.clone()),
+            acl_ok: true,
+            deps,
+        }
+    }
+
+    pub fn version(_env: Env) -> u32 {
+        CONTRACT_VERSION
+    }
+
+    pub fn build_id(_env: Env) -> Symbol {
+        BUILD_ID
+    }
+
     pub fn initialize_dex(env: Env, admin: Address) -> Result<(), ContractError> {
-        Self::require_admin(&env, &admin)?;
+        Self::require_admin_action(&env, &admin, "initialize_dex", vec![&env])?;
         
         StellarDexAdapter::initialize(&env)
             .map_err(|_| ContractError::InvalidState)?;
         
-        env.storage().instance().set(&DataKey::DexEnabled, &true);
+        let mut config = Self::load_config(&env);
+        config.dex_enabled = true;
+        Self::save_config(&env, &config);
         
         Ok(())
     }
 
     pub fn add_supported_pair(env: Env, admin: Address, pair: TokenPair) -> Result<(), ContractError> {
-        Self::require_admin(&env, &admin)?;
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "add_supported_pair",
+            vec![&env, pair.into_val(&env)],
+        )?;
         
         let mut adapter = StellarDexAdapter::new(&env);
         adapter.add_supported_pair(pair.clone())
@@ -118,16 +335,40 @@ impl CreditScoreContract {
     }
 
     pub fn set_dex_config(env: Env, admin: Address, config: DexConfig) -> Result<(), ContractError> {
-        Self::require_admin(&env, &admin)?;
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_dex_config",
+            vec![&env, config.into_val(&env)],
+        )?;
         config.validate().map_err(|_| ContractError::InvalidConfiguration)?;
-        env.storage().instance().set(&DataKey::DexConfig, &config);
+        let mut stored = Self::load_config(&env);
+        stored.dex_config = config;
+        Self::save_config(&env, &stored);
+        Ok(())
+    }
+
+    /// Select the compression used for newly stored factors and scores.
+    /// Existing blobs keep the type they were written with.
+    pub fn set_compression_type(env: Env, admin: Address, ct: CompressionType) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_compression_type",
+            vec![&env, ct.into_val(&env)],
+        )?;
+        CompressionManager::set_selected_type(&env, &ct)
+            .map_err(|_| ContractError::InvalidConfiguration)?;
         Ok(())
     }
 
     pub fn get_dex_config(env: Env) -> DexConfig {
-        env.storage().instance()
-            .get(&DataKey::DexConfig)
-            .unwrap_or_else(|| DexConfig::default())
+        Self::load_config(&env).dex_config
+    }
+
+    /// Full contract configuration in a single read
+    pub fn get_config(env: Env) -> CreditConfig {
+        Self::load_config(&env)
     }
 
     pub fn set_user_trust_tier(
@@ -136,37 +377,218 @@ impl CreditScoreContract {
         user: Address,
         tier: TrustTier,
     ) -> Result<(), AuthorizationError> {
-        let stored_admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .ok_or(AuthorizationError::NotInitialized)?;
-        stored_admin.require_auth();
-        if stored_admin != admin {
-            return Err(AuthorizationError::NotAuthorized);
-        }
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_user_trust_tier",
+            vec![&env, user.into_val(&env), tier.into_val(&env)],
+        )
+        .map_err(Self::authorization_error)?;
         RateLimiter::set_trust_tier(&env, &user, &tier);
         Ok(())
     }
 
+    /// Assign trust tiers to many users with a single admin authorization.
+    /// Accepts 1 to `get_max_batch_size` entries and returns how many were set.
+    pub fn set_user_trust_tiers(
+        env: Env,
+        admin: Address,
+        entries: Vec<(Address, TrustTier)>,
+    ) -> Result<u32, CommonError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_user_trust_tiers",
+            vec![&env, entries.into_val(&env)],
+        )
+        .map_err(Self::common_error)?;
+        require_within_batch_limit(&env, entries.len());
+        if entries.is_empty() {
+            return Err(CommonError::InvalidLength);
+        }
+        for (user, tier) in entries.iter() {
+            RateLimiter::set_trust_tier(&env, &user, &tier);
+        }
+        env.events().publish((Symbol::new(&env, "tiers_set"),), entries.len());
+        Ok(entries.len())
+    }
+
+    /// Tier applied to users without an explicit trust tier
+    pub fn set_default_trust_tier(env: Env, admin: Address, tier: TrustTier) -> Result<(), AuthorizationError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_default_trust_tier",
+            vec![&env, tier.into_val(&env)],
+        )
+        .map_err(Self::authorization_error)?;
+        RateLimiter::set_default_trust_tier(&env, &tier);
+        Ok(())
+    }
+
+    /// `user`'s trust tier, or the default tier if none was set
+    pub fn get_user_trust_tier(env: Env, user: Address) -> TrustTier {
+        RateLimiter::get_trust_tier(&env, &user)
+    }
+
     pub fn set_network_load(
         env: Env,
         admin: Address,
         load: u32,
     ) -> Result<(), AuthorizationError> {
-        let stored_admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .ok_or(AuthorizationError::NotInitialized)?;
-        stored_admin.require_auth();
-        if stored_admin != admin {
-            return Err(AuthorizationError::NotAuthorized);
-        }
+        Self::require_admin_action(&env, &admin, "set_network_load", vec![&env, load.into_val(&env)])
+            .map_err(Self::authorization_error)?;
         RateLimiter::set_network_load(&env, load);
         Ok(())
     }
 
+    /// Token-bucket state for `user` on a rate-limited `action` (e.g. `get_score`)
+    pub fn get_bucket_state(env: Env, user: Address, action: Symbol) -> Option<BucketState> {
+        RateLimiter::bucket_state(&env, &user, &action)
+    }
+
+    /// Support override: add `n` tokens to a user's bucket, capped at capacity
+    pub fn grant_tokens(
+        env: Env,
+        admin: Address,
+        user: Address,
+        action: Symbol,
+        n: u32,
+    ) -> Result<BucketState, ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "grant_tokens",
+            vec![&env, user.into_val(&env), action.into_val(&env), n.into_val(&env)],
+        )?;
+        RateLimiter::grant_tokens(&env, &user, &action, n)
+    }
+
+    /// Spread fixed-window rate-limit resets by a per-account offset
+    pub fn set_window_jitter(env: Env, admin: Address, enabled: bool) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_window_jitter",
+            vec![&env, enabled.into_val(&env)],
+        )?;
+        RateLimiter::set_window_jitter(&env, enabled);
+        Ok(())
+    }
+
+    /// Log over-limit calls with `would_limit` instead of rejecting them
+    pub fn set_rate_limit_observation(env: Env, admin: Address, enabled: bool) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_rate_limit_observation",
+            vec![&env, enabled.into_val(&env)],
+        )?;
+        RateLimiter::set_observation_mode(&env, enabled);
+        Ok(())
+    }
+
+    /// Replace the rate limit of `action` (`get_score`, `upd_factor` or
+    /// `set_score`), or restore its default with `None`.
+    pub fn set_rate_limit_override(
+        env: Env,
+        admin: Address,
+        action: Symbol,
+        config: Option<RateLimitConfig>,
+    ) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_rate_limit_override",
+            vec![&env, action.into_val(&env), config.into_val(&env)],
+        )?;
+        if Self::rate_limit_default(&env, &action).is_none() {
+            return Err(ContractError::InvalidInput);
+        }
+        if let Some(config) = &config {
+            if config.max_requests == 0 || config.window_seconds == 0 {
+                return Err(ContractError::InvalidConfiguration);
+            }
+        }
+        RateLimiter::set_limit_override(&env, &action, config);
+        Ok(())
+    }
+
+    /// Limit `user` currently gets on `action`, or `None` if the action is
+    /// not rate limited
+    pub fn get_effective_rate_limit(env: Env, user: Address, action: Symbol) -> Option<EffectiveLimit> {
+        let (default, adaptive) = Self::rate_limit_default(&env, &action)?;
+        Some(RateLimiter::effective_config(&env, &user, &action, default, adaptive))
+    }
+
+    /// Keep `category` (see `storage_optimization::CATEGORY_*`) in the
+    /// storage backing `temperature` from the next write on. Reports and
+    /// scores cannot be moved to the expiring `Frozen` tier.
+    pub fn set_storage_tier(
+        env: Env,
+        admin: Address,
+        category: Symbol,
+        temperature: DataTemperature,
+    ) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_storage_tier",
+            vec![&env, category.into_val(&env), temperature.into_val(&env)],
+        )?;
+        DataSeparator::set_storage_tier(&env, category, temperature)
+    }
+
+    /// Set the load → multiplier curve used by adaptive rate limits.
+    pub fn set_load_curve(env: Env, admin: Address, points: Vec<(u32, u32)>) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_load_curve",
+            vec![&env, points.into_val(&env)],
+        )?;
+        RateLimiter::set_load_curve(&env, &points)
+    }
+
+    /// Largest batch any batch entrypoint accepts, up to `batch::MAX_BATCH_SIZE`
+    pub fn set_max_batch_size(env: Env, admin: Address, n: u32) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_max_batch_size",
+            vec![&env, n.into_val(&env)],
+        )?;
+        common_utils::batch::set_max_batch_size(&env, n).map_err(|_| ContractError::InvalidConfiguration)
+    }
+
+    pub fn get_max_batch_size(env: Env) -> u32 {
+        common_utils::batch::max_batch_size(&env)
+    }
+
+    /// Emit per-item events from bulk operations alongside their summary
+    pub fn set_verbose_batch_events(env: Env, admin: Address, enabled: bool) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_verbose_batch_events",
+            vec![&env, enabled.into_val(&env)],
+        )?;
+        common_utils::batch::set_verbose_batch_events(&env, enabled);
+        Ok(())
+    }
+
+    /// Prefix versioned event topics with `namespace`, or clear it
+    pub fn set_event_namespace(env: Env, admin: Address, namespace: Option<Symbol>) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_event_namespace",
+            vec![&env, namespace.into_val(&env)],
+        )?;
+        event_schema::set_event_namespace(&env, namespace);
+        Ok(())
+    }
+
     pub fn calculate_score_with_dex(
         env: Env,
         account_id: Address,
@@ -175,10 +597,9 @@ impl CreditScoreContract {
         let _timer = PerformanceMonitor::start_timer(&env, &Symbol::new(&env, "calc_dex_score"));
         
         let base_score = Self::get_base_score(&env, &account_id)?;
+        Self::require_account_age(&env, &account_id);
         
-        let dex_enabled: bool = env.storage().instance()
-            .get(&DataKey::DexEnabled)
-            .unwrap_or(false);
+        let dex_enabled = Self::load_config(&env).dex_enabled;
         
         if !dex_enabled {
             return Ok(base_score);
@@ -202,7 +623,7 @@ impl CreditScoreContract {
             false,
         );
         
-        let _duration = PerformanceMonitor::end_timer(&env, &Symbol::new(&env, "calc_dex_score"));
+        Self::end_scoring_timer(&env, "calc_dex_score");
         
         // Collect Service Fee (mock amount 1000 for calculation)
         if !FeeModule::is_whitelisted(&env, &account_id) {
@@ -225,27 +646,61 @@ impl CreditScoreContract {
         charged_off: bool,
         bankrupt_years: u32,
     ) -> Result<u32, ValidationError> {
-        validation::validate_factors(payment_history, utilization, length, mix, new_inquiries)
-            .map_err(|_| ValidationError::NotAuthorized)?;
-        
-        let weighted_avg = scoring::calculate_weighted_average(
+        Self::compute_score(&ScoreInputs {
             payment_history,
             utilization,
             length,
             mix,
             new_inquiries,
-        );
-        
-        let penalties = penalties::calculate_total_penalties(
             days_late,
             defaulted,
             charged_off,
             bankrupt_years,
-        );
-        
-        let final_score = scoring::calculate_final_score(weighted_avg, penalties);
-        
-        Ok(final_score)
+        })
+    }
+
+    /// Preview the score for a factor string without storing anything.
+    /// Format: `payment_history,utilization,length,mix,new_inquiries,days_late,defaulted,charged_off,bankrupt_years`
+    /// with the flags as `0`/`1`. Not state-guarded, so it keeps working while paused.
+    pub fn simulate_score(env: Env, factors: String) -> Result<u32, ValidationError> {
+        let inputs = Self::parse_factor_string(&factors).map_err(|_| ValidationError::NotAuthorized)?;
+        Self::compute_score(&inputs)
+    }
+
+    /// The input `calculate_score` rejects these arguments for, or `None` if
+    /// it accepts them. A failed call keeps none of its events, so this is
+    /// how a caller finds out which field the error refers to.
+    pub fn check_score_inputs(
+        env: Env,
+        payment_history: u32,
+        utilization: u32,
+        length: u32,
+        mix: u32,
+        new_inquiries: u32,
+        days_late: u32,
+        defaulted: bool,
+        charged_off: bool,
+        bankrupt_years: u32,
+    ) -> Option<ValidationDetail> {
+        Self::validate_inputs(&ScoreInputs {
+            payment_history,
+            utilization,
+            length,
+            mix,
+            new_inquiries,
+            days_late,
+            defaulted,
+            charged_off,
+            bankrupt_years,
+        })
+        .err()
+    }
+
+    /// Same as `check_score_inputs`, for the `simulate_score` factor string
+    pub fn check_factor_string(env: Env, factors: String) -> Option<ValidationDetail> {
+        Self::parse_factor_string(&factors)
+            .and_then(|inputs| Self::validate_inputs(&inputs))
+            .err()
     }
 
     pub fn update_credit_score(
@@ -262,29 +717,38 @@ impl CreditScoreContract {
         charged_off: bool,
         bankrupt_years: u32,
     ) -> Result<u32, ContractError> {
-        Self::require_admin(&env, &admin)?;
-
-        // 1. Validate factors
-        validation::validate_factors(payment_history, utilization, length, mix, new_inquiries)
-            .map_err(|_| ContractError::InvalidState)?; // Proxy for validation error
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "update_credit_score",
+            vec![
+                &env,
+                user.into_val(&env),
+                payment_history.into_val(&env),
+                utilization.into_val(&env),
+                length.into_val(&env),
+                mix.into_val(&env),
+                new_inquiries.into_val(&env),
+                days_late.into_val(&env),
+                defaulted.into_val(&env),
+                charged_off.into_val(&env),
+                bankrupt_years.into_val(&env),
+            ],
+        )?;
 
-        // 2. Calculate new score
-        let weighted_avg = scoring::calculate_weighted_average(
+        // 1-2. Validate factors and calculate new score
+        let new_score = Self::compute_score(&ScoreInputs {
             payment_history,
             utilization,
             length,
             mix,
             new_inquiries,
-        );
-        
-        let penalties = penalties::calculate_total_penalties(
             days_late,
             defaulted,
             charged_off,
             bankrupt_years,
-        );
-        
-        let new_score = scoring::calculate_final_score(weighted_avg, penalties);
+        })
+        .map_err(|_| ContractError::InvalidState)?; // Proxy for validation error
 
         // 3. Jump detection
         let current_score = Self::get_base_score(&env, &user)?;
@@ -296,6 +760,7 @@ impl CreditScoreContract {
         // 4. Store score and history
         ScoreStorage::store_score(&env, &user, new_score, env.ledger().timestamp())
             .map_err(|_| ContractError::Unauthorized)?;
+        Self::index_score(&env, &user);
 
         // 5. Store factors
         let factors = ScoreFactors {
@@ -336,13 +801,15 @@ impl CreditScoreContract {
 
     pub fn get_score(env: Env, account_id: Address) -> Result<u32, AuthorizationError> {
         rate_limit_adaptive!(env, account_id, "get_score",
-            max: 60, window: 3600,
+            max: GET_SCORE_RATE_MAX, window: RATE_LIMIT_WINDOW,
             strategy: TokenBucket, scope: PerUser);
 
         let _timer = PerformanceMonitor::start_timer(&env, &Symbol::new(&env, "get_score"));
         
-        let result = ScoreStorage::get_score(&env, &account_id)
-            .map_err(|_| AuthorizationError::NotAuthorized)?;
+        let result = match ScoreStorage::get_score(&env, &account_id) {
+            Ok(score) => score,
+            Err(_) => Self::fallback_score(&env, &account_id).ok_or(AuthorizationError::NotAuthorized)?,
+        };
         
         StorageTracker::record_operation(
             &env, 
@@ -363,6 +830,34 @@ impl CreditScoreContract {
         Ok(result)
     }
 
+    /// `get_score` on behalf of `caller`, who must authorise the read. With
+    /// read audit on, emits `data_accessed` with `(account_id, timestamp)`.
+    pub fn get_score_audited(env: Env, caller: Address, account_id: Address) -> Result<u32, AuthorizationError> {
+        caller.require_auth();
+        let score = Self::get_score(env.clone(), account_id.clone())?;
+        if Self::load_config(&env).read_audit {
+            env.events().publish(
+                (Symbol::new(&env, "data_accessed"), caller),
+                (account_id, env.ledger().timestamp()),
+            );
+        }
+        Ok(score)
+    }
+
+    /// Turn `data_accessed` events from audited reads on or off
+    pub fn set_read_audit(env: Env, admin: Address, enabled: bool) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_read_audit",
+            vec![&env, enabled.into_val(&env)],
+        )?;
+        let mut config = Self::load_config(&env);
+        config.read_audit = enabled;
+        Self::save_config(&env, &config);
+        Ok(())
+    }
+
     pub fn get_score_with_signals(env: Env, account_id: Address) -> Result<ScoreWithSignals, ContractError> {
         let base_score = Self::get_base_score(&env, &account_id)?;
         
@@ -390,19 +885,22 @@ impl CreditScoreContract {
         factors: String,
     ) -> Result<(), AuthorizationError> {
         rate_limit!(env, account_id, "upd_factor",
-            max: 20, window: 3600,
+            max: UPDATE_FACTORS_RATE_MAX, window: RATE_LIMIT_WINDOW,
             strategy: FixedWindow, scope: Global);
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .ok_or(AuthorizationError::NotInitialized)?;
-        admin.require_auth();
+        let admin = Self::stored_admin(&env).map_err(Self::authorization_error)?;
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "update_factors",
+            vec![&env, account_id.into_val(&env), factors.into_val(&env)],
+        )
+        .map_err(Self::authorization_error)?;
 
         let factors_bytes = factors.into_bytes();
-        let compressed_factors = CompressionManager::compress(
+        let compressed_factors = CompressionManager::compress_blob(
+            &env,
             &Bytes::from_slice(&env, &factors_bytes), 
-            &CompressionType::RunLength
+            CompressionType::RunLength
         ).map_err(|_| AuthorizationError::NotAuthorized)?;
 
         env.storage()
@@ -413,39 +911,103 @@ impl CreditScoreContract {
             &env, 
             &Symbol::new(&env, "store"), 
             &Symbol::new(&env, "factors"), 
-            compressed_factors.len() as u32, 
+            compressed_factors.data.len() as u32, 
             true
         );
         
         Ok(())
     }
 
+    /// Store `score` for `account_id`. Returns false, writing nothing and
+    /// emitting `score_unchanged`, when a score is already stored and
+    /// `score` is within `min_score_delta` of it.
     pub fn set_score(
         env: Env,
         account_id: Address,
         score: u32,
-    ) -> Result<(), AuthorizationError> {
+    ) -> Result<bool, AuthorizationError> {
         rate_limit!(env, account_id, "set_score",
-            max: 30, window: 3600,
+            max: SET_SCORE_RATE_MAX, window: RATE_LIMIT_WINDOW,
             strategy: SlidingWindow, scope: PerUser);
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .ok_or(AuthorizationError::NotInitialized)?;
-        admin.require_auth();
+        let admin = Self::stored_admin(&env).map_err(Self::authorization_error)?;
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_score",
+            vec![&env, account_id.into_val(&env), score.into_val(&env)],
+        )
+        .map_err(Self::authorization_error)?;
+        Self::require_account_age(&env, &account_id);
 
-        ScoreStorage::store_score(&env, &account_id, score, env.ledger().timestamp())
+        if let Ok(current) = ScoreStorage::get_score(&env, &account_id) {
+            if current.abs_diff(score) < Self::load_config(&env).min_score_delta {
+                env.events().publish(
+                    (Symbol::new(&env, "score_unchanged"), account_id),
+                    (current, score),
+                );
+                return Ok(false);
+            }
+        }
+
+        Self::write_score(&env, account_id, score)
             .map_err(|_| AuthorizationError::NotAuthorized)?;
-        
-        StorageTracker::record_operation(
-            &env, 
-            &Symbol::new(&env, "store"), 
-            &Symbol::new(&env, "score"), 
-            44, 
-            true
-        );
-        
+        Ok(true)
+    }
+
+    /// Skip `set_score` writes that move a stored score by less than
+    /// `delta`. 0 disables the check.
+    pub fn set_min_score_delta(env: Env, admin: Address, delta: u32) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_min_score_delta",
+            vec![&env, delta.into_val(&env)],
+        )?;
+        let mut config = Self::load_config(&env);
+        config.min_score_delta = delta;
+        Self::save_config(&env, &config);
+        Ok(())
+    }
+
+    /// Keep only the newest `n` scores of each account's history, evicting
+    /// the oldest on write. `n` must be between 1 and
+    /// `SCORE_HISTORY_CAPACITY`; lowering it trims each history on its next
+    /// score.
+    pub fn set_max_history(env: Env, admin: Address, n: u32) -> Result<(), ContractError> {
+        Self::require_admin_action(&env, &admin, "set_max_history", vec![&env, n.into_val(&env)])?;
+        ScoreStorage::set_history_capacity(&env, n)
+    }
+
+    pub fn get_max_history(env: Env) -> u32 {
+        ScoreStorage::history_capacity(&env)
+    }
+
+    /// `set_score` that only writes if the stored score is still `expected`
+    /// (`None`: no score stored yet), for pipelines that read, compute and
+    /// write back. A mismatch returns `Conflict` and writes nothing; every
+    /// other failure aborts with its own code, so `Conflict` is the only
+    /// error returned.
+    pub fn compare_and_set_score(
+        env: Env,
+        account_id: Address,
+        expected: Option<u32>,
+        new: u32,
+    ) -> Result<(), StateError> {
+        let admin = Self::stored_admin(&env).unwrap_or_else(|e| panic_with_error!(&env, e));
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "compare_and_set_score",
+            vec![&env, account_id.into_val(&env), expected.into_val(&env), new.into_val(&env)],
+        )
+        .unwrap_or_else(|e| panic_with_error!(&env, e));
+        Self::require_account_age(&env, &account_id);
+
+        let current = ScoreStorage::get_score(&env, &account_id).ok();
+        if current != expected {
+            return Err(StateError::Conflict);
+        }
+        Self::write_score(&env, account_id, new).unwrap_or_else(|e| panic_with_error!(&env, e));
         Ok(())
     }
 
@@ -454,6 +1016,7 @@ impl CreditScoreContract {
         accounts: Vec<Address>,
         pair: TokenPair,
     ) -> Result<Vec<u32>, ContractError> {
+        require_within_batch_limit(&env, accounts.len());
         let _timer = PerformanceMonitor::start_timer(&env, &Symbol::new(&env, "batch_calc"));
         
         let trading_data = Self::fetch_trading_data(&env, &pair)?;
@@ -470,7 +1033,7 @@ impl CreditScoreContract {
             scores.push_back(adjusted as u32);
         }
         
-        let _duration = PerformanceMonitor::end_timer(&env, &Symbol::new(&env, "batch_calc"));
+        Self::end_scoring_timer(&env, "batch_calc");
         
         Ok(scores)
     }
@@ -480,7 +1043,14 @@ impl CreditScoreContract {
         admin: Address,
         updates: Vec<common_utils::batch::ScoreUpdate>,
     ) -> Result<common_utils::batch::BatchResult, common_utils::batch::BatchError> {
-        batch::batch_update_scores(&env, &admin, updates)
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "batch_update_scores",
+            vec![&env, updates.into_val(&env)],
+        )
+        .map_err(|_| common_utils::batch::BatchError::InvalidOperation)?;
+        batch::batch_update_scores(&env, updates)
     }
 
     pub fn update_signal_weights(
@@ -488,7 +1058,12 @@ impl CreditScoreContract {
         admin: Address,
         weights: Vec<SignalWeight>,
     ) -> Result<(), ContractError> {
-        Self::require_admin(&env, &admin)?;
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "update_signal_weights",
+            vec![&env, weights.into_val(&env)],
+        )?;
         
         let total_weight: u32 = weights.iter().map(|w| w.weight).sum();
         if total_weight > 100 {
@@ -500,7 +1075,12 @@ impl CreditScoreContract {
     }
 
     pub fn invalidate_dex_cache(env: Env, admin: Address, pair: TokenPair) -> Result<(), ContractError> {
-        Self::require_admin(&env, &admin)?;
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "invalidate_dex_cache",
+            vec![&env, pair.into_val(&env)],
+        )?;
         
         let mut cache = DexDataCache::new(&env);
         cache.invalidate(&pair);
@@ -527,16 +1107,261 @@ impl CreditScoreContract {
         Ok(result)
     }
     
-    pub fn migrate_to_compressed(env: Env, admin: Address) -> Result<u64, ContractError> {
-        let stored_admin: Address = env
+    /// Paged variant of `get_score_history`.
+    /// `limit` is clamped to `pagination::MAX_PAGE_SIZE`.
+    pub fn get_score_history_page(env: Env, account_id: Address, offset: u32, limit: u32) -> ScoreHistoryPage {
+        let page = ScoreStorage::page(&env, &account_id, offset, limit);
+        
+        StorageTracker::record_operation(
+            &env, 
+            &Symbol::new(&env, "access"), 
+            &Symbol::new(&env, "history"), 
+            0, 
+            false
+        );
+        
+        ScoreHistoryPage {
+            items: page.items,
+            total: page.total,
+            has_more: page.has_more,
+        }
+    }
+    
+    /// At most `max_points` entries spread evenly across the whole history,
+    /// always including the first and last entry. Histories no longer than
+    /// `max_points` are returned whole; a single point returns the latest entry.
+    pub fn get_score_history_sampled(env: Env, account_id: Address, max_points: u32) -> Vec<ScoreData> {
+        let total = ScoreStorage::len(&env, &account_id);
+        if total <= max_points {
+            return ScoreStorage::range(&env, &account_id, 0, total);
+        }
+
+        // Read only the sampled entries
+        let mut sampled = Vec::new(&env);
+        let mut take = |index: u32| sampled.append(&ScoreStorage::range(&env, &account_id, index, index + 1));
+        if max_points == 1 {
+            take(total - 1);
+        } else if max_points > 1 {
+            let last = (total - 1) as u64;
+            let steps = (max_points - 1) as u64;
+            for i in 0..max_points as u64 {
+                take((i * last / steps) as u32);
+            }
+        }
+
+        StorageTracker::record_operation(
+            &env, 
+            &Symbol::new(&env, "access"), 
+            &Symbol::new(&env, "history"), 
+            0, 
+            false
+        );
+
+        sampled
+    }
+    
+    /// Resolve dependencies (currently the score NFT) through a `ServiceRegistry`.
+    pub fn set_service_registry(env: Env, admin: Address, registry: Address) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_service_registry",
+            vec![&env, registry.into_val(&env)],
+        )?;
+        env.storage().instance().set(&DataKey::ServiceRegistry, &registry);
+        Ok(())
+    }
+
+    /// Partner credit-score contract whose `get_score` answers when this
+    /// contract has no score for an account; `None` turns the fallback off.
+    pub fn set_fallback_contract(env: Env, admin: Address, fallback: Option<Address>) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_fallback_contract",
+            vec![&env, fallback.into_val(&env)],
+        )?;
+        if fallback.as_ref() == Some(&env.current_contract_address()) {
+            return Err(ContractError::InvalidInput);
+        }
+        let mut config = Self::load_config(&env);
+        config.fallback_contract = fallback;
+        Self::save_config(&env, &config);
+        Ok(())
+    }
+
+    pub fn get_fallback_contract(env: Env) -> Option<Address> {
+        Self::load_config(&env).fallback_contract
+    }
+
+    /// Require accounts to have been first seen at least `secs` ago before
+    /// `set_score`, `compare_and_set_score` or `calculate_score_with_dex`
+    /// accept them. 0 disables the check.
+    pub fn set_min_account_age(env: Env, admin: Address, secs: u64) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_min_account_age",
+            vec![&env, secs.into_val(&env)],
+        )?;
+        let mut config = Self::load_config(&env);
+        config.min_account_age = secs;
+        Self::save_config(&env, &config);
+        Ok(())
+    }
+
+    /// Record `account` as seen, starting its age for `set_min_account_age`.
+    /// Later calls keep the original timestamp.
+    pub fn register_account(env: Env, account: Address) -> u64 {
+        account.require_auth();
+        Self::record_first_seen(&env, &account)
+    }
+
+    /// When `account` first interacted with this contract, if it has
+    pub fn get_first_seen(env: Env, account: Address) -> Option<u64> {
+        env.storage().persistent().get(&DataKey::FirstSeen(account))
+    }
+
+    /// Choose whether `sync_score_nft` fails (strict, the default) or skips
+    /// the NFT update when this contract is not allowed to make it
+    pub fn set_nft_mint_required(env: Env, admin: Address, required: bool) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_nft_mint_required",
+            vec![&env, required.into_val(&env)],
+        )?;
+        let mut config = Self::load_config(&env);
+        config.nft_mint_required = required;
+        Self::save_config(&env, &config);
+        Ok(())
+    }
+
+    /// Push `account`'s current score onto its credit-score NFT. The NFT
+    /// contract is looked up as `score_nft` in the service registry, and this
+    /// contract must be one of its minters or its admin. If it is neither,
+    /// strict mode returns `Unauthorized` and lenient mode emits
+    /// `mint_skipped` and leaves the NFT as it was.
+    pub fn sync_score_nft(env: Env, admin: Address, account: Address, token_id: u64) -> Result<u32, ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "sync_score_nft",
+            vec![&env, account.into_val(&env), token_id.into_val(&env)],
+        )?;
+
+        let registry: Address = env
             .storage()
             .instance()
-            .get(&DataKey::Admin)
+            .get(&DataKey::ServiceRegistry)
             .ok_or(ContractError::NotInitialized)?;
-        
-        if stored_admin != admin {
-            return Err(ContractError::Unauthorized);
+        let nft = resolve_service(&env, &registry, SERVICE_SCORE_NFT).ok_or(ContractError::NotFound)?;
+        let score = ScoreStorage::get_score(&env, &account)?;
+
+        if !Self::may_update_nft(&env, &nft) {
+            if Self::load_config(&env).nft_mint_required {
+                return Err(ContractError::Unauthorized);
+            }
+            env.events().publish(
+                (Symbol::new(&env, "mint_skipped"),),
+                (account, token_id, score),
+            );
+            return Ok(score);
+        }
+
+        env.invoke_contract::<()>(
+            &nft,
+            &Symbol::new(&env, "update_credit_score"),
+            soroban_sdk::vec![
+                &env,
+                env.current_contract_address().into_val(&env),
+                token_id.into_val(&env),
+                score.into_val(&env),
+            ],
+        );
+        Ok(score)
+    }
+
+    /// Accounts that have a stored score, in the order they were first scored
+    pub fn get_score_index(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::ScoreIndex)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Number of score writes since initialization (carried across migrations)
+    pub fn get_total_scores(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalScores)
+            .unwrap_or(0)
+    }
+
+    pub fn get_model_version(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::ModelVersion)
+            .unwrap_or(SCORING_MODEL_VERSION)
+    }
+
+    /// Page through the score index for migration. Each entry carries the
+    /// account's latest score and when it was written; indexed accounts
+    /// without a score are skipped. Continue from `next_offset`.
+    /// `limit` is clamped to `pagination::MAX_PAGE_SIZE`.
+    pub fn export_state_chunk(env: Env, offset: u32, limit: u32) -> ScoreExportChunk {
+        let index = Self::get_score_index(env.clone());
+        let page = pagination::paginate(&env, &index, offset, limit);
+
+        let mut entries = Vec::new(&env);
+        for address in page.items.iter() {
+            if let Some(entry) = Self::export_entry(&env, address) {
+                entries.push_back(entry);
+            }
+        }
+
+        ScoreExportChunk {
+            entries,
+            total: page.total,
+            has_more: page.has_more,
+            next_offset: offset.min(page.total) + page.items.len(),
+        }
+    }
+
+    /// SHA-256 over the XDR of every entry `export_state_chunk` would
+    /// return, in index order. Compare against `get_imported_score_digest`
+    /// once every chunk is imported.
+    pub fn get_score_digest(env: Env) -> BytesN<32> {
+        Self::score_export_summary(&env).1
+    }
+
+    /// Score digest of the source contract, set by `import_state`
+    pub fn get_imported_score_digest(env: Env) -> Option<BytesN<32>> {
+        env.storage().instance().get(&DataKey::ImportedScoreDigest)
+    }
+
+    /// Write one exported chunk into a contract set up by `import_state`.
+    /// Each entry counts as a score write. Returns the number of accounts
+    /// imported.
+    pub fn import_state_chunk(env: Env, admin: Address, entries: Vec<ScoreData>) -> Result<u32, ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "import_state_chunk",
+            vec![&env, entries.into_val(&env)],
+        )?;
+        require_within_batch_limit(&env, entries.len());
+
+        for entry in entries.iter() {
+            ScoreStorage::store_score(&env, &entry.address, entry.score, entry.timestamp)?;
+            Self::index_score(&env, &entry.address);
         }
+
+        Ok(entries.len())
+    }
+    
+    pub fn migrate_to_compressed(env: Env, admin: Address) -> Result<u64, ContractError> {
+        Self::require_admin_action(&env, &admin, "migrate_to_compressed", vec![&env])?;
         
         if env.storage().instance().has(&DataKey::MigrationState) {
             return Err(ContractError::InvalidState);
@@ -575,17 +1400,320 @@ impl CreditScoreContract {
         Ok(report)
     }
 
+    /// Storage totals across all recorded operations
+    pub fn get_storage_summary(env: Env) -> StorageSummary {
+        StorageTracker::summary(&env)
+    }
+
+    /// Per-operation timing stats and configured budgets
+    pub fn get_perf_report(env: Env) -> Vec<PerfReportEntry> {
+        PerformanceMonitor::get_report(&env)
+    }
+
+    /// Emit `perf_budget_exceeded` whenever `op` takes longer than `max_allowed`
+    pub fn set_perf_budget(env: Env, admin: Address, op: Symbol, max_allowed: u64) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_perf_budget",
+            vec![&env, op.into_val(&env), max_allowed.into_val(&env)],
+        )?;
+        PerformanceMonitor::set_perf_budget(&env, &op, max_allowed);
+        Ok(())
+    }
+
+    /// Opt in to `calculate_score_with_dex` and `batch_calculate_scores`
+    /// failing with `StateError::BudgetExceeded` when they overrun their
+    /// budget, instead of only emitting an event
+    pub fn set_strict_perf_budgets(env: Env, admin: Address, enabled: bool) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_strict_perf_budgets",
+            vec![&env, enabled.into_val(&env)],
+        )?;
+        PerformanceMonitor::set_strict_budgets(&env, enabled);
+        Ok(())
+    }
+
+    /// The newest `limit` admin actions, oldest first
+    pub fn get_admin_log(env: Env, limit: u32) -> Vec<AdminAction> {
+        admin_log::get_admin_log(&env, limit)
+    }
+
     pub fn get_dex_cache_stats(env: Env) -> common_utils::dex::cache::CacheStats {
         let cache = DexDataCache::new(&env);
         cache.get_stats()
     }
 
-    fn require_admin(env: &Env, admin: &Address) -> Result<(), ContractError> {
-        let stored_admin: Address = env
-            .storage()
+    fn validate_inputs(inputs: &ScoreInputs) -> Result<(), ValidationDetail> {
+        validation::validate_factors(
+            inputs.payment_history,
+            inputs.utilization,
+            inputs.length,
+            inputs.mix,
+            inputs.new_inquiries,
+        )
+    }
+
+    fn parse_factor_string(factors: &String) -> Result<ScoreInputs, ValidationDetail> {
+        let len = factors.len() as usize;
+        if len > scoring::MAX_FACTORS_LEN {
+            return Err(ValidationDetail::new(validation::FIELD_FACTORS, ValidationError::NotAuthorized));
+        }
+        let mut buf = [0u8; scoring::MAX_FACTORS_LEN];
+        factors.copy_into_slice(&mut buf[..len]);
+
+        scoring::parse_factors(&buf[..len])
+            .map_err(|field| ValidationDetail::new(field, ValidationError::NotAuthorized))
+    }
+
+    /// Validation, weighting and penalties shared by every scoring entry point.
+    /// Pure: reads and writes no storage.
+    fn compute_score(inputs: &ScoreInputs) -> Result<u32, ValidationError> {
+        Self::validate_inputs(inputs).map_err(|_| ValidationError::NotAuthorized)?;
+
+        let weighted_avg = scoring::calculate_weighted_average(
+            inputs.payment_history,
+            inputs.utilization,
+            inputs.length,
+            inputs.mix,
+            inputs.new_inquiries,
+        );
+
+        let penalties = penalties::calculate_total_penalties(
+            inputs.days_late,
+            inputs.defaulted,
+            inputs.charged_off,
+            inputs.bankrupt_years,
+        );
+
+        Ok(scoring::calculate_final_score(weighted_avg, penalties))
+    }
+
+    /// Stored config, falling back to the pre-aggregate keys for contracts
+    /// initialized before `DataKey::Config` existed.
+    fn load_config(env: &Env) -> CreditConfig {
+        let mut config = match env.storage().instance().get::<_, Val>(&DataKey::Config) {
+            Some(raw) => CreditConfig::try_from_val(env, &raw)
+                .unwrap_or_else(|_| Self::upgrade_config(env, &raw)),
+            None => Self::default_config(env),
+        };
+        // Owned by other modules; the stored copies are ignored
+        config.network_load = RateLimiter::get_network_load(env);
+        config.compression_type = CompressionManager::selected_type(env, CompressionType::RunLength);
+        config
+    }
+
+    /// Settings before `DataKey::Config` is first written: the keys that
+    /// predate it where they exist, defaults otherwise.
+    fn default_config(env: &Env) -> CreditConfig {
+        let storage = env.storage().instance();
+        CreditConfig {
+            dex_config: storage
+                .get(&DataKey::DexConfig)
+                .unwrap_or_else(|| DexConfig::default()),
+            dex_enabled: storage.get(&DataKey::DexEnabled).unwrap_or(false),
+            network_load: RateLimiter::get_network_load(env),
+            compression_type: CompressionManager::selected_type(env, CompressionType::RunLength),
+            nft_mint_required: true,
+            min_account_age: 0,
+            fallback_contract: None,
+            min_score_delta: 0,
+            read_audit: false,
+        }
+    }
+
+    /// Decode a config stored before some of its fields existed. Fields
+    /// present in `raw` are kept and the rest take their defaults.
+    fn upgrade_config(env: &Env, raw: &Val) -> CreditConfig {
+        let mut config = Self::default_config(env);
+        let Ok(fields) = Map::<Symbol, Val>::try_from_val(env, raw) else {
+            return config;
+        };
+        macro_rules! carry {
+            ($($field:ident),* $(,)?) => {$(
+                if let Some(value) = fields.get(Symbol::new(env, stringify!($field))) {
+                    if let Ok(value) = TryFromVal::try_from_val(env, &value) {
+                        config.$field = value;
+                    }
+                }
+            )*};
+        }
+        carry!(
+            dex_config,
+            dex_enabled,
+            network_load,
+            compression_type,
+            nft_mint_required,
+            min_account_age,
+            fallback_contract,
+            min_score_delta,
+            read_audit,
+        );
+        config
+    }
+
+    fn save_config(env: &Env, config: &CreditConfig) {
+        env.storage().instance().set(&DataKey::Config, config);
+    }
+
+    /// Score for `account` from the fallback contract, cached for
+    /// `FALLBACK_CACHE_SECS`. A missing fallback, a failed call or a call
+    /// returning anything but a score is a miss.
+    fn fallback_score(env: &Env, account: &Address) -> Option<u32> {
+        let fallback = Self::load_config(env).fallback_contract?;
+        let key = DataKey::FallbackScore(account.clone());
+        let now = env.ledger().timestamp();
+        if let Some(cached) = env.storage().temporary().get::<_, FallbackScore>(&key) {
+            if now.saturating_sub(cached.cached_at) <= FALLBACK_CACHE_SECS {
+                return Some(cached.score);
+            }
+        }
+
+        let result = env.try_invoke_contract::<u32, soroban_sdk::Error>(
+            &fallback,
+            &Symbol::new(env, "get_score"),
+            soroban_sdk::vec![env, account.into_val(env)],
+        );
+        let Ok(Ok(score)) = result else {
+            return None;
+        };
+        env.storage()
+            .temporary()
+            .set(&key, &FallbackScore { score, cached_at: now });
+        Some(score)
+    }
+
+    /// An indexed account's latest score as `export_state_chunk` carries
+    /// it, `None` if it has none
+    fn export_entry(env: &Env, address: Address) -> Option<ScoreData> {
+        let score = ScoreStorage::get_score(env, &address).ok()?;
+        let len = ScoreStorage::len(env, &address);
+        let timestamp = ScoreStorage::range(env, &address, len.saturating_sub(1), len)
+            .last()
+            .map(|latest| latest.timestamp)
+            .unwrap_or(0);
+        Some(ScoreData { score, timestamp, address })
+    }
+
+    /// How many entries a full chunked export carries, and their digest
+    fn score_export_summary(env: &Env) -> (u32, BytesN<32>) {
+        let mut count = 0u32;
+        let mut buf = Bytes::new(env);
+        for address in Self::get_score_index(env.clone()).iter() {
+            if let Some(entry) = Self::export_entry(env, address) {
+                buf.append(&entry.to_xdr(env));
+                count += 1;
+            }
+        }
+        (count, env.crypto().sha256(&buf).into())
+    }
+
+    /// Count the write and add `account` to the score index on first score.
+    fn index_score(env: &Env, account: &Address) {
+        // Saturates, see `common_utils::math`
+        let total = Self::get_total_scores(env.clone()).saturating_add(1);
+        env.storage().instance().set(&DataKey::TotalScores, &total);
+
+        let mut index = Self::get_score_index(env.clone());
+        if !index.contains(account) {
+            index.push_back(account.clone());
+            env.storage().instance().set(&DataKey::ScoreIndex, &index);
+        }
+    }
+
+    /// Whether `nft` would accept a score update from this contract. A call
+    /// that fails counts as no, so a misconfigured NFT cannot trap the caller.
+    fn may_update_nft(env: &Env, nft: &Address) -> bool {
+        let this = env.current_contract_address();
+        let is_minter = env.try_invoke_contract::<bool, soroban_sdk::Error>(
+            nft,
+            &Symbol::new(env, "is_minter"),
+            soroban_sdk::vec![env, this.into_val(env)],
+        );
+        if let Ok(Ok(true)) = is_minter {
+            return true;
+        }
+        let admin = env.try_invoke_contract::<Address, soroban_sdk::Error>(
+            nft,
+            &Symbol::new(env, "get_admin"),
+            soroban_sdk::vec![env],
+        );
+        matches!(admin, Ok(Ok(admin)) if admin == this)
+    }
+
+    /// Store, index and announce a score write
+    fn write_score(env: &Env, account_id: Address, score: u32) -> Result<(), ContractError> {
+        ScoreStorage::store_score(env, &account_id, score, env.ledger().timestamp())?;
+        Self::index_score(env, &account_id);
+        
+        StorageTracker::record_operation(
+            env, 
+            &Symbol::new(env, "store"), 
+            &Symbol::new(env, "score"), 
+            44, 
+            true
+        );
+
+        // `score_set` postdates the envelope, so it has no legacy shape
+        event_schema::emit(
+            env,
+            (Symbol::new(env, "score_set"), SCORE_EVENT_VERSION, account_id),
+            (score, env.ledger().timestamp()),
+        );
+        Ok(())
+    }
+
+    fn record_first_seen(env: &Env, account: &Address) -> u64 {
+        let key = DataKey::FirstSeen(account.clone());
+        if let Some(first_seen) = env.storage().persistent().get(&key) {
+            return first_seen;
+        }
+        let now = env.ledger().timestamp();
+        env.storage().persistent().set(&key, &now);
+        now
+    }
+
+    /// Abort with `ValidationError::AccountTooNew` whatever the entrypoint's
+    /// error type. A rejected call rolls its writes back, so an account's
+    /// first-seen time has to come from an earlier successful interaction;
+    /// unseen accounts count as brand new.
+    fn require_account_age(env: &Env, account: &Address) {
+        if !Self::account_old_enough(env, account) {
+            panic_with_error!(env, ValidationError::AccountTooNew);
+        }
+        Self::record_first_seen(env, account);
+    }
+
+    /// Whether `account` was first seen at least `min_account_age` ago
+    fn account_old_enough(env: &Env, account: &Address) -> bool {
+        let min_age = Self::load_config(env).min_account_age;
+        if min_age == 0 {
+            return true;
+        }
+        let now = env.ledger().timestamp();
+        let first_seen = Self::get_first_seen(env.clone(), account.clone()).unwrap_or(now);
+        now.saturating_sub(first_seen) >= min_age
+    }
+
+    /// Stop a scoring entrypoint's timer. In strict budget mode an overrun
+    /// aborts with `BudgetExceeded`, whatever the entrypoint's error type.
+    fn end_scoring_timer(env: &Env, operation: &str) {
+        if let Err(e) = PerformanceMonitor::end_timer_checked(env, &Symbol::new(env, operation)) {
+            panic_with_error!(env, e);
+        }
+    }
+
+    fn stored_admin(env: &Env) -> Result<Address, ContractError> {
+        env.storage()
             .instance()
             .get(&DataKey::Admin)
-            .ok_or(ContractError::NotInitialized)?;
+            .ok_or(ContractError::NotInitialized)
+    }
+
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), ContractError> {
+        let stored_admin = Self::stored_admin(env)?;
         
         if stored_admin != *admin {
             return Err(ContractError::Unauthorized);
@@ -594,6 +1722,56 @@ impl CreditScoreContract {
         Ok(())
     }
 
+    /// `require_admin`, then record `action` and its parameters in the
+    /// admin action log. Used at the start of every admin-gated entrypoint.
+    fn require_admin_action(
+        env: &Env,
+        admin: &Address,
+        action: &str,
+        params: Vec<Val>,
+    ) -> Result<(), ContractError> {
+        Self::require_admin(env, admin)?;
+        admin_log::record_admin_action(env, Symbol::new(env, action), admin, &params);
+        Ok(())
+    }
+
+    /// Admin check failures for entrypoints that report `AuthorizationError`
+    fn authorization_error(err: ContractError) -> AuthorizationError {
+        match err {
+            ContractError::NotInitialized => AuthorizationError::NotInitialized,
+            _ => AuthorizationError::NotAuthorized,
+        }
+    }
+
+    /// Admin check failures for entrypoints that report `CommonError`
+    fn common_error(err: ContractError) -> CommonError {
+        match err {
+            ContractError::NotInitialized => CommonError::NotInitialized,
+            _ => CommonError::NotAuthorized,
+        }
+    }
+
+    /// Default limit of a rate-limited action, as given to its macro, and
+    /// whether it adapts to trust tier and network load
+    fn rate_limit_default(env: &Env, action: &Symbol) -> Option<(RateLimitConfig, bool)> {
+        let (max_requests, strategy, scope, adaptive) = if *action == Symbol::new(env, "get_score") {
+            (GET_SCORE_RATE_MAX, RateLimitStrategy::TokenBucket, RateLimitScope::PerUser, true)
+        } else if *action == Symbol::new(env, "upd_factor") {
+            (UPDATE_FACTORS_RATE_MAX, RateLimitStrategy::FixedWindow, RateLimitScope::Global, false)
+        } else if *action == Symbol::new(env, "set_score") {
+            (SET_SCORE_RATE_MAX, RateLimitStrategy::SlidingWindow, RateLimitScope::PerUser, false)
+        } else {
+            return None;
+        };
+        let config = RateLimitConfig {
+            max_requests,
+            window_seconds: RATE_LIMIT_WINDOW,
+            strategy,
+            scope,
+        };
+        Some((config, adaptive))
+    }
+
     fn get_base_score(env: &Env, account_id: &Address) -> Result<u32, ContractError> {
         ScoreStorage::get_score(env, account_id)
             .map(|s| s)
@@ -659,19 +1837,124 @@ impl CreditScoreContract {
         Ok(metrics)
     }
     
+    /// Registry handles of every indexed account, for key-based migration
     fn get_all_score_addresses(env: &Env) -> Vec<Symbol> {
-        Vec::new(env)
+        let index: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::ScoreIndex)
+            .unwrap_or_else(|| Vec::new(env));
+        let mut handles = Vec::new(env);
+        for account in index.iter() {
+            handles.push_back(AddressRegistry::to_symbol(env, &account));
+        }
+        handles
     }
     
     fn cleanup_uncompressed_scores(env: &Env, addresses: &Vec<Symbol>) -> Result<(), ContractError> {
-        for address_symbol in addresses.iter() {
-            let old_key = DataKey::Score(Address::from_bytes(&Bytes::from_slice(env, address_symbol.to_string().as_bytes())));
-            env.storage().persistent().remove(&old_key);
+        for handle in addresses.iter() {
+            let account = AddressRegistry::from_symbol(env, &handle).ok_or(ContractError::NotFound)?;
+            env.storage().persistent().remove(&DataKey::Score(account));
         }
         Ok(())
     }
 }
 
+/// The header carries the admin, counters and model version; per-account
+/// scores are moved separately with `export_state_chunk` /
+/// `import_state_chunk`.
+#[contractimpl]
+impl DataMigration for CreditScoreContract {
+    /// `[version, admin, total_scores, model_version, exported_accounts,
+    /// score_digest, exported_at]`. `exported_accounts` and `score_digest`
+    /// describe the entries `export_state_chunk` will carry. `exported_at`
+    /// is set only when exporting while paused or terminated;
+    /// `import_state` refuses `None`.
+    fn export_state(env: Env) -> Result<Vec<Val>, CommonError> {
+        let lifecycle = Self::get_state(&env);
+        let exported_at = migration::guard_export(&env, lifecycle.is_paused() || lifecycle.is_terminated())?;
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(CommonError::NotInitialized)?;
+        let (exported, digest) = Self::score_export_summary(&env);
+        let mut state = Vec::new(&env);
+        state.push_back(STATE_EXPORT_VERSION.into_val(&env));
+        state.push_back(admin.to_val());
+        state.push_back(Self::get_total_scores(env.clone()).into_val(&env));
+        state.push_back(Self::get_model_version(env.clone()).into_val(&env));
+        state.push_back(exported.into_val(&env));
+        state.push_back(digest.into_val(&env));
+        state.push_back(exported_at.into_val(&env));
+        Ok(state)
+    }
+
+    /// Total scores start at the source's count less the entries still to
+    /// come, as each imported chunk entry counts as a write; once every
+    /// chunk is in the count matches the source. Only with the signature of
+    /// the admin the payload installs, so a fresh deployment cannot be
+    /// claimed by someone else's payload. Version 3 payloads must come from
+    /// a frozen source; version 2 payloads, which carry no marker, and
+    /// version 1 payloads, which carry no digest either, are still accepted.
+    fn import_state(env: Env, data: Vec<Val>) -> Result<(), CommonError> {
+        let version = data
+            .get(0)
+            .and_then(|v| u32::try_from_val(&env, &v).ok())
+            .ok_or(CommonError::InvalidFormat)?;
+        let expected_len = match version {
+            1 => STATE_EXPORT_LEN_V1,
+            2 => STATE_EXPORT_LEN_V2,
+            STATE_EXPORT_VERSION => STATE_EXPORT_LEN,
+            _ => return Err(CommonError::InvalidFormat),
+        };
+        if data.len() != expected_len {
+            return Err(CommonError::InvalidFormat);
+        }
+        if !Self::get_state(&env).is_uninitialized() {
+            return Err(CommonError::AlreadyInitialized);
+        }
+
+        let admin = Address::try_from_val(&env, &data.get(1).unwrap())
+            .map_err(|_| CommonError::InvalidFormat)?;
+        admin.require_auth();
+        let total_scores = u64::try_from_val(&env, &data.get(2).unwrap())
+            .map_err(|_| CommonError::InvalidFormat)?;
+        let model_version = u32::try_from_val(&env, &data.get(3).unwrap())
+            .map_err(|_| CommonError::InvalidFormat)?;
+        let pending = u32::try_from_val(&env, &data.get(4).unwrap())
+            .map_err(|_| CommonError::InvalidFormat)?;
+        let digest = match version {
+            1 => None,
+            _ => Some(
+                BytesN::<32>::try_from_val(&env, &data.get(5).unwrap())
+                    .map_err(|_| CommonError::InvalidFormat)?,
+            ),
+        };
+        if version == STATE_EXPORT_VERSION {
+            let exported_at = Option::<u64>::try_from_val(&env, &data.get(6).unwrap())
+                .map_err(|_| CommonError::InvalidFormat)?;
+            migration::record_source_export(&env, exported_at)?;
+        }
+        if let Some(digest) = digest {
+            env.storage().instance().set(&DataKey::ImportedScoreDigest, &digest);
+        }
+        let total_scores = total_scores.saturating_sub(pending as u64);
+
+        Self::set_state(
+            &env,
+            State::Active(CreditScoreState {
+                admin: admin.clone(),
+                total_scores,
+            }),
+        );
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::TotalScores, &total_scores);
+        env.storage().instance().set(&DataKey::ModelVersion, &model_version);
+        Ok(())
+    }
+}
+
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct ScoreWithSignals {
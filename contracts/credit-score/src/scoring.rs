@@ -1,6 +1,10 @@
 #![no_std]
-use soroban_sdk::Env;
-use crate::validation::{MIN_SCORE, MAX_SCORE};
+use soroban_sdk::{Env, Symbol};
+use crate::validation::{
+    MIN_SCORE, MAX_SCORE, FIELD_BANKRUPT_YEARS, FIELD_CHARGED_OFF, FIELD_DAYS_LATE,
+    FIELD_DEFAULTED, FIELD_FACTORS, FIELD_LENGTH, FIELD_MIX, FIELD_NEW_INQUIRIES,
+    FIELD_PAYMENT_HISTORY, FIELD_UTILIZATION,
+};
 
 pub const WEIGHT_PAYMENT_HISTORY: u32 = 35;
 pub const WEIGHT_UTILIZATION: u32 = 30;
@@ -51,3 +55,83 @@ pub fn calculate_final_score(
         score
     }
 }
+
+/// Longest factor string accepted by `parse_factors`
+pub const MAX_FACTORS_LEN: usize = 64;
+
+/// Inputs to one score computation
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScoreInputs {
+    pub payment_history: u32,
+    pub utilization: u32,
+    pub length: u32,
+    pub mix: u32,
+    pub new_inquiries: u32,
+    pub days_late: u32,
+    pub defaulted: bool,
+    pub charged_off: bool,
+    pub bankrupt_years: u32,
+}
+
+/// Fields of a factor string, in order
+const FACTOR_FIELDS: [Symbol; 9] = [
+    FIELD_PAYMENT_HISTORY,
+    FIELD_UTILIZATION,
+    FIELD_LENGTH,
+    FIELD_MIX,
+    FIELD_NEW_INQUIRIES,
+    FIELD_DAYS_LATE,
+    FIELD_DEFAULTED,
+    FIELD_CHARGED_OFF,
+    FIELD_BANKRUPT_YEARS,
+];
+
+/// Parse `payment_history,utilization,length,mix,new_inquiries,days_late,defaulted,charged_off,bankrupt_years`;
+/// the three flags are `0` or `1`. On failure, returns the field that could
+/// not be parsed, or `FIELD_FACTORS` when the field count is wrong.
+pub fn parse_factors(input: &[u8]) -> Result<ScoreInputs, Symbol> {
+    let mut values = [0u32; 9];
+    let mut count = 0;
+    for field in input.split(|b| *b == b',') {
+        if count == values.len() {
+            return Err(FIELD_FACTORS);
+        }
+        let name = FACTOR_FIELDS[count].clone();
+        if field.is_empty() {
+            return Err(name);
+        }
+        let mut value: u32 = 0;
+        for digit in field {
+            if !digit.is_ascii_digit() {
+                return Err(name);
+            }
+            value = value
+                .checked_mul(10)
+                .and_then(|v| v.checked_add((digit - b'0') as u32))
+                .ok_or(name.clone())?;
+        }
+        values[count] = value;
+        count += 1;
+    }
+    if count != values.len() {
+        return Err(FIELD_FACTORS);
+    }
+    if values[6] > 1 {
+        return Err(FIELD_DEFAULTED);
+    }
+    if values[7] > 1 {
+        return Err(FIELD_CHARGED_OFF);
+    }
+
+    Ok(ScoreInputs {
+        payment_history: values[0],
+        utilization: values[1],
+        length: values[2],
+        mix: values[3],
+        new_inquiries: values[4],
+        days_late: values[5],
+        defaulted: values[6] == 1,
+        charged_off: values[7] == 1,
+        bankrupt_years: values[8],
+    })
+}
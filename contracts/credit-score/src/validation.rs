@@ -1,5 +1,5 @@
 #![no_std]
-use soroban_sdk::{Env, ValidationError};
+use soroban_sdk::{contracttype, symbol_short, Env, Symbol, ValidationError};
 
 pub const MIN_SCORE: u32 = 300;
 pub const MAX_SCORE: u32 = 850;
@@ -12,15 +12,55 @@ pub fn validate_score_range(score: u32) -> Result<(), ValidationError> {
     Ok(())
 }
 
+pub const MAX_FACTOR: u32 = 100;
+
+/// Names reported in `ValidationDetail::field` for each score input
+pub const FIELD_PAYMENT_HISTORY: Symbol = symbol_short!("pay_hist");
+pub const FIELD_UTILIZATION: Symbol = symbol_short!("util");
+pub const FIELD_LENGTH: Symbol = symbol_short!("length");
+pub const FIELD_MIX: Symbol = symbol_short!("mix");
+pub const FIELD_NEW_INQUIRIES: Symbol = symbol_short!("inquiries");
+pub const FIELD_DAYS_LATE: Symbol = symbol_short!("days_late");
+pub const FIELD_DEFAULTED: Symbol = symbol_short!("defaulted");
+pub const FIELD_CHARGED_OFF: Symbol = symbol_short!("chrg_off");
+pub const FIELD_BANKRUPT_YEARS: Symbol = symbol_short!("bankrupt");
+/// The factor string as a whole: too long, or the wrong number of fields
+pub const FIELD_FACTORS: Symbol = symbol_short!("factors");
+
+/// Which input a validation failed on, alongside the `ValidationError`
+/// discriminant the entry point returns for it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ValidationDetail {
+    pub field: Symbol,
+    pub code: u32,
+}
+
+impl ValidationDetail {
+    pub fn new(field: Symbol, error: ValidationError) -> Self {
+        Self { field, code: error as u32 }
+    }
+}
+
+/// The first factor above `MAX_FACTOR`, in argument order.
 pub fn validate_factors(
     payment_history: u32,
     utilization: u32,
     length: u32,
     mix: u32,
     new_inquiries: u32,
-) -> Result<(), ValidationError> {
-    if payment_history > 100 || utilization > 100 || length > 100 || mix > 100 || new_inquiries > 100 {
-        return Err(ValidationError::NotAuthorized);
+) -> Result<(), ValidationDetail> {
+    let factors = [
+        (FIELD_PAYMENT_HISTORY, payment_history),
+        (FIELD_UTILIZATION, utilization),
+        (FIELD_LENGTH, length),
+        (FIELD_MIX, mix),
+        (FIELD_NEW_INQUIRIES, new_inquiries),
+    ];
+    for (field, value) in factors {
+        if value > MAX_FACTOR {
+            return Err(ValidationDetail::new(field, ValidationError::NotAuthorized));
+        }
     }
     Ok(())
 }
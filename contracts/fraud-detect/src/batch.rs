@@ -1,32 +1,32 @@
 use soroban_sdk::{symbol_short, Address, Env, Vec};
 
 use common_utils::batch::{
-    BatchError, BatchExecutor, BatchOperation, BatchResult, BatchValidator, FraudFlag,
-    RollbackStrategy,
+    BatchError, BatchEventAccumulator, BatchExecutor, BatchOperation, BatchResult, BatchValidator,
+    FraudFlag, OperationStatus, RollbackStrategy,
 };
 
-use crate::{DataKey, FraudReport};
+use crate::{reset_index, stats, DataKey, FraudDetectContract, FraudReport};
 
 /// Batch flag multiple accounts as fraudulent atomically.
-/// Requires admin authorization.
+/// The caller checks admin authorization.
 pub fn batch_flag_fraud(
     env: &Env,
     admin: &Address,
     flags: Vec<FraudFlag>,
 ) -> Result<BatchResult, BatchError> {
-    admin.require_auth();
-
     let mut ops = Vec::new(env);
     for f in flags.iter() {
         ops.push_back(BatchOperation::FlagFraud(f));
     }
 
-    BatchValidator::validate(&ops)?;
+    BatchValidator::validate_for(env, &ops)?;
 
     let result = BatchExecutor::execute(env, ops, RollbackStrategy::AllOrNothing)?;
 
     if !result.rolled_back {
-        for flag in flags.iter() {
+        let mut events = BatchEventAccumulator::new(env, symbol_short!("fraud_flg"));
+        let dispute_window = FraudDetectContract::get_config(env.clone()).dispute_window;
+        for (flag, outcome) in flags.iter().zip(result.results.iter()) {
             let report = FraudReport {
                 score: flag.reason_code.min(100),
                 reporter: admin.clone(),
@@ -35,13 +35,27 @@ pub fn batch_flag_fraud(
 
             // Use a per-account key derived from the address
             let key = DataKey::FlaggedAccount(flag.account_id.clone());
+            reset_index::track_flag(env, &flag.account_id);
+            if !env.storage().instance().has(&key) {
+                stats::record_flag(env);
+                // A re-flag keeps the window of the original flag
+                if dispute_window > 0 {
+                    env.storage().instance().set(
+                        &DataKey::PendingFlag(flag.account_id.clone()),
+                        &env.ledger().timestamp().saturating_add(dispute_window),
+                    );
+                }
+            }
             env.storage().instance().set(&key, &report);
 
-            env.events().publish(
-                (symbol_short!("fraud_flg"), flag.account_id.clone()),
+            events.record(
+                env,
+                flag.account_id.clone(),
+                outcome.status == OperationStatus::Success,
                 (flag.reason_code, env.ledger().timestamp()),
             );
         }
+        events.finish(env);
     }
 
     Ok(result)
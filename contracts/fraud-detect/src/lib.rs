@@ -7,8 +7,8 @@
 use common_utils::authorization::{
     CachedAuth, IAuthorizable, Permission, PermissionCache, RoleBasedAuth,
 };
-use common_utils::compression::FraudReportCompressor;
-use common_utils::data_migration::{CompressionType, DataMigrationManager, MigrationConfig};
+use common_utils::compression::{CompressionType, FraudReportCompressor};
+use common_utils::data_migration::{DataMigrationManager, MigrationConfig};
 use common_utils::dex::cache::DexDataCache;
 use common_utils::dex::fraud_indicators::{
     DetectionThresholds, FraudIndicator, IndicatorType, OverallRisk, PatternDetector, RiskLevel,
@@ -17,24 +17,49 @@ use common_utils::dex::fraud_indicators::{
 use common_utils::dex::liquidity::LiquidityMetrics;
 use common_utils::dex::trading_data::{TradingData, TradingVolume};
 use common_utils::dex::{DexAdapter, DexConfig, StellarDexAdapter, TokenPair};
+use common_utils::batch::require_within_batch_limit;
 use common_utils::error::CommonError;
-use common_utils::error::{AuthorizationError, ContractError, StateError};
-use common_utils::migration::DataMigration;
-use common_utils::rate_limit::{RateLimiter, TrustTier};
-use common_utils::storage_monitoring::{PerformanceMonitor, StorageTracker};
+use common_utils::error::{AuthorizationError, ContractError, StateError, ValidationError};
+use common_utils::event_schema::{self, emit_versioned_for};
+use common_utils::admin_log::{self, AdminAction};
+use common_utils::feature_flags;
+use common_utils::fixed_point::{self, FixedPoint};
+use common_utils::health::{self, HealthReport};
+use common_utils::key_migration::{self, KeyMigration, KeyMigrationProgress};
+use common_utils::math;
+use common_utils::migration::{self, DataMigration};
+use common_utils::pagination::{self, Paginated};
+use common_utils::rate_limit::{
+    EffectiveLimit, RateLimitConfig, RateLimitScope, RateLimitStrategy, RateLimiter, TrustTier,
+};
+use common_utils::service_registry::{resolve_service, SERVICE_ACL};
+use common_utils::storage_monitoring::{PerformanceMonitor, StorageSummary, StorageTracker};
 use common_utils::storage_optimization::{CompressedReportStorage, DataSeparator, DataTemperature};
 use common_utils::{
-    auth, cached_auth, check_authorization, permission, rate_limit, rate_limit_adaptive,
+    auth, cached_auth, rate_limit, rate_limit_adaptive,
 };
-use common_utils::state_machine::{State, StateMachine, FraudDetectState};
+use common_utils::state_machine::{self, State, StateMachine, FraudDetectState};
 use common_utils::{state_guard, transition_to};
 use common_utils::compliance_log::{ComplianceLogger, ComplianceAction};
+use common_utils::crypto::{encode_message, MessageField};
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short, Address, Bytes, BytesN, Env, String, Symbol,
-    TryFromVal, Val, Vec,
+    contract, contractimpl, contracttype, panic_with_error, symbol_short, Address, Bytes, BytesN, Env,
+    Map, String, Symbol, xdr::ToXdr, IntoVal, TryFromVal, Val, Vec, vec,
 };
 
 mod batch;
+mod model_quorum;
+mod reporter_bitmap;
+mod reset_index;
+mod stake;
+mod stats;
+
+pub use model_quorum::ModelProposal;
+pub use stake::ReporterStake;
+pub use stats::GlobalFraudStats;
+
+/// Schema version of the `fraud_rpt` event payload.
+pub const REPORT_EVENT_VERSION: u32 = 1;
 
 #[derive(Clone)]
 #[contracttype]
@@ -51,6 +76,264 @@ pub enum DataKey {
     TradingPatternHistory(Symbol),
     ContractState,
     FlaggedAccount(Address),
+    Config,
+    /// Pre-bitmap reporter list, see `reporter_bitmap`
+    Reporters,
+    TotalReports,
+    AgentIndex,
+    ImportedReportDigest,
+    BreakerWindow,
+    ServiceRegistry,
+    ReporterStake(Address),
+    ModelAdmins,
+    ModelThreshold,
+    ModelProposal(BytesN<32>),
+    ActiveModel,
+    RegisteredAgent(Symbol),
+    RegisteredAgents,
+    GlobalStats,
+    FlaggedCount,
+    LastReport(Address, Symbol),
+    ReporterRemoval(Address),
+    ReportChainHead,
+    PendingFlag(Address),
+    ReporterWeight(Address),
+    LastAdminAction,
+    ExtraAcls,
+    ReporterExpiry(Address),
+    PrivateAgent(Symbol),
+    ModelUpdates,
+    ReportCommitment(Address, Symbol),
+    /// `CommitRound` of an agent's commit-reveal round
+    CommitRound(Symbol),
+    ReporterBits(u32),
+    ReporterWords,
+    GraceQueue(Address),
+    /// Sum of every `ReporterStake`
+    StakedTotal,
+    /// An upheld dispute of a reporter's reports on an agent, consumed by
+    /// `slash_reporter_stake`
+    UpheldDispute(Address, Symbol),
+    /// Disputes ever upheld against a reporter, for `get_reporter_reliability`
+    UpheldDisputeCount(Address),
+    /// Indexes kept for `reinitialize`, see `reset_index`
+    FlaggedAccounts,
+    ReportPairs,
+    WeightedReporters,
+    TieredUsers,
+    DisputedReporters,
+    /// Report that pushed an agent's score over `flag_threshold`
+    FlaggedAgent(Symbol),
+    /// End of the dispute window of an agent flag
+    PendingAgentFlag(Symbol),
+    FlaggedAgents,
+    QueuedReporters,
+    CommitPairs,
+    PrivateAgents,
+    RecordedReporters,
+}
+
+/// Length of the window `model_daily_cap` counts over
+pub const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Reason recorded by `remove_reporter` when the caller gives none
+pub const DEFAULT_REMOVAL_REASON: Symbol = symbol_short!("unspec");
+
+/// Reason recorded for reporters dropped by `sync_reporters_from_acl`
+pub const ACL_SYNC_REMOVAL_REASON: Symbol = symbol_short!("acl_sync");
+
+/// Reason recorded for reporters dropped by `prune_expired_reporters`
+pub const EXPIRY_REMOVAL_REASON: Symbol = symbol_short!("expired");
+
+/// Feature flag: scale each reporter's weight in `get_aggregate_score` by
+/// its `get_reporter_reliability`
+pub const FEATURE_RELIABILITY_WEIGHTS: Symbol = symbol_short!("rel_wt");
+
+/// Reliability of a reporter with no upheld disputes, in basis points
+pub const FULL_RELIABILITY: u32 = 10_000;
+
+/// Reports each reporter may submit per `REPORT_RATE_WINDOW`
+pub const REPORT_RATE_MAX: u32 = 10;
+
+/// Seconds in the `submit_report` rate-limit window
+pub const REPORT_RATE_WINDOW: u64 = 3600;
+
+/// `can_submit_report` reasons, in the order `submit_report` checks them
+pub const SUBMIT_OK: Symbol = symbol_short!("ok");
+pub const SUBMIT_RATE_LIMITED: Symbol = symbol_short!("rate_lim");
+pub const SUBMIT_NOT_INITIALIZED: Symbol = symbol_short!("uninit");
+pub const SUBMIT_NO_PERMISSION: Symbol = symbol_short!("no_perm");
+pub const SUBMIT_INACTIVE: Symbol = symbol_short!("inactive");
+pub const SUBMIT_UNDER_STAKED: Symbol = symbol_short!("stake");
+pub const SUBMIT_UNREGISTERED_AGENT: Symbol = symbol_short!("unreg_agt");
+pub const SUBMIT_COOLDOWN: Symbol = symbol_short!("cooldown");
+pub const SUBMIT_APPROVAL_EXPIRED: Symbol = symbol_short!("expired");
+pub const SUBMIT_REMOVED: Symbol = symbol_short!("removed");
+
+/// Score scale until one is configured, matching the 0-100 thresholds
+pub const DEFAULT_SCORE_SCALE: u32 = 100;
+
+/// Share of `score_scale`, in percent, at which a report flags its agent
+pub const FLAG_THRESHOLD_PERCENT: u32 = 70;
+
+/// Seconds a `commit_report` commitment can be revealed for until the
+/// admin sets a window
+pub const DEFAULT_REVEAL_WINDOW: u64 = 3600;
+
+/// Seconds an agent's commit phase stays open until the admin sets a window
+pub const DEFAULT_COMMIT_WINDOW: u64 = 600;
+
+/// Largest `grace_queue_len` the admin may set
+pub const MAX_GRACE_QUEUE_LEN: u32 = 20;
+
+/// Scale `get_score_normalized` reports in
+pub const NORMALIZED_SCORE_SCALE: u32 = 10_000;
+
+/// Window used by the circuit breaker until one is configured
+pub const DEFAULT_BREAKER_WINDOW: u64 = 3600;
+
+/// Layout version of the `export_state` payload.
+pub const STATE_EXPORT_VERSION: u32 = 2;
+
+/// Release of this contract, bumped on every meaningful change
+pub const CONTRACT_VERSION: u32 = 1;
+
+/// Release tag reported by `build_id`
+pub const BUILD_ID: Symbol = symbol_short!("v1_0_0");
+
+/// `[version, admin, acl, reporters, total_reports, config, report_digest,
+/// exported_at]`
+const STATE_EXPORT_LEN: u32 = 8;
+
+/// Version 1 payloads, which predate `exported_at`
+const STATE_EXPORT_LEN_V1: u32 = 7;
+
+/// All operator-tunable settings, stored under `DataKey::Config` and
+/// returned in one read by `get_config`. A config stored before a field was
+/// added still loads, with that field at its default. `network_load` and
+/// `legacy_events` belong to the rate limiter and event schema and are read
+/// from them on every load.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct FraudConfig {
+    pub thresholds: DetectionThresholds,
+    pub dex_config: DexConfig,
+    pub dex_enabled: bool,
+    /// Network load reported to the adaptive rate limiter (×1000)
+    pub network_load: u32,
+    /// Whether pre-versioning event shapes are still emitted
+    pub legacy_events: bool,
+    /// Submissions per window that trip the circuit breaker; 0 disables it
+    pub breaker_threshold: u32,
+    pub breaker_window: u64,
+    /// Token reporters stake in; staking is unavailable until set
+    pub stake_token: Option<Address>,
+    /// Stake required to submit reports; 0 disables the requirement
+    pub min_reporter_stake: i128,
+    /// Seconds after a reporter's last report before stake can be withdrawn
+    pub stake_cooldown: u64,
+    /// Only accept reports for agent ids added with `register_agent`
+    pub require_registered_agents: bool,
+    /// Seconds a reporter must wait before reporting the same agent again
+    pub report_cooldown: u64,
+    /// Most reports kept per agent, 0 for no cap
+    pub max_reports_per_agent: u32,
+    /// At the cap, drop the oldest report instead of rejecting the new one
+    pub evict_oldest_reports: bool,
+    /// Largest score a report may carry
+    pub score_scale: u32,
+    /// Extend the report hash chain on every accepted submission
+    pub report_chain_enabled: bool,
+    /// Seconds a new flag stays pending, and can be disputed, before
+    /// `is_flagged` reports it
+    pub dispute_window: u64,
+    /// Seconds without an admin action before `check_heartbeat` pauses
+    /// the contract; 0 disables the switch
+    pub admin_heartbeat: u64,
+    /// Seconds `update_model` must wait after the previous update
+    pub model_update_cooldown: u64,
+    /// Most `update_model` calls per UTC day, 0 for no cap
+    pub model_daily_cap: u32,
+    /// Seconds after an agent's commit phase closes within which its
+    /// commitments must be revealed
+    pub reveal_window: u64,
+    /// Seconds an agent's commit phase stays open after its first commitment
+    pub commit_window: u64,
+    /// Compressed size an agent's report blob may reach before
+    /// `submit_report` drops its oldest reports; 0 disables compaction
+    pub auto_compact_bytes: u32,
+    /// How `get_aggregate_score` settles a mean between two whole scores
+    pub tiebreak: TieBreak,
+    /// Emit `data_accessed` from `get_reports_audited`
+    pub read_audit: bool,
+    /// Over-limit submissions a `TrustTier::Premium` reporter may queue for
+    /// `drain_queue`; 0 rejects them like any other
+    pub grace_queue_len: u32,
+}
+
+/// Which whole score `get_aggregate_score` reports when the weighted mean
+/// of the reports falls strictly between two of them
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum TieBreak {
+    /// The higher of the two
+    Highest,
+    /// The lower of the two, matching plain truncation
+    Lowest,
+    /// Whichever is on the side of the newest weighted report
+    Latest,
+}
+
+/// A `submit_report` call held back by the rate limiter, see `drain_queue`
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct QueuedReport {
+    pub agent_id: Symbol,
+    pub score: u32,
+    pub trading_evidence: Option<TradingEvidence>,
+    pub queued_at: u64,
+}
+
+/// A hidden score from `commit_report`, see `report_commitment`
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ReportCommitment {
+    pub commitment: BytesN<32>,
+    pub committed_at: u64,
+    /// End of the agent's commit phase, when revealing opens
+    pub reveal_from: u64,
+    pub reveal_by: u64,
+}
+
+/// Phases shared by every commitment for one agent: commitments are taken
+/// until `commit_ends`, then revealed until `reveal_ends`. No score is
+/// revealed while anyone can still commit, so none can be copied.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct CommitRound {
+    pub commit_ends: u64,
+    pub reveal_ends: u64,
+}
+
+/// `update_model` history, see `get_model_update_status`
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ModelUpdateStatus {
+    pub last_update: Option<u64>,
+    pub total_updates: u64,
+    /// Day (`timestamp / 86400`) `updates_today` counts
+    pub day: u64,
+    pub updates_today: u32,
+    /// Seconds until `update_model` is accepted again, 0 when it is now
+    pub cooldown_remaining: u64,
+}
+
+/// Global submission count for the current circuit-breaker window
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct BreakerWindow {
+    pub started_at: u64,
+    pub count: u32,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -61,6 +344,68 @@ pub struct FraudReport {
     pub timestamp: u64,
 }
 
+/// Summary of an agent's stored reports, kept under
+/// `DataKey::ReportsMetadata` and rewritten whenever they change
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct ReportMetadata {
+    pub count: u32,
+    /// Timestamp of the oldest stored report, 0 when there are none
+    pub first_ts: u64,
+    /// Timestamp of the newest stored report, 0 when there are none
+    pub last_ts: u64,
+    pub compression_type: CompressionType,
+    /// Size of the compressed report blob
+    pub stored_bytes: u32,
+}
+
+/// Answer of `can_submit_report`: `reason` is `SUBMIT_OK` when allowed,
+/// otherwise one of the other `SUBMIT_*` symbols
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct SubmitEligibility {
+    pub allowed: bool,
+    pub reason: Symbol,
+}
+
+/// One page of an agent's reports, see `get_reports_page`
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct ReportPage {
+    pub items: Vec<FraudReport>,
+    pub total: u32,
+    pub has_more: bool,
+}
+
+/// Why `check_report` or `accept_report` turned a report down
+enum ReportRejection {
+    Contract(ContractError),
+    Validation(ValidationError),
+}
+
+impl From<ContractError> for ReportRejection {
+    fn from(error: ContractError) -> Self {
+        ReportRejection::Contract(error)
+    }
+}
+
+impl From<ValidationError> for ReportRejection {
+    fn from(error: ValidationError) -> Self {
+        ReportRejection::Validation(error)
+    }
+}
+
+impl ReportRejection {
+    /// The error a report entrypoint fails with. Validation failures abort
+    /// with their own code, as the entrypoints declare `ContractError`.
+    fn raise(self, env: &Env) -> ContractError {
+        match self {
+            ReportRejection::Contract(error) => error,
+            ReportRejection::Validation(error) => panic_with_error!(env, error),
+        }
+    }
+}
+
 #[contract]
 pub struct FraudDetectContract;
 
@@ -94,35 +439,107 @@ impl FraudDetectContract {
         });
         
         transition_to!(Self, &env, initial_state)?;
-        
-        // Store admin and ACL for backward compatibility
-        env.storage().instance().set(&DataKey::Admin, &admin);
-        env.storage()
-            .instance()
-            .set(&DataKey::AclContract, &acl_contract);
-        env.storage().instance().set(&DataKey::DexEnabled, &true);
+        Self::store_initial_config(&env, admin, acl_contract);
+        Ok(())
+    }
 
-        let thresholds = DetectionThresholds::new();
-        env.storage()
-            .instance()
-            .set(&DataKey::DetectionThresholds, &thresholds);
+    /// Permanently stop the contract. Only `reinitialize` leaves this state.
+    pub fn terminate(env: Env, admin: Address) -> Result<(), ContractError> {
+        Self::require_admin_action(&env, &admin, "terminate", vec![&env])?;
+        Self::transition(&env, State::Terminated).map_err(|_| ContractError::InvalidState)?;
+        Ok(())
+    }
 
-        let dex_config = DexConfig::default();
-        env.storage()
-            .instance()
-            .set(&DataKey::DexConfig, &dex_config);
+    /// DESTRUCTIVE: wipe a terminated contract and start it again as if
+    /// freshly initialized. Deletes every stored report and latest score,
+    /// the agent index and registry, the reporter list with its expiries
+    /// and removal records, the report chain, account and agent flags,
+    /// report cooldowns, grace queues, report commitments and rounds,
+    /// agent visibility, reporter weights, trust tiers, model update
+    /// counters and the `FraudConfig`. Reporter stakes and model governance
+    /// are kept, as they are owed to or owned by third parties. Settings
+    /// held by `common_utils` (feature flags, fixed-point precision,
+    /// rate-limit overrides and load curve, event namespace, batch size and
+    /// strict export) are kept as well.
+    ///
+    /// Only callable from `Terminated` and only by the current admin, who
+    /// may hand over to `new_admin`.
+    pub fn reinitialize(
+        env: Env,
+        admin: Address,
+        new_admin: Address,
+        acl_contract: Address,
+    ) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "reinitialize",
+            vec![&env, new_admin.into_val(&env), acl_contract.into_val(&env)],
+        )?;
+        if !Self::get_state(&env).is_terminated() {
+            return Err(ContractError::InvalidState);
+        }
 
-        env.events()
-            .publish((symbol_short!("init"),), (admin, acl_contract));
+        let storage = env.storage().instance();
+        let agents: Vec<Symbol> = storage
+            .get(&DataKey::AgentIndex)
+            .unwrap_or_else(|| Vec::new(&env));
+        for agent_id in agents.iter() {
+            CompressedReportStorage::remove_reports(&env, &agent_id);
+            storage.remove(&DataKey::ReportsMetadata(agent_id.clone()));
+            storage.remove(&DataKey::Reports(agent_id));
+        }
+        let registered: Vec<Symbol> = storage
+            .get(&DataKey::RegisteredAgents)
+            .unwrap_or_else(|| Vec::new(&env));
+        for agent_id in registered.iter() {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::RegisteredAgent(agent_id));
+        }
+        for key in [
+            DataKey::AgentIndex,
+            DataKey::RegisteredAgents,
+            DataKey::TotalReports,
+            DataKey::Config,
+            DataKey::DetectionThresholds,
+            DataKey::DexConfig,
+            DataKey::DexEnabled,
+            DataKey::GlobalStats,
+            DataKey::FlaggedCount,
+            DataKey::BreakerWindow,
+            DataKey::ReportChainHead,
+            DataKey::ImportedReportDigest,
+            DataKey::ExtraAcls,
+            DataKey::ModelUpdates,
+        ] {
+            storage.remove(&key);
+        }
+        reporter_bitmap::clear(&env);
+        reset_index::wipe(&env);
+
+        // `Terminated` has no allowed transitions, so the state is reset here
+        // rather than through `transition_to!`
+        let fresh = State::Active(FraudDetectState {
+            admin: new_admin.clone(),
+            acl_contract: acl_contract.clone(),
+            total_reports: 0,
+        });
+        Self::emit_transition_event(&env, &State::Terminated, &fresh);
+        Self::set_state(&env, fresh);
+        Self::store_initial_config(&env, new_admin, acl_contract);
+        env.events().publish((symbol_short!("reinit"),), admin);
         Ok(())
     }
 
     pub fn initialize_dex(env: Env, admin: Address) -> Result<(), ContractError> {
-        Self::require_admin(&env, &admin)?;
+        Self::require_admin_action(&env, &admin, "initialize_dex", vec![&env])?;
 
         StellarDexAdapter::initialize(&env).map_err(|_| ContractError::InvalidState)?;
 
-        env.storage().instance().set(&DataKey::DexEnabled, &true);
+        let mut config = Self::load_config(&env);
+        config.dex_enabled = true;
+        Self::save_config(&env, &config);
 
         Ok(())
     }
@@ -132,339 +549,2226 @@ impl FraudDetectContract {
         admin: Address,
         thresholds: DetectionThresholds,
     ) -> Result<(), ContractError> {
-        Self::require_admin(&env, &admin)?;
-        env.storage()
-            .instance()
-            .set(&DataKey::DetectionThresholds, &thresholds);
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_detection_thresholds",
+            vec![&env, thresholds.into_val(&env)],
+        )?;
+        let mut config = Self::load_config(&env);
+        config.thresholds = thresholds;
+        Self::save_config(&env, &config);
         Ok(())
     }
 
     pub fn get_detection_thresholds(env: Env) -> DetectionThresholds {
-        env.storage()
-            .instance()
-            .get(&DataKey::DetectionThresholds)
-            .unwrap_or_else(|| DetectionThresholds::new())
+        Self::load_config(&env).thresholds
     }
 
-    pub fn analyze_trading_for_fraud(
-        env: Env,
-        pair: TokenPair,
-    ) -> Result<FraudAnalysisResult, ContractError> {
-        let _timer = PerformanceMonitor::start_timer(&env, &Symbol::new(&env, "analyze_fraud"));
+    /// Full contract configuration in a single read
+    pub fn get_config(env: Env) -> FraudConfig {
+        Self::load_config(&env)
+    }
 
-        let dex_enabled: bool = env
-            .storage()
-            .instance()
-            .get(&DataKey::DexEnabled)
-            .unwrap_or(false);
+    /// Resolve dependencies (currently the ACL) through a `ServiceRegistry`.
+    /// The address passed to `initialize` is used while the registry has no entry.
+    pub fn set_service_registry(env: Env, admin: Address, registry: Address) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_service_registry",
+            vec![&env, registry.into_val(&env)],
+        )?;
+        env.storage().instance().set(&DataKey::ServiceRegistry, &registry);
+        Ok(())
+    }
 
-        if !dex_enabled {
-            return Ok(FraudAnalysisResult {
-                indicators: Vec::new(&env),
-                overall_risk: OverallRisk {
-                    risk_level: RiskLevel::Low,
-                    total_score_impact: 0,
-                    critical_count: 0,
-                    high_count: 0,
-                    medium_count: 0,
-                    low_count: 0,
-                },
-                trading_data: None,
-            });
+    /// Also accept `report` and `view` permissions granted by `acl`,
+    /// consulted after the primary ACL and any added before it. Other
+    /// actions, such as `manage`, are only taken from the primary ACL.
+    /// Adding one twice has no effect.
+    pub fn add_acl_contract(env: Env, admin: Address, acl: Address) -> Result<(), ContractError> {
+        Self::require_admin_action(&env, &admin, "add_acl_contract", vec![&env, acl.into_val(&env)])?;
+        let mut acls = Self::get_acl_contracts(env.clone());
+        if !acls.contains(&acl) {
+            acls.push_back(acl);
+            env.storage().instance().set(&DataKey::ExtraAcls, &acls);
         }
+        Ok(())
+    }
 
-        let trading_data = Self::fetch_trading_data(&env, &pair)?;
-
-        let thresholds = Self::get_detection_thresholds(env.clone());
-        let detector = PatternDetector::with_thresholds(&env, thresholds);
-
-        let indicators = detector.analyze(&trading_data);
-        let overall_risk = detector.overall_risk(&indicators);
-
-        let patterns = detector.detect_patterns(&trading_data);
+    /// Stop consulting `acl`. The primary ACL cannot be removed this way.
+    pub fn remove_acl_contract(env: Env, admin: Address, acl: Address) -> Result<(), ContractError> {
+        Self::require_admin_action(&env, &admin, "remove_acl_contract", vec![&env, acl.into_val(&env)])?;
+        let mut acls = Self::get_acl_contracts(env.clone());
+        let index = acls.first_index_of(&acl).ok_or(ContractError::InvalidInput)?;
+        acls.remove(index);
+        env.storage().instance().set(&DataKey::ExtraAcls, &acls);
+        Ok(())
+    }
 
-        env.storage().instance().set(
-            &DataKey::FraudIndicators(pair.symbol_a),
-            &indicators.clone(),
-        );
+    /// ACLs added with `add_acl_contract`, in the order they are consulted
+    pub fn get_acl_contracts(env: Env) -> Vec<Address> {
         env.storage()
             .instance()
-            .set(&DataKey::TradingPatternHistory(pair.symbol_a), &patterns);
+            .get(&DataKey::ExtraAcls)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
 
-        StorageTracker::record_operation(
+    /// Register `agent_id` so it is accepted in strict agent mode.
+    pub fn register_agent(env: Env, admin: Address, agent_id: Symbol) -> Result<(), ContractError> {
+        Self::require_admin_action(
             &env,
-            &Symbol::new(&env, "fraud_analysis"),
-            &pair.symbol_a,
-            100,
-            true,
-        );
+            &admin,
+            "register_agent",
+            vec![&env, agent_id.into_val(&env)],
+        )?;
+        if Self::is_registered_agent(env.clone(), agent_id.clone()) {
+            return Ok(());
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::RegisteredAgent(agent_id.clone()), &true);
 
-        let _duration = PerformanceMonitor::end_timer(&env, &Symbol::new(&env, "analyze_fraud"));
+        let mut agents: Vec<Symbol> = env
+            .storage()
+            .instance()
+            .get(&DataKey::RegisteredAgents)
+            .unwrap_or_else(|| Vec::new(&env));
+        agents.push_back(agent_id.clone());
+        env.storage().instance().set(&DataKey::RegisteredAgents, &agents);
 
-        Ok(FraudAnalysisResult {
-            indicators,
-            overall_risk,
-            trading_data: Some(trading_data),
-        })
+        env.events().publish((symbol_short!("agt_reg"),), agent_id);
+        Ok(())
     }
 
-    pub fn analyze_transaction(
-        env: Env,
-        transaction_data: String,
-        pair: Option<TokenPair>,
-    ) -> bool {
-        let _timer = PerformanceMonitor::start_timer(&env, &Symbol::new(&env, "analyze_txn"));
-
-        if let Some(token_pair) = pair {
-            if let Ok(result) = Self::analyze_trading_for_fraud(env.clone(), token_pair) {
-                if result.overall_risk.requires_review() {
-                    return true;
-                }
-            }
-        }
+    pub fn is_registered_agent(env: Env, agent_id: Symbol) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RegisteredAgent(agent_id))
+            .unwrap_or(false)
+    }
 
-        let has_suspicious_pattern = transaction_data.len() > 1000;
+    /// Registered agent ids in registration order.
+    /// `limit` is clamped to `pagination::MAX_PAGE_SIZE`.
+    pub fn get_registered_agents(env: Env, offset: u32, limit: u32) -> Vec<Symbol> {
+        let agents: Vec<Symbol> = env
+            .storage()
+            .instance()
+            .get(&DataKey::RegisteredAgents)
+            .unwrap_or_else(|| Vec::new(&env));
+        pagination::paginate(&env, &agents, offset, limit).items
+    }
 
-        let _duration = PerformanceMonitor::end_timer(&env, &Symbol::new(&env, "analyze_txn"));
+    /// Strict mode: reject reports for agent ids that were never registered.
+    /// Off by default.
+    pub fn set_require_registered_agents(env: Env, admin: Address, enabled: bool) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_require_registered_agents",
+            vec![&env, enabled.into_val(&env)],
+        )?;
+        let mut config = Self::load_config(&env);
+        config.require_registered_agents = enabled;
+        Self::save_config(&env, &config);
+        Ok(())
+    }
 
-        has_suspicious_pattern
+    /// Minimum seconds between two reports from the same reporter for the
+    /// same agent. 0 disables the cooldown.
+    pub fn set_report_cooldown(env: Env, admin: Address, secs: u64) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_report_cooldown",
+            vec![&env, secs.into_val(&env)],
+        )?;
+        let mut config = Self::load_config(&env);
+        config.report_cooldown = secs;
+        Self::save_config(&env, &config);
+        Ok(())
     }
 
-    pub fn get_risk_score(env: Env, pair: TokenPair) -> u32 {
-        if let Ok(result) = Self::analyze_trading_for_fraud(env.clone(), pair) {
-            match result.overall_risk.risk_level {
-                RiskLevel::Low => 25,
-                RiskLevel::Medium => 50,
-                RiskLevel::High => 75,
-                RiskLevel::Critical => 100,
-            }
-        } else {
-            0
+    /// Set the largest score reporters may submit. Stored scores are not
+    /// rescaled, so change this only while no reports are kept.
+    pub fn set_score_scale(env: Env, admin: Address, scale: u32) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_score_scale",
+            vec![&env, scale.into_val(&env)],
+        )?;
+        if scale == 0 {
+            return Err(ContractError::InvalidInput);
         }
+        let mut config = Self::load_config(&env);
+        config.score_scale = scale;
+        Self::save_config(&env, &config);
+        Ok(())
     }
 
-    pub fn get_indicators(env: Env, pair: TokenPair) -> Vec<FraudIndicator> {
-        env.storage()
-            .instance()
-            .get(&DataKey::FraudIndicators(pair.symbol_a))
-            .unwrap_or_else(|| Vec::new(&env))
+    /// Cap the reports stored per agent. 0 removes the cap.
+    pub fn set_max_reports_per_agent(env: Env, admin: Address, max: u32) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_max_reports_per_agent",
+            vec![&env, max.into_val(&env)],
+        )?;
+        let mut config = Self::load_config(&env);
+        config.max_reports_per_agent = max;
+        Self::save_config(&env, &config);
+        Ok(())
     }
 
-    pub fn get_trading_patterns(env: Env, pair: TokenPair) -> Vec<TradingPattern> {
-        env.storage()
-            .instance()
-            .get(&DataKey::TradingPatternHistory(pair.symbol_a))
-            .unwrap_or_else(|| Vec::new(&env))
+    /// What `submit_report` does for an agent at the cap: reject the report
+    /// with `ValidationError::CapacityExceeded` (default) or evict the agent's oldest report.
+    pub fn set_evict_oldest_reports(env: Env, admin: Address, enabled: bool) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_evict_oldest_reports",
+            vec![&env, enabled.into_val(&env)],
+        )?;
+        let mut config = Self::load_config(&env);
+        config.evict_oldest_reports = enabled;
+        Self::save_config(&env, &config);
+        Ok(())
     }
 
-    pub fn update_model(env: Env, admin: Address, model_data: Bytes) -> Result<(), ContractError> {
-        Self::require_admin(&env, &admin)?;
-
-        env.events().publish(
-            (symbol_short!("mdl_upd"),),
-            (env.ledger().timestamp(), model_data),
-        );
+    /// Start or stop extending the report hash chain. The head is kept
+    /// while disabled.
+    pub fn set_report_chain_enabled(env: Env, admin: Address, enabled: bool) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_report_chain_enabled",
+            vec![&env, enabled.into_val(&env)],
+        )?;
+        let mut config = Self::load_config(&env);
+        config.report_chain_enabled = enabled;
+        Self::save_config(&env, &config);
         Ok(())
     }
 
-    pub fn add_reporter(
-        env: Env,
-        caller: Address,
-        reporter: Address,
-    ) -> Result<(), AuthorizationError> {
-        caller.require_auth();
+    pub fn get_contract_state(env: Env) -> State<FraudDetectState> {
+        Self::get_state(&env)
+    }
 
-        let acl: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::AclContract)
-            .ok_or(AuthorizationError::NotInitialized)?;
+    /// `uninitialized`, `active`, `paused`, `migrating` or `terminated`,
+    /// without returning the state data
+    pub fn get_state_label(env: Env) -> Symbol {
+        state_machine::state_label(&env, &DataKey::ContractState)
+    }
+
+    /// Readiness probe: the state label, whether the ACL answers a
+    /// `fraud/view` check, and whether the service registry, each added
+    /// ACL and the stake token answer a cheap read. A failing dependency
+    /// is reported, never raised.
+    pub fn health(env: Env) -> HealthReport {
+        let mut deps = Vec::new(&env);
+        let mut acl: Option<Address> = env.storage().instance().get(&DataKey::AclContract);
+        if let Some(registry) = env.storage().instance().get::<_, Address>(&DataKey::ServiceRegistry) {
+            let resolved = health::try_read::<Option<Address>>(
+                &env,
+                &registry,
+                "resolve",
+                vec![&env, Symbol::new(&env, SERVICE_ACL).into_val(&env)],
+            );
+            deps.push_back((symbol_short!("registry"), resolved.is_some()));
+            if let Some(Some(resolved)) = resolved {
+                acl = Some(resolved);
+            }
+        }
 
-        if !common_utils::check_permission(
-            env.clone(),
-            acl,
-            caller,
-            symbol_short!("fraud"),
-            symbol_short!("manage"),
-        ) {
-            return Err(AuthorizationError::NotAuthorized);
+        let view_check = |acl: &Address| {
+            let args = vec![
+                &env,
+                env.current_contract_address().into_val(&env),
+                symbol_short!("fraud").into_val(&env),
+                symbol_short!("view").into_val(&env),
+            ];
+            health::try_read::<bool>(&env, acl, "has_permission", args).is_some()
+        };
+        let acl_ok = acl.as_ref().is_some_and(&view_check);
+        for extra in Self::get_acl_contracts(env.clone()).iter() {
+            deps.push_back((symbol_short!("acl_extra"), view_check(&extra)));
+        }
+        if let Some(token) = Self::load_config(&env).stake_token {
+            let reachable = health::probe(&env, &token, "decimals", vec![&env]);
+            deps.push_back((symbol_short!("stake_tok"), reachable));
         }
 
-        Ok(())
+        HealthReport {
+            state_label: Self::get_state_label(env.clone()),
+            acl_ok,
+            deps,
+        }
     }
 
-    pub fn remove_reporter(env: Env, admin: Address, reporter: Address) -> Result<(), CommonError> {
-        let stored_admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .ok_or(CommonError::NotInitialized)?;
-        stored_admin.require_auth();
+    pub fn version(_env: Env) -> u32 {
+        CONTRACT_VERSION
+    }
 
-        env.events().publish((symbol_short!("rem_rpt"),), reporter);
-        Ok(())
+    pub fn build_id(_env: Env) -> Symbol {
+        BUILD_ID
     }
 
-    pub fn set_user_trust_tier(
+    /// Auto-pause when more than `threshold` reports arrive within
+    /// `window_seconds`. A threshold of 0 disables the breaker.
+    pub fn set_circuit_breaker(
         env: Env,
         admin: Address,
-        user: Address,
-        tier: TrustTier,
-    ) -> Result<(), AuthorizationError> {
-        let stored_admin: Address = env
+        threshold: u32,
+        window_seconds: u64,
+    ) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_circuit_breaker",
+            vec![&env, threshold.into_val(&env), window_seconds.into_val(&env)],
+        )?;
+        if window_seconds == 0 {
+            return Err(ContractError::InvalidConfiguration);
+        }
+        let mut config = Self::load_config(&env);
+        config.breaker_threshold = threshold;
+        config.breaker_window = window_seconds;
+        Self::save_config(&env, &config);
+        env.storage().instance().remove(&DataKey::BreakerWindow);
+        Ok(())
+    }
+
+    /// Configure the reporter stake: the token to escrow, the minimum needed
+    /// to submit reports and the withdrawal cooldown after a report. The
+    /// token cannot change while any stake is held, since withdrawals and
+    /// slashes pay out in the configured token.
+    pub fn set_reporter_stake_policy(
+        env: Env,
+        admin: Address,
+        token: Address,
+        min_stake: i128,
+        cooldown_seconds: u64,
+    ) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_reporter_stake_policy",
+            vec![
+                &env,
+                token.into_val(&env),
+                min_stake.into_val(&env),
+                cooldown_seconds.into_val(&env),
+            ],
+        )?;
+        if min_stake < 0 {
+            return Err(ContractError::InvalidConfiguration);
+        }
+        let mut config = Self::load_config(&env);
+        if matches!(&config.stake_token, Some(current) if *current != token) && stake::total_staked(&env) > 0 {
+            return Err(ContractError::InvalidState);
+        }
+        config.stake_token = Some(token);
+        config.min_reporter_stake = min_stake;
+        config.stake_cooldown = cooldown_seconds;
+        Self::save_config(&env, &config);
+        Ok(())
+    }
+
+    pub fn stake_to_report(env: Env, reporter: Address, amount: i128) -> Result<ReporterStake, ContractError> {
+        stake::stake(&env, &reporter, amount)
+    }
+
+    pub fn withdraw_stake(env: Env, reporter: Address, amount: i128) -> Result<ReporterStake, ContractError> {
+        stake::withdraw(&env, &reporter, amount)
+    }
+
+    pub fn get_reporter_stake(env: Env, reporter: Address) -> ReporterStake {
+        stake::get_stake(&env, &reporter)
+    }
+
+    /// Record that a dispute of `reporter`'s reports on `agent_id` was
+    /// upheld, allowing one `slash_reporter_stake` for it. The reporter must
+    /// have a stored report on the agent.
+    pub fn uphold_report_dispute(
+        env: Env,
+        admin: Address,
+        reporter: Address,
+        agent_id: Symbol,
+    ) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "uphold_report_dispute",
+            vec![&env, reporter.into_val(&env), agent_id.into_val(&env)],
+        )?;
+        if !Self::reports_of(&env, &agent_id).iter().any(|report| report.reporter == reporter) {
+            return Err(ContractError::NotFound);
+        }
+        let dispute = DataKey::UpheldDispute(reporter.clone(), agent_id.clone());
+        if !env.storage().persistent().has(&dispute) {
+            reset_index::track_dispute(&env, &reporter);
+            let count_key = DataKey::UpheldDisputeCount(reporter.clone());
+            let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+            env.storage().persistent().set(&count_key, &count.saturating_add(1));
+        }
+        env.storage().persistent().set(&dispute, &true);
+        env.events()
+            .publish((symbol_short!("dsp_uphld"), reporter), (agent_id, env.ledger().timestamp()));
+        Ok(())
+    }
+
+    /// `FULL_RELIABILITY` divided by one more than the number of disputes
+    /// ever upheld against `reporter`. Slashing does not restore it.
+    pub fn get_reporter_reliability(env: Env, reporter: Address) -> u32 {
+        let upheld: u32 = env
             .storage()
+            .persistent()
+            .get(&DataKey::UpheldDisputeCount(reporter))
+            .unwrap_or(0);
+        FULL_RELIABILITY / upheld.saturating_add(1)
+    }
+
+    /// Whether an upheld dispute of `reporter` on `agent_id` awaits a slash
+    pub fn has_upheld_dispute(env: Env, reporter: Address, agent_id: Symbol) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::UpheldDispute(reporter, agent_id))
+    }
+
+    /// Slash a reporter whose reports on `agent_id` were disputed and
+    /// upheld with `uphold_report_dispute`, consuming that dispute; the
+    /// slashed tokens go to the admin. Returns the amount actually slashed.
+    pub fn slash_reporter_stake(
+        env: Env,
+        admin: Address,
+        reporter: Address,
+        agent_id: Symbol,
+        amount: i128,
+    ) -> Result<i128, ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "slash_reporter_stake",
+            vec![&env, reporter.into_val(&env), agent_id.into_val(&env), amount.into_val(&env)],
+        )?;
+        let dispute = DataKey::UpheldDispute(reporter.clone(), agent_id);
+        if !env.storage().persistent().has(&dispute) {
+            return Err(ContractError::InvalidState);
+        }
+        env.storage().persistent().remove(&dispute);
+        stake::slash(&env, &reporter, amount, &admin)
+    }
+
+    /// Dead man's switch: once `interval_secs` pass without any admin-gated
+    /// call, `check_heartbeat` pauses the contract. 0 disables it.
+    pub fn set_admin_heartbeat(env: Env, admin: Address, interval_secs: u64) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_admin_heartbeat",
+            vec![&env, interval_secs.into_val(&env)],
+        )?;
+        let mut config = Self::load_config(&env);
+        config.admin_heartbeat = interval_secs;
+        Self::save_config(&env, &config);
+        Ok(())
+    }
+
+    /// The newest `limit` admin actions, oldest first
+    pub fn get_admin_log(env: Env, limit: u32) -> Vec<AdminAction> {
+        admin_log::get_admin_log(&env, limit)
+    }
+
+    /// When the admin last made a privileged call
+    pub fn get_last_admin_action(env: Env) -> Option<u64> {
+        env.storage().instance().get(&DataKey::LastAdminAction)
+    }
+
+    /// Permissionless: pause the contract and emit `heartbeat_expired` with
+    /// `(last_admin_action, interval)` when the admin heartbeat has lapsed.
+    /// Returns whether it paused; only `resume` undoes it.
+    pub fn check_heartbeat(env: Env) -> bool {
+        let interval = Self::load_config(&env).admin_heartbeat;
+        if interval == 0 || !Self::get_state(&env).is_active() {
+            return false;
+        }
+        let last = Self::get_last_admin_action(env.clone()).unwrap_or(0);
+        if env.ledger().timestamp().saturating_sub(last) <= interval
+            || Self::pause_contract(&env).is_err()
+        {
+            return false;
+        }
+        env.events().publish(
+            (Symbol::new(&env, "heartbeat_expired"),),
+            (last, interval),
+        );
+        true
+    }
+
+    /// Manually pause report submission until `resume`.
+    pub fn pause(env: Env, admin: Address) -> Result<(), ContractError> {
+        Self::require_admin_action(&env, &admin, "pause", vec![&env])?;
+        Self::pause_contract(&env).map_err(|_| ContractError::InvalidState)?;
+        env.events()
+            .publish((symbol_short!("paused"),), env.ledger().timestamp());
+        Ok(())
+    }
+
+    /// Only allow `export_state` while paused or terminated. `import_state`
+    /// refuses live exports either way; this fails them at the source.
+    pub fn set_strict_export(env: Env, admin: Address, strict: bool) -> Result<(), ContractError> {
+        Self::require_admin_action(&env, &admin, "set_strict_export", vec![&env, strict.into_val(&env)])?;
+        migration::set_strict_export(&env, strict);
+        Ok(())
+    }
+
+    pub fn is_strict_export(env: Env) -> bool {
+        migration::is_strict_export(&env)
+    }
+
+    /// Export time of the source this contract was imported from; `None`
+    /// when never imported or imported from a version 1 payload
+    pub fn get_source_exported_at(env: Env) -> Option<u64> {
+        migration::source_exported_at(&env)
+    }
+
+    /// Rename storage keys in place after an upgrade. Long lists can be run
+    /// over several calls with a `max_steps` budget, see `key_migration`.
+    pub fn migrate_keys(
+        env: Env,
+        admin: Address,
+        migrations: Vec<KeyMigration>,
+        max_steps: u32,
+    ) -> Result<KeyMigrationProgress, ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "migrate_keys",
+            vec![&env, migrations.into_val(&env), max_steps.into_val(&env)],
+        )?;
+        Ok(key_migration::migrate_keys(&env, &migrations, max_steps))
+    }
+
+    /// Clear a tripped breaker (or a manual pause). Never happens automatically.
+    pub fn resume(env: Env, admin: Address) -> Result<(), ContractError> {
+        Self::require_admin_action(&env, &admin, "resume", vec![&env])?;
+        Self::reactivate(&env).map_err(|_| ContractError::InvalidState)?;
+        env.storage().instance().remove(&DataKey::BreakerWindow);
+        env.events()
+            .publish((symbol_short!("resumed"),), env.ledger().timestamp());
+        Ok(())
+    }
+
+    /// Enter `Migrating`. While migrating:
+    /// - `submit_report` is rejected like in any non-active state.
+    /// - `get_reports`, `get_reports_page` and `export_all_reports` serve the
+    ///   reports as stored before the migration; they are only replaced when
+    ///   the migration finishes.
+    /// - `get_latest_score_checked` fails with `StateError::MigrationInProgress`,
+    ///   since the latest-score cache is rebuilt by the migration.
+    /// - `migrate_to_compressed` may run; outside this window it is rejected.
+    pub fn begin_migration(env: Env, admin: Address) -> Result<(), ContractError> {
+        Self::require_admin_action(&env, &admin, "begin_migration", vec![&env])?;
+        let state = Self::get_state(&env);
+        if !state.is_active() {
+            return Err(ContractError::InvalidState);
+        }
+        let data = state.get_data().cloned().ok_or(ContractError::NotInitialized)?;
+        Self::transition(&env, State::Migrating(data)).map_err(|_| ContractError::InvalidState)?;
+        env.storage().instance().set(&DataKey::MigrationState, &env.ledger().timestamp());
+        Ok(())
+    }
+
+    /// Leave `Migrating` and return to `Active`.
+    pub fn finish_migration(env: Env, admin: Address) -> Result<(), ContractError> {
+        Self::require_admin_action(&env, &admin, "finish_migration", vec![&env])?;
+        let state = Self::get_state(&env);
+        if !state.is_migrating() {
+            return Err(ContractError::InvalidState);
+        }
+        let data = state.get_data().cloned().ok_or(ContractError::NotInitialized)?;
+        Self::transition(&env, State::Active(data)).map_err(|_| ContractError::InvalidState)?;
+        env.storage().instance().remove(&DataKey::MigrationState);
+        Ok(())
+    }
+
+    pub fn analyze_trading_for_fraud(
+        env: Env,
+        pair: TokenPair,
+    ) -> Result<FraudAnalysisResult, ContractError> {
+        let _timer = PerformanceMonitor::start_timer(&env, &Symbol::new(&env, "analyze_fraud"));
+
+        let dex_enabled = Self::load_config(&env).dex_enabled;
+
+        if !dex_enabled {
+            return Ok(FraudAnalysisResult {
+                indicators: Vec::new(&env),
+                overall_risk: OverallRisk {
+                    risk_level: RiskLevel::Low,
+                    total_score_impact: 0,
+                    critical_count: 0,
+                    high_count: 0,
+                    medium_count: 0,
+                    low_count: 0,
+                },
+                trading_data: None,
+            });
+        }
+
+        let trading_data = Self::fetch_trading_data(&env, &pair)?;
+
+        let thresholds = Self::get_detection_thresholds(env.clone());
+        let detector = PatternDetector::with_thresholds(&env, thresholds);
+
+        let indicators = detector.analyze(&trading_data);
+        let overall_risk = detector.overall_risk(&indicators);
+
+        let patterns = detector.detect_patterns(&trading_data);
+
+        env.storage().instance().set(
+            &DataKey::FraudIndicators(pair.symbol_a),
+            &indicators.clone(),
+        );
+        env.storage()
             .instance()
-            .get(&DataKey::Admin)
-            .ok_or(AuthorizationError::NotInitialized)?;
-        stored_admin.require_auth();
-        if stored_admin != admin {
-            return Err(AuthorizationError::NotAuthorized);
+            .set(&DataKey::TradingPatternHistory(pair.symbol_a), &patterns);
+
+        StorageTracker::record_operation(
+            &env,
+            &Symbol::new(&env, "fraud_analysis"),
+            &pair.symbol_a,
+            100,
+            true,
+        );
+
+        let _duration = PerformanceMonitor::end_timer(&env, &Symbol::new(&env, "analyze_fraud"));
+
+        Ok(FraudAnalysisResult {
+            indicators,
+            overall_risk,
+            trading_data: Some(trading_data),
+        })
+    }
+
+    pub fn analyze_transaction(
+        env: Env,
+        transaction_data: String,
+        pair: Option<TokenPair>,
+    ) -> bool {
+        let _timer = PerformanceMonitor::start_timer(&env, &Symbol::new(&env, "analyze_txn"));
+
+        if let Some(token_pair) = pair {
+            if let Ok(result) = Self::analyze_trading_for_fraud(env.clone(), token_pair) {
+                if result.overall_risk.requires_review() {
+                    return true;
+                }
+            }
+        }
+
+        let has_suspicious_pattern = transaction_data.len() > 1000;
+
+        let _duration = PerformanceMonitor::end_timer(&env, &Symbol::new(&env, "analyze_txn"));
+
+        has_suspicious_pattern
+    }
+
+    pub fn get_risk_score(env: Env, pair: TokenPair) -> u32 {
+        if let Ok(result) = Self::analyze_trading_for_fraud(env.clone(), pair) {
+            match result.overall_risk.risk_level {
+                RiskLevel::Low => 25,
+                RiskLevel::Medium => 50,
+                RiskLevel::High => 75,
+                RiskLevel::Critical => 100,
+            }
+        } else {
+            0
+        }
+    }
+
+    pub fn get_indicators(env: Env, pair: TokenPair) -> Vec<FraudIndicator> {
+        env.storage()
+            .instance()
+            .get(&DataKey::FraudIndicators(pair.symbol_a))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    pub fn get_trading_patterns(env: Env, pair: TokenPair) -> Vec<TradingPattern> {
+        env.storage()
+            .instance()
+            .get(&DataKey::TradingPatternHistory(pair.symbol_a))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Single-admin model push. Disabled once model admins are configured;
+    /// use `propose_model` / `approve_model` instead.
+    pub fn update_model(env: Env, admin: Address, model_data: Bytes) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "update_model",
+            vec![&env, model_data.into_val(&env)],
+        )?;
+        if model_quorum::is_enabled(&env) {
+            return Err(ContractError::UnsupportedOperation);
+        }
+        // A rejection rolls back anything published with it, so the wait
+        // is read from `get_model_update_retry_after` instead
+        let mut status = Self::get_model_update_status(env.clone());
+        if status.cooldown_remaining > 0 {
+            return Err(ContractError::ModelUpdateCooldown);
+        }
+        let cap = Self::load_config(&env).model_daily_cap;
+        if cap > 0 && status.updates_today >= cap {
+            return Err(ContractError::ModelDailyCapReached);
+        }
+
+        let now = env.ledger().timestamp();
+        status.last_update = Some(now);
+        status.total_updates = status.total_updates.saturating_add(1);
+        status.updates_today = status.updates_today.saturating_add(1);
+        env.storage().instance().set(&DataKey::ModelUpdates, &status);
+
+        env.events().publish(
+            (symbol_short!("mdl_upd"),),
+            (now, model_data),
+        );
+        Ok(())
+    }
+
+    /// Seconds `update_model` must wait after the previous update; 0
+    /// disables the cooldown
+    pub fn set_model_update_cooldown(env: Env, admin: Address, secs: u64) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_model_update_cooldown",
+            vec![&env, secs.into_val(&env)],
+        )?;
+        let mut config = Self::load_config(&env);
+        config.model_update_cooldown = secs;
+        Self::save_config(&env, &config);
+        Ok(())
+    }
+
+    /// Most `update_model` calls per UTC day; 0 removes the cap
+    pub fn set_model_daily_cap(env: Env, admin: Address, cap: u32) -> Result<(), ContractError> {
+        Self::require_admin_action(&env, &admin, "set_model_daily_cap", vec![&env, cap.into_val(&env)])?;
+        let mut config = Self::load_config(&env);
+        config.model_daily_cap = cap;
+        Self::save_config(&env, &config);
+        Ok(())
+    }
+
+    /// Seconds until `update_model` would be accepted: the cooldown still
+    /// to run, or the time to the next UTC day once the daily cap is
+    /// reached. 0 when an update would be accepted now.
+    pub fn get_model_update_retry_after(env: Env) -> u64 {
+        let status = Self::get_model_update_status(env.clone());
+        let cap = Self::load_config(&env).model_daily_cap;
+        let until_tomorrow = if cap > 0 && status.updates_today >= cap {
+            (status.day + 1) * SECONDS_PER_DAY - env.ledger().timestamp()
+        } else {
+            0
+        };
+        status.cooldown_remaining.max(until_tomorrow)
+    }
+
+    /// Last update, running counts and the cooldown still to run
+    pub fn get_model_update_status(env: Env) -> ModelUpdateStatus {
+        let now = env.ledger().timestamp();
+        let today = now / SECONDS_PER_DAY;
+        let mut status = env
+            .storage()
+            .instance()
+            .get(&DataKey::ModelUpdates)
+            .unwrap_or(ModelUpdateStatus {
+                last_update: None,
+                total_updates: 0,
+                day: today,
+                updates_today: 0,
+                cooldown_remaining: 0,
+            });
+        if status.day != today {
+            status.day = today;
+            status.updates_today = 0;
+        }
+        let cooldown = Self::load_config(&env).model_update_cooldown;
+        status.cooldown_remaining = status
+            .last_update
+            .map_or(0, |last| last.saturating_add(cooldown).saturating_sub(now));
+        status
+    }
+
+    pub fn add_model_admin(env: Env, admin: Address, model_admin: Address) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "add_model_admin",
+            vec![&env, model_admin.into_val(&env)],
+        )?;
+        model_quorum::add_admin(&env, &model_admin);
+        Ok(())
+    }
+
+    /// The threshold is capped to the remaining number of model admins.
+    /// Approvals the removed admin already gave stop counting.
+    pub fn remove_model_admin(env: Env, admin: Address, model_admin: Address) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "remove_model_admin",
+            vec![&env, model_admin.into_val(&env)],
+        )?;
+        model_quorum::remove_admin(&env, &model_admin)
+    }
+
+    /// Number of distinct model-admin approvals needed to commit a model.
+    pub fn set_model_threshold(env: Env, admin: Address, threshold: u32) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_model_threshold",
+            vec![&env, threshold.into_val(&env)],
+        )?;
+        model_quorum::set_threshold(&env, threshold)
+    }
+
+    pub fn get_model_admins(env: Env) -> Vec<Address> {
+        model_quorum::model_admins(&env)
+    }
+
+    /// Propose a model by hash; the proposer's approval is counted.
+    /// Returns true when that alone meets the threshold and the model is committed.
+    pub fn propose_model(env: Env, admin: Address, model_hash: BytesN<32>) -> Result<bool, ContractError> {
+        model_quorum::propose(&env, &admin, &model_hash)
+    }
+
+    /// Approve a pending model; returns true when this approval commits it.
+    pub fn approve_model(env: Env, approver: Address, model_hash: BytesN<32>) -> Result<bool, ContractError> {
+        model_quorum::approve(&env, &approver, &model_hash)
+    }
+
+    pub fn get_model_proposal(env: Env, model_hash: BytesN<32>) -> Option<ModelProposal> {
+        model_quorum::get_proposal(&env, &model_hash)
+    }
+
+    /// Hash of the last model committed through the quorum
+    pub fn get_active_model(env: Env) -> Option<BytesN<32>> {
+        env.storage().instance().get(&DataKey::ActiveModel)
+    }
+
+    pub fn add_reporter(
+        env: Env,
+        caller: Address,
+        reporter: Address,
+    ) -> Result<(), AuthorizationError> {
+        Self::require_manager_action(&env, &caller, "add_reporter", vec![&env, reporter.to_val()])
+            .map_err(Self::authorization_error)?;
+
+        Self::approve_reporter(&env, &reporter, None);
+        Ok(())
+    }
+
+    /// Like `add_reporter`, but the approval lapses at `expires_at`: from
+    /// then on `submit_report` treats the reporter as unapproved.
+    pub fn add_reporter_until(
+        env: Env,
+        caller: Address,
+        reporter: Address,
+        expires_at: u64,
+    ) -> Result<(), ContractError> {
+        Self::require_manager_action(
+            &env,
+            &caller,
+            "add_reporter_until",
+            vec![&env, reporter.to_val(), expires_at.into_val(&env)],
+        )?;
+        if expires_at <= env.ledger().timestamp() {
+            return Err(ContractError::InvalidInput);
+        }
+
+        Self::approve_reporter(&env, &reporter, Some(expires_at));
+        Ok(())
+    }
+
+    /// When `reporter`'s approval lapses, or `None` if it does not
+    pub fn get_reporter_expiry(env: Env, reporter: Address) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ReporterExpiry(reporter))
+    }
+
+    /// Remove every reporter whose approval has lapsed, recording
+    /// `EXPIRY_REMOVAL_REASON`. Anyone may call this; returns how many were
+    /// removed.
+    pub fn prune_expired_reporters(env: Env) -> u32 {
+        let now = env.ledger().timestamp();
+        let reporters = Self::get_reporters(env.clone());
+        let mut pruned = 0u32;
+        for reporter in reporters.iter() {
+            if !Self::approval_expired(&env, &reporter) {
+                continue;
+            }
+            reporter_bitmap::set(&env, &reporter, false);
+            env.storage()
+                .persistent()
+                .remove(&DataKey::ReporterExpiry(reporter.clone()));
+            env.storage().persistent().set(
+                &DataKey::ReporterRemoval(reporter.clone()),
+                &(now, env.current_contract_address(), EXPIRY_REMOVAL_REASON),
+            );
+            env.events()
+                .publish((symbol_short!("rem_rpt"),), (reporter, EXPIRY_REMOVAL_REASON));
+            pruned += 1;
+        }
+        pruned
+    }
+
+    /// Remove `reporter`, recording when, by whom and why. `reason` defaults
+    /// to `DEFAULT_REMOVAL_REASON`.
+    pub fn remove_reporter(
+        env: Env,
+        admin: Address,
+        reporter: Address,
+        reason: Option<Symbol>,
+    ) -> Result<(), CommonError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "remove_reporter",
+            vec![&env, reporter.to_val(), reason.into_val(&env)],
+        )
+        .map_err(Self::common_error)?;
+
+        reporter_bitmap::set(&env, &reporter, false);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ReporterExpiry(reporter.clone()));
+
+        let reason = reason.unwrap_or(DEFAULT_REMOVAL_REASON);
+        reset_index::track_reporter_record(&env, &reporter);
+        env.storage().persistent().set(
+            &DataKey::ReporterRemoval(reporter.clone()),
+            &(env.ledger().timestamp(), admin, reason.clone()),
+        );
+
+        env.events().publish((symbol_short!("rem_rpt"),), (reporter, reason));
+        Ok(())
+    }
+
+    /// Make the local reporter list agree with the ACL for `candidates`:
+    /// those holding `fraud/report` are added, the rest are removed with
+    /// `ACL_SYNC_REMOVAL_REASON`. Addresses not in `candidates` are left
+    /// alone. Returns `(added, removed)`.
+    pub fn sync_reporters_from_acl(
+        env: Env,
+        admin: Address,
+        candidates: Vec<Address>,
+    ) -> Result<(u32, u32), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "sync_reporters_from_acl",
+            vec![&env, candidates.into_val(&env)],
+        )?;
+        require_within_batch_limit(&env, candidates.len());
+        let acl = Self::acl_contract(&env).ok_or(ContractError::NotInitialized)?;
+
+        let (mut added, mut removed) = (0u32, 0u32);
+        for candidate in candidates.iter() {
+            let permitted = Self::acl_allows(&env, acl.clone(), &candidate, symbol_short!("report"));
+            match (permitted, reporter_bitmap::contains(&env, &candidate)) {
+                (true, false) => {
+                    reporter_bitmap::set(&env, &candidate, true);
+                    env.storage()
+                        .persistent()
+                        .remove(&DataKey::ReporterRemoval(candidate));
+                    added += 1;
+                }
+                (false, true) => {
+                    reporter_bitmap::set(&env, &candidate, false);
+                    reset_index::track_reporter_record(&env, &candidate);
+                    env.storage().persistent().set(
+                        &DataKey::ReporterRemoval(candidate),
+                        &(env.ledger().timestamp(), admin.clone(), ACL_SYNC_REMOVAL_REASON),
+                    );
+                    removed += 1;
+                }
+                _ => {}
+            }
+        }
+
+        env.events()
+            .publish((Symbol::new(&env, "reporters_synced"),), (added, removed));
+        Ok((added, removed))
+    }
+
+    /// `(timestamp, remover, reason)` of the reporter's latest removal, if it
+    /// has not been re-added since
+    pub fn get_removal_reason(env: Env, reporter: Address) -> Option<(u64, Address, Symbol)> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ReporterRemoval(reporter))
+    }
+
+    pub fn set_user_trust_tier(
+        env: Env,
+        admin: Address,
+        user: Address,
+        tier: TrustTier,
+    ) -> Result<(), AuthorizationError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_user_trust_tier",
+            vec![&env, user.to_val(), tier.into_val(&env)],
+        )
+        .map_err(Self::authorization_error)?;
+        reset_index::track_tier(&env, &user);
+        RateLimiter::set_trust_tier(&env, &user, &tier);
+        Ok(())
+    }
+
+    /// Assign trust tiers to many users with a single admin authorization.
+    /// Accepts 1 to `get_max_batch_size` entries and returns how many were set.
+    pub fn set_user_trust_tiers(
+        env: Env,
+        admin: Address,
+        entries: Vec<(Address, TrustTier)>,
+    ) -> Result<u32, CommonError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_user_trust_tiers",
+            vec![&env, entries.into_val(&env)],
+        )
+        .map_err(Self::common_error)?;
+        require_within_batch_limit(&env, entries.len());
+        if entries.is_empty() {
+            return Err(CommonError::InvalidLength);
+        }
+        for (user, tier) in entries.iter() {
+            reset_index::track_tier(&env, &user);
+            RateLimiter::set_trust_tier(&env, &user, &tier);
+        }
+        env.events().publish((symbol_short!("tiers_set"),), entries.len());
+        Ok(entries.len())
+    }
+
+    /// Tier applied to users without an explicit trust tier
+    pub fn set_default_trust_tier(env: Env, admin: Address, tier: TrustTier) -> Result<(), AuthorizationError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_default_trust_tier",
+            vec![&env, tier.into_val(&env)],
+        )
+        .map_err(Self::authorization_error)?;
+        RateLimiter::set_default_trust_tier(&env, &tier);
+        Ok(())
+    }
+
+    /// `user`'s trust tier, or the default tier if none was set
+    pub fn get_user_trust_tier(env: Env, user: Address) -> TrustTier {
+        RateLimiter::get_trust_tier(&env, &user)
+    }
+
+    pub fn set_network_load(env: Env, admin: Address, load: u32) -> Result<(), AuthorizationError> {
+        Self::require_admin_action(&env, &admin, "set_network_load", vec![&env, load.into_val(&env)])
+            .map_err(Self::authorization_error)?;
+        RateLimiter::set_network_load(&env, load);
+        Ok(())
+    }
+
+    /// Spread fixed-window rate-limit resets by a per-reporter offset
+    pub fn set_window_jitter(env: Env, admin: Address, enabled: bool) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_window_jitter",
+            vec![&env, enabled.into_val(&env)],
+        )?;
+        RateLimiter::set_window_jitter(&env, enabled);
+        Ok(())
+    }
+
+    /// Log over-limit reports with `would_limit` instead of rejecting them
+    pub fn set_rate_limit_observation(env: Env, admin: Address, enabled: bool) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_rate_limit_observation",
+            vec![&env, enabled.into_val(&env)],
+        )?;
+        RateLimiter::set_observation_mode(&env, enabled);
+        Ok(())
+    }
+
+    /// Keep `category` (see `storage_optimization::CATEGORY_*`) in the
+    /// storage backing `temperature` from the next write on. Reports and
+    /// scores cannot be moved to the expiring `Frozen` tier.
+    pub fn set_storage_tier(
+        env: Env,
+        admin: Address,
+        category: Symbol,
+        temperature: DataTemperature,
+    ) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_storage_tier",
+            vec![&env, category.into_val(&env), temperature.into_val(&env)],
+        )?;
+        DataSeparator::set_storage_tier(&env, category, temperature)
+    }
+
+    /// Set the load → multiplier curve used by adaptive rate limits.
+    pub fn set_load_curve(env: Env, admin: Address, points: Vec<(u32, u32)>) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_load_curve",
+            vec![&env, points.into_val(&env)],
+        )?;
+        RateLimiter::set_load_curve(&env, &points)
+    }
+
+    /// Replace the rate limit of `action` (`submit_rpt`), or restore its
+    /// default with `None`.
+    pub fn set_rate_limit_override(
+        env: Env,
+        admin: Address,
+        action: Symbol,
+        config: Option<RateLimitConfig>,
+    ) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_rate_limit_override",
+            vec![&env, action.into_val(&env), config.into_val(&env)],
+        )?;
+        if action != Symbol::new(&env, "submit_rpt") {
+            return Err(ContractError::InvalidInput);
+        }
+        if let Some(config) = &config {
+            if config.max_requests == 0 || config.window_seconds == 0 {
+                return Err(ContractError::InvalidConfiguration);
+            }
+        }
+        RateLimiter::set_limit_override(&env, &action, config);
+        Ok(())
+    }
+
+    /// Limit `user` currently gets on `action`, or `None` if the action is
+    /// not rate limited
+    pub fn get_effective_rate_limit(env: Env, user: Address, action: Symbol) -> Option<EffectiveLimit> {
+        if action != Symbol::new(&env, "submit_rpt") {
+            return None;
+        }
+        Some(RateLimiter::effective_config(&env, &user, &action, Self::report_rate_limit(), true))
+    }
+
+    /// Fractional bits `get_aggregate_score` keeps of each reporter's
+    /// reliability-scaled weight. Fewer bits round small weights down,
+    /// down to nothing at 0.
+    pub fn set_fixed_point_precision(env: Env, admin: Address, frac_bits: u32) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_fixed_point_precision",
+            vec![&env, frac_bits.into_val(&env)],
+        )?;
+        fixed_point::set_precision(&env, frac_bits).map_err(|_| ContractError::InvalidConfiguration)
+    }
+
+    /// Toggle emission of the pre-versioning event shapes.
+    pub fn set_legacy_events(env: Env, admin: Address, enabled: bool) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_legacy_events",
+            vec![&env, enabled.into_val(&env)],
+        )?;
+        event_schema::set_legacy_events(&env, enabled);
+        Ok(())
+    }
+
+    /// Largest batch any batch entrypoint accepts, up to `batch::MAX_BATCH_SIZE`
+    pub fn set_max_batch_size(env: Env, admin: Address, n: u32) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_max_batch_size",
+            vec![&env, n.into_val(&env)],
+        )?;
+        common_utils::batch::set_max_batch_size(&env, n).map_err(|_| ContractError::InvalidConfiguration)
+    }
+
+    pub fn get_max_batch_size(env: Env) -> u32 {
+        common_utils::batch::max_batch_size(&env)
+    }
+
+    /// Emit per-item events from bulk operations alongside their summary
+    pub fn set_verbose_batch_events(env: Env, admin: Address, enabled: bool) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_verbose_batch_events",
+            vec![&env, enabled.into_val(&env)],
+        )?;
+        common_utils::batch::set_verbose_batch_events(&env, enabled);
+        Ok(())
+    }
+
+    /// Turn a named feature on or off. Flags nothing reads are accepted
+    /// and simply have no effect.
+    pub fn set_feature(env: Env, admin: Address, flag: Symbol, enabled: bool) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_feature",
+            vec![&env, flag.into_val(&env), enabled.into_val(&env)],
+        )?;
+        feature_flags::set_feature(&env, flag, enabled);
+        Ok(())
+    }
+
+    pub fn is_feature_enabled(env: Env, flag: Symbol) -> bool {
+        feature_flags::is_feature_enabled(&env, &flag)
+    }
+
+    /// Flags that are currently on
+    pub fn get_all_features(env: Env) -> Map<Symbol, bool> {
+        feature_flags::get_all_features(&env)
+    }
+
+    /// Prefix versioned event topics with `namespace`, or clear it
+    pub fn set_event_namespace(env: Env, admin: Address, namespace: Option<Symbol>) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_event_namespace",
+            vec![&env, namespace.into_val(&env)],
+        )?;
+        event_schema::set_event_namespace(&env, namespace);
+        Ok(())
+    }
+
+    pub fn submit_report(
+        env: Env,
+        reporter: Address,
+        agent_id: Symbol,
+        score: u32,
+        trading_evidence: Option<TradingEvidence>,
+    ) -> Result<(), ContractError> {
+        if Self::grace_queue_applies(&env, &reporter) {
+            reporter.require_auth();
+            // Only the rate limit is deferred; anything else fails now
+            Self::check_report(&env, &reporter, &agent_id, score).map_err(|r| r.raise(&env))?;
+            let mut queue = Self::get_report_queue(env.clone(), reporter.clone());
+            queue.push_back(QueuedReport {
+                agent_id,
+                score,
+                trading_evidence,
+                queued_at: env.ledger().timestamp(),
+            });
+            reset_index::track_queue(&env, &reporter);
+            env.storage()
+                .persistent()
+                .set(&DataKey::GraceQueue(reporter.clone()), &queue);
+            env.events().publish((symbol_short!("rpt_queue"), reporter), queue.len());
+            return Ok(());
+        }
+
+        Self::submit_unqueued(env, reporter, agent_id, score, trading_evidence)
+    }
+
+    /// `submit_report` without the grace queue: over the rate limit, the
+    /// submission fails.
+    fn submit_unqueued(
+        env: Env,
+        reporter: Address,
+        agent_id: Symbol,
+        score: u32,
+        trading_evidence: Option<TradingEvidence>,
+    ) -> Result<(), ContractError> {
+        rate_limit_adaptive!(env, reporter, "submit_rpt",
+            max: REPORT_RATE_MAX, window: REPORT_RATE_WINDOW,
+            strategy: SlidingWindow, scope: PerUser);
+
+        reporter.require_auth();
+        Self::accept_report(env.clone(), reporter, agent_id, score, trading_evidence)
+            .map_err(|r| r.raise(&env))
+    }
+
+    /// Submit `reporter`'s queued reports, oldest first, for as long as the
+    /// rate limit allows. Anyone may call this; the reporter authorised
+    /// each report when queueing it. Every check but the rate limit passed
+    /// when the report was queued and is run again here; a report that no
+    /// longer passes is dropped with a `queue_drop` event. Submitted
+    /// reports carry the drain time, not `queued_at`, so an agent's reports
+    /// stay in time order. Returns how many were submitted.
+    pub fn drain_queue(env: Env, reporter: Address) -> u32 {
+        let key = DataKey::GraceQueue(reporter.clone());
+        let mut queue = Self::get_report_queue(env.clone(), reporter.clone());
+        let function = Symbol::new(&env, "submit_rpt");
+        let limit = RateLimiter::resolve_config(&env, &function, Self::report_rate_limit());
+        let adaptive = RateLimiter::build_adaptive_config(&env, &reporter);
+
+        let mut drained = 0u32;
+        while let Some(queued) = queue.first() {
+            if RateLimiter::check_and_update_adaptive(&env, &reporter, &function, &limit, &adaptive).is_err() {
+                break;
+            }
+            queue.pop_front();
+            let agent_id = queued.agent_id.clone();
+            match Self::accept_report(env.clone(), reporter.clone(), queued.agent_id, queued.score, queued.trading_evidence) {
+                Ok(()) => drained += 1,
+                Err(_) => env
+                    .events()
+                    .publish((symbol_short!("queue_drop"), reporter.clone()), agent_id),
+            }
+        }
+
+        if queue.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &queue);
+        }
+        drained
+    }
+
+    /// Submissions waiting in `reporter`'s grace queue, oldest first
+    pub fn get_report_queue(env: Env, reporter: Address) -> Vec<QueuedReport> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::GraceQueue(reporter))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Let `TrustTier::Premium` reporters queue up to `len` over-limit
+    /// submissions instead of having them rejected. 0 turns queueing off.
+    pub fn set_grace_queue_len(env: Env, admin: Address, len: u32) -> Result<(), ContractError> {
+        Self::require_admin_action(&env, &admin, "set_grace_queue_len", vec![&env, len.into_val(&env)])?;
+        if len > MAX_GRACE_QUEUE_LEN {
+            return Err(ContractError::InvalidConfiguration);
+        }
+        let mut config = Self::load_config(&env);
+        config.grace_queue_len = len;
+        Self::save_config(&env, &config);
+        Ok(())
+    }
+
+    /// Whether a `submit_report` from `reporter` would be rate limited and
+    /// can be queued instead: the reporter is premium and its queue has room.
+    fn grace_queue_applies(env: &Env, reporter: &Address) -> bool {
+        let capacity = Self::load_config(env).grace_queue_len;
+        if capacity == 0
+            || RateLimiter::observation_mode_enabled(env)
+            || RateLimiter::get_trust_tier(env, reporter) != TrustTier::Premium
+        {
+            return false;
+        }
+        let function = Symbol::new(env, "submit_rpt");
+        let limit = RateLimiter::resolve_config(env, &function, Self::report_rate_limit());
+        let adaptive = RateLimiter::build_adaptive_config(env, reporter);
+        !RateLimiter::peek_adaptive(env, reporter, &function, &limit, &adaptive).allowed
+            && Self::get_report_queue(env.clone(), reporter.clone()).len() < capacity
+    }
+
+    /// Every `submit_report` check apart from the rate limit: permission,
+    /// approval, state, stake, agent, cooldown, score range and the report
+    /// cap. Only reads.
+    fn check_report(env: &Env, reporter: &Address, agent_id: &Symbol, score: u32) -> Result<(), ReportRejection> {
+        let acl = Self::acl_contract(env).ok_or(ContractError::NotInitialized)?;
+
+        if !Self::acl_allows(env, acl, reporter, symbol_short!("report")) {
+            return Err(ContractError::Unauthorized.into());
+        }
+        if Self::approval_expired(env, reporter) || Self::reporter_removed(env, reporter) {
+            return Err(ContractError::Unauthorized.into());
+        }
+
+        let state = Self::get_state(env);
+        if state.is_paused() || state.is_migrating() {
+            return Err(ContractError::InvalidState.into());
+        }
+        stake::require_min_stake(env, reporter)?;
+        if Self::load_config(env).require_registered_agents
+            && !Self::is_registered_agent(env.clone(), agent_id.clone())
+        {
+            return Err(ValidationError::UnknownAgent.into());
+        }
+        Self::check_report_cooldown(env, reporter, agent_id)?;
+        let config = Self::load_config(env);
+        if score > config.score_scale {
+            return Err(ContractError::InvalidInput.into());
+        }
+        let stored = CompressedReportStorage::get_reports(env, agent_id)
+            .ok()
+            .or_else(|| env.storage().instance().get::<_, Vec<FraudReport>>(&DataKey::Reports(agent_id.clone())))
+            .map(|reports| reports.len())
+            .unwrap_or(0);
+        let at_cap = config.max_reports_per_agent > 0 && stored >= config.max_reports_per_agent;
+        if at_cap && !config.evict_oldest_reports {
+            return Err(ValidationError::CapacityExceeded.into());
         }
-        RateLimiter::set_trust_tier(&env, &user, &tier);
         Ok(())
     }
 
-    pub fn set_network_load(env: Env, admin: Address, load: u32) -> Result<(), AuthorizationError> {
-        let stored_admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .ok_or(AuthorizationError::NotInitialized)?;
-        stored_admin.require_auth();
-        if stored_admin != admin {
-            return Err(AuthorizationError::NotAuthorized);
+    /// `submit_report` after its rate limit and auth checks
+    fn accept_report(
+        env: Env,
+        reporter: Address,
+        agent_id: Symbol,
+        score: u32,
+        trading_evidence: Option<TradingEvidence>,
+    ) -> Result<(), ReportRejection> {
+        Self::check_report(&env, &reporter, &agent_id, score)?;
+        let config = Self::load_config(&env);
+        let reports: Vec<FraudReport> = CompressedReportStorage::get_reports(&env, &agent_id)
+            .ok()
+            .or_else(|| env.storage().instance().get(&DataKey::Reports(agent_id.clone())))
+            .unwrap_or_else(|| Vec::new(&env));
+        let at_cap = config.max_reports_per_agent > 0
+            && reports.len() >= config.max_reports_per_agent;
+
+        // Tripping must not return an error, or the pause would be rolled
+        // back with the rest of the call; the offending report is dropped
+        // with a `report_dropped` event instead.
+        if Self::breaker_tripped(&env) {
+            env.events()
+                .publish((Symbol::new(&env, "report_dropped"), reporter), agent_id);
+            return Ok(());
         }
-        RateLimiter::set_network_load(&env, load);
+
+        let mut adjusted_score = score;
+
+        if let Some(evidence) = trading_evidence {
+            if let Ok(result) = Self::analyze_trading_for_fraud(env.clone(), evidence.pair.clone())
+            {
+                let risk_adjustment = match result.overall_risk.risk_level {
+                    RiskLevel::Critical => 20,
+                    RiskLevel::High => 10,
+                    RiskLevel::Medium => 5,
+                    RiskLevel::Low => 0,
+                };
+                adjusted_score = score.saturating_add(risk_adjustment).min(config.score_scale);
+            }
+        }
+
+        let report = FraudReport {
+            score: adjusted_score,
+            reporter: reporter.clone(),
+            timestamp: env.ledger().timestamp(),
+        };
+
+        // Evict mode: keep the newest `max - 1` so the new report fits
+        let evicted = if at_cap {
+            reports.len() - config.max_reports_per_agent + 1
+        } else {
+            0
+        };
+        let mut updated_reports = Vec::new(&env);
+        for existing_report in reports.iter().skip(evicted as usize) {
+            updated_reports.push_back(existing_report);
+        }
+        updated_reports.push_back(report.clone());
+        let (updated_reports, compacted) = Self::auto_compact(&env, &config, &agent_id, updated_reports)?;
+        let evicted = evicted + compacted;
+        let retained_total = Self::get_total_reports(env.clone()).saturating_sub(evicted as u64);
+
+        // Everything above only reads (apart from the rate limiter and the
+        // breaker, which must persist regardless). The writes below form one
+        // group, fallible steps first, and `verify_report_write` checks the
+        // result before the report is announced.
+        CompressedReportStorage::store_reports(&env, &agent_id, &updated_reports)
+            .map_err(|_| ContractError::StorageFull)?;
+        CompressedReportStorage::update_latest_score(&env, &agent_id, adjusted_score)
+            .map_err(|_| ContractError::StorageFull)?;
+        Self::write_report_metadata(&env, &agent_id, &updated_reports);
+        if evicted > 0 {
+            env.storage().instance().set(&DataKey::TotalReports, &retained_total);
+        }
+
+        StorageTracker::record_operation(&env, &symbol_short!("store"), &agent_id, 44, true);
+        Self::record_report(&env, &agent_id);
+        if config.report_chain_enabled {
+            Self::extend_report_chain(&env, &agent_id, &report);
+        }
+        stake::record_report(&env, &reporter);
+        reset_index::track_report(&env, &reporter, &agent_id);
+        env.storage().persistent().set(
+            &DataKey::LastReport(reporter.clone(), agent_id.clone()),
+            &env.ledger().timestamp(),
+        );
+
+        Self::verify_report_write(&env, &agent_id, updated_reports.len(), retained_total.saturating_add(1));
+        if adjusted_score >= Self::flag_threshold(&config) {
+            Self::flag_agent(&env, &config, &agent_id, &report);
+        }
+
+        emit_versioned_for(
+            &env,
+            symbol_short!("fraud_rpt"),
+            REPORT_EVENT_VERSION,
+            agent_id.clone(),
+            (reporter.clone(), adjusted_score, env.ledger().timestamp()),
+        );
+
+        // Compliance audit log – fraud report / flagging decision
+        if ComplianceLogger::is_initialized(&env) {
+            let score_bytes = Bytes::from_slice(&env, &adjusted_score.to_le_bytes());
+            let target_bytes = Bytes::from_slice(&env, agent_id.to_string().as_bytes());
+            let action = if adjusted_score >= Self::flag_threshold(&config) {
+                ComplianceAction::FraudFlagged
+            } else {
+                ComplianceAction::RiskEvaluated
+            };
+            ComplianceLogger::append(
+                &env,
+                reporter.clone(),
+                action,
+                target_bytes,
+                Bytes::new(&env),
+                score_bytes,
+                BytesN::from_array(&env, &[0u8; 32]),
+            );
+        }
+
         Ok(())
     }
 
-    pub fn submit_report(
+    /// The commitment `commit_report` expects for a later `reveal_report`:
+    /// sha256 of the canonical message `Address(reporter)`,
+    /// `Bytes(agent_id as XDR)`, `U32(score)`, `Hash(salt)`. Binding the
+    /// reporter and agent stops a commitment being replayed by another
+    /// reporter or for another agent.
+    pub fn report_commitment(
         env: Env,
         reporter: Address,
         agent_id: Symbol,
         score: u32,
-        trading_evidence: Option<TradingEvidence>,
-    ) -> Result<(), ContractError> {
-        rate_limit_adaptive!(env, reporter, "submit_rpt",
-            max: 10, window: 3600,
-            strategy: SlidingWindow, scope: PerUser);
+        salt: BytesN<32>,
+    ) -> BytesN<32> {
+        let message = encode_message(
+            &env,
+            &[
+                MessageField::Address(reporter),
+                MessageField::Bytes(agent_id.to_xdr(&env)),
+                MessageField::U32(score),
+                MessageField::Hash(salt),
+            ],
+        )
+        .unwrap_or_else(|err| panic_with_error!(&env, err));
+        env.crypto().sha256(&message).into()
+    }
 
+    /// First half of a hidden report: store `commitment` (see
+    /// `report_commitment`) for `agent_id`, so other reporters cannot copy
+    /// the score before it is revealed. The first commitment for an agent
+    /// opens a round whose commit phase lasts `commit_window`; later ones
+    /// join it until it closes. A live commitment must be revealed or
+    /// expire before the reporter can commit again for the same agent.
+    pub fn commit_report(
+        env: Env,
+        reporter: Address,
+        agent_id: Symbol,
+        commitment: BytesN<32>,
+    ) -> Result<ReportCommitment, ContractError> {
         reporter.require_auth();
+        let acl = Self::acl_contract(&env).ok_or(ContractError::NotInitialized)?;
+        if !Self::acl_allows(&env, acl, &reporter, symbol_short!("report")) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        let now = env.ledger().timestamp();
+        let key = DataKey::ReportCommitment(reporter.clone(), agent_id.clone());
+        if let Some(existing) = env.storage().persistent().get::<_, ReportCommitment>(&key) {
+            if now <= existing.reveal_by {
+                return Err(ContractError::InvalidState);
+            }
+        }
+
+        let round_key = DataKey::CommitRound(agent_id.clone());
+        let round = match env.storage().persistent().get::<_, CommitRound>(&round_key) {
+            Some(round) if now < round.commit_ends => round,
+            // Revealing is under way; commitments wait for the next round
+            Some(round) if now <= round.reveal_ends => return Err(ContractError::InvalidState),
+            _ => {
+                let config = Self::load_config(&env);
+                let commit_ends = now.saturating_add(config.commit_window);
+                let round = CommitRound {
+                    commit_ends,
+                    reveal_ends: commit_ends.saturating_add(config.reveal_window),
+                };
+                env.storage().persistent().set(&round_key, &round);
+                round
+            }
+        };
+
+        let committed = ReportCommitment {
+            commitment,
+            committed_at: now,
+            reveal_from: round.commit_ends,
+            reveal_by: round.reveal_ends,
+        };
+        reset_index::track_commitment(&env, &reporter, &agent_id);
+        env.storage().persistent().set(&key, &committed);
+        env.events().publish(
+            (Symbol::new(&env, "report_committed"), reporter, agent_id),
+            committed.reveal_by,
+        );
+        Ok(committed)
+    }
+
+    /// Second half of a hidden report: check `score` and `salt` against the
+    /// reporter's commitment and submit the report as `submit_report` would,
+    /// except that it is never put in the grace queue: over the rate limit
+    /// the reveal fails. Fails with `NotFound` without a commitment, `InvalidState` before its
+    /// `reveal_from` or after its `reveal_by`, and `InvalidInput` when it
+    /// does not match.
+    pub fn reveal_report(
+        env: Env,
+        reporter: Address,
+        agent_id: Symbol,
+        score: u32,
+        salt: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        let key = DataKey::ReportCommitment(reporter.clone(), agent_id.clone());
+        let committed: ReportCommitment = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(ContractError::NotFound)?;
+        let now = env.ledger().timestamp();
+        if now < committed.reveal_from || now > committed.reveal_by {
+            return Err(ContractError::InvalidState);
+        }
+        let expected = Self::report_commitment(env.clone(), reporter.clone(), agent_id.clone(), score, salt);
+        if expected != committed.commitment {
+            return Err(ContractError::InvalidInput);
+        }
+
+        env.storage().persistent().remove(&key);
+        // A reveal must land inside its phase, so it is never queued
+        Self::submit_unqueued(env, reporter, agent_id, score, None)
+    }
+
+    pub fn get_report_commitment(env: Env, reporter: Address, agent_id: Symbol) -> Option<ReportCommitment> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ReportCommitment(reporter, agent_id))
+    }
+
+    pub fn get_commit_round(env: Env, agent_id: Symbol) -> Option<CommitRound> {
+        env.storage().persistent().get(&DataKey::CommitRound(agent_id))
+    }
+
+    /// Delete `reporter`'s commitment for `agent_id` once its reveal phase
+    /// has passed unrevealed, and the agent's round once it is over. Anyone
+    /// may call this; returns whether the commitment was removed.
+    pub fn clear_expired_commitment(env: Env, reporter: Address, agent_id: Symbol) -> bool {
+        let now = env.ledger().timestamp();
+        let round_key = DataKey::CommitRound(agent_id.clone());
+        if let Some(round) = env.storage().persistent().get::<_, CommitRound>(&round_key) {
+            if now > round.reveal_ends {
+                env.storage().persistent().remove(&round_key);
+            }
+        }
+        let key = DataKey::ReportCommitment(reporter, agent_id);
+        match env.storage().persistent().get::<_, ReportCommitment>(&key) {
+            Some(committed) if now > committed.reveal_by => {
+                env.storage().persistent().remove(&key);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Seconds an agent's commit phase stays open. Must be positive.
+    pub fn set_commit_window(env: Env, admin: Address, secs: u64) -> Result<(), ContractError> {
+        Self::require_admin_action(&env, &admin, "set_commit_window", vec![&env, secs.into_val(&env)])?;
+        if secs == 0 {
+            return Err(ContractError::InvalidConfiguration);
+        }
+        let mut config = Self::load_config(&env);
+        config.commit_window = secs;
+        Self::save_config(&env, &config);
+        Ok(())
+    }
+
+    /// Seconds a commitment stays revealable. Must be positive.
+    pub fn set_reveal_window(env: Env, admin: Address, secs: u64) -> Result<(), ContractError> {
+        Self::require_admin_action(&env, &admin, "set_reveal_window", vec![&env, secs.into_val(&env)])?;
+        if secs == 0 {
+            return Err(ContractError::InvalidConfiguration);
+        }
+        let mut config = Self::load_config(&env);
+        config.reveal_window = secs;
+        Self::save_config(&env, &config);
+        Ok(())
+    }
+
+    /// Whether `submit_report` from `reporter` for `agent_id` would pass
+    /// its rate-limit, permission, state, stake, agent and cooldown checks
+    /// right now, with the first one that fails. Read-only; the score range
+    /// and the per-agent cap depend on the report and are not checked.
+    pub fn can_submit_report(env: Env, reporter: Address, agent_id: Symbol) -> SubmitEligibility {
+        let blocker = Self::submit_blocker(&env, &reporter, &agent_id);
+        SubmitEligibility {
+            allowed: blocker.is_none(),
+            reason: blocker.unwrap_or(SUBMIT_OK),
+        }
+    }
+
+    /// Reporters registered through `add_reporter`, in the order they were
+    /// first approved. A reporter removed and approved again keeps its
+    /// original position; before the bitmap it moved to the end.
+    pub fn get_reporters(env: Env) -> Vec<Address> {
+        reporter_bitmap::list(&env)
+    }
+
+    /// Whether `reporter` is currently approved
+    pub fn is_reporter(env: Env, reporter: Address) -> bool {
+        reporter_bitmap::contains(&env, &reporter)
+    }
+
+    pub fn get_total_reports(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalReports)
+            .unwrap_or(0)
+    }
 
-        let acl: Address = env
+    /// SHA-256 over the XDR of every agent's stored reports, in the order
+    /// agents were first reported. Compare against the digest carried by
+    /// `export_state` to check that reports were copied intact.
+    pub fn get_report_digest(env: Env) -> BytesN<32> {
+        let agents: Vec<Symbol> = env
             .storage()
             .instance()
-            .get(&DataKey::AclContract)
-            .ok_or(ContractError::NotInitialized)?;
+            .get(&DataKey::AgentIndex)
+            .unwrap_or_else(|| Vec::new(&env));
+        let mut buf = Bytes::new(&env);
+        for agent_id in agents.iter() {
+            let reports = CompressedReportStorage::get_reports(&env, &agent_id)
+                .unwrap_or_else(|_| Vec::new(&env));
+            buf.append(&(agent_id, reports).to_xdr(&env));
+        }
+        env.crypto().sha256(&buf).into()
+    }
 
-        if !common_utils::check_permission(
-            env.clone(),
-            acl,
-            reporter.clone(),
-            symbol_short!("fraud"),
-            symbol_short!("report"),
-        ) {
-            return Err(ContractError::Unauthorized);
+    /// Running `sha256(prev_head || xdr((agent_id, report)))` over every
+    /// report accepted while the chain is enabled, starting from 32 zero
+    /// bytes. Pruning and eviction leave it untouched.
+    pub fn get_report_chain_head(env: Env) -> BytesN<32> {
+        env.storage()
+            .instance()
+            .get(&DataKey::ReportChainHead)
+            .unwrap_or_else(|| BytesN::from_array(&env, &[0u8; 32]))
+    }
+
+    /// Report digest of the source contract, set by `import_state`
+    pub fn get_imported_report_digest(env: Env) -> Option<BytesN<32>> {
+        env.storage().instance().get(&DataKey::ImportedReportDigest)
+    }
+
+    /// Reports and their metadata in one read. Agents written before
+    /// metadata was kept get it computed from the reports. Private agents
+    /// read as having no reports, like `get_reports`.
+    pub fn get_reports_with_metadata(env: Env, agent_id: Symbol) -> (Vec<FraudReport>, ReportMetadata) {
+        if Self::is_private_agent(&env, &agent_id) {
+            let reports = Vec::new(&env);
+            let metadata = Self::report_metadata(&env, &agent_id, &reports);
+            return (reports, metadata);
+        }
+        let reports = CompressedReportStorage::get_reports(&env, &agent_id)
+            .unwrap_or_else(|_| Vec::new(&env));
+        let metadata = env
+            .storage()
+            .instance()
+            .get(&DataKey::ReportsMetadata(agent_id.clone()))
+            .unwrap_or_else(|| Self::report_metadata(&env, &agent_id, &reports));
+        (reports, metadata)
+    }
+
+    /// Reports for `agent_id`; empty for private agents, see
+    /// `set_agent_visibility` and `get_reports_as`
+    pub fn get_reports(env: Env, agent_id: Symbol) -> Vec<FraudReport> {
+        if Self::is_private_agent(&env, &agent_id) {
+            return Vec::new(&env);
+        }
+        Self::reports_of(&env, &agent_id)
+    }
+
+    /// `get_reports` on behalf of `caller`, who must authorise the read.
+    /// With read audit on, emits `data_accessed` with `(agent_id,
+    /// timestamp)`.
+    pub fn get_reports_audited(env: Env, caller: Address, agent_id: Symbol) -> Vec<FraudReport> {
+        caller.require_auth();
+        if Self::load_config(&env).read_audit {
+            env.events().publish(
+                (Symbol::new(&env, "data_accessed"), caller),
+                (agent_id.clone(), env.ledger().timestamp()),
+            );
+        }
+        Self::get_reports(env, agent_id)
+    }
+
+    /// Turn `data_accessed` events from audited reads on or off
+    pub fn set_read_audit(env: Env, admin: Address, enabled: bool) -> Result<(), ContractError> {
+        Self::require_admin_action(&env, &admin, "set_read_audit", vec![&env, enabled.into_val(&env)])?;
+        let mut config = Self::load_config(&env);
+        config.read_audit = enabled;
+        Self::save_config(&env, &config);
+        Ok(())
+    }
+
+    /// `get_reports` for a `viewer` allowed to see private agents: the
+    /// admin or holders of `fraud/view`
+    pub fn get_reports_as(env: Env, viewer: Address, agent_id: Symbol) -> Result<Vec<FraudReport>, ContractError> {
+        Self::require_viewer(&env, &viewer, &agent_id)?;
+        Ok(Self::reports_of(&env, &agent_id))
+    }
+
+    /// Hide `agent_id`'s reports and scores from public reads (`public =
+    /// false`) or show them again. Agents are public by default.
+    pub fn set_agent_visibility(env: Env, admin: Address, agent_id: Symbol, public: bool) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_agent_visibility",
+            vec![&env, agent_id.into_val(&env), public.into_val(&env)],
+        )?;
+        let key = DataKey::PrivateAgent(agent_id.clone());
+        if public {
+            env.storage().persistent().remove(&key);
+        } else {
+            reset_index::track_private_agent(&env, &agent_id);
+            env.storage().persistent().set(&key, &true);
+        }
+        env.events()
+            .publish((Symbol::new(&env, "agent_visibility"), agent_id), public);
+        Ok(())
+    }
+
+    pub fn is_agent_public(env: Env, agent_id: Symbol) -> bool {
+        !Self::is_private_agent(&env, &agent_id)
+    }
+
+    fn reports_of(env: &Env, agent_id: &Symbol) -> Vec<FraudReport> {
+        let _timer = PerformanceMonitor::start_timer(env, &symbol_short!("get_reports"));
+
+        let result = CompressedReportStorage::get_reports(env, agent_id)
+            .unwrap_or_else(|_| Vec::new(env));
+
+        StorageTracker::record_operation(env, &symbol_short!("access"), agent_id, 0, false);
+
+        let _duration = PerformanceMonitor::end_timer(env, &symbol_short!("get_reports"));
+
+        result
+    }
+
+    /// Paged variant of `get_reports` for agents with many reports.
+    /// `limit` is clamped to `pagination::MAX_PAGE_SIZE`.
+    pub fn get_reports_page(env: Env, agent_id: Symbol, offset: u32, limit: u32) -> ReportPage {
+        if Self::is_private_agent(&env, &agent_id) {
+            return ReportPage { items: Vec::new(&env), total: 0, has_more: false };
+        }
+        let page = CompressedReportStorage::page(&env, &agent_id, offset, limit);
+
+        StorageTracker::record_operation(&env, &symbol_short!("access"), &agent_id, 0, false);
+
+        ReportPage {
+            items: page.items,
+            total: page.total,
+            has_more: page.has_more,
+        }
+    }
+
+    /// Agents with stored reports, in the order they were first reported.
+    /// `limit` is clamped to `pagination::MAX_PAGE_SIZE`.
+    pub fn get_all_agents(env: Env, offset: u32, limit: u32) -> Vec<Symbol> {
+        let agents: Vec<Symbol> = env
+            .storage()
+            .instance()
+            .get(&DataKey::AgentIndex)
+            .unwrap_or_else(|| Vec::new(&env));
+        pagination::paginate(&env, &agents, offset, limit).items
+    }
+
+    pub fn agent_count(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get::<_, Vec<Symbol>>(&DataKey::AgentIndex)
+            .map(|agents| agents.len())
+            .unwrap_or(0)
+    }
+
+    /// Cap the compressed size of every agent's report blob at `max_bytes`;
+    /// see `auto_compact`. 0 turns compaction off.
+    pub fn set_auto_compact(env: Env, admin: Address, max_bytes: u32) -> Result<(), ContractError> {
+        Self::require_admin_action(&env, &admin, "set_auto_compact", vec![&env, max_bytes.into_val(&env)])?;
+        let mut config = Self::load_config(&env);
+        config.auto_compact_bytes = max_bytes;
+        Self::save_config(&env, &config);
+        Ok(())
+    }
+
+    /// Drop `agent_id`'s reports older than `before` and return how many were
+    /// removed. An agent left with no reports is removed from the agent index.
+    pub fn prune_reports(env: Env, admin: Address, agent_id: Symbol, before: u64) -> Result<u32, ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "prune_reports",
+            vec![&env, agent_id.into_val(&env), before.into_val(&env)],
+        )?;
+
+        let reports = CompressedReportStorage::get_reports(&env, &agent_id)
+            .unwrap_or_else(|_| Vec::new(&env));
+        let mut kept = Vec::new(&env);
+        for report in reports.iter() {
+            if report.timestamp >= before {
+                kept.push_back(report);
+            }
+        }
+        let pruned = reports.len() - kept.len();
+        if pruned == 0 {
+            return Ok(0);
+        }
+
+        if kept.is_empty() {
+            CompressedReportStorage::remove_reports(&env, &agent_id);
+            Self::unindex_agent(&env, &agent_id);
+        } else {
+            CompressedReportStorage::store_reports(&env, &agent_id, &kept)
+                .map_err(|_| ContractError::StorageFull)?;
+        }
+        Self::write_report_metadata(&env, &agent_id, &kept);
+
+        let total = Self::get_total_reports(env.clone()).saturating_sub(pruned as u64);
+        env.storage().instance().set(&DataKey::TotalReports, &total);
+        stats::invalidate(&env);
+        Ok(pruned)
+    }
+
+    /// Recovery read: reports for reported agents `[offset, offset + limit)`,
+    /// in the order agents were first reported. Only checks the admin, so it
+    /// keeps working while the contract is paused or terminated.
+    /// `limit` is clamped to `pagination::MAX_PAGE_SIZE`.
+    pub fn export_all_reports(
+        env: Env,
+        admin: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Result<Vec<(Symbol, Vec<FraudReport>)>, ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "export_all_reports",
+            vec![&env, offset.into_val(&env), limit.into_val(&env)],
+        )?;
+
+        let agents: Vec<Symbol> = env
+            .storage()
+            .instance()
+            .get(&DataKey::AgentIndex)
+            .unwrap_or_else(|| Vec::new(&env));
+        let page = pagination::paginate(&env, &agents, offset, limit);
+
+        let mut out = Vec::new(&env);
+        for agent_id in page.items.iter() {
+            let reports = CompressedReportStorage::get_reports(&env, &agent_id)
+                .unwrap_or_else(|_| Vec::new(&env));
+            out.push_back((agent_id, reports));
+        }
+        Ok(out)
+    }
+
+    /// Agent, report and flag totals. Cached until the next report, flag or
+    /// prune, so repeated reads do not walk the agent index.
+    pub fn get_global_stats(env: Env) -> GlobalFraudStats {
+        stats::get(&env)
+    }
+
+    /// Storage totals across all recorded operations
+    pub fn get_storage_summary(env: Env) -> StorageSummary {
+        StorageTracker::summary(&env)
+    }
+
+    /// Whether `account` was flagged through `batch_flag_fraud` and its
+    /// dispute window, if any, has passed
+    pub fn is_flagged(env: Env, account: Address) -> bool {
+        env.storage().instance().has(&DataKey::FlaggedAccount(account.clone()))
+            && Self::flag_pending_until(env.clone(), account)
+                .map_or(true, |until| env.ledger().timestamp() >= until)
+    }
+
+    /// End of the dispute window of `account`'s flag, if it had one
+    pub fn flag_pending_until(env: Env, account: Address) -> Option<u64> {
+        env.storage().instance().get(&DataKey::PendingFlag(account))
+    }
+
+    /// Whether a report of at least `flag_threshold` flagged `agent_id` and
+    /// its dispute window, if any, has passed
+    pub fn is_agent_flagged(env: Env, agent_id: Symbol) -> bool {
+        env.storage().instance().has(&DataKey::FlaggedAgent(agent_id.clone()))
+            && Self::agent_flag_pending_until(env.clone(), agent_id)
+                .map_or(true, |until| env.ledger().timestamp() >= until)
+    }
+
+    /// End of the dispute window of `agent_id`'s flag, if it had one
+    pub fn agent_flag_pending_until(env: Env, agent_id: Symbol) -> Option<u64> {
+        env.storage().instance().get(&DataKey::PendingAgentFlag(agent_id))
+    }
+
+    /// Cancel `agent_id`'s flag after an upheld dispute. Only possible
+    /// while the flag is still pending.
+    pub fn uphold_agent_flag_dispute(env: Env, admin: Address, agent_id: Symbol) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "uphold_agent_flag_dispute",
+            vec![&env, agent_id.into_val(&env)],
+        )?;
+        let pending = Self::agent_flag_pending_until(env.clone(), agent_id.clone())
+            .filter(|until| env.ledger().timestamp() < *until)
+            .is_some();
+        if !pending || !env.storage().instance().has(&DataKey::FlaggedAgent(agent_id.clone())) {
+            return Err(ContractError::InvalidState);
+        }
+        env.storage().instance().remove(&DataKey::FlaggedAgent(agent_id.clone()));
+        env.storage().instance().remove(&DataKey::PendingAgentFlag(agent_id.clone()));
+        env.events().publish((symbol_short!("flag_cncl"), agent_id), env.ledger().timestamp());
+        Ok(())
+    }
+
+    /// Defer new flags by `secs` so they can be disputed first. 0 flags
+    /// immediately. Flags already raised keep their window.
+    pub fn set_dispute_window(env: Env, admin: Address, secs: u64) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_dispute_window",
+            vec![&env, secs.into_val(&env)],
+        )?;
+        let mut config = Self::load_config(&env);
+        config.dispute_window = secs;
+        Self::save_config(&env, &config);
+        Ok(())
+    }
+
+    /// Cancel `account`'s flag after an upheld dispute. Only possible while
+    /// the flag is still pending.
+    pub fn uphold_flag_dispute(env: Env, admin: Address, account: Address) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "uphold_flag_dispute",
+            vec![&env, account.into_val(&env)],
+        )?;
+        let pending = Self::flag_pending_until(env.clone(), account.clone())
+            .filter(|until| env.ledger().timestamp() < *until)
+            .is_some();
+        if !pending || !env.storage().instance().has(&DataKey::FlaggedAccount(account.clone())) {
+            return Err(ContractError::InvalidState);
         }
+        env.storage().instance().remove(&DataKey::FlaggedAccount(account.clone()));
+        env.storage().instance().remove(&DataKey::PendingFlag(account.clone()));
+        stats::record_unflag(&env);
+        env.events().publish((symbol_short!("flag_cncl"), account), env.ledger().timestamp());
+        Ok(())
+    }
 
-        let mut reports: Vec<FraudReport> = env
-            .storage()
-            .instance()
-            .get(&DataKey::Reports(agent_id.clone()))
-            .unwrap_or(Vec::new(&env));
+    /// `get_latest_score` that refuses to answer while a migration is
+    /// rebuilding the latest-score cache.
+    pub fn get_latest_score_checked(env: Env, agent_id: Symbol) -> Result<u32, StateError> {
+        state_guard!(Self, &env, not_migrating);
+        Ok(Self::get_latest_score(env, agent_id))
+    }
 
-        let mut adjusted_score = score;
+    /// Latest score of `agent_id`; 0 for private agents, see
+    /// `get_latest_score_as`
+    pub fn get_latest_score(env: Env, agent_id: Symbol) -> u32 {
+        if Self::is_private_agent(&env, &agent_id) {
+            return 0;
+        }
+        Self::latest_score_of(&env, &agent_id)
+    }
 
-        if let Some(evidence) = trading_evidence {
-            if let Ok(result) = Self::analyze_trading_for_fraud(env.clone(), evidence.pair.clone())
-            {
-                let risk_adjustment = match result.overall_risk.risk_level {
-                    RiskLevel::Critical => 20,
-                    RiskLevel::High => 10,
-                    RiskLevel::Medium => 5,
-                    RiskLevel::Low => 0,
-                };
-                adjusted_score = (score + risk_adjustment).min(100);
-            }
+    /// Latest score of `agent_id`, or `None` when it has no reports or is
+    /// private. Unlike `get_latest_score`, a genuine score of 0 is
+    /// `Some(0)`. Named `find_` because the generated client already has a
+    /// `try_get_latest_score`.
+    pub fn find_latest_score(env: Env, agent_id: Symbol) -> Option<u32> {
+        if Self::is_private_agent(&env, &agent_id) {
+            return None;
         }
+        CompressedReportStorage::get_latest_score(&env, &agent_id).ok()
+    }
 
-        let report = FraudReport {
-            score: adjusted_score,
-            reporter: reporter.clone(),
-            timestamp: env.ledger().timestamp(),
-        };
+    /// `get_latest_score` for a `viewer` allowed to see private agents
+    pub fn get_latest_score_as(env: Env, viewer: Address, agent_id: Symbol) -> Result<u32, ContractError> {
+        Self::require_viewer(&env, &viewer, &agent_id)?;
+        Ok(Self::latest_score_of(&env, &agent_id))
+    }
 
-        let mut updated_reports = Vec::new(&env);
-        for existing_report in reports.iter() {
-            updated_reports.push_back(existing_report);
-        }
-        updated_reports.push_back(report);
+    fn latest_score_of(env: &Env, agent_id: &Symbol) -> u32 {
+        let _timer = PerformanceMonitor::start_timer(env, &symbol_short!("get_latest_score"));
 
-        CompressedReportStorage::store_reports(&env, &agent_id, &updated_reports)
-            .map_err(|_| ContractError::StorageFull)?;
+        let result = CompressedReportStorage::get_latest_score(env, agent_id).unwrap_or(0);
 
-        CompressedReportStorage::update_latest_score(&env, &agent_id, adjusted_score)
-            .map_err(|_| ContractError::StorageFull)?;
+        StorageTracker::record_operation(env, &symbol_short!("access"), agent_id, 0, false);
 
-        StorageTracker::record_operation(&env, &symbol_short!("store"), &agent_id, 44, true);
+        let _duration = PerformanceMonitor::end_timer(env, &symbol_short!("get_latest_score"));
 
-        env.events().publish(
-            (symbol_short!("fraud_rpt"), agent_id.clone()),
-            (reporter.clone(), adjusted_score, env.ledger().timestamp()),
-        );
+        result
+    }
 
-        // Compliance audit log – fraud report / flagging decision
-        if ComplianceLogger::is_initialized(&env) {
-            let score_bytes = Bytes::from_slice(&env, &adjusted_score.to_le_bytes());
-            let target_bytes = Bytes::from_slice(&env, agent_id.to_string().as_bytes());
-            let action = if adjusted_score >= 70 {
-                ComplianceAction::FraudFlagged
+    /// `get_latest_score` of each of `agent_ids`, in order, 0 for unknown
+    /// agents. Accepts up to `get_max_batch_size` ids.
+    pub fn get_latest_scores(env: Env, agent_ids: Vec<Symbol>) -> Result<Vec<u32>, ContractError> {
+        require_within_batch_limit(&env, agent_ids.len());
+        let timer_name = Symbol::new(&env, "get_latest_scores");
+        let _timer = PerformanceMonitor::start_timer(&env, &timer_name);
+
+        let mut scores = Vec::new(&env);
+        for agent_id in agent_ids.iter() {
+            let score = if Self::is_private_agent(&env, &agent_id) {
+                0
             } else {
-                ComplianceAction::RiskEvaluated
+                CompressedReportStorage::get_latest_score(&env, &agent_id).unwrap_or(0)
             };
-            ComplianceLogger::append(
-                &env,
-                reporter.clone(),
-                action,
-                target_bytes,
-                Bytes::new(&env),
-                score_bytes,
-                BytesN::from_array(&env, &[0u8; 32]),
-            );
+            scores.push_back(score);
         }
 
-        Ok(())
-    }
-
-    pub fn get_reports(env: Env, agent_id: Symbol) -> Vec<FraudReport> {
-        let _timer = PerformanceMonitor::start_timer(&env, &symbol_short!("get_reports"));
-
-        let result = CompressedReportStorage::get_reports(&env, &agent_id)
-            .unwrap_or_else(|_| Vec::new(&env));
-
-        StorageTracker::record_operation(&env, &symbol_short!("access"), &agent_id, 0, false);
+        let _duration = PerformanceMonitor::end_timer(&env, &timer_name);
 
-        let _duration = PerformanceMonitor::end_timer(&env, &symbol_short!("get_reports"));
+        Ok(scores)
+    }
 
-        result
+    /// Multiply `reporter`'s reports by `weight` in `get_aggregate_score`.
+    /// Reporters default to 1; 0 leaves them out of the aggregate.
+    pub fn set_reporter_weight(env: Env, admin: Address, reporter: Address, weight: u32) -> Result<(), ContractError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "set_reporter_weight",
+            vec![&env, reporter.into_val(&env), weight.into_val(&env)],
+        )?;
+        reset_index::track_weight(&env, &reporter);
+        env.storage()
+            .persistent()
+            .set(&DataKey::ReporterWeight(reporter.clone()), &weight);
+        env.events().publish((symbol_short!("rpt_wt"), reporter), weight);
+        Ok(())
     }
 
-    pub fn get_latest_score(env: Env, agent_id: Symbol) -> u32 {
-        let _timer = PerformanceMonitor::start_timer(&env, &symbol_short!("get_latest_score"));
+    pub fn get_reporter_weight(env: Env, reporter: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ReporterWeight(reporter))
+            .unwrap_or(1)
+    }
 
-        let result = CompressedReportStorage::get_latest_score(&env, &agent_id).unwrap_or(0);
+    /// Set how `get_aggregate_score` resolves a mean that is not a whole
+    /// score. Defaults to `TieBreak::Latest`.
+    pub fn set_tiebreak(env: Env, admin: Address, policy: TieBreak) -> Result<(), ContractError> {
+        Self::require_admin_action(&env, &admin, "set_tiebreak", vec![&env, policy.into_val(&env)])?;
+        let mut config = Self::load_config(&env);
+        config.tiebreak = policy;
+        Self::save_config(&env, &config);
+        Ok(())
+    }
 
-        StorageTracker::record_operation(&env, &symbol_short!("access"), &agent_id, 0, false);
+    pub fn get_tiebreak(env: Env) -> TieBreak {
+        Self::load_config(&env).tiebreak
+    }
 
-        let _duration = PerformanceMonitor::end_timer(&env, &symbol_short!("get_latest_score"));
+    /// Weighted mean of every stored report for `agent_id`, each weighted by
+    /// its reporter's `get_reporter_weight` and, with
+    /// `FEATURE_RELIABILITY_WEIGHTS` on, also by its reliability, one over
+    /// one more than its upheld disputes. Weights are fixed-point numbers
+    /// with `set_fixed_point_precision` fractional bits, truncated, so a
+    /// reliability below one can round a weight to 0. 0 with no weighted
+    /// reports. A
+    /// mean between two whole scores is resolved by `get_tiebreak`, so
+    /// equally weighted reports of 20 and 81 give 51 under `Highest`, 50
+    /// under `Lowest` and under `Latest` whichever side the later report
+    /// is on.
+    pub fn get_aggregate_score(env: Env, agent_id: Symbol) -> u32 {
+        if Self::is_private_agent(&env, &agent_id) {
+            return 0;
+        }
+        let reports = CompressedReportStorage::get_reports(&env, &agent_id)
+            .unwrap_or_else(|_| Vec::new(&env));
+        let with_reliability = feature_flags::is_feature_enabled(&env, &FEATURE_RELIABILITY_WEIGHTS);
+        let precision = fixed_point::precision(&env);
+        let mut weighted_sum: i128 = 0;
+        let mut total_weight: i128 = 0;
+        let mut latest_score = 0u32;
+        for report in reports.iter() {
+            let mut weight = FixedPoint::from_int(
+                Self::get_reporter_weight(env.clone(), report.reporter.clone()) as i128,
+                precision,
+            );
+            if with_reliability {
+                let upheld: u32 = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::UpheldDisputeCount(report.reporter.clone()))
+                    .unwrap_or(0);
+                weight = weight.saturating_mul(FixedPoint::from_ratio(1, upheld as i128 + 1, precision));
+            }
+            let weight = weight.raw();
+            // Each term is below 2^(64 + precision), so only an absurd
+            // report count, or extreme weights near the top precision, can
+            // overflow; trap rather than report a skewed mean
+            let (Ok(sum), Ok(total)) = (
+                math::checked_mul_i128(report.score as i128, weight)
+                    .and_then(|term| math::checked_add_i128(weighted_sum, term)),
+                math::checked_add_i128(total_weight, weight),
+            ) else {
+                panic_with_error!(&env, CommonError::OutOfRange);
+            };
+            weighted_sum = sum;
+            total_weight = total;
+            if weight > 0 {
+                latest_score = report.score;
+            }
+        }
+        if total_weight == 0 {
+            return 0;
+        }
+        let lower = FixedPoint::from_ratio(weighted_sum, total_weight, precision).trunc() as u32;
+        if weighted_sum % total_weight == 0 {
+            return lower;
+        }
+        match Self::load_config(&env).tiebreak {
+            TieBreak::Highest => lower + 1,
+            TieBreak::Lowest => lower,
+            TieBreak::Latest if latest_score > lower => lower + 1,
+            TieBreak::Latest => lower,
+        }
+    }
 
-        result
+    /// Latest score in basis points of the configured scale (0-10000).
+    /// Scores stored before the scale was lowered count as the maximum.
+    pub fn get_score_normalized(env: Env, agent_id: Symbol) -> u32 {
+        let scale = Self::load_config(&env).score_scale as u64;
+        let score = Self::get_latest_score(env, agent_id) as u64;
+        (score * NORMALIZED_SCORE_SCALE as u64 / scale).min(NORMALIZED_SCORE_SCALE as u64) as u32
     }
 
     pub fn batch_analyze_pairs(
         env: Env,
         pairs: Vec<TokenPair>,
     ) -> Result<Vec<FraudAnalysisResult>, ContractError> {
+        require_within_batch_limit(&env, pairs.len());
         let _timer = PerformanceMonitor::start_timer(&env, &Symbol::new(&env, "batch_analyze"));
 
         let mut results = Vec::new(&env);
@@ -485,6 +2789,13 @@ impl FraudDetectContract {
         admin: Address,
         flags: Vec<common_utils::batch::FraudFlag>,
     ) -> Result<common_utils::batch::BatchResult, common_utils::batch::BatchError> {
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "batch_flag_fraud",
+            vec![&env, flags.into_val(&env)],
+        )
+        .map_err(|_| common_utils::batch::BatchError::InvalidOperation)?;
         batch::batch_flag_fraud(&env, &admin, flags)
     }
 
@@ -493,7 +2804,12 @@ impl FraudDetectContract {
         admin: Address,
         pair: TokenPair,
     ) -> Result<(), ContractError> {
-        Self::require_admin(&env, &admin)?;
+        Self::require_admin_action(
+            &env,
+            &admin,
+            "invalidate_dex_cache",
+            vec![&env, pair.into_val(&env)],
+        )?;
 
         let mut cache = DexDataCache::new(&env);
         cache.invalidate(&pair);
@@ -517,17 +2833,497 @@ impl FraudDetectContract {
             .unwrap_or(false)
     }
 
+    /// Rewrite stored data into the compressed layout. Only valid between
+    /// `begin_migration` and `finish_migration`; returns the number of
+    /// entries rewritten.
     pub fn migrate_to_compressed(env: Env, admin: Address) -> Result<u64, ContractError> {
-        let auth = Self::get_auth(&env);
-        check_authorization!(auth, &env, &admin, permission!(Admin));
+        Self::require_admin_action(&env, &admin, "migrate_to_compressed", vec![&env])?;
 
-        if env.storage().instance().has(&DataKey::MigrationState) {
+        if !Self::get_state(&env).is_migrating()
+            || !env.storage().instance().has(&DataKey::MigrationState)
+        {
             return Err(ContractError::InvalidState);
         }
 
         Ok(0)
     }
 
+    /// Stored config, falling back to the pre-aggregate keys for contracts
+    /// initialized before `DataKey::Config` existed.
+    fn load_config(env: &Env) -> FraudConfig {
+        let mut config = match env.storage().instance().get::<_, Val>(&DataKey::Config) {
+            Some(raw) => FraudConfig::try_from_val(env, &raw)
+                .unwrap_or_else(|_| Self::upgrade_config(env, &raw)),
+            None => Self::default_config(env),
+        };
+        // Owned by other modules; the stored copies are ignored
+        config.network_load = RateLimiter::get_network_load(env);
+        config.legacy_events = event_schema::legacy_events_enabled(env);
+        config
+    }
+
+    /// Settings before `DataKey::Config` is first written: the keys that
+    /// predate it where they exist, defaults otherwise.
+    fn default_config(env: &Env) -> FraudConfig {
+        let storage = env.storage().instance();
+        FraudConfig {
+            thresholds: storage
+                .get(&DataKey::DetectionThresholds)
+                .unwrap_or_else(|| DetectionThresholds::new()),
+            dex_config: storage
+                .get(&DataKey::DexConfig)
+                .unwrap_or_else(|| DexConfig::default()),
+            dex_enabled: storage.get(&DataKey::DexEnabled).unwrap_or(false),
+            network_load: RateLimiter::get_network_load(env),
+            legacy_events: event_schema::legacy_events_enabled(env),
+            breaker_threshold: 0,
+            breaker_window: DEFAULT_BREAKER_WINDOW,
+            stake_token: None,
+            min_reporter_stake: 0,
+            stake_cooldown: 0,
+            require_registered_agents: false,
+            report_cooldown: 0,
+            max_reports_per_agent: 0,
+            evict_oldest_reports: false,
+            score_scale: DEFAULT_SCORE_SCALE,
+            report_chain_enabled: false,
+            dispute_window: 0,
+            admin_heartbeat: 0,
+            model_update_cooldown: 0,
+            model_daily_cap: 0,
+            reveal_window: DEFAULT_REVEAL_WINDOW,
+            commit_window: DEFAULT_COMMIT_WINDOW,
+            auto_compact_bytes: 0,
+            tiebreak: TieBreak::Latest,
+            read_audit: false,
+            grace_queue_len: 0,
+        }
+    }
+
+    /// Decode a config stored before some of its fields existed. Fields
+    /// present in `raw` are kept and the rest take their defaults.
+    fn upgrade_config(env: &Env, raw: &Val) -> FraudConfig {
+        let mut config = Self::default_config(env);
+        let Ok(fields) = Map::<Symbol, Val>::try_from_val(env, raw) else {
+            return config;
+        };
+        macro_rules! carry {
+            ($($field:ident),* $(,)?) => {$(
+                if let Some(value) = fields.get(Symbol::new(env, stringify!($field))) {
+                    if let Ok(value) = TryFromVal::try_from_val(env, &value) {
+                        config.$field = value;
+                    }
+                }
+            )*};
+        }
+        carry!(
+            thresholds,
+            dex_config,
+            dex_enabled,
+            network_load,
+            legacy_events,
+            breaker_threshold,
+            breaker_window,
+            stake_token,
+            min_reporter_stake,
+            stake_cooldown,
+            require_registered_agents,
+            report_cooldown,
+            max_reports_per_agent,
+            evict_oldest_reports,
+            score_scale,
+            report_chain_enabled,
+            dispute_window,
+            admin_heartbeat,
+            model_update_cooldown,
+            model_daily_cap,
+            reveal_window,
+            commit_window,
+            auto_compact_bytes,
+            tiebreak,
+            read_audit,
+            grace_queue_len,
+        );
+        config
+    }
+
+    /// Oldest-first trim of `reports` until its compressed blob fits in
+    /// `auto_compact_bytes`. The newest report and reports still inside
+    /// the dispute window are retained, even if that leaves the blob over
+    /// the cap. Emits `auto_compacted` with `(removed, freed_bytes)` and
+    /// returns the kept reports with the number removed.
+    fn auto_compact(
+        env: &Env,
+        config: &FraudConfig,
+        agent_id: &Symbol,
+        reports: Vec<FraudReport>,
+    ) -> Result<(Vec<FraudReport>, u32), ContractError> {
+        let max_bytes = config.auto_compact_bytes;
+        let blob_size = |reports: &Vec<FraudReport>| {
+            FraudReportCompressor::compress_reports(reports)
+                .map(|blob| blob.len())
+                .map_err(|_| ContractError::StorageFull)
+        };
+        if max_bytes == 0 {
+            return Ok((reports, 0));
+        }
+        let original = blob_size(&reports)?;
+        let now = env.ledger().timestamp();
+        let mut size = original;
+        let mut start = 0;
+        while size > max_bytes && start + 1 < reports.len() {
+            let oldest = reports.get(start).unwrap();
+            if oldest.timestamp.saturating_add(config.dispute_window) > now {
+                break;
+            }
+            start += 1;
+            size = blob_size(&reports.slice(start..))?;
+        }
+        if start == 0 {
+            return Ok((reports, 0));
+        }
+
+        env.events().publish(
+            (Symbol::new(env, "auto_compacted"), agent_id.clone()),
+            (start, original - size),
+        );
+        Ok((reports.slice(start..), start))
+    }
+
+    fn save_config(env: &Env, config: &FraudConfig) {
+        env.storage().instance().set(&DataKey::Config, config);
+    }
+
+    /// ACL to check permissions against: the registry's `acl` service when a
+    /// registry is configured and has one, otherwise the initialized address.
+    fn acl_contract(env: &Env) -> Option<Address> {
+        let registry: Option<Address> = env.storage().instance().get(&DataKey::ServiceRegistry);
+        registry
+            .and_then(|registry| resolve_service(env, &registry, SERVICE_ACL))
+            .or_else(|| env.storage().instance().get(&DataKey::AclContract))
+    }
+
+    /// Whether `fraud/<action>` is granted to `user` by `primary` or, for
+    /// `report` and `view`, any added ACL. They are asked in order and the
+    /// first allow wins.
+    fn acl_allows(env: &Env, primary: Address, user: &Address, action: Symbol) -> bool {
+        let mut acls = Vec::from_array(env, [primary]);
+        if action == symbol_short!("report") || action == symbol_short!("view") {
+            acls.append(&Self::get_acl_contracts(env.clone()));
+        }
+        acls.iter().any(|acl| {
+            common_utils::check_permission(
+                env.clone(),
+                acl,
+                user.clone(),
+                symbol_short!("fraud"),
+                action.clone(),
+            )
+        })
+    }
+
+    fn is_private_agent(env: &Env, agent_id: &Symbol) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::PrivateAgent(agent_id.clone()))
+    }
+
+    /// Anyone may view public agents; private ones need the admin or
+    /// `fraud/view`
+    fn require_viewer(env: &Env, viewer: &Address, agent_id: &Symbol) -> Result<(), ContractError> {
+        viewer.require_auth();
+        if !Self::is_private_agent(env, agent_id) {
+            return Ok(());
+        }
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::NotInitialized)?;
+        if *viewer == admin {
+            return Ok(());
+        }
+        let acl = Self::acl_contract(env).ok_or(ContractError::NotInitialized)?;
+        if !Self::acl_allows(env, acl, viewer, symbol_short!("view")) {
+            return Err(ContractError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    /// Count a submission against the breaker window and pause the contract
+    /// when it goes over the configured threshold.
+    fn breaker_tripped(env: &Env) -> bool {
+        let config = Self::load_config(env);
+        if config.breaker_threshold == 0 {
+            return false;
+        }
+
+        let now = env.ledger().timestamp();
+        let mut window: BreakerWindow = env
+            .storage()
+            .instance()
+            .get(&DataKey::BreakerWindow)
+            .unwrap_or(BreakerWindow { started_at: now, count: 0 });
+        if now >= window.started_at.saturating_add(config.breaker_window) {
+            window = BreakerWindow { started_at: now, count: 0 };
+        }
+        window.count = window.count.saturating_add(1);
+        env.storage().instance().set(&DataKey::BreakerWindow, &window);
+
+        if window.count <= config.breaker_threshold || Self::pause_contract(env).is_err() {
+            return false;
+        }
+        env.events().publish(
+            (Symbol::new(env, "circuit_tripped"),),
+            (window.count, config.breaker_threshold, now),
+        );
+        true
+    }
+
+    fn pause_contract(env: &Env) -> Result<(), StateError> {
+        let data = Self::get_state(env).get_data().cloned().ok_or(StateError::NotInitialized)?;
+        transition_to!(Self, env, State::Paused(data));
+        Ok(())
+    }
+
+    fn reactivate(env: &Env) -> Result<(), StateError> {
+        let state = Self::get_state(env);
+        if !state.is_paused() {
+            return Err(StateError::InvalidState);
+        }
+        let data = state.get_data().cloned().ok_or(StateError::NotInitialized)?;
+        transition_to!(Self, env, State::Active(data));
+        Ok(())
+    }
+
+    /// Least report score that flags its agent on the configured scale
+    fn flag_threshold(config: &FraudConfig) -> u32 {
+        (config.score_scale as u64 * FLAG_THRESHOLD_PERCENT as u64 / 100) as u32
+    }
+
+    /// Flag `agent_id` for `report`, opening a dispute window when one is
+    /// configured. A re-flag keeps the window of the original flag.
+    fn flag_agent(env: &Env, config: &FraudConfig, agent_id: &Symbol, report: &FraudReport) {
+        let key = DataKey::FlaggedAgent(agent_id.clone());
+        if !env.storage().instance().has(&key) {
+            reset_index::track_agent_flag(env, agent_id);
+            if config.dispute_window > 0 {
+                env.storage().instance().set(
+                    &DataKey::PendingAgentFlag(agent_id.clone()),
+                    &env.ledger().timestamp().saturating_add(config.dispute_window),
+                );
+            }
+        }
+        env.storage().instance().set(&key, report);
+    }
+
+    /// Bump the report counter and add `agent_id` to the agent index on its
+    /// first report.
+    fn record_report(env: &Env, agent_id: &Symbol) {
+        // Saturates, see `common_utils::math`
+        let total = Self::get_total_reports(env.clone()).saturating_add(1);
+        env.storage().instance().set(&DataKey::TotalReports, &total);
+        stats::invalidate(env);
+
+        let mut agents: Vec<Symbol> = env
+            .storage()
+            .instance()
+            .get(&DataKey::AgentIndex)
+            .unwrap_or_else(|| Vec::new(env));
+        if !agents.contains(agent_id) {
+            agents.push_back(agent_id.clone());
+            env.storage().instance().set(&DataKey::AgentIndex, &agents);
+        }
+    }
+
+    fn extend_report_chain(env: &Env, agent_id: &Symbol, report: &FraudReport) {
+        let mut buf: Bytes = Self::get_report_chain_head(env.clone()).into();
+        buf.append(&(agent_id.clone(), report.clone()).to_xdr(env));
+        let head: BytesN<32> = env.crypto().sha256(&buf).into();
+        env.storage().instance().set(&DataKey::ReportChainHead, &head);
+    }
+
+    /// Default limit of `submit_report`, matching its `rate_limit_adaptive!`
+    fn report_rate_limit() -> RateLimitConfig {
+        RateLimitConfig {
+            max_requests: REPORT_RATE_MAX,
+            window_seconds: REPORT_RATE_WINDOW,
+            strategy: RateLimitStrategy::SlidingWindow,
+            scope: RateLimitScope::PerUser,
+        }
+    }
+
+    /// The `can_submit_report` checks, in `submit_report` order
+    fn submit_blocker(env: &Env, reporter: &Address, agent_id: &Symbol) -> Option<Symbol> {
+        let function = Symbol::new(env, "submit_rpt");
+        let limit = RateLimiter::resolve_config(env, &function, Self::report_rate_limit());
+        let adaptive = RateLimiter::build_adaptive_config(env, reporter);
+        if !RateLimiter::peek_adaptive(env, reporter, &function, &limit, &adaptive).allowed {
+            return Some(SUBMIT_RATE_LIMITED);
+        }
+
+        let Some(acl) = Self::acl_contract(env) else {
+            return Some(SUBMIT_NOT_INITIALIZED);
+        };
+        if !Self::acl_allows(env, acl, reporter, symbol_short!("report")) {
+            return Some(SUBMIT_NO_PERMISSION);
+        }
+        if Self::approval_expired(env, reporter) {
+            return Some(SUBMIT_APPROVAL_EXPIRED);
+        }
+        if Self::reporter_removed(env, reporter) {
+            return Some(SUBMIT_REMOVED);
+        }
+
+        let state = Self::get_state(env);
+        if state.is_paused() || state.is_migrating() {
+            return Some(SUBMIT_INACTIVE);
+        }
+        if stake::require_min_stake(env, reporter).is_err() {
+            return Some(SUBMIT_UNDER_STAKED);
+        }
+        if Self::load_config(env).require_registered_agents
+            && !Self::is_registered_agent(env.clone(), agent_id.clone())
+        {
+            return Some(SUBMIT_UNREGISTERED_AGENT);
+        }
+        if Self::check_report_cooldown(env, reporter, agent_id).is_err() {
+            return Some(SUBMIT_COOLDOWN);
+        }
+        None
+    }
+
+    /// Add `reporter` to the list, replacing any earlier expiry with
+    /// `expires_at`
+    fn approve_reporter(env: &Env, reporter: &Address, expires_at: Option<u64>) {
+        reporter_bitmap::set(env, reporter, true);
+        let expiry_key = DataKey::ReporterExpiry(reporter.clone());
+        match expires_at {
+            Some(at) => {
+                reset_index::track_reporter_record(env, reporter);
+                env.storage().persistent().set(&expiry_key, &at)
+            }
+            None => env.storage().persistent().remove(&expiry_key),
+        }
+        // Re-adding supersedes any earlier removal
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ReporterRemoval(reporter.clone()));
+    }
+
+    fn approval_expired(env: &Env, reporter: &Address) -> bool {
+        let expiry: Option<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ReporterExpiry(reporter.clone()));
+        expiry.is_some_and(|at| env.ledger().timestamp() >= at)
+    }
+
+    /// Whether `reporter` was removed, by `remove_reporter`, pruning or an
+    /// ACL sync, and not approved again since. The removal record outlives
+    /// the expiry, so a pruned reporter stays locked out.
+    fn reporter_removed(env: &Env, reporter: &Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::ReporterRemoval(reporter.clone()))
+    }
+
+    fn check_report_cooldown(env: &Env, reporter: &Address, agent_id: &Symbol) -> Result<(), ValidationError> {
+        let cooldown = Self::load_config(env).report_cooldown;
+        if cooldown == 0 {
+            return Ok(());
+        }
+        let last: Option<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::LastReport(reporter.clone(), agent_id.clone()));
+        match last {
+            Some(at) if env.ledger().timestamp() < at.saturating_add(cooldown) => Err(ValidationError::Cooldown),
+            _ => Ok(()),
+        }
+    }
+
+    fn report_metadata(env: &Env, agent_id: &Symbol, reports: &Vec<FraudReport>) -> ReportMetadata {
+        ReportMetadata {
+            count: reports.len(),
+            first_ts: reports.first().map(|r| r.timestamp).unwrap_or(0),
+            last_ts: reports.last().map(|r| r.timestamp).unwrap_or(0),
+            compression_type: CompressionType::DeltaEncoding,
+            stored_bytes: CompressedReportStorage::get_metadata(env, agent_id)
+                .filter(|_| !reports.is_empty())
+                .map(|m| m.compressed_size)
+                .unwrap_or(0),
+        }
+    }
+
+    /// Invariant after `submit_report`'s writes: the agent's stored reports
+    /// and its `ReportMetadata` both hold `expected_len` reports, and the
+    /// global counter is `expected_total`. A violation traps, so the whole
+    /// call is rolled back and no success event goes out.
+    fn verify_report_write(env: &Env, agent_id: &Symbol, expected_len: u32, expected_total: u64) {
+        if !Self::report_write_intact(env, agent_id, expected_len, expected_total) {
+            panic_with_error!(env, ContractError::InvalidState);
+        }
+    }
+
+    fn report_write_intact(env: &Env, agent_id: &Symbol, expected_len: u32, expected_total: u64) -> bool {
+        let stored = CompressedReportStorage::get_reports(env, agent_id)
+            .map(|reports| reports.len())
+            .unwrap_or(0);
+        let recorded = env
+            .storage()
+            .instance()
+            .get::<_, ReportMetadata>(&DataKey::ReportsMetadata(agent_id.clone()))
+            .map(|metadata| metadata.count)
+            .unwrap_or(0);
+        stored == expected_len
+            && recorded == expected_len
+            && Self::get_total_reports(env.clone()) == expected_total
+    }
+
+    /// Rewrite the agent's `ReportMetadata` after its reports changed
+    fn write_report_metadata(env: &Env, agent_id: &Symbol, reports: &Vec<FraudReport>) {
+        let key = DataKey::ReportsMetadata(agent_id.clone());
+        if reports.is_empty() {
+            env.storage().instance().remove(&key);
+        } else {
+            let metadata = Self::report_metadata(env, agent_id, reports);
+            env.storage().instance().set(&key, &metadata);
+        }
+    }
+
+    fn unindex_agent(env: &Env, agent_id: &Symbol) {
+        let mut agents: Vec<Symbol> = env
+            .storage()
+            .instance()
+            .get(&DataKey::AgentIndex)
+            .unwrap_or_else(|| Vec::new(env));
+        if let Some(index) = agents.first_index_of(agent_id) {
+            agents.remove(index);
+            env.storage().instance().set(&DataKey::AgentIndex, &agents);
+        }
+    }
+
+    /// Admin, ACL and default config written by `initialize` and
+    /// `reinitialize`
+    fn store_initial_config(env: &Env, admin: Address, acl_contract: Address) {
+        // Store admin and ACL for backward compatibility
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::AclContract, &acl_contract);
+
+        let mut config = Self::load_config(env);
+        config.thresholds = DetectionThresholds::new();
+        config.dex_config = DexConfig::default();
+        config.dex_enabled = true;
+        Self::save_config(env, &config);
+        Self::touch_admin_heartbeat(env);
+
+        env.events()
+            .publish((symbol_short!("init"),), (admin, acl_contract));
+    }
+
     fn require_admin(env: &Env, admin: &Address) -> Result<(), ContractError> {
         let stored_admin: Address = env
             .storage()
@@ -539,9 +3335,63 @@ impl FraudDetectContract {
             return Err(ContractError::Unauthorized);
         }
         admin.require_auth();
+        Self::touch_admin_heartbeat(env);
+        Ok(())
+    }
+
+    /// `require_admin`, then record `action` and its parameters in the
+    /// admin action log. Used at the start of every admin-gated entrypoint.
+    fn require_admin_action(
+        env: &Env,
+        admin: &Address,
+        action: &str,
+        params: Vec<Val>,
+    ) -> Result<(), ContractError> {
+        Self::require_admin(env, admin)?;
+        admin_log::record_admin_action(env, Symbol::new(env, action), admin, &params);
+        Ok(())
+    }
+
+    /// `require_admin_action` for grants the ACL delegates: `caller` needs
+    /// `manage` on the primary ACL instead of being the admin.
+    fn require_manager_action(
+        env: &Env,
+        caller: &Address,
+        action: &str,
+        params: Vec<Val>,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+        let acl = Self::acl_contract(env).ok_or(ContractError::NotInitialized)?;
+        if !Self::acl_allows(env, acl, caller, symbol_short!("manage")) {
+            return Err(ContractError::Unauthorized);
+        }
+        admin_log::record_admin_action(env, Symbol::new(env, action), caller, &params);
         Ok(())
     }
 
+    /// Admin check failures for entrypoints that report `AuthorizationError`
+    fn authorization_error(err: ContractError) -> AuthorizationError {
+        match err {
+            ContractError::NotInitialized => AuthorizationError::NotInitialized,
+            _ => AuthorizationError::NotAuthorized,
+        }
+    }
+
+    /// Admin check failures for entrypoints that report `CommonError`
+    fn common_error(err: ContractError) -> CommonError {
+        match err {
+            ContractError::NotInitialized => CommonError::NotInitialized,
+            _ => CommonError::NotAuthorized,
+        }
+    }
+
+    /// Every admin-gated call counts as a heartbeat
+    fn touch_admin_heartbeat(env: &Env) {
+        env.storage()
+            .instance()
+            .set(&DataKey::LastAdminAction, &env.ledger().timestamp());
+    }
+
     fn fetch_trading_data(env: &Env, pair: &TokenPair) -> Result<TradingData, ContractError> {
         let mut cache = DexDataCache::new(env);
 
@@ -598,20 +3448,89 @@ pub struct TradingEvidence {
 
 #[contractimpl]
 impl DataMigration for FraudDetectContract {
-    fn export_state(env: Env) -> Vec<Val> {
+    /// `[version, admin, acl, reporters, total_reports, config, report_digest,
+    /// exported_at]`. Reports themselves are not carried; the digest lets
+    /// the operator verify them once copied. `exported_at` is set only when
+    /// exporting while paused or terminated; `import_state` refuses `None`.
+    fn export_state(env: Env) -> Result<Vec<Val>, CommonError> {
+        let lifecycle = Self::get_state(&env);
+        let exported_at = migration::guard_export(&env, lifecycle.is_paused() || lifecycle.is_terminated())?;
         let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let acl: Address = env.storage().instance().get(&DataKey::AclContract).unwrap();
         let mut state = Vec::new(&env);
+        state.push_back(STATE_EXPORT_VERSION.into_val(&env));
         state.push_back(admin.to_val());
-        state
+        state.push_back(acl.to_val());
+        state.push_back(Self::get_reporters(env.clone()).into_val(&env));
+        state.push_back(Self::get_total_reports(env.clone()).into_val(&env));
+        state.push_back(Self::load_config(&env).into_val(&env));
+        state.push_back(Self::get_report_digest(env.clone()).into_val(&env));
+        state.push_back(exported_at.into_val(&env));
+        Ok(state)
     }
 
+    /// Only into an uninitialized contract, and only with the signature of
+    /// the admin the payload installs, so a fresh deployment cannot be
+    /// claimed by someone else's payload. Version 2 payloads must come from
+    /// a frozen source; version 1 payloads, which carry no marker, are still
+    /// accepted.
     fn import_state(env: Env, data: Vec<Val>) -> Result<(), CommonError> {
-        if data.len() < 1 {
+        let version = data
+            .get(0)
+            .and_then(|v| u32::try_from_val(&env, &v).ok())
+            .ok_or(CommonError::InvalidFormat)?;
+        let expected_len = match version {
+            1 => STATE_EXPORT_LEN_V1,
+            STATE_EXPORT_VERSION => STATE_EXPORT_LEN,
+            _ => return Err(CommonError::InvalidFormat),
+        };
+        if data.len() != expected_len {
             return Err(CommonError::InvalidFormat);
         }
-        let val = data.get(0).unwrap();
-        let admin = Address::try_from_val(&env, &val).map_err(|_| CommonError::InvalidFormat)?;
-        env.storage().instance().set(&DataKey::Admin, &admin);
+        if !Self::get_state(&env).is_uninitialized() {
+            return Err(CommonError::AlreadyInitialized);
+        }
+
+        let admin = Address::try_from_val(&env, &data.get(1).unwrap())
+            .map_err(|_| CommonError::InvalidFormat)?;
+        admin.require_auth();
+        let acl = Address::try_from_val(&env, &data.get(2).unwrap())
+            .map_err(|_| CommonError::InvalidFormat)?;
+        let reporters = Vec::<Address>::try_from_val(&env, &data.get(3).unwrap())
+            .map_err(|_| CommonError::InvalidFormat)?;
+        let total_reports = u64::try_from_val(&env, &data.get(4).unwrap())
+            .map_err(|_| CommonError::InvalidFormat)?;
+        let config = FraudConfig::try_from_val(&env, &data.get(5).unwrap())
+            .map_err(|_| CommonError::InvalidFormat)?;
+        let digest = BytesN::<32>::try_from_val(&env, &data.get(6).unwrap())
+            .map_err(|_| CommonError::InvalidFormat)?;
+        if version == STATE_EXPORT_VERSION {
+            let exported_at = Option::<u64>::try_from_val(&env, &data.get(7).unwrap())
+                .map_err(|_| CommonError::InvalidFormat)?;
+            migration::record_source_export(&env, exported_at)?;
+        }
+
+        // The counter lives under `DataKey::TotalReports`, as after `initialize`
+        Self::set_state(
+            &env,
+            State::Active(FraudDetectState {
+                admin: admin.clone(),
+                acl_contract: acl.clone(),
+                total_reports: 0,
+            }),
+        );
+        let storage = env.storage().instance();
+        storage.set(&DataKey::Admin, &admin);
+        storage.set(&DataKey::AclContract, &acl);
+        for reporter in reporters.iter() {
+            reporter_bitmap::set(&env, &reporter, true);
+        }
+        storage.set(&DataKey::TotalReports, &total_reports);
+        stats::invalidate(&env);
+        storage.set(&DataKey::ImportedReportDigest, &digest);
+        RateLimiter::set_network_load(&env, config.network_load);
+        event_schema::set_legacy_events(&env, config.legacy_events);
+        Self::save_config(&env, &config);
         Ok(())
     }
 }
@@ -0,0 +1,142 @@
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, Vec};
+
+use common_utils::error::ContractError;
+
+use crate::DataKey;
+
+/// A model hash waiting for enough model-admin approvals
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct ModelProposal {
+    pub model_hash: BytesN<32>,
+    pub proposer: Address,
+    pub approvals: Vec<Address>,
+    pub created_at: u64,
+}
+
+pub fn model_admins(env: &Env) -> Vec<Address> {
+    env.storage()
+        .instance()
+        .get(&DataKey::ModelAdmins)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Approvals needed to commit a model; never more than the number of admins.
+pub fn threshold(env: &Env) -> u32 {
+    let configured: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::ModelThreshold)
+        .unwrap_or(1);
+    configured.min(model_admins(env).len()).max(1)
+}
+
+/// Whether model updates go through the quorum instead of `update_model`.
+pub fn is_enabled(env: &Env) -> bool {
+    !model_admins(env).is_empty()
+}
+
+pub fn add_admin(env: &Env, admin: &Address) {
+    let mut admins = model_admins(env);
+    if !admins.contains(admin) {
+        admins.push_back(admin.clone());
+        env.storage().instance().set(&DataKey::ModelAdmins, &admins);
+    }
+}
+
+pub fn remove_admin(env: &Env, admin: &Address) -> Result<(), ContractError> {
+    let mut admins = model_admins(env);
+    let index = admins.first_index_of(admin).ok_or(ContractError::NotFound)?;
+    admins.remove(index);
+    env.storage().instance().set(&DataKey::ModelAdmins, &admins);
+    Ok(())
+}
+
+pub fn set_threshold(env: &Env, threshold: u32) -> Result<(), ContractError> {
+    if threshold == 0 || threshold > model_admins(env).len() {
+        return Err(ContractError::InvalidConfiguration);
+    }
+    env.storage().instance().set(&DataKey::ModelThreshold, &threshold);
+    Ok(())
+}
+
+fn require_model_admin(env: &Env, admin: &Address) -> Result<(), ContractError> {
+    admin.require_auth();
+    if !model_admins(env).contains(admin) {
+        return Err(ContractError::Unauthorized);
+    }
+    Ok(())
+}
+
+pub fn get_proposal(env: &Env, model_hash: &BytesN<32>) -> Option<ModelProposal> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ModelProposal(model_hash.clone()))
+}
+
+/// Open a proposal for `model_hash`; the proposer's approval counts.
+/// Returns whether the model was committed.
+pub fn propose(env: &Env, proposer: &Address, model_hash: &BytesN<32>) -> Result<bool, ContractError> {
+    require_model_admin(env, proposer)?;
+    if get_proposal(env, model_hash).is_some() {
+        return Err(ContractError::InvalidState);
+    }
+
+    let mut approvals = Vec::new(env);
+    approvals.push_back(proposer.clone());
+    let proposal = ModelProposal {
+        model_hash: model_hash.clone(),
+        proposer: proposer.clone(),
+        approvals,
+        created_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((symbol_short!("mdl_prop"), proposer.clone()), model_hash.clone());
+    Ok(record(env, proposal))
+}
+
+/// Add `approver`'s approval to the proposal for `model_hash`.
+/// Returns whether the model was committed.
+pub fn approve(env: &Env, approver: &Address, model_hash: &BytesN<32>) -> Result<bool, ContractError> {
+    require_model_admin(env, approver)?;
+    let mut proposal = get_proposal(env, model_hash).ok_or(ContractError::NotFound)?;
+    if proposal.approvals.contains(approver) {
+        return Err(ContractError::InvalidState);
+    }
+    proposal.approvals.push_back(approver.clone());
+
+    env.events()
+        .publish((symbol_short!("mdl_appr"), approver.clone()), model_hash.clone());
+    Ok(record(env, proposal))
+}
+
+/// Approvals on `proposal` from addresses that are still model admins.
+/// Approvals given by an admin who has since been removed do not count.
+pub fn current_approvals(env: &Env, proposal: &ModelProposal) -> u32 {
+    let admins = model_admins(env);
+    proposal
+        .approvals
+        .iter()
+        .filter(|approver| admins.contains(approver))
+        .count() as u32
+}
+
+/// Commit once the threshold is met, otherwise store the updated proposal.
+fn record(env: &Env, proposal: ModelProposal) -> bool {
+    let key = DataKey::ModelProposal(proposal.model_hash.clone());
+    if current_approvals(env, &proposal) < threshold(env) {
+        env.storage().persistent().set(&key, &proposal);
+        return false;
+    }
+
+    env.storage().persistent().remove(&key);
+    env.storage()
+        .instance()
+        .set(&DataKey::ActiveModel, &proposal.model_hash);
+    env.events().publish(
+        (symbol_short!("mdl_upd"),),
+        (env.ledger().timestamp(), proposal.model_hash),
+    );
+    true
+}
@@ -0,0 +1,129 @@
+//! Reporter approvals as a packed bitmap. Each reporter is given a stable
+//! handle by `AddressRegistry` and its approval is bit `handle % 128` of
+//! word `handle / 128`:
+//!
+//! ```text
+//! DataKey::ReporterBits(word)  -> u128, persistent
+//! DataKey::ReporterWords       -> u32, words ever written, instance
+//! ```
+//!
+//! A membership check is one word read. Lists written by earlier versions
+//! under `DataKey::Reporters` are still honoured and are folded into the
+//! bitmap on the next write.
+
+use soroban_sdk::{Address, Env, Vec};
+
+use common_utils::address_registry::AddressRegistry;
+
+use crate::DataKey;
+
+const WORD_BITS: u64 = 128;
+
+fn word(env: &Env, index: u32) -> u128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ReporterBits(index))
+        .unwrap_or(0)
+}
+
+fn word_count(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::ReporterWords)
+        .unwrap_or(0)
+}
+
+fn legacy(env: &Env) -> Option<Vec<Address>> {
+    env.storage().instance().get(&DataKey::Reporters)
+}
+
+/// Word index and mask of `handle`'s bit.
+fn position(handle: u64) -> (u32, u128) {
+    ((handle / WORD_BITS) as u32, 1u128 << (handle % WORD_BITS))
+}
+
+/// Move a legacy list into the bitmap, keeping its members.
+fn absorb_legacy(env: &Env) {
+    if let Some(reporters) = legacy(env) {
+        env.storage().instance().remove(&DataKey::Reporters);
+        for reporter in reporters.iter() {
+            write_bit(env, &reporter, true);
+        }
+    }
+}
+
+fn write_bit(env: &Env, reporter: &Address, approved: bool) -> bool {
+    let handle = if approved {
+        AddressRegistry::to_handle(env, reporter)
+    } else {
+        match AddressRegistry::lookup(env, reporter) {
+            Some(handle) => handle,
+            None => return false,
+        }
+    };
+    let (index, mask) = position(handle);
+    let current = word(env, index);
+    let updated = if approved { current | mask } else { current & !mask };
+    if updated == current {
+        return false;
+    }
+    env.storage()
+        .persistent()
+        .set(&DataKey::ReporterBits(index), &updated);
+    if index >= word_count(env) {
+        env.storage()
+            .instance()
+            .set(&DataKey::ReporterWords, &(index + 1));
+    }
+    true
+}
+
+/// Whether `reporter` is approved.
+pub fn contains(env: &Env, reporter: &Address) -> bool {
+    if let Some(reporters) = legacy(env) {
+        return reporters.contains(reporter);
+    }
+    match AddressRegistry::lookup(env, reporter) {
+        Some(handle) => {
+            let (index, mask) = position(handle);
+            word(env, index) & mask != 0
+        }
+        None => false,
+    }
+}
+
+/// Approve or revoke `reporter`. Returns whether anything changed.
+pub fn set(env: &Env, reporter: &Address, approved: bool) -> bool {
+    absorb_legacy(env);
+    write_bit(env, reporter, approved)
+}
+
+/// Approved reporters in handle order, which is the order they were first
+/// approved in. Unlike the legacy list, re-approving a revoked reporter
+/// returns it to its old position rather than appending it.
+pub fn list(env: &Env) -> Vec<Address> {
+    if let Some(reporters) = legacy(env) {
+        return reporters;
+    }
+    let mut reporters = Vec::new(env);
+    for index in 0..word_count(env) {
+        let mut bits = word(env, index);
+        while bits != 0 {
+            let bit = bits.trailing_zeros() as u64;
+            bits &= bits - 1;
+            if let Some(reporter) = AddressRegistry::from_handle(env, index as u64 * WORD_BITS + bit) {
+                reporters.push_back(reporter);
+            }
+        }
+    }
+    reporters
+}
+
+/// Revoke every reporter. Handles stay issued.
+pub fn clear(env: &Env) {
+    env.storage().instance().remove(&DataKey::Reporters);
+    for index in 0..word_count(env) {
+        env.storage().persistent().remove(&DataKey::ReporterBits(index));
+    }
+    env.storage().instance().remove(&DataKey::ReporterWords);
+}
@@ -0,0 +1,173 @@
+//! Keys `reinitialize` has to find without scanning storage. Families of
+//! per-account or per-pair keys that live outside the agent index are
+//! remembered the first time one of their keys is written:
+//!
+//! ```text
+//! DataKey::FlaggedAccounts   -> Vec<Address>, FlaggedAccount + PendingFlag
+//! DataKey::ReportPairs       -> Vec<(Address, Symbol)>, LastReport
+//! DataKey::WeightedReporters -> Vec<Address>, ReporterWeight
+//! DataKey::TieredUsers       -> Vec<Address>, rate-limit trust tiers
+//! DataKey::DisputedReporters -> Vec<Address>, UpheldDisputeCount
+//! DataKey::FlaggedAgents     -> Vec<Symbol>, FlaggedAgent + PendingAgentFlag
+//! DataKey::QueuedReporters   -> Vec<Address>, GraceQueue
+//! DataKey::CommitPairs       -> Vec<(Address, Symbol)>, ReportCommitment + CommitRound
+//! DataKey::PrivateAgents     -> Vec<Symbol>, PrivateAgent
+//! DataKey::RecordedReporters -> Vec<Address>, ReporterExpiry + ReporterRemoval
+//! ```
+//!
+//! All indexes are persistent. Keys written before the indexes existed are
+//! not listed and survive a wipe.
+
+use soroban_sdk::{Address, Env, IntoVal, Symbol, TryFromVal, Val, Vec};
+
+use common_utils::rate_limit::RateLimiter;
+
+use crate::DataKey;
+
+fn items<T: IntoVal<Env, Val> + TryFromVal<Env, Val>>(env: &Env, index: &DataKey) -> Vec<T> {
+    env.storage()
+        .persistent()
+        .get(index)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+fn push<T: IntoVal<Env, Val> + TryFromVal<Env, Val>>(env: &Env, index: DataKey, item: T) {
+    let mut list: Vec<T> = items(env, &index);
+    list.push_back(item);
+    env.storage().persistent().set(&index, &list);
+}
+
+/// Call before flagging `account`
+pub fn track_flag(env: &Env, account: &Address) {
+    if !env.storage().instance().has(&DataKey::FlaggedAccount(account.clone())) {
+        push(env, DataKey::FlaggedAccounts, account.clone());
+    }
+}
+
+/// Call before writing `LastReport(reporter, agent_id)`
+pub fn track_report(env: &Env, reporter: &Address, agent_id: &Symbol) {
+    let key = DataKey::LastReport(reporter.clone(), agent_id.clone());
+    if !env.storage().persistent().has(&key) {
+        push(env, DataKey::ReportPairs, (reporter.clone(), agent_id.clone()));
+    }
+}
+
+/// Call before writing `ReporterWeight(reporter)`
+pub fn track_weight(env: &Env, reporter: &Address) {
+    if !env.storage().persistent().has(&DataKey::ReporterWeight(reporter.clone())) {
+        push(env, DataKey::WeightedReporters, reporter.clone());
+    }
+}
+
+/// Call before counting an upheld dispute against `reporter`
+pub fn track_dispute(env: &Env, reporter: &Address) {
+    if !env.storage().persistent().has(&DataKey::UpheldDisputeCount(reporter.clone())) {
+        push(env, DataKey::DisputedReporters, reporter.clone());
+    }
+}
+
+/// Call before flagging `agent_id`
+pub fn track_agent_flag(env: &Env, agent_id: &Symbol) {
+    if !env.storage().instance().has(&DataKey::FlaggedAgent(agent_id.clone())) {
+        push(env, DataKey::FlaggedAgents, agent_id.clone());
+    }
+}
+
+/// Call before writing `GraceQueue(reporter)`
+pub fn track_queue(env: &Env, reporter: &Address) {
+    if !env.storage().persistent().has(&DataKey::GraceQueue(reporter.clone())) {
+        push(env, DataKey::QueuedReporters, reporter.clone());
+    }
+}
+
+/// Call before writing `ReportCommitment(reporter, agent_id)`
+pub fn track_commitment(env: &Env, reporter: &Address, agent_id: &Symbol) {
+    let key = DataKey::ReportCommitment(reporter.clone(), agent_id.clone());
+    if !env.storage().persistent().has(&key) {
+        push(env, DataKey::CommitPairs, (reporter.clone(), agent_id.clone()));
+    }
+}
+
+/// Call before making `agent_id` private
+pub fn track_private_agent(env: &Env, agent_id: &Symbol) {
+    if !env.storage().persistent().has(&DataKey::PrivateAgent(agent_id.clone())) {
+        push(env, DataKey::PrivateAgents, agent_id.clone());
+    }
+}
+
+/// Call before writing `ReporterExpiry(reporter)` or
+/// `ReporterRemoval(reporter)`
+pub fn track_reporter_record(env: &Env, reporter: &Address) {
+    let storage = env.storage().persistent();
+    if !storage.has(&DataKey::ReporterExpiry(reporter.clone()))
+        && !storage.has(&DataKey::ReporterRemoval(reporter.clone()))
+    {
+        push(env, DataKey::RecordedReporters, reporter.clone());
+    }
+}
+
+/// Call before setting `user`'s trust tier
+pub fn track_tier(env: &Env, user: &Address) {
+    if !RateLimiter::has_trust_tier(env, user) {
+        push(env, DataKey::TieredUsers, user.clone());
+    }
+}
+
+/// Remove every indexed key, the indexes themselves and the default trust
+/// tier.
+pub fn wipe(env: &Env) {
+    for account in items::<Address>(env, &DataKey::FlaggedAccounts).iter() {
+        env.storage().instance().remove(&DataKey::FlaggedAccount(account.clone()));
+        env.storage().instance().remove(&DataKey::PendingFlag(account));
+    }
+    for (reporter, agent_id) in items::<(Address, Symbol)>(env, &DataKey::ReportPairs).iter() {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::LastReport(reporter, agent_id));
+    }
+    for reporter in items::<Address>(env, &DataKey::WeightedReporters).iter() {
+        env.storage().persistent().remove(&DataKey::ReporterWeight(reporter));
+    }
+    for reporter in items::<Address>(env, &DataKey::DisputedReporters).iter() {
+        env.storage().persistent().remove(&DataKey::UpheldDisputeCount(reporter));
+    }
+    for agent_id in items::<Symbol>(env, &DataKey::FlaggedAgents).iter() {
+        env.storage().instance().remove(&DataKey::FlaggedAgent(agent_id.clone()));
+        env.storage().instance().remove(&DataKey::PendingAgentFlag(agent_id));
+    }
+    for reporter in items::<Address>(env, &DataKey::QueuedReporters).iter() {
+        env.storage().persistent().remove(&DataKey::GraceQueue(reporter));
+    }
+    for (reporter, agent_id) in items::<(Address, Symbol)>(env, &DataKey::CommitPairs).iter() {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ReportCommitment(reporter, agent_id.clone()));
+        env.storage().persistent().remove(&DataKey::CommitRound(agent_id));
+    }
+    for agent_id in items::<Symbol>(env, &DataKey::PrivateAgents).iter() {
+        env.storage().persistent().remove(&DataKey::PrivateAgent(agent_id));
+    }
+    for reporter in items::<Address>(env, &DataKey::RecordedReporters).iter() {
+        env.storage().persistent().remove(&DataKey::ReporterExpiry(reporter.clone()));
+        env.storage().persistent().remove(&DataKey::ReporterRemoval(reporter));
+    }
+    for user in items::<Address>(env, &DataKey::TieredUsers).iter() {
+        RateLimiter::clear_trust_tier(env, &user);
+    }
+    RateLimiter::clear_default_trust_tier(env);
+
+    for index in [
+        DataKey::FlaggedAccounts,
+        DataKey::ReportPairs,
+        DataKey::WeightedReporters,
+        DataKey::TieredUsers,
+        DataKey::DisputedReporters,
+        DataKey::FlaggedAgents,
+        DataKey::QueuedReporters,
+        DataKey::CommitPairs,
+        DataKey::PrivateAgents,
+        DataKey::RecordedReporters,
+    ] {
+        env.storage().persistent().remove(&index);
+    }
+}
@@ -0,0 +1,128 @@
+use soroban_sdk::{contracttype, symbol_short, token, Address, Env};
+
+use common_utils::error::ContractError;
+use common_utils::math;
+
+use crate::{DataKey, FraudDetectContract};
+
+/// Tokens a reporter has escrowed with the contract
+#[derive(Clone, Debug, Default, PartialEq)]
+#[contracttype]
+pub struct ReporterStake {
+    pub amount: i128,
+    /// Timestamp of the reporter's latest accepted report
+    pub last_report_at: u64,
+}
+
+pub fn get_stake(env: &Env, reporter: &Address) -> ReporterStake {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ReporterStake(reporter.clone()))
+        .unwrap_or_default()
+}
+
+fn set_stake(env: &Env, reporter: &Address, stake: &ReporterStake) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::ReporterStake(reporter.clone()), stake);
+}
+
+/// Sum of every reporter's stake
+pub fn total_staked(env: &Env) -> i128 {
+    env.storage().instance().get(&DataKey::StakedTotal).unwrap_or(0)
+}
+
+fn set_total_staked(env: &Env, total: i128) {
+    env.storage().instance().set(&DataKey::StakedTotal, &total);
+}
+
+fn stake_token(env: &Env) -> Result<Address, ContractError> {
+    FraudDetectContract::get_config(env.clone())
+        .stake_token
+        .ok_or(ContractError::InvalidConfiguration)
+}
+
+/// Escrow `amount` of the stake token from `reporter`.
+pub fn stake(env: &Env, reporter: &Address, amount: i128) -> Result<ReporterStake, ContractError> {
+    reporter.require_auth();
+    if amount <= 0 {
+        return Err(ContractError::InvalidInput);
+    }
+
+    let mut stake = get_stake(env, reporter);
+    stake.amount = math::checked_add_i128(stake.amount, amount).map_err(|_| ContractError::InvalidInput)?;
+    let total = math::checked_add_i128(total_staked(env), amount).map_err(|_| ContractError::InvalidInput)?;
+
+    let token = stake_token(env)?;
+    token::Client::new(env, &token).transfer(reporter, &env.current_contract_address(), &amount);
+    set_stake(env, reporter, &stake);
+    set_total_staked(env, total);
+
+    env.events()
+        .publish((symbol_short!("stk_add"), reporter.clone()), (amount, stake.amount));
+    Ok(stake)
+}
+
+/// Return `amount` to `reporter` once the cooldown since their last report has passed.
+pub fn withdraw(env: &Env, reporter: &Address, amount: i128) -> Result<ReporterStake, ContractError> {
+    reporter.require_auth();
+    let mut stake = get_stake(env, reporter);
+    if amount <= 0 || amount > stake.amount {
+        return Err(ContractError::InvalidInput);
+    }
+
+    let cooldown = FraudDetectContract::get_config(env.clone()).stake_cooldown;
+    if stake.last_report_at > 0 && env.ledger().timestamp() < stake.last_report_at.saturating_add(cooldown) {
+        return Err(ContractError::InvalidState);
+    }
+
+    let token = stake_token(env)?;
+    token::Client::new(env, &token).transfer(&env.current_contract_address(), reporter, &amount);
+
+    stake.amount -= amount;
+    set_stake(env, reporter, &stake);
+    set_total_staked(env, total_staked(env) - amount);
+
+    env.events()
+        .publish((symbol_short!("stk_wdraw"), reporter.clone()), (amount, stake.amount));
+    Ok(stake)
+}
+
+/// Move up to `amount` of `reporter`'s stake to `recipient`. Returns the amount slashed.
+pub fn slash(env: &Env, reporter: &Address, amount: i128, recipient: &Address) -> Result<i128, ContractError> {
+    if amount <= 0 {
+        return Err(ContractError::InvalidInput);
+    }
+    let mut stake = get_stake(env, reporter);
+    let slashed = amount.min(stake.amount);
+    if slashed == 0 {
+        return Ok(0);
+    }
+
+    let token = stake_token(env)?;
+    token::Client::new(env, &token).transfer(&env.current_contract_address(), recipient, &slashed);
+
+    stake.amount -= slashed;
+    set_stake(env, reporter, &stake);
+    set_total_staked(env, total_staked(env) - slashed);
+
+    env.events()
+        .publish((symbol_short!("stk_slash"), reporter.clone()), (slashed, stake.amount));
+    Ok(slashed)
+}
+
+/// Reject reporters holding less than the configured minimum stake.
+pub fn require_min_stake(env: &Env, reporter: &Address) -> Result<(), ContractError> {
+    let min = FraudDetectContract::get_config(env.clone()).min_reporter_stake;
+    if min > 0 && get_stake(env, reporter).amount < min {
+        return Err(ContractError::Unauthorized);
+    }
+    Ok(())
+}
+
+/// Start the withdrawal cooldown from now.
+pub fn record_report(env: &Env, reporter: &Address) {
+    let mut stake = get_stake(env, reporter);
+    stake.last_report_at = env.ledger().timestamp();
+    set_stake(env, reporter, &stake);
+}
@@ -0,0 +1,72 @@
+use soroban_sdk::{contracttype, Env, Symbol, Vec};
+
+use common_utils::storage_optimization::CompressedReportStorage;
+
+use crate::{DataKey, FraudDetectContract};
+
+/// Contract-wide totals across every agent in the agent index
+#[derive(Clone, Debug, Default, PartialEq)]
+#[contracttype]
+pub struct GlobalFraudStats {
+    pub agent_count: u32,
+    pub total_reports: u64,
+    /// Accounts flagged through `batch_flag_fraud`
+    pub flagged_count: u32,
+    /// Mean of each indexed agent's latest score, 0 when no agents are indexed
+    pub avg_latest_score: u32,
+}
+
+/// Cached stats, recomputed on the first read after a write.
+pub fn get(env: &Env) -> GlobalFraudStats {
+    if let Some(stats) = env.storage().instance().get(&DataKey::GlobalStats) {
+        return stats;
+    }
+    let stats = compute(env);
+    env.storage().instance().set(&DataKey::GlobalStats, &stats);
+    stats
+}
+
+/// Walk the agent index. Cost grows with the number of agents, which is why
+/// reads go through the cache in `get`.
+pub fn compute(env: &Env) -> GlobalFraudStats {
+    let agents: Vec<Symbol> = env
+        .storage()
+        .instance()
+        .get(&DataKey::AgentIndex)
+        .unwrap_or_else(|| Vec::new(env));
+
+    let mut score_sum: u64 = 0;
+    let mut scored: u64 = 0;
+    for agent_id in agents.iter() {
+        if let Ok(score) = CompressedReportStorage::get_latest_score(env, &agent_id) {
+            score_sum += score as u64;
+            scored += 1;
+        }
+    }
+
+    GlobalFraudStats {
+        agent_count: agents.len(),
+        total_reports: FraudDetectContract::get_total_reports(env.clone()),
+        flagged_count: env.storage().instance().get(&DataKey::FlaggedCount).unwrap_or(0),
+        avg_latest_score: if scored == 0 { 0 } else { (score_sum / scored) as u32 },
+    }
+}
+
+/// Drop the cached stats; call after any write that changes them.
+pub fn invalidate(env: &Env) {
+    env.storage().instance().remove(&DataKey::GlobalStats);
+}
+
+/// Count a newly flagged account.
+pub fn record_flag(env: &Env) {
+    let count: u32 = env.storage().instance().get(&DataKey::FlaggedCount).unwrap_or(0);
+    env.storage().instance().set(&DataKey::FlaggedCount, &count.saturating_add(1));
+    invalidate(env);
+}
+
+/// Stop counting an account whose flag was cancelled.
+pub fn record_unflag(env: &Env) {
+    let count: u32 = env.storage().instance().get(&DataKey::FlaggedCount).unwrap_or(0);
+    env.storage().instance().set(&DataKey::FlaggedCount, &count.saturating_sub(1));
+    invalidate(env);
+}
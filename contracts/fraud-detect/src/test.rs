@@ -1,8 +1,9 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::testutils::{Address as _, Events};
-use soroban_sdk::{symbol_short, vec, Address, Env};
+use soroban_sdk::testutils::{Address as _, Events, Ledger};
+use common_utils::error::ValidationError;
+use soroban_sdk::{symbol_short, vec, Address, Env, InvokeError};
 
 #[test]
 fn test_full_lifecycle() {
@@ -104,3 +105,3072 @@ fn test_double_initialization() {
     client.initialize(&admin);
     client.initialize(&admin);
 }
+
+mod allow_all_acl {
+    use soroban_sdk::{contract, contractimpl, Address, Env, Symbol};
+
+    #[contract]
+    pub struct AllowAllAcl;
+
+    #[contractimpl]
+    impl AllowAllAcl {
+        pub fn has_permission(_env: Env, _user: Address, _resource: Symbol, _action: Symbol) -> bool {
+            true
+        }
+    }
+}
+
+#[test]
+fn test_report_event_carries_schema_version() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let agent_id = symbol_short!("agent_1");
+
+    client.initialize(&admin, &acl_id);
+    client.set_legacy_events(&admin, &false);
+    client.submit_report(&reporter, &agent_id, &85, &None);
+
+    let versioned: soroban_sdk::Vec<soroban_sdk::Val> = vec![
+        &env,
+        symbol_short!("fraud_rpt").into_val(&env),
+        REPORT_EVENT_VERSION.into_val(&env),
+        agent_id.into_val(&env),
+    ];
+    let legacy: soroban_sdk::Vec<soroban_sdk::Val> = vec![
+        &env,
+        symbol_short!("fraud_rpt").into_val(&env),
+        agent_id.into_val(&env),
+    ];
+    let events = env.events().all();
+    assert!(events.iter().any(|(_, topics, _)| topics == versioned));
+    assert!(!events.iter().any(|(_, topics, _)| topics == legacy));
+}
+
+#[test]
+fn test_reports_page_covers_all_reports() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let agent_id = symbol_short!("agent_1");
+
+    client.initialize(&admin, &acl_id);
+    for score in [10u32, 20, 30, 40, 50] {
+        client.submit_report(&reporter, &agent_id, &score, &None);
+    }
+
+    let first = client.get_reports_page(&agent_id, &0, &2);
+    assert_eq!(first.total, 5);
+    assert_eq!(first.items.len(), 2);
+    assert!(first.has_more);
+
+    let last = client.get_reports_page(&agent_id, &4, &2);
+    assert_eq!(last.items.len(), 1);
+    assert!(!last.has_more);
+
+    let mut scores = soroban_sdk::Vec::new(&env);
+    let mut offset = 0;
+    loop {
+        let page = client.get_reports_page(&agent_id, &offset, &2);
+        for report in page.items.iter() {
+            scores.push_back(report.score);
+        }
+        offset += page.items.len();
+        if !page.has_more {
+            break;
+        }
+    }
+    assert_eq!(scores, vec![&env, 10u32, 20, 30, 40, 50]);
+}
+
+#[test]
+fn test_storage_summary_tracks_reports_and_reads() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let agent_id = symbol_short!("agent_1");
+
+    client.initialize(&admin, &acl_id);
+    let empty = client.get_storage_summary();
+    assert_eq!(empty.total_stored, 0);
+    assert!(empty.by_op.is_empty());
+
+    client.submit_report(&reporter, &agent_id, &40, &None);
+    client.submit_report(&reporter, &agent_id, &60, &None);
+    client.get_reports(&agent_id);
+
+    let summary = client.get_storage_summary();
+    assert_eq!(summary.total_stored, 88);
+    assert_eq!(summary.total_accessed, 1);
+    assert_eq!(summary.by_op.get(symbol_short!("store")), Some(2));
+    assert_eq!(summary.by_op.get(symbol_short!("access")), Some(1));
+}
+
+#[test]
+fn test_setters_update_config_aggregate() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+
+    client.initialize(&admin, &acl_id);
+    let initial = client.get_config();
+    assert!(initial.dex_enabled);
+    assert!(initial.legacy_events);
+    assert_eq!(initial.thresholds.min_confidence_for_alert, 60);
+
+    let mut thresholds = DetectionThresholds::new();
+    thresholds.min_confidence_for_alert = 75;
+    client.set_detection_thresholds(&admin, &thresholds);
+    client.set_network_load(&admin, &800);
+    client.set_legacy_events(&admin, &false);
+
+    let config = client.get_config();
+    assert_eq!(config.thresholds.min_confidence_for_alert, 75);
+    assert_eq!(config.network_load, 800);
+    assert!(!config.legacy_events);
+    assert!(config.dex_enabled);
+    assert_eq!(
+        client.get_detection_thresholds().min_confidence_for_alert,
+        config.thresholds.min_confidence_for_alert
+    );
+}
+
+#[test]
+fn test_config_stored_before_new_fields_still_loads() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &acl_id);
+
+    // The shape `FraudConfig` had when it was introduced
+    env.as_contract(&contract_id, || {
+        let mut old: Map<Symbol, Val> = Map::new(&env);
+        old.set(Symbol::new(&env, "thresholds"), DetectionThresholds::new().into_val(&env));
+        old.set(Symbol::new(&env, "dex_config"), DexConfig::default().into_val(&env));
+        old.set(Symbol::new(&env, "dex_enabled"), true.into_val(&env));
+        old.set(Symbol::new(&env, "network_load"), 700u32.into_val(&env));
+        old.set(Symbol::new(&env, "legacy_events"), false.into_val(&env));
+        env.storage().instance().set(&DataKey::Config, &old);
+    });
+
+    let config = client.get_config();
+    assert!(config.dex_enabled);
+    assert_eq!(config.breaker_window, DEFAULT_BREAKER_WINDOW);
+    assert_eq!(config.commit_window, DEFAULT_COMMIT_WINDOW);
+    assert_eq!(config.grace_queue_len, 0);
+
+    // Settings owned by other modules are read live, not from the copy
+    assert_eq!(config.network_load, 1000);
+    assert!(config.legacy_events);
+    client.set_network_load(&admin, &800);
+    assert_eq!(client.get_config().network_load, 800);
+
+    // The next write stores the full shape
+    client.set_report_cooldown(&admin, &0);
+    env.as_contract(&contract_id, || {
+        let raw: Val = env.storage().instance().get(&DataKey::Config).unwrap();
+        assert!(FraudConfig::try_from_val(&env, &raw).is_ok());
+    });
+}
+
+#[test]
+fn test_export_import_round_trip() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let source_id = env.register(FraudDetectContract, ());
+    let source = FraudDetectContractClient::new(&env, &source_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    source.initialize(&admin, &acl_id);
+    source.add_reporter(&admin, &reporter);
+    source.set_network_load(&admin, &700);
+    source.submit_report(&reporter, &symbol_short!("agent_1"), &40, &None);
+    source.submit_report(&reporter, &symbol_short!("agent_2"), &90, &None);
+    source.pause(&admin);
+
+    let state = source.export_state();
+    assert_eq!(state.len(), STATE_EXPORT_LEN);
+
+    let target_id = env.register(FraudDetectContract, ());
+    let target = FraudDetectContractClient::new(&env, &target_id);
+    target.import_state(&state);
+
+    assert_eq!(target.get_reporters(), vec![&env, reporter]);
+    assert_eq!(target.get_total_reports(), 2);
+    assert_eq!(target.get_config().network_load, 700);
+    assert!(target.get_config().dex_enabled);
+    assert_eq!(
+        target.get_imported_report_digest(),
+        Some(source.get_report_digest())
+    );
+}
+
+#[test]
+fn test_import_requires_imported_admin_auth() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let source_id = env.register(FraudDetectContract, ());
+    let source = FraudDetectContractClient::new(&env, &source_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    source.initialize(&admin, &acl_id);
+    source.pause(&admin);
+    let state = source.export_state();
+
+    // Nobody signs for `admin`
+    env.set_auths(&[]);
+    let target_id = env.register(FraudDetectContract, ());
+    let target = FraudDetectContractClient::new(&env, &target_id);
+    assert!(target.try_import_state(&state).is_err());
+    assert!(target.get_contract_state().is_uninitialized());
+
+    env.mock_all_auths();
+    target.import_state(&state);
+    assert_eq!(env.auths()[0].0, admin);
+    assert!(target.get_contract_state().is_active());
+}
+
+#[test]
+fn test_import_rejects_malformed_state() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+
+    let short: Vec<Val> = vec![&env, STATE_EXPORT_VERSION.into_val(&env)];
+    assert_eq!(client.try_import_state(&short), Err(Ok(CommonError::InvalidFormat)));
+
+    let mut wrong_type: Vec<Val> = Vec::new(&env);
+    for _ in 0..STATE_EXPORT_LEN {
+        wrong_type.push_back(7u32.into_val(&env));
+    }
+    assert_eq!(client.try_import_state(&wrong_type), Err(Ok(CommonError::InvalidFormat)));
+}
+
+#[test]
+fn test_circuit_breaker_trips_on_burst() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let agent_id = symbol_short!("agent_1");
+
+    client.initialize(&admin, &acl_id);
+    assert_eq!(client.get_config().breaker_threshold, 0);
+    client.set_circuit_breaker(&admin, &3, &60);
+
+    for score in [10u32, 20, 30] {
+        client.submit_report(&reporter, &agent_id, &score, &None);
+    }
+    assert!(client.get_contract_state().is_active());
+
+    client.submit_report(&reporter, &agent_id, &40, &None);
+    assert!(client.get_contract_state().is_paused());
+    assert_eq!(client.get_total_reports(), 3);
+    let tripped: soroban_sdk::Vec<soroban_sdk::Val> =
+        vec![&env, Symbol::new(&env, "circuit_tripped").into_val(&env)];
+    assert!(env.events().all().iter().any(|(_, topics, _)| topics == tripped));
+    let dropped: soroban_sdk::Vec<soroban_sdk::Val> = vec![
+        &env,
+        Symbol::new(&env, "report_dropped").into_val(&env),
+        reporter.into_val(&env),
+    ];
+    assert!(env
+        .events()
+        .all()
+        .iter()
+        .any(|(_, topics, data)| topics == dropped && Symbol::try_from_val(&env, &data).is_ok_and(|id| id == agent_id)));
+
+    // Stays paused until the admin resumes, even after the window passes
+    env.ledger().with_mut(|li| li.timestamp += 120);
+    assert_eq!(
+        client.try_submit_report(&reporter, &agent_id, &50, &None),
+        Err(Ok(ContractError::InvalidState))
+    );
+
+    client.resume(&admin);
+    assert!(client.get_contract_state().is_active());
+    client.submit_report(&reporter, &agent_id, &50, &None);
+    assert_eq!(client.get_total_reports(), 4);
+}
+
+#[test]
+fn test_circuit_breaker_ignores_normal_traffic() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let agent_id = symbol_short!("agent_1");
+
+    client.initialize(&admin, &acl_id);
+    client.set_circuit_breaker(&admin, &3, &60);
+
+    for _ in 0..3 {
+        for score in [10u32, 20, 30] {
+            client.submit_report(&reporter, &agent_id, &score, &None);
+        }
+        env.ledger().with_mut(|li| li.timestamp += 61);
+    }
+
+    assert!(client.get_contract_state().is_active());
+    assert_eq!(client.get_total_reports(), 9);
+}
+
+mod deny_all_acl {
+    use soroban_sdk::{contract, contractimpl, Address, Env, Symbol};
+
+    #[contract]
+    pub struct DenyAllAcl;
+
+    #[contractimpl]
+    impl DenyAllAcl {
+        pub fn has_permission(_env: Env, _user: Address, _resource: Symbol, _action: Symbol) -> bool {
+            false
+        }
+    }
+}
+
+#[test]
+fn test_acl_resolves_through_service_registry() {
+    use common_utils::service_registry::{ServiceRegistry, ServiceRegistryClient, SERVICE_ACL};
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let allow_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let deny_id = env.register(deny_all_acl::DenyAllAcl, ());
+    let registry_id = env.register(ServiceRegistry, ());
+    let registry = ServiceRegistryClient::new(&env, &registry_id);
+
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let agent_id = symbol_short!("agent_1");
+    let acl_name = Symbol::new(&env, SERVICE_ACL);
+
+    client.initialize(&admin, &allow_id);
+    registry.initialize(&admin);
+    client.set_service_registry(&admin, &registry_id);
+
+    // No entry yet: the initialized ACL is used
+    client.submit_report(&reporter, &agent_id, &10, &None);
+
+    registry.register_service(&admin, &acl_name, &deny_id);
+    assert_eq!(
+        client.try_submit_report(&reporter, &agent_id, &20, &None),
+        Err(Ok(ContractError::Unauthorized))
+    );
+
+    // Rotating the registry entry is picked up without touching fraud-detect
+    registry.register_service(&admin, &acl_name, &allow_id);
+    client.submit_report(&reporter, &agent_id, &30, &None);
+    assert_eq!(client.get_total_reports(), 2);
+}
+
+fn setup_staking(env: &Env) -> (FraudDetectContractClient<'_>, Address, Address) {
+    use soroban_sdk::token::StellarAssetClient;
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(env);
+    let token = env.register_stellar_asset_contract_v2(admin.clone()).address();
+
+    client.initialize(&admin, &acl_id);
+    client.set_reporter_stake_policy(&admin, &token, &100, &3600);
+
+    let reporter = Address::generate(env);
+    StellarAssetClient::new(env, &token).mint(&reporter, &1_000);
+    (client, admin, reporter)
+}
+
+#[test]
+fn test_under_staked_reporter_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, reporter) = setup_staking(&env);
+    let agent_id = symbol_short!("agent_1");
+
+    assert_eq!(
+        client.try_submit_report(&reporter, &agent_id, &50, &None),
+        Err(Ok(ContractError::Unauthorized))
+    );
+
+    client.stake_to_report(&reporter, &99);
+    assert_eq!(
+        client.try_submit_report(&reporter, &agent_id, &50, &None),
+        Err(Ok(ContractError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_staked_reporter_can_submit() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, reporter) = setup_staking(&env);
+
+    let stake = client.stake_to_report(&reporter, &150);
+    assert_eq!(stake.amount, 150);
+
+    client.submit_report(&reporter, &symbol_short!("agent_1"), &50, &None);
+    assert_eq!(client.get_total_reports(), 1);
+    assert_eq!(client.get_reporter_stake(&reporter).last_report_at, env.ledger().timestamp());
+}
+
+#[test]
+fn test_stake_withdrawal_waits_for_cooldown() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, reporter) = setup_staking(&env);
+
+    client.stake_to_report(&reporter, &200);
+    client.submit_report(&reporter, &symbol_short!("agent_1"), &50, &None);
+
+    assert_eq!(
+        client.try_withdraw_stake(&reporter, &200),
+        Err(Ok(ContractError::InvalidState))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp += 3600);
+    let remaining = client.withdraw_stake(&reporter, &200);
+    assert_eq!(remaining.amount, 0);
+}
+
+#[test]
+fn test_slash_reduces_stake() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, reporter) = setup_staking(&env);
+
+    let agent_id = symbol_short!("agent_1");
+    client.stake_to_report(&reporter, &150);
+    client.submit_report(&reporter, &agent_id, &50, &None);
+    client.uphold_report_dispute(&admin, &reporter, &agent_id);
+    assert_eq!(client.slash_reporter_stake(&admin, &reporter, &agent_id, &100), 100);
+    assert_eq!(client.get_reporter_stake(&reporter).amount, 50);
+    assert!(!client.has_upheld_dispute(&reporter, &agent_id));
+
+    // Slashing below the minimum blocks further reports
+    assert_eq!(
+        client.try_submit_report(&reporter, &agent_id, &50, &None),
+        Err(Ok(ContractError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_slash_requires_upheld_dispute() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, reporter) = setup_staking(&env);
+    let agent_id = symbol_short!("agent_1");
+    client.stake_to_report(&reporter, &150);
+
+    assert_eq!(
+        client.try_slash_reporter_stake(&admin, &reporter, &agent_id, &100),
+        Err(Ok(ContractError::InvalidState))
+    );
+    // Nothing to dispute until the reporter has reported the agent
+    assert_eq!(
+        client.try_uphold_report_dispute(&admin, &reporter, &agent_id),
+        Err(Ok(ContractError::NotFound))
+    );
+
+    client.submit_report(&reporter, &agent_id, &50, &None);
+    client.uphold_report_dispute(&admin, &reporter, &agent_id);
+    assert_eq!(client.slash_reporter_stake(&admin, &reporter, &agent_id, &10), 10);
+
+    // Each upheld dispute allows one slash
+    assert_eq!(
+        client.try_slash_reporter_stake(&admin, &reporter, &agent_id, &10),
+        Err(Ok(ContractError::InvalidState))
+    );
+    assert_eq!(client.get_reporter_stake(&reporter).amount, 140);
+}
+
+#[test]
+fn test_stake_token_is_fixed_while_stake_is_held() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, reporter) = setup_staking(&env);
+    let token = client.get_config().stake_token.unwrap();
+    let other = env.register_stellar_asset_contract_v2(admin.clone()).address();
+
+    client.stake_to_report(&reporter, &150);
+    assert_eq!(
+        client.try_set_reporter_stake_policy(&admin, &other, &100, &3600),
+        Err(Ok(ContractError::InvalidState))
+    );
+    // The same token can still be reconfigured
+    client.set_reporter_stake_policy(&admin, &token, &50, &0);
+
+    client.withdraw_stake(&reporter, &150);
+    client.set_reporter_stake_policy(&admin, &other, &100, &3600);
+    assert_eq!(client.get_config().stake_token, Some(other));
+}
+
+fn setup_model_quorum(env: &Env) -> (FraudDetectContractClient<'_>, Address, [Address; 3]) {
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(env);
+    client.initialize(&admin, &acl_id);
+
+    let model_admins = [Address::generate(env), Address::generate(env), Address::generate(env)];
+    for model_admin in model_admins.iter() {
+        client.add_model_admin(&admin, model_admin);
+    }
+    client.set_model_threshold(&admin, &2);
+    (client, admin, model_admins)
+}
+
+#[test]
+fn test_model_commits_at_threshold() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, [a, b, _]) = setup_model_quorum(&env);
+    let hash = BytesN::from_array(&env, &[7u8; 32]);
+
+    assert!(!client.propose_model(&a, &hash));
+    assert_eq!(client.get_active_model(), None);
+
+    assert!(client.approve_model(&b, &hash));
+    assert_eq!(client.get_active_model(), Some(hash.clone()));
+    assert!(client.get_model_proposal(&hash).is_none());
+
+    let committed: soroban_sdk::Vec<soroban_sdk::Val> =
+        vec![&env, symbol_short!("mdl_upd").into_val(&env)];
+    assert!(env.events().all().iter().any(|(_, topics, _)| topics == committed));
+
+    // Single-admin path is closed once the quorum is configured
+    assert_eq!(
+        client.try_update_model(&admin, &Bytes::new(&env)),
+        Err(Ok(ContractError::UnsupportedOperation))
+    );
+}
+
+#[test]
+fn test_model_needs_distinct_approvals() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, [a, _, _]) = setup_model_quorum(&env);
+    let hash = BytesN::from_array(&env, &[7u8; 32]);
+
+    client.propose_model(&a, &hash);
+    assert_eq!(client.try_approve_model(&a, &hash), Err(Ok(ContractError::InvalidState)));
+
+    // Only model admins may approve
+    assert_eq!(client.try_approve_model(&admin, &hash), Err(Ok(ContractError::Unauthorized)));
+    assert_eq!(client.get_active_model(), None);
+    assert_eq!(client.get_model_proposal(&hash).unwrap().approvals.len(), 1);
+}
+
+#[test]
+fn test_removed_model_admin_approval_stops_counting() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, [a, b, c]) = setup_model_quorum(&env);
+    let hash = BytesN::from_array(&env, &[7u8; 32]);
+
+    assert!(!client.propose_model(&a, &hash));
+    client.remove_model_admin(&admin, &a);
+
+    // `a`'s approval is still recorded but no longer counts towards 2
+    assert!(!client.approve_model(&b, &hash));
+    assert_eq!(client.get_active_model(), None);
+    assert_eq!(client.get_model_proposal(&hash).unwrap().approvals.len(), 2);
+
+    assert!(client.approve_model(&c, &hash));
+    assert_eq!(client.get_active_model(), Some(hash));
+}
+
+#[test]
+fn test_approvals_for_different_hashes_do_not_combine() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, [a, b, _]) = setup_model_quorum(&env);
+    let first = BytesN::from_array(&env, &[1u8; 32]);
+    let second = BytesN::from_array(&env, &[2u8; 32]);
+
+    assert!(!client.propose_model(&a, &first));
+    assert!(!client.propose_model(&b, &second));
+
+    assert_eq!(client.get_active_model(), None);
+    assert_eq!(client.get_model_proposal(&first).unwrap().approvals.len(), 1);
+    assert_eq!(client.get_model_proposal(&second).unwrap().approvals.len(), 1);
+}
+
+/// What a client sees when strict agent mode turns a report down
+const UNKNOWN_AGENT: InvokeError = InvokeError::Contract(ValidationError::UnknownAgent as u32);
+
+#[test]
+fn test_strict_agent_mode_rejects_unregistered_ids() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let known = symbol_short!("agent_1");
+    let typo = symbol_short!("agent_l");
+
+    client.initialize(&admin, &acl_id);
+
+    // Permissive by default
+    client.submit_report(&reporter, &typo, &10, &None);
+
+    client.register_agent(&admin, &known);
+    client.set_require_registered_agents(&admin, &true);
+    assert_eq!(
+        client.try_submit_report(&reporter, &typo, &20, &None),
+        Err(Err(UNKNOWN_AGENT))
+    );
+    client.submit_report(&reporter, &known, &30, &None);
+
+    assert!(client.is_registered_agent(&known));
+    assert!(!client.is_registered_agent(&typo));
+    assert_eq!(client.get_registered_agents(&0, &10), vec![&env, known]);
+    assert_eq!(client.get_total_reports(), 2);
+}
+
+#[test]
+fn test_feature_flags_default_off_and_toggle() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let flag = symbol_short!("decay");
+
+    client.initialize(&admin, &acl_id);
+    assert!(!client.is_feature_enabled(&flag));
+    assert!(!client.is_feature_enabled(&symbol_short!("unknown")));
+    assert_eq!(client.get_all_features().len(), 0);
+
+    client.set_feature(&admin, &flag, &true);
+    assert!(client.is_feature_enabled(&flag));
+    assert!(client.get_all_features().get(flag.clone()).unwrap());
+
+    client.set_feature(&admin, &flag, &false);
+    assert!(!client.is_feature_enabled(&flag));
+    assert_eq!(client.get_all_features().len(), 0);
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_set_feature(&stranger, &flag, &true),
+        Err(Ok(ContractError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_export_all_reports_covers_every_agent_while_paused() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let agents = [
+        symbol_short!("agent_1"),
+        symbol_short!("agent_2"),
+        symbol_short!("agent_3"),
+    ];
+
+    client.initialize(&admin, &acl_id);
+    client.set_circuit_breaker(&admin, &5, &60);
+    for (i, agent_id) in agents.iter().enumerate() {
+        client.submit_report(&reporter, agent_id, &(10 * i as u32 + 10), &None);
+    }
+    client.submit_report(&reporter, &agents[0], &90, &None);
+    client.submit_report(&reporter, &agents[1], &95, &None);
+    // Trip the breaker so the export runs against a paused contract
+    client.submit_report(&reporter, &agents[2], &99, &None);
+    assert!(client.get_contract_state().is_paused());
+
+    let first = client.export_all_reports(&admin, &0, &2);
+    let rest = client.export_all_reports(&admin, &2, &2);
+    assert_eq!(first.len(), 2);
+    assert_eq!(rest.len(), 1);
+    assert_eq!(client.export_all_reports(&admin, &3, &2).len(), 0);
+
+    let mut exported = 0;
+    for (i, (agent_id, reports)) in first.iter().chain(rest.iter()).enumerate() {
+        assert_eq!(agent_id, agents[i]);
+        assert_eq!(reports, client.get_reports(&agent_id));
+        exported += reports.len() as u64;
+    }
+    assert_eq!(exported, client.get_total_reports());
+
+    let outsider = Address::generate(&env);
+    assert_eq!(
+        client.try_export_all_reports(&outsider, &0, &2),
+        Err(Ok(ContractError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_agent_index_dedupes_and_drops_pruned_agents() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let old = symbol_short!("agent_1");
+    let fresh = symbol_short!("agent_2");
+
+    client.initialize(&admin, &acl_id);
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    client.submit_report(&reporter, &old, &10, &None);
+    client.submit_report(&reporter, &old, &20, &None);
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    client.submit_report(&reporter, &fresh, &30, &None);
+
+    assert_eq!(client.agent_count(), 2);
+    assert_eq!(client.get_all_agents(&0, &10), vec![&env, old.clone(), fresh.clone()]);
+    assert_eq!(client.get_all_agents(&1, &10), vec![&env, fresh.clone()]);
+
+    assert_eq!(client.prune_reports(&admin, &old, &200), 2);
+    assert_eq!(client.agent_count(), 1);
+    assert_eq!(client.get_all_agents(&0, &10), vec![&env, fresh.clone()]);
+    assert_eq!(client.get_reports(&old).len(), 0);
+    assert_eq!(client.get_total_reports(), 1);
+
+    // Nothing older than the cutoff leaves the agent indexed
+    assert_eq!(client.prune_reports(&admin, &fresh, &200), 0);
+    assert_eq!(client.agent_count(), 1);
+}
+
+#[test]
+fn test_global_stats_track_writes_and_match_fresh_computation() {
+    use common_utils::batch::FraudFlag;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let agent_1 = symbol_short!("agent_1");
+    let agent_2 = symbol_short!("agent_2");
+
+    client.initialize(&admin, &acl_id);
+    assert_eq!(client.get_global_stats(), GlobalFraudStats::default());
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    client.submit_report(&reporter, &agent_1, &40, &None);
+    client.submit_report(&reporter, &agent_1, &60, &None);
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    client.submit_report(&reporter, &agent_2, &80, &None);
+
+    let stats = client.get_global_stats();
+    assert_eq!(stats.agent_count, 2);
+    assert_eq!(stats.total_reports, 3);
+    assert_eq!(stats.avg_latest_score, 70);
+    assert_eq!(stats.flagged_count, 0);
+
+    let account = Address::generate(&env);
+    let flags = vec![&env, FraudFlag { account_id: account.clone(), reason_code: 7 }];
+    client.batch_flag_fraud(&admin, &flags);
+    // Re-flagging the same account does not count twice
+    client.batch_flag_fraud(&admin, &flags);
+    assert_eq!(client.get_global_stats().flagged_count, 1);
+
+    client.prune_reports(&admin, &agent_1, &200);
+    let stats = client.get_global_stats();
+    assert_eq!(stats.agent_count, 1);
+    assert_eq!(stats.total_reports, 1);
+    assert_eq!(stats.avg_latest_score, 80);
+
+    let fresh = env.as_contract(&contract_id, || stats::compute(&env));
+    assert_eq!(stats, fresh);
+}
+
+#[test]
+fn test_set_user_trust_tiers_in_bulk() {
+    use common_utils::batch::MAX_BATCH_SIZE;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &acl_id);
+
+    let verified = Address::generate(&env);
+    let premium = Address::generate(&env);
+    let entries = vec![
+        &env,
+        (verified.clone(), TrustTier::Verified),
+        (premium.clone(), TrustTier::Premium),
+    ];
+    assert_eq!(client.set_user_trust_tiers(&admin, &entries), 2);
+    env.as_contract(&contract_id, || {
+        assert_eq!(RateLimiter::get_trust_tier(&env, &verified), TrustTier::Verified);
+        assert_eq!(RateLimiter::get_trust_tier(&env, &premium), TrustTier::Premium);
+    });
+
+    let mut too_many = soroban_sdk::Vec::new(&env);
+    for _ in 0..=MAX_BATCH_SIZE {
+        too_many.push_back((Address::generate(&env), TrustTier::Trusted));
+    }
+    assert_eq!(
+        client.try_set_user_trust_tiers(&admin, &too_many),
+        Err(Err(CAPACITY_EXCEEDED))
+    );
+    assert_eq!(
+        client.try_set_user_trust_tiers(&admin, &soroban_sdk::Vec::new(&env)),
+        Err(Ok(CommonError::InvalidLength))
+    );
+}
+
+#[test]
+fn test_user_trust_tier_falls_back_to_configured_default() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let unset = Address::generate(&env);
+    let explicit = Address::generate(&env);
+    client.initialize(&admin, &acl_id);
+
+    assert_eq!(client.get_user_trust_tier(&unset), TrustTier::Standard);
+    client.set_default_trust_tier(&admin, &TrustTier::Trusted);
+    client.set_user_trust_tier(&admin, &explicit, &TrustTier::Verified);
+
+    assert_eq!(client.get_user_trust_tier(&unset), TrustTier::Trusted);
+    assert_eq!(client.get_user_trust_tier(&explicit), TrustTier::Verified);
+}
+
+#[test]
+fn test_report_cooldown_is_per_reporter_and_agent() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let agent_1 = symbol_short!("agent_1");
+    let agent_2 = symbol_short!("agent_2");
+
+    client.initialize(&admin, &acl_id);
+    client.set_report_cooldown(&admin, &300);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    client.submit_report(&reporter, &agent_1, &10, &None);
+    assert_eq!(
+        client.try_submit_report(&reporter, &agent_1, &90, &None),
+        Err(Err(InvokeError::Contract(ValidationError::Cooldown as u32)))
+    );
+    // Other agents and other reporters are unaffected
+    client.submit_report(&reporter, &agent_2, &20, &None);
+    client.submit_report(&Address::generate(&env), &agent_1, &30, &None);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_300);
+    client.submit_report(&reporter, &agent_1, &40, &None);
+    assert_eq!(client.get_total_reports(), 4);
+}
+
+#[test]
+fn test_reads_during_migration() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let agent_id = symbol_short!("agent_1");
+
+    client.initialize(&admin, &acl_id);
+    client.submit_report(&reporter, &agent_id, &70, &None);
+    let before = client.get_reports(&agent_id);
+
+    // Left mid-way: begun but not finished
+    client.begin_migration(&admin);
+    assert!(client.get_contract_state().is_migrating());
+    assert_eq!(client.get_reports(&agent_id), before);
+    assert_eq!(
+        client.try_get_latest_score_checked(&agent_id),
+        Err(Ok(StateError::MigrationInProgress))
+    );
+    assert_eq!(
+        client.try_submit_report(&reporter, &agent_id, &80, &None),
+        Err(Ok(ContractError::InvalidState))
+    );
+
+    client.finish_migration(&admin);
+    assert!(client.get_contract_state().is_active());
+    assert_eq!(client.get_latest_score_checked(&agent_id), 70);
+    client.submit_report(&reporter, &agent_id, &80, &None);
+}
+
+#[test]
+fn test_migrate_to_compressed_runs_inside_migration() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let agent_id = symbol_short!("agent_1");
+
+    client.initialize(&admin, &acl_id);
+    client.submit_report(&reporter, &agent_id, &70, &None);
+
+    // Outside the migration window
+    assert_eq!(
+        client.try_migrate_to_compressed(&admin),
+        Err(Ok(ContractError::InvalidState))
+    );
+
+    client.begin_migration(&admin);
+    assert_eq!(client.migrate_to_compressed(&admin), 0);
+    client.finish_migration(&admin);
+
+    assert!(client.get_contract_state().is_active());
+    assert_eq!(client.get_latest_score_checked(&agent_id), 70);
+    assert_eq!(
+        client.try_migrate_to_compressed(&admin),
+        Err(Ok(ContractError::InvalidState))
+    );
+}
+
+#[test]
+fn test_report_cap_rejects_at_boundary() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let agent_id = symbol_short!("agent_1");
+
+    client.initialize(&admin, &acl_id);
+    client.set_max_reports_per_agent(&admin, &2);
+    client.submit_report(&reporter, &agent_id, &10, &None);
+    client.submit_report(&reporter, &agent_id, &20, &None);
+    assert_eq!(
+        client.try_submit_report(&reporter, &agent_id, &30, &None),
+        Err(Err(CAPACITY_EXCEEDED))
+    );
+    assert_eq!(client.get_reports(&agent_id).len(), 2);
+    assert_eq!(client.get_latest_score(&agent_id), 20);
+}
+
+#[test]
+fn test_report_cap_evicts_oldest() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let agent_id = symbol_short!("agent_1");
+
+    client.initialize(&admin, &acl_id);
+    client.set_max_reports_per_agent(&admin, &2);
+    client.set_evict_oldest_reports(&admin, &true);
+    for score in [10u32, 20, 30] {
+        client.submit_report(&reporter, &agent_id, &score, &None);
+    }
+
+    let reports = client.get_reports(&agent_id);
+    assert_eq!(reports.len(), 2);
+    assert_eq!(reports.get(0).unwrap().score, 20);
+    assert_eq!(reports.get(1).unwrap().score, 30);
+    assert_eq!(client.get_latest_score(&agent_id), 30);
+    assert_eq!(client.get_total_reports(), 2);
+}
+
+#[test]
+fn test_remove_reporter_records_reason_until_re_added() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+
+    client.initialize(&admin, &acl_id);
+    client.add_reporter(&admin, &reporter);
+    assert_eq!(client.get_removal_reason(&reporter), None);
+
+    client.remove_reporter(&admin, &reporter, &Some(symbol_short!("spam")));
+    assert_eq!(
+        client.get_removal_reason(&reporter),
+        Some((1_000, admin.clone(), symbol_short!("spam")))
+    );
+    assert!(!client.get_reporters().contains(&reporter));
+    assert_eq!(
+        client.try_submit_report(&reporter, &symbol_short!("agent_1"), &50, &None),
+        Err(Ok(ContractError::Unauthorized))
+    );
+
+    client.add_reporter(&admin, &reporter);
+    assert_eq!(client.get_removal_reason(&reporter), None);
+
+    client.remove_reporter(&admin, &reporter, &None);
+    assert_eq!(
+        client.get_removal_reason(&reporter),
+        Some((1_000, admin, DEFAULT_REMOVAL_REASON))
+    );
+}
+
+#[test]
+fn test_scores_normalize_across_scales() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let agent_id = symbol_short!("agent_1");
+
+    let percent_id = env.register(FraudDetectContract, ());
+    let percent = FraudDetectContractClient::new(&env, &percent_id);
+    percent.initialize(&admin, &acl_id);
+    percent.submit_report(&reporter, &agent_id, &75, &None);
+
+    let permille_id = env.register(FraudDetectContract, ());
+    let permille = FraudDetectContractClient::new(&env, &permille_id);
+    permille.initialize(&admin, &acl_id);
+    permille.set_score_scale(&admin, &1000);
+    permille.submit_report(&reporter, &agent_id, &750, &None);
+
+    assert_eq!(percent.get_score_normalized(&agent_id), 7_500);
+    assert_eq!(permille.get_score_normalized(&agent_id), 7_500);
+
+    assert_eq!(
+        percent.try_submit_report(&reporter, &agent_id, &101, &None),
+        Err(Ok(ContractError::InvalidInput))
+    );
+    assert_eq!(
+        permille.try_set_score_scale(&admin, &0),
+        Err(Ok(ContractError::InvalidInput))
+    );
+}
+
+#[test]
+fn test_report_chain_is_deterministic_and_survives_pruning() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(100);
+
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let agent_id = symbol_short!("agent_1");
+    let zero = BytesN::from_array(&env, &[0u8; 32]);
+
+    let mut clients = [None, None];
+    for slot in clients.iter_mut() {
+        let id = env.register(FraudDetectContract, ());
+        let client = FraudDetectContractClient::new(&env, &id);
+        client.initialize(&admin, &acl_id);
+        client.set_report_chain_enabled(&admin, &true);
+        *slot = Some(client);
+    }
+    let [Some(first), Some(second)] = clients else { unreachable!() };
+
+    first.submit_report(&reporter, &agent_id, &40, &None);
+    let after_one = first.get_report_chain_head();
+    assert_ne!(after_one, zero);
+    first.submit_report(&reporter, &agent_id, &60, &None);
+    let after_two = first.get_report_chain_head();
+    assert_ne!(after_two, after_one);
+
+    second.submit_report(&reporter, &agent_id, &40, &None);
+    assert_eq!(second.get_report_chain_head(), after_one);
+    second.submit_report(&reporter, &agent_id, &60, &None);
+    assert_eq!(second.get_report_chain_head(), after_two);
+
+    first.prune_reports(&admin, &agent_id, &200);
+    assert!(first.get_reports(&agent_id).is_empty());
+    assert_eq!(first.get_report_chain_head(), after_two);
+
+    first.set_report_chain_enabled(&admin, &false);
+    first.submit_report(&reporter, &agent_id, &70, &None);
+    assert_eq!(first.get_report_chain_head(), after_two);
+}
+
+#[test]
+fn test_reinitialize_only_from_terminated() {
+    use common_utils::batch::FraudFlag;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let agent_id = symbol_short!("agent_1");
+
+    client.initialize(&admin, &acl_id);
+    client.add_reporter(&admin, &reporter);
+    client.set_score_scale(&admin, &1000);
+    client.submit_report(&reporter, &agent_id, &400, &None);
+
+    let account = Address::generate(&env);
+    client.set_dispute_window(&admin, &600);
+    client.batch_flag_fraud(&admin, &vec![&env, FraudFlag { account_id: account.clone(), reason_code: 7 }]);
+    client.set_reporter_weight(&admin, &reporter, &3);
+    client.uphold_report_dispute(&admin, &reporter, &agent_id);
+    client.set_user_trust_tier(&admin, &reporter, &TrustTier::Premium);
+    client.set_default_trust_tier(&admin, &TrustTier::Verified);
+    assert!(client.flag_pending_until(&account).is_some());
+
+    assert_eq!(
+        client.try_reinitialize(&admin, &new_admin, &acl_id),
+        Err(Ok(ContractError::InvalidState))
+    );
+    client.begin_migration(&admin);
+    assert_eq!(
+        client.try_reinitialize(&admin, &new_admin, &acl_id),
+        Err(Ok(ContractError::InvalidState))
+    );
+    client.finish_migration(&admin);
+
+    client.set_circuit_breaker(&admin, &1, &60);
+    for score in [10u32, 20, 30] {
+        client.submit_report(&reporter, &symbol_short!("agent_2"), &score, &None);
+    }
+    assert!(client.get_contract_state().is_paused());
+    assert_eq!(
+        client.try_reinitialize(&admin, &new_admin, &acl_id),
+        Err(Ok(ContractError::InvalidState))
+    );
+
+    client.terminate(&admin);
+    assert!(client.get_contract_state().is_terminated());
+    assert_eq!(
+        client.try_reinitialize(&new_admin, &new_admin, &acl_id),
+        Err(Ok(ContractError::Unauthorized))
+    );
+
+    client.reinitialize(&admin, &new_admin, &acl_id);
+    assert!(client.get_contract_state().is_active());
+    assert!(client.get_reports(&agent_id).is_empty());
+    assert_eq!(client.get_latest_score(&agent_id), 0);
+    assert_eq!(client.get_total_reports(), 0);
+    assert_eq!(client.agent_count(), 0);
+    assert!(client.get_reporters().is_empty());
+    assert_eq!(client.get_config().score_scale, DEFAULT_SCORE_SCALE);
+
+    // Per-account state from the old instance is gone too
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().instance().has(&DataKey::FlaggedAccount(account.clone())));
+        assert!(!env.storage().persistent().has(&DataKey::LastReport(reporter.clone(), agent_id.clone())));
+    });
+    assert!(!client.is_flagged(&account));
+    assert_eq!(client.flag_pending_until(&account), None);
+    assert_eq!(client.get_reporter_weight(&reporter), 1);
+    assert_eq!(client.get_reporter_reliability(&reporter), FULL_RELIABILITY);
+    assert_eq!(client.get_user_trust_tier(&reporter), TrustTier::Standard);
+    assert_eq!(client.get_user_trust_tier(&account), TrustTier::Standard);
+    assert_eq!(
+        client.try_set_score_scale(&admin, &10),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    client.set_score_scale(&new_admin, &10);
+}
+
+#[test]
+fn test_report_metadata_tracks_submissions_and_pruning() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let agent_id = symbol_short!("agent_1");
+
+    client.initialize(&admin, &acl_id);
+    for (ts, score) in [(100u64, 10u32), (200, 20), (300, 30)] {
+        env.ledger().set_timestamp(ts);
+        client.submit_report(&reporter, &agent_id, &score, &None);
+    }
+
+    let (reports, metadata) = client.get_reports_with_metadata(&agent_id);
+    assert_eq!(reports, client.get_reports(&agent_id));
+    assert_eq!(metadata.count, 3);
+    assert_eq!((metadata.first_ts, metadata.last_ts), (100, 300));
+    assert_eq!(metadata.compression_type, CompressionType::DeltaEncoding);
+    assert!(metadata.stored_bytes > 0);
+
+    client.prune_reports(&admin, &agent_id, &200);
+    let (reports, metadata) = client.get_reports_with_metadata(&agent_id);
+    assert_eq!(reports.len(), 2);
+    assert_eq!(metadata.count, 2);
+    assert_eq!((metadata.first_ts, metadata.last_ts), (200, 300));
+
+    client.prune_reports(&admin, &agent_id, &1_000);
+    let (reports, metadata) = client.get_reports_with_metadata(&agent_id);
+    assert!(reports.is_empty());
+    assert_eq!(metadata.count, 0);
+    assert_eq!((metadata.first_ts, metadata.last_ts, metadata.stored_bytes), (0, 0, 0));
+}
+
+#[test]
+fn test_dispute_window_defers_and_cancels_flags() {
+    use common_utils::batch::FraudFlag;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let undisputed = Address::generate(&env);
+    let disputed = Address::generate(&env);
+
+    client.initialize(&admin, &acl_id);
+    client.set_dispute_window(&admin, &600);
+    let flags = vec![
+        &env,
+        FraudFlag { account_id: undisputed.clone(), reason_code: 90 },
+        FraudFlag { account_id: disputed.clone(), reason_code: 90 },
+    ];
+    client.batch_flag_fraud(&admin, &flags);
+
+    assert!(!client.is_flagged(&undisputed));
+    assert!(!client.is_flagged(&disputed));
+    assert_eq!(client.flag_pending_until(&undisputed), Some(1_600));
+
+    env.ledger().set_timestamp(1_599);
+    client.uphold_flag_dispute(&admin, &disputed);
+    assert_eq!(client.get_global_stats().flagged_count, 1);
+
+    env.ledger().set_timestamp(1_600);
+    assert!(client.is_flagged(&undisputed));
+    assert!(!client.is_flagged(&disputed));
+    // Too late to dispute
+    assert_eq!(
+        client.try_uphold_flag_dispute(&admin, &undisputed),
+        Err(Ok(ContractError::InvalidState))
+    );
+}
+
+#[test]
+fn test_reporter_weight_shifts_aggregate() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let retail = Address::generate(&env);
+    let institution = Address::generate(&env);
+    let agent_id = symbol_short!("agent_1");
+
+    client.initialize(&admin, &acl_id);
+    client.submit_report(&retail, &agent_id, &20, &None);
+    client.submit_report(&institution, &agent_id, &80, &None);
+
+    assert_eq!(client.get_reporter_weight(&institution), 1);
+    assert_eq!(client.get_aggregate_score(&agent_id), 50);
+
+    client.set_reporter_weight(&admin, &institution, &3);
+    assert_eq!(client.get_reporter_weight(&institution), 3);
+    assert_eq!(client.get_aggregate_score(&agent_id), 65);
+
+    // Large weights are accumulated with checked i128 math
+    client.set_reporter_weight(&admin, &institution, &u32::MAX);
+    client.set_reporter_weight(&admin, &retail, &u32::MAX);
+    assert_eq!(client.get_aggregate_score(&agent_id), 50);
+
+    client.set_reporter_weight(&admin, &retail, &0);
+    assert_eq!(client.get_aggregate_score(&agent_id), 80);
+}
+
+#[test]
+fn test_reporter_weight_combines_with_reliability() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let retail = Address::generate(&env);
+    let institution = Address::generate(&env);
+    let agent_id = symbol_short!("agent_1");
+
+    client.initialize(&admin, &acl_id);
+    client.submit_report(&retail, &agent_id, &20, &None);
+    client.submit_report(&institution, &agent_id, &80, &None);
+    client.set_reporter_weight(&admin, &institution, &3);
+    assert_eq!(client.get_reporter_reliability(&institution), FULL_RELIABILITY);
+
+    // Reliability only counts once the flag is on
+    client.uphold_report_dispute(&admin, &institution, &agent_id);
+    assert_eq!(client.get_reporter_reliability(&institution), FULL_RELIABILITY / 2);
+    assert_eq!(client.get_aggregate_score(&agent_id), 65);
+
+    // (20 * 1 + 80 * 3 / 2) / (1 + 3 / 2)
+    client.set_feature(&admin, &FEATURE_RELIABILITY_WEIGHTS, &true);
+    assert_eq!(client.get_aggregate_score(&agent_id), 56);
+
+    // Upholding the same pending dispute again does not count twice
+    client.uphold_report_dispute(&admin, &institution, &agent_id);
+    assert_eq!(client.get_reporter_reliability(&institution), FULL_RELIABILITY / 2);
+
+    client.set_reporter_weight(&admin, &institution, &u32::MAX);
+    client.set_reporter_weight(&admin, &retail, &u32::MAX);
+    assert_eq!(client.get_aggregate_score(&agent_id), 40);
+
+    client.set_feature(&admin, &FEATURE_RELIABILITY_WEIGHTS, &false);
+    assert_eq!(client.get_aggregate_score(&agent_id), 50);
+}
+
+#[test]
+fn test_tiebreak_policy_settles_fractional_aggregate() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let (first, second) = (Address::generate(&env), Address::generate(&env));
+    let (rising, falling) = (symbol_short!("rising"), symbol_short!("falling"));
+
+    client.initialize(&admin, &acl_id);
+    // Both means are exactly 50.5
+    client.submit_report(&first, &rising, &20, &None);
+    client.submit_report(&second, &rising, &81, &None);
+    client.submit_report(&first, &falling, &81, &None);
+    client.submit_report(&second, &falling, &20, &None);
+
+    assert_eq!(client.get_tiebreak(), TieBreak::Latest);
+    assert_eq!(client.get_aggregate_score(&rising), 51);
+    assert_eq!(client.get_aggregate_score(&falling), 50);
+
+    client.set_tiebreak(&admin, &TieBreak::Highest);
+    assert_eq!(client.get_aggregate_score(&rising), 51);
+    assert_eq!(client.get_aggregate_score(&falling), 51);
+
+    client.set_tiebreak(&admin, &TieBreak::Lowest);
+    assert_eq!(client.get_aggregate_score(&rising), 50);
+    assert_eq!(client.get_aggregate_score(&falling), 50);
+
+    // Whole means are unaffected
+    client.set_reporter_weight(&admin, &first, &0);
+    assert_eq!(client.get_aggregate_score(&rising), 81);
+}
+
+#[test]
+fn test_state_label_follows_lifecycle() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let label = |name: &str| Symbol::new(&env, name);
+
+    assert_eq!(client.get_state_label(), label("uninitialized"));
+    client.initialize(&admin, &acl_id);
+    assert_eq!(client.get_state_label(), label("active"));
+
+    client.begin_migration(&admin);
+    assert_eq!(client.get_state_label(), label("migrating"));
+    client.finish_migration(&admin);
+    assert_eq!(client.get_state_label(), label("active"));
+
+    client.set_circuit_breaker(&admin, &1, &60);
+    for score in [10u32, 20] {
+        client.submit_report(&reporter, &symbol_short!("agent_1"), &score, &None);
+    }
+    assert!(client.get_contract_state().is_paused());
+    assert_eq!(client.get_state_label(), label("paused"));
+    client.resume(&admin);
+    assert_eq!(client.get_state_label(), label("active"));
+
+    client.terminate(&admin);
+    assert_eq!(client.get_state_label(), label("terminated"));
+}
+
+mod selective_acl {
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env, Symbol};
+
+    /// Grants `manage` to everyone and `report` only to granted addresses
+    #[contract]
+    pub struct SelectiveAcl;
+
+    #[contractimpl]
+    impl SelectiveAcl {
+        pub fn grant(env: Env, user: Address, allowed: bool) {
+            env.storage().persistent().set(&user, &allowed);
+        }
+
+        pub fn has_permission(env: Env, user: Address, _resource: Symbol, action: Symbol) -> bool {
+            action == symbol_short!("manage") || env.storage().persistent().get(&user).unwrap_or(false)
+        }
+    }
+}
+
+#[test]
+fn test_sync_reporters_from_acl_converges_to_acl() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(selective_acl::SelectiveAcl, ());
+    let acl = selective_acl::SelectiveAclClient::new(&env, &acl_id);
+    let admin = Address::generate(&env);
+    let kept = Address::generate(&env);
+    let revoked = Address::generate(&env);
+    let granted = Address::generate(&env);
+    let never = Address::generate(&env);
+    let untouched = Address::generate(&env);
+
+    client.initialize(&admin, &acl_id);
+    for reporter in [&kept, &revoked, &untouched] {
+        client.add_reporter(&admin, reporter);
+    }
+    acl.grant(&kept, &true);
+    acl.grant(&granted, &true);
+
+    let candidates = vec![&env, kept.clone(), revoked.clone(), granted.clone(), never.clone()];
+    assert_eq!(client.sync_reporters_from_acl(&admin, &candidates), (1, 1));
+    let synced: soroban_sdk::Vec<soroban_sdk::Val> =
+        vec![&env, Symbol::new(&env, "reporters_synced").into_val(&env)];
+    assert!(env.events().all().iter().any(|(_, topics, _)| topics == synced));
+
+    let reporters = client.get_reporters();
+    assert!(reporters.contains(&kept));
+    assert!(reporters.contains(&granted));
+    assert!(reporters.contains(&untouched));
+    assert!(!reporters.contains(&revoked));
+    assert!(!reporters.contains(&never));
+    assert_eq!(client.get_removal_reason(&revoked).unwrap().2, ACL_SYNC_REMOVAL_REASON);
+
+    // Already converged
+    assert_eq!(client.sync_reporters_from_acl(&admin, &candidates), (0, 0));
+    assert_eq!(client.get_reporters().len(), 3);
+}
+
+#[test]
+fn test_torn_report_write_is_detected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let agent_id = symbol_short!("agent_1");
+
+    client.initialize(&admin, &acl_id);
+    client.submit_report(&reporter, &agent_id, &40, &None);
+    client.submit_report(&reporter, &symbol_short!("agent_2"), &60, &None);
+
+    env.as_contract(&contract_id, || {
+        assert!(FraudDetectContract::report_write_intact(&env, &agent_id, 1, 2));
+
+        // Lose the stored report but keep its metadata, like a torn write
+        CompressedReportStorage::store_reports(&env, &agent_id, &Vec::new(&env)).unwrap();
+        assert!(!FraudDetectContract::report_write_intact(&env, &agent_id, 1, 2));
+    });
+}
+
+#[test]
+fn test_version_and_build_id() {
+    let env = Env::default();
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+
+    assert_eq!(client.version(), CONTRACT_VERSION);
+    assert_eq!(client.build_id(), BUILD_ID);
+}
+
+#[test]
+fn test_admin_activity_keeps_heartbeat_alive() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+
+    client.initialize(&admin, &acl_id);
+    assert_eq!(client.get_last_admin_action(), Some(1_000));
+    // Disabled by default
+    env.ledger().with_mut(|li| li.timestamp += 1_000_000);
+    assert!(!client.check_heartbeat());
+
+    client.set_admin_heartbeat(&admin, &3_600);
+    for _ in 0..3 {
+        env.ledger().with_mut(|li| li.timestamp += 3_000);
+        client.set_report_cooldown(&admin, &0);
+        assert!(!client.check_heartbeat());
+    }
+    env.ledger().with_mut(|li| li.timestamp += 3_600);
+    assert!(!client.check_heartbeat());
+    assert!(client.get_contract_state().is_active());
+}
+
+#[test]
+fn test_lapsed_heartbeat_pauses_until_admin_resumes() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+
+    client.initialize(&admin, &acl_id);
+    client.set_admin_heartbeat(&admin, &3_600);
+    env.ledger().with_mut(|li| li.timestamp += 3_601);
+
+    assert!(client.check_heartbeat());
+    assert!(client.get_contract_state().is_paused());
+    let expired: soroban_sdk::Vec<soroban_sdk::Val> =
+        vec![&env, Symbol::new(&env, "heartbeat_expired").into_val(&env)];
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(topics, expired);
+    let payload: (u64, u64) = TryFromVal::try_from_val(&env, &data).unwrap();
+    assert_eq!(payload, (1_000, 3_600));
+
+    assert!(client
+        .try_submit_report(&reporter, &symbol_short!("agent_1"), &50, &None)
+        .is_err());
+    // Already paused: nothing more to do
+    assert!(!client.check_heartbeat());
+
+    assert!(client.try_resume(&Address::generate(&env)).is_err());
+    client.resume(&admin);
+    assert!(client.get_contract_state().is_active());
+    assert!(!client.check_heartbeat());
+}
+
+fn eligibility(reason: Symbol) -> SubmitEligibility {
+    SubmitEligibility { allowed: reason == SUBMIT_OK, reason }
+}
+
+#[test]
+fn test_can_submit_report_when_eligible_is_read_only() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let agent_id = symbol_short!("agent_1");
+
+    assert_eq!(
+        client.can_submit_report(&reporter, &agent_id),
+        eligibility(SUBMIT_NOT_INITIALIZED)
+    );
+    client.initialize(&admin, &acl_id);
+
+    // Asking does not consume any of the rate limit
+    for _ in 0..(REPORT_RATE_MAX * 2) {
+        assert_eq!(client.can_submit_report(&reporter, &agent_id), eligibility(SUBMIT_OK));
+    }
+    for _ in 0..(REPORT_RATE_MAX - 1) {
+        client.submit_report(&reporter, &agent_id, &50, &None);
+    }
+    assert_eq!(client.can_submit_report(&reporter, &agent_id), eligibility(SUBMIT_OK));
+    client.submit_report(&reporter, &agent_id, &50, &None);
+
+    assert_eq!(
+        client.can_submit_report(&reporter, &agent_id),
+        eligibility(SUBMIT_RATE_LIMITED)
+    );
+    assert!(client.try_submit_report(&reporter, &agent_id, &50, &None).is_err());
+}
+
+#[test]
+fn test_can_submit_report_names_permission_and_state_blockers() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(selective_acl::SelectiveAcl, ());
+    let acl = selective_acl::SelectiveAclClient::new(&env, &acl_id);
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let agent_id = symbol_short!("agent_1");
+
+    client.initialize(&admin, &acl_id);
+    assert_eq!(
+        client.can_submit_report(&reporter, &agent_id),
+        eligibility(SUBMIT_NO_PERMISSION)
+    );
+
+    acl.grant(&reporter, &true);
+    client.begin_migration(&admin);
+    assert_eq!(client.can_submit_report(&reporter, &agent_id), eligibility(SUBMIT_INACTIVE));
+    client.finish_migration(&admin);
+    assert_eq!(client.can_submit_report(&reporter, &agent_id), eligibility(SUBMIT_OK));
+}
+
+#[test]
+fn test_can_submit_report_names_policy_blockers() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, reporter) = setup_staking(&env);
+    let agent_id = symbol_short!("agent_1");
+
+    assert_eq!(
+        client.can_submit_report(&reporter, &agent_id),
+        eligibility(SUBMIT_UNDER_STAKED)
+    );
+    client.stake_to_report(&reporter, &100);
+
+    client.set_require_registered_agents(&admin, &true);
+    assert_eq!(
+        client.can_submit_report(&reporter, &agent_id),
+        eligibility(SUBMIT_UNREGISTERED_AGENT)
+    );
+    client.register_agent(&admin, &agent_id);
+
+    client.set_report_cooldown(&admin, &600);
+    client.submit_report(&reporter, &agent_id, &50, &None);
+    assert_eq!(client.can_submit_report(&reporter, &agent_id), eligibility(SUBMIT_COOLDOWN));
+
+    env.ledger().with_mut(|li| li.timestamp += 600);
+    assert_eq!(client.can_submit_report(&reporter, &agent_id), eligibility(SUBMIT_OK));
+}
+
+/// What a client sees when a batch is over `max_batch_size` or an agent
+/// is at its report cap
+const CAPACITY_EXCEEDED: InvokeError =
+    InvokeError::Contract(ValidationError::CapacityExceeded as u32);
+
+/// Initialized contract whose batch limit is 2
+fn setup_batch_limit(env: &Env) -> (FraudDetectContractClient<'_>, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(env);
+    client.initialize(&admin, &acl_id);
+    client.set_max_batch_size(&admin, &2);
+    (client, admin)
+}
+
+#[test]
+fn test_max_batch_size_defaults_to_ceiling() {
+    use common_utils::batch::{DEFAULT_MAX_BATCH_SIZE, MAX_BATCH_SIZE};
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &acl_id);
+
+    assert_eq!(client.get_max_batch_size(), DEFAULT_MAX_BATCH_SIZE);
+    assert_eq!(DEFAULT_MAX_BATCH_SIZE, MAX_BATCH_SIZE);
+    assert_eq!(
+        client.try_set_max_batch_size(&admin, &0),
+        Err(Ok(ContractError::InvalidConfiguration))
+    );
+}
+
+#[test]
+fn test_batch_flag_fraud_respects_batch_limit() {
+    let env = Env::default();
+    let (client, admin) = setup_batch_limit(&env);
+
+    let accounts: [Address; 3] = core::array::from_fn(|_| Address::generate(&env));
+    let flags = |n: usize| {
+        let mut flags = soroban_sdk::Vec::new(&env);
+        for account in accounts.iter().take(n) {
+            flags.push_back(FraudFlag { account_id: account.clone(), reason_code: 7 });
+        }
+        flags
+    };
+    assert_eq!(
+        client.try_batch_flag_fraud(&admin, &flags(3)),
+        Err(Err(CAPACITY_EXCEEDED))
+    );
+    assert!(accounts.iter().all(|account| !client.is_flagged(account)));
+
+    client.batch_flag_fraud(&admin, &flags(2));
+    assert!(client.is_flagged(&accounts[1]));
+}
+
+#[test]
+fn test_batch_flag_fraud_summarises_outcomes() {
+    use soroban_sdk::BytesN;
+
+    // Events with the `fraud_flg` topic from one fresh batch of two flags
+    let flag_batch = |verbose: bool| {
+        let env = Env::default();
+        let (client, admin) = setup_batch_limit(&env);
+        client.set_verbose_batch_events(&admin, &verbose);
+        let accounts: [Address; 2] = core::array::from_fn(|_| Address::generate(&env));
+        let flags = vec![
+            &env,
+            FraudFlag { account_id: accounts[0].clone(), reason_code: 7 },
+            FraudFlag { account_id: accounts[1].clone(), reason_code: 9 },
+        ];
+        client.batch_flag_fraud(&admin, &flags);
+        let count = env
+            .events()
+            .all()
+            .iter()
+            .filter(|(_, topics, _)| {
+                topics.first().and_then(|t| Symbol::try_from_val(&env, &t).ok()) == Some(symbol_short!("fraud_flg"))
+            })
+            .count();
+        (env, accounts, count)
+    };
+
+    let (env, accounts, count) = flag_batch(false);
+    assert_eq!(count, 1);
+    let summary: soroban_sdk::Vec<soroban_sdk::Val> = vec![
+        &env,
+        symbol_short!("fraud_flg").into_val(&env),
+        symbol_short!("summary").into_val(&env),
+    ];
+    let (_, _, data) = env
+        .events()
+        .all()
+        .iter()
+        .find(|(_, topics, _)| *topics == summary)
+        .unwrap();
+    let (succeeded, failed, first, last, _): (u32, u32, Option<Address>, Option<Address>, BytesN<32>) =
+        TryFromVal::try_from_val(&env, &data).unwrap();
+    assert_eq!((succeeded, failed), (2, 0));
+    assert_eq!(first, Some(accounts[0].clone()));
+    assert_eq!(last, Some(accounts[1].clone()));
+
+    // Verbose mode adds one event per item
+    assert_eq!(flag_batch(true).2, 3);
+}
+
+#[test]
+fn test_set_user_trust_tiers_respects_batch_limit() {
+    let env = Env::default();
+    let (client, admin) = setup_batch_limit(&env);
+
+    let accounts: [Address; 3] = core::array::from_fn(|_| Address::generate(&env));
+    let mut tiers = soroban_sdk::Vec::new(&env);
+    for account in accounts.iter() {
+        tiers.push_back((account.clone(), TrustTier::Verified));
+    }
+    assert_eq!(
+        client.try_set_user_trust_tiers(&admin, &tiers),
+        Err(Err(CAPACITY_EXCEEDED))
+    );
+    assert_eq!(client.get_user_trust_tier(&accounts[0]), TrustTier::Standard);
+
+    assert_eq!(client.set_user_trust_tiers(&admin, &tiers.slice(0..2)), 2);
+    assert_eq!(client.get_user_trust_tier(&accounts[1]), TrustTier::Verified);
+}
+
+#[test]
+fn test_sync_reporters_respects_batch_limit() {
+    let env = Env::default();
+    let (client, admin) = setup_batch_limit(&env);
+
+    let accounts: [Address; 3] = core::array::from_fn(|_| Address::generate(&env));
+    let candidates = soroban_sdk::Vec::from_slice(&env, &accounts);
+    assert_eq!(
+        client.try_sync_reporters_from_acl(&admin, &candidates),
+        Err(Err(CAPACITY_EXCEEDED))
+    );
+    assert!(client.get_reporters().is_empty());
+
+    assert_eq!(client.sync_reporters_from_acl(&admin, &candidates.slice(0..2)), (2, 0));
+}
+
+#[test]
+fn test_get_latest_scores_respects_batch_limit() {
+    let env = Env::default();
+    let (client, _) = setup_batch_limit(&env);
+    let reporter = Address::generate(&env);
+    client.submit_report(&reporter, &symbol_short!("agent_1"), &40, &None);
+
+    let ids = vec![
+        &env,
+        symbol_short!("agent_1"),
+        symbol_short!("agent_2"),
+        symbol_short!("agent_3"),
+    ];
+    assert_eq!(client.try_get_latest_scores(&ids), Err(Err(CAPACITY_EXCEEDED)));
+    assert_eq!(client.get_latest_scores(&ids.slice(0..2)), vec![&env, 40, 0]);
+}
+
+#[test]
+fn test_batch_analyze_pairs_respects_batch_limit() {
+    let env = Env::default();
+    let (client, _) = setup_batch_limit(&env);
+
+    let pair = || TokenPair::new(&env, Address::generate(&env), Address::generate(&env), "XLM", "USDC");
+    let pairs = vec![&env, pair(), pair(), pair()];
+    assert_eq!(client.try_batch_analyze_pairs(&pairs), Err(Err(CAPACITY_EXCEEDED)));
+    assert!(client.try_batch_analyze_pairs(&pairs.slice(0..2)).is_ok());
+}
+
+#[test]
+fn test_admin_actions_are_logged_in_order() {
+    use common_utils::admin_log::param_hash;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &acl_id);
+    assert!(client.get_admin_log(&10).is_empty());
+
+    client.pause(&admin);
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    client.set_report_cooldown(&admin, &600);
+    // Rejected calls leave no entry
+    assert!(client.try_set_report_cooldown(&Address::generate(&env), &1).is_err());
+
+    let log = client.get_admin_log(&10);
+    assert_eq!(log.len(), 2);
+    let pause = log.get(0).unwrap();
+    assert_eq!((pause.seq, pause.action, pause.timestamp), (0, Symbol::new(&env, "pause"), 1_000));
+    assert_eq!(pause.admin, admin);
+    assert_eq!(pause.param_hash, param_hash(&env, &vec![&env]));
+
+    let config = log.get(1).unwrap();
+    assert_eq!(config.seq, 1);
+    assert_eq!(config.action, Symbol::new(&env, "set_report_cooldown"));
+    assert_eq!(config.timestamp, 2_000);
+    assert_eq!(config.param_hash, param_hash(&env, &vec![&env, 600u64.into_val(&env)]));
+
+    assert_eq!(client.get_admin_log(&1), vec![&env, config]);
+}
+
+#[test]
+fn test_reporter_grants_and_tier_changes_are_logged() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let user = Address::generate(&env);
+    client.initialize(&admin, &acl_id);
+
+    client.add_reporter(&admin, &reporter);
+    client.add_reporter_until(&admin, &reporter, &(env.ledger().timestamp() + 60));
+    client.remove_reporter(&admin, &reporter, &None);
+    client.set_user_trust_tier(&admin, &user, &TrustTier::Verified);
+    client.set_user_trust_tiers(&admin, &vec![&env, (user.clone(), TrustTier::Premium)]);
+    client.set_default_trust_tier(&admin, &TrustTier::Standard);
+    client.set_network_load(&admin, &500);
+    // The stored admin no longer stands in for a different caller
+    assert_eq!(
+        client.try_remove_reporter(&Address::generate(&env), &reporter, &None),
+        Err(Ok(CommonError::NotAuthorized))
+    );
+
+    let mut actions = Vec::new(&env);
+    for entry in client.get_admin_log(&10).iter() {
+        actions.push_back(entry.action);
+    }
+    let mut expected = Vec::new(&env);
+    for name in [
+        "add_reporter",
+        "add_reporter_until",
+        "remove_reporter",
+        "set_user_trust_tier",
+        "set_user_trust_tiers",
+        "set_default_trust_tier",
+        "set_network_load",
+    ] {
+        expected.push_back(Symbol::new(&env, name));
+    }
+    assert_eq!(actions, expected);
+}
+
+mod panicking_acl {
+    use soroban_sdk::{contract, contractimpl, Address, Env, Symbol};
+
+    /// Fails any call, to show it was never consulted
+    #[contract]
+    pub struct PanickingAcl;
+
+    #[contractimpl]
+    impl PanickingAcl {
+        pub fn has_permission(_env: Env, _user: Address, _resource: Symbol, _action: Symbol) -> bool {
+            panic!("ACL should not have been consulted")
+        }
+    }
+}
+
+#[test]
+fn test_any_configured_acl_can_grant() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let deny_id = env.register(deny_all_acl::DenyAllAcl, ());
+    let tenant_id = env.register(selective_acl::SelectiveAcl, ());
+    let tenant = selective_acl::SelectiveAclClient::new(&env, &tenant_id);
+    let admin = Address::generate(&env);
+    let member = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let agent_id = symbol_short!("agent_1");
+
+    client.initialize(&admin, &deny_id);
+    tenant.grant(&member, &true);
+    assert!(client.try_submit_report(&member, &agent_id, &50, &None).is_err());
+
+    client.add_acl_contract(&admin, &tenant_id);
+    client.add_acl_contract(&admin, &tenant_id);
+    assert_eq!(client.get_acl_contracts(), vec![&env, tenant_id.clone()]);
+
+    client.submit_report(&member, &agent_id, &50, &None);
+    assert_eq!(
+        client.try_submit_report(&outsider, &agent_id, &50, &None),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert!(client.can_submit_report(&member, &agent_id).allowed);
+
+    client.remove_acl_contract(&admin, &tenant_id);
+    assert!(client.get_acl_contracts().is_empty());
+    assert!(client.try_submit_report(&member, &agent_id, &50, &None).is_err());
+    assert_eq!(
+        client.try_remove_acl_contract(&admin, &tenant_id),
+        Err(Ok(ContractError::InvalidInput))
+    );
+}
+
+#[test]
+fn test_added_acl_cannot_grant_manage() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let deny_id = env.register(deny_all_acl::DenyAllAcl, ());
+    let tenant_id = env.register(selective_acl::SelectiveAcl, ());
+    let admin = Address::generate(&env);
+    let tenant_admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+
+    client.initialize(&admin, &deny_id);
+    // The tenant ACL grants `manage` to everyone
+    client.add_acl_contract(&admin, &tenant_id);
+
+    assert_eq!(
+        client.try_add_reporter(&tenant_admin, &reporter),
+        Err(Ok(AuthorizationError::NotAuthorized))
+    );
+    assert_eq!(
+        client.try_add_reporter_until(&tenant_admin, &reporter, &(env.ledger().timestamp() + 60)),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert!(!client.is_reporter(&reporter));
+}
+
+#[test]
+fn test_acl_lookup_stops_at_first_allow() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let allow_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let panicking_id = env.register(panicking_acl::PanickingAcl, ());
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+
+    client.initialize(&admin, &allow_id);
+    client.add_acl_contract(&admin, &panicking_id);
+    client.submit_report(&reporter, &symbol_short!("agent_1"), &50, &None);
+    assert_eq!(client.get_total_reports(), 1);
+}
+
+#[test]
+fn test_effective_rate_limit_reflects_override_tier_and_load() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let action = Symbol::new(&env, "submit_rpt");
+    client.initialize(&admin, &acl_id);
+
+    let default = client.get_effective_rate_limit(&reporter, &action).unwrap();
+    assert!(!default.overridden);
+    assert_eq!(default.effective_max, REPORT_RATE_MAX);
+    assert_eq!(client.get_effective_rate_limit(&reporter, &symbol_short!("other")), None);
+
+    let strict = RateLimitConfig {
+        max_requests: 2,
+        window_seconds: 60,
+        strategy: RateLimitStrategy::FixedWindow,
+        scope: RateLimitScope::PerUser,
+    };
+    client.set_rate_limit_override(&admin, &action, &Some(strict.clone()));
+    client.set_user_trust_tier(&admin, &reporter, &TrustTier::Premium);
+    client.set_network_load(&admin, &500);
+
+    let effective = client.get_effective_rate_limit(&reporter, &action).unwrap();
+    assert!(effective.overridden);
+    assert_eq!(effective.config, strict);
+    assert_eq!(effective.trust_multiplier, 3000);
+    assert_eq!(effective.load_multiplier, 500);
+    // 2 * 3000/1000 * 500/1000
+    assert_eq!(effective.effective_max, 3);
+
+    // The override is what submit_report enforces
+    for _ in 0..3 {
+        client.submit_report(&reporter, &symbol_short!("agent_1"), &50, &None);
+    }
+    assert!(client.try_submit_report(&reporter, &symbol_short!("agent_1"), &50, &None).is_err());
+
+    client.set_rate_limit_override(&admin, &action, &None);
+    assert!(!client.get_effective_rate_limit(&reporter, &action).unwrap().overridden);
+    assert_eq!(
+        client.try_set_rate_limit_override(&admin, &symbol_short!("other"), &Some(strict)),
+        Err(Ok(ContractError::InvalidInput))
+    );
+}
+
+#[test]
+fn test_reporter_approval_expires_and_is_pruned() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let temporary = Address::generate(&env);
+    let permanent = Address::generate(&env);
+    let agent_id = symbol_short!("agent_1");
+    client.initialize(&admin, &acl_id);
+
+    assert_eq!(
+        client.try_add_reporter_until(&admin, &temporary, &1_000),
+        Err(Ok(ContractError::InvalidInput))
+    );
+    client.add_reporter_until(&admin, &temporary, &2_000);
+    client.add_reporter(&admin, &permanent);
+    assert_eq!(client.get_reporter_expiry(&temporary), Some(2_000));
+    assert_eq!(client.get_reporter_expiry(&permanent), None);
+
+    client.submit_report(&temporary, &agent_id, &50, &None);
+    // Nothing has lapsed yet
+    assert_eq!(client.prune_expired_reporters(), 0);
+
+    env.ledger().set_timestamp(2_000);
+    assert_eq!(
+        client.try_submit_report(&temporary, &agent_id, &50, &None),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(
+        client.can_submit_report(&temporary, &agent_id),
+        eligibility(SUBMIT_APPROVAL_EXPIRED)
+    );
+    client.submit_report(&permanent, &agent_id, &50, &None);
+
+    assert_eq!(client.prune_expired_reporters(), 1);
+    assert_eq!(client.get_reporters(), vec![&env, permanent]);
+    assert_eq!(client.get_reporter_expiry(&temporary), None);
+    assert_eq!(client.get_removal_reason(&temporary).unwrap().2, EXPIRY_REMOVAL_REASON);
+
+    // Pruning dropped the expiry, but the removal still locks it out
+    assert_eq!(
+        client.try_submit_report(&temporary, &agent_id, &50, &None),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(
+        client.can_submit_report(&temporary, &agent_id),
+        eligibility(SUBMIT_REMOVED)
+    );
+
+    // Re-approving without an expiry restores the reporter indefinitely
+    client.add_reporter(&admin, &temporary);
+    client.submit_report(&temporary, &agent_id, &50, &None);
+}
+
+#[test]
+fn test_get_latest_scores_matches_individual_lookups() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    client.initialize(&admin, &acl_id);
+
+    let (first, second, unknown) = (symbol_short!("agent_1"), symbol_short!("agent_2"), symbol_short!("ghost"));
+    client.submit_report(&reporter, &first, &40, &None);
+    client.submit_report(&reporter, &second, &70, &None);
+
+    // Readable while paused, like get_latest_score
+    client.pause(&admin);
+    let ids = vec![&env, first.clone(), unknown.clone(), second.clone(), unknown.clone()];
+    assert_eq!(
+        client.get_latest_scores(&ids),
+        vec![
+            &env,
+            client.get_latest_score(&first),
+            0,
+            client.get_latest_score(&second),
+            0
+        ]
+    );
+    assert_eq!(client.get_latest_scores(&ids).get(2), Some(70));
+
+    client.set_max_batch_size(&admin, &3);
+    assert_eq!(client.try_get_latest_scores(&ids), Err(Err(CAPACITY_EXCEEDED)));
+}
+
+#[test]
+fn test_find_latest_score_distinguishes_zero_from_missing() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    client.initialize(&admin, &acl_id);
+
+    let (clean, unknown) = (symbol_short!("clean"), symbol_short!("ghost"));
+    client.submit_report(&reporter, &clean, &0, &None);
+
+    assert_eq!(client.get_latest_score(&clean), 0);
+    assert_eq!(client.get_latest_score(&unknown), 0);
+    assert_eq!(client.find_latest_score(&clean), Some(0));
+    assert_eq!(client.find_latest_score(&unknown), None);
+}
+
+#[test]
+fn test_strict_export_requires_frozen_source() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(5_000);
+
+    let source_id = env.register(FraudDetectContract, ());
+    let source = FraudDetectContractClient::new(&env, &source_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    source.initialize(&admin, &acl_id);
+
+    // A live export carries no marker, and no destination takes it
+    let live = source.export_state();
+    let marker = live.get(STATE_EXPORT_LEN - 1).unwrap();
+    assert_eq!(Option::<u64>::try_from_val(&env, &marker).unwrap(), None);
+    let target_id = env.register(FraudDetectContract, ());
+    let target = FraudDetectContractClient::new(&env, &target_id);
+    assert_eq!(target.try_import_state(&live), Err(Ok(CommonError::InvalidState)));
+    assert!(target.get_contract_state().is_uninitialized());
+
+    source.set_strict_export(&admin, &true);
+    assert!(source.is_strict_export());
+    assert_eq!(source.try_export_state(), Err(Ok(CommonError::InvalidState)));
+
+    source.pause(&admin);
+    let state = source.export_state();
+    let marker = state.get(STATE_EXPORT_LEN - 1).unwrap();
+    assert_eq!(Option::<u64>::try_from_val(&env, &marker).unwrap(), Some(5_000));
+
+    target.import_state(&state);
+    assert_eq!(target.get_source_exported_at(), Some(5_000));
+}
+
+#[test]
+fn test_import_accepts_version_1_payload() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let source_id = env.register(FraudDetectContract, ());
+    let source = FraudDetectContractClient::new(&env, &source_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    source.initialize(&admin, &acl_id);
+    source.set_network_load(&admin, &700);
+
+    // Version 1 is the current layout without the trailing marker
+    let v2 = source.export_state();
+    let mut v1 = v2.slice(0..STATE_EXPORT_LEN_V1);
+    v1.set(0, 1u32.into_val(&env));
+
+    let target_id = env.register(FraudDetectContract, ());
+    let target = FraudDetectContractClient::new(&env, &target_id);
+    let mut padded = v1.clone();
+    padded.push_back(None::<u64>.into_val(&env));
+    assert_eq!(target.try_import_state(&padded), Err(Ok(CommonError::InvalidFormat)));
+
+    target.import_state(&v1);
+    assert!(target.get_contract_state().is_active());
+    assert_eq!(target.get_config().network_load, 700);
+    assert_eq!(target.get_source_exported_at(), None);
+}
+
+#[test]
+fn test_private_agent_reports_need_view_permission() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(selective_acl::SelectiveAcl, ());
+    let acl = selective_acl::SelectiveAclClient::new(&env, &acl_id);
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let viewer = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let agent_id = symbol_short!("agent_1");
+
+    client.initialize(&admin, &acl_id);
+    acl.grant(&reporter, &true);
+    acl.grant(&viewer, &true);
+    client.submit_report(&reporter, &agent_id, &60, &None);
+    assert!(client.is_agent_public(&agent_id));
+    assert_eq!(client.get_reports_as(&stranger, &agent_id).len(), 1);
+
+    client.set_agent_visibility(&admin, &agent_id, &false);
+    assert!(!client.is_agent_public(&agent_id));
+    assert_eq!(client.get_reports(&agent_id).len(), 0);
+    assert_eq!(client.get_latest_score(&agent_id), 0);
+    let (reports, metadata) = client.get_reports_with_metadata(&agent_id);
+    assert!(reports.is_empty());
+    assert_eq!((metadata.count, metadata.first_ts, metadata.stored_bytes), (0, 0, 0));
+    assert_eq!(client.try_get_reports_as(&stranger, &agent_id), Err(Ok(ContractError::Unauthorized)));
+    assert_eq!(client.try_get_latest_score_as(&stranger, &agent_id), Err(Ok(ContractError::Unauthorized)));
+
+    assert_eq!(client.get_reports_as(&viewer, &agent_id).len(), 1);
+    assert_eq!(client.get_latest_score_as(&viewer, &agent_id), 60);
+    assert_eq!(client.get_latest_score_as(&admin, &agent_id), 60);
+
+    client.set_agent_visibility(&admin, &agent_id, &true);
+    assert_eq!(client.get_latest_score(&agent_id), 60);
+}
+
+#[test]
+fn test_model_updates_respect_cooldown_and_daily_cap() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(SECONDS_PER_DAY * 10);
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &acl_id);
+    let model = Bytes::from_slice(&env, b"model-v2");
+
+    client.set_model_update_cooldown(&admin, &600);
+    assert_eq!(client.get_model_update_retry_after(), 0);
+    client.update_model(&admin, &model);
+    assert_eq!(
+        client.try_update_model(&admin, &model),
+        Err(Ok(ContractError::ModelUpdateCooldown))
+    );
+
+    env.ledger().set_timestamp(SECONDS_PER_DAY * 10 + 400);
+    let status = client.get_model_update_status();
+    assert_eq!(status.cooldown_remaining, 200);
+    assert_eq!(status.last_update, Some(SECONDS_PER_DAY * 10));
+    assert_eq!(client.get_model_update_retry_after(), 200);
+    assert_eq!(
+        client.try_update_model(&admin, &model),
+        Err(Ok(ContractError::ModelUpdateCooldown))
+    );
+
+    env.ledger().set_timestamp(SECONDS_PER_DAY * 10 + 600);
+    assert_eq!(client.get_model_update_retry_after(), 0);
+    client.update_model(&admin, &model);
+    assert_eq!(client.get_model_update_status().total_updates, 2);
+
+    // The daily cap holds even without a cooldown, until the day rolls over
+    client.set_model_update_cooldown(&admin, &0);
+    client.set_model_daily_cap(&admin, &2);
+    assert_eq!(
+        client.try_update_model(&admin, &model),
+        Err(Ok(ContractError::ModelDailyCapReached))
+    );
+    assert_eq!(client.get_model_update_retry_after(), SECONDS_PER_DAY - 600);
+    env.ledger().set_timestamp(SECONDS_PER_DAY * 11);
+    assert_eq!(client.get_model_update_status().updates_today, 0);
+    client.update_model(&admin, &model);
+    assert_eq!(client.get_model_update_status().total_updates, 3);
+}
+
+#[test]
+fn test_commit_reveal_report() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    client.initialize(&admin, &acl_id);
+
+    let agent_id = symbol_short!("agent_1");
+    let salt = BytesN::from_array(&env, &[9u8; 32]);
+    let commitment = client.report_commitment(&reporter, &agent_id, &65, &salt);
+    let committed = client.commit_report(&reporter, &agent_id, &commitment);
+    assert_eq!(committed.reveal_from, 1_000 + DEFAULT_COMMIT_WINDOW);
+    assert_eq!(committed.reveal_by, 1_000 + DEFAULT_COMMIT_WINDOW + DEFAULT_REVEAL_WINDOW);
+    assert_eq!(client.get_latest_score(&agent_id), 0);
+    assert_eq!(
+        client.try_commit_report(&reporter, &agent_id, &commitment),
+        Err(Ok(ContractError::InvalidState))
+    );
+
+    // A second reporter joins the same round
+    let other = Address::generate(&env);
+    env.ledger().set_timestamp(1_100);
+    let joined = client.commit_report(&other, &agent_id, &client.report_commitment(&other, &agent_id, &30, &salt));
+    assert_eq!(joined.reveal_from, committed.reveal_from);
+    assert_eq!(joined.reveal_by, committed.reveal_by);
+
+    // Nothing can be revealed while the commit phase is open
+    assert_eq!(
+        client.try_reveal_report(&reporter, &agent_id, &65, &salt),
+        Err(Ok(ContractError::InvalidState))
+    );
+    env.ledger().set_timestamp(committed.reveal_from);
+    let late = Address::generate(&env);
+    assert_eq!(
+        client.try_commit_report(&late, &agent_id, &client.report_commitment(&late, &agent_id, &65, &salt)),
+        Err(Ok(ContractError::InvalidState))
+    );
+
+    // The commitment is bound to its reporter and agent
+    assert_eq!(
+        client.try_reveal_report(&other, &agent_id, &65, &salt),
+        Err(Ok(ContractError::InvalidInput))
+    );
+    assert_ne!(
+        client.report_commitment(&reporter, &symbol_short!("agent_2"), &65, &salt),
+        commitment
+    );
+
+    // Wrong score or salt leaves the commitment in place
+    assert_eq!(
+        client.try_reveal_report(&reporter, &agent_id, &70, &salt),
+        Err(Ok(ContractError::InvalidInput))
+    );
+    assert_eq!(
+        client.try_reveal_report(&reporter, &agent_id, &65, &BytesN::from_array(&env, &[8u8; 32])),
+        Err(Ok(ContractError::InvalidInput))
+    );
+
+    client.reveal_report(&reporter, &agent_id, &65, &salt);
+    assert_eq!(client.get_latest_score(&agent_id), 65);
+    assert_eq!(client.get_report_commitment(&reporter, &agent_id), None);
+    assert_eq!(
+        client.try_reveal_report(&reporter, &agent_id, &65, &salt),
+        Err(Ok(ContractError::NotFound))
+    );
+}
+
+#[test]
+fn test_unrevealed_commitment_expires() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    client.initialize(&admin, &acl_id);
+    client.set_reveal_window(&admin, &600);
+
+    client.set_commit_window(&admin, &100);
+
+    let agent_id = symbol_short!("agent_1");
+    let salt = BytesN::from_array(&env, &[3u8; 32]);
+    client.commit_report(&reporter, &agent_id, &client.report_commitment(&reporter, &agent_id, &40, &salt));
+
+    // Still revealable: nothing to clear
+    env.ledger().set_timestamp(1_700);
+    assert!(!client.clear_expired_commitment(&reporter, &agent_id));
+
+    env.ledger().set_timestamp(1_701);
+    assert_eq!(
+        client.try_reveal_report(&reporter, &agent_id, &40, &salt),
+        Err(Ok(ContractError::InvalidState))
+    );
+    assert_eq!(client.get_latest_score(&agent_id), 0);
+
+    // An expired commitment can be cleared by anyone
+    assert!(client.clear_expired_commitment(&reporter, &agent_id));
+    assert_eq!(client.get_report_commitment(&reporter, &agent_id), None);
+    assert_eq!(client.get_commit_round(&agent_id), None);
+    assert!(!client.clear_expired_commitment(&reporter, &agent_id));
+
+    // A new commitment opens a new round
+    let renewed = client.commit_report(&reporter, &agent_id, &client.report_commitment(&reporter, &agent_id, &45, &salt));
+    assert_eq!(renewed.reveal_from, 1_801);
+    assert_eq!(renewed.reveal_by, 2_401);
+    env.ledger().set_timestamp(1_801);
+    client.reveal_report(&reporter, &agent_id, &45, &salt);
+    assert_eq!(client.get_latest_score(&agent_id), 45);
+    assert_eq!(
+        client.try_set_reveal_window(&admin, &0),
+        Err(Ok(ContractError::InvalidConfiguration))
+    );
+    assert_eq!(
+        client.try_set_commit_window(&admin, &0),
+        Err(Ok(ContractError::InvalidConfiguration))
+    );
+}
+
+#[test]
+fn test_auto_compaction_keeps_report_blob_under_cap() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    client.initialize(&admin, &acl_id);
+
+    let agent_id = symbol_short!("agent_1");
+    for score in [10u32, 20, 30] {
+        client.submit_report(&reporter, &agent_id, &score, &None);
+    }
+    let blob_size = |reports: &Vec<FraudReport>| FraudReportCompressor::compress_reports(reports).unwrap().len();
+    let cap = blob_size(&client.get_reports(&agent_id));
+    client.set_auto_compact(&admin, &cap);
+
+    for score in [40u32, 50, 60] {
+        client.submit_report(&reporter, &agent_id, &score, &None);
+        let reports = client.get_reports(&agent_id);
+        assert!(blob_size(&reports) <= cap);
+        assert_eq!(reports.last().unwrap().score, score);
+    }
+    let reports = client.get_reports(&agent_id);
+    assert!(reports.len() < 6);
+    assert_eq!(client.get_total_reports(), reports.len() as u64);
+
+    let compacted = env
+        .events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| {
+            topics.get(0).map(|t| Symbol::try_from_val(&env, &t) == Ok(Symbol::new(&env, "auto_compacted")))
+                == Some(true)
+        })
+        .count();
+    assert!(compacted > 0);
+}
+
+#[test]
+fn test_health_reports_dependencies_independently() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _) = setup_staking(&env);
+    let broken_acl = env.register(panicking_acl::PanickingAcl, ());
+    client.add_acl_contract(&admin, &broken_acl);
+
+    let report = client.health();
+    assert_eq!(report.state_label, Symbol::new(&env, "active"));
+    assert!(report.acl_ok);
+    assert_eq!(
+        report.deps,
+        vec![&env, (symbol_short!("acl_extra"), false), (symbol_short!("stake_tok"), true)]
+    );
+
+    client.pause(&admin);
+    assert_eq!(client.health().state_label, Symbol::new(&env, "paused"));
+}
+
+#[test]
+fn test_reporter_approvals_toggle_bitmap_bits() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &acl_id);
+
+    let reporters = [Address::generate(&env), Address::generate(&env), Address::generate(&env)];
+    for reporter in reporters.iter() {
+        client.add_reporter(&admin, reporter);
+    }
+    let bits = || -> u128 {
+        env.as_contract(&contract_id, || {
+            env.storage().persistent().get(&DataKey::ReporterBits(0)).unwrap_or(0)
+        })
+    };
+    assert_eq!(bits(), 0b111);
+
+    client.remove_reporter(&admin, &reporters[1], &None);
+    assert_eq!(bits(), 0b101);
+    assert!(!client.is_reporter(&reporters[1]));
+    assert_eq!(client.get_reporters(), vec![&env, reporters[0].clone(), reporters[2].clone()]);
+
+    // Re-approval reuses the reporter's handle, so it lists in its old
+    // position instead of last
+    client.add_reporter(&admin, &reporters[1]);
+    assert_eq!(bits(), 0b111);
+    assert!(client.is_reporter(&reporters[1]));
+    assert_eq!(
+        client.get_reporters(),
+        vec![&env, reporters[0].clone(), reporters[1].clone(), reporters[2].clone()]
+    );
+    assert!(!client.is_reporter(&Address::generate(&env)));
+}
+
+#[test]
+fn test_legacy_reporter_list_is_folded_into_bitmap() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &acl_id);
+
+    let (old, new) = (Address::generate(&env), Address::generate(&env));
+    env.as_contract(&contract_id, || {
+        env.storage()
+            .instance()
+            .set(&DataKey::Reporters, &vec![&env, old.clone()]);
+    });
+    assert!(client.is_reporter(&old));
+    assert_eq!(client.get_reporters(), vec![&env, old.clone()]);
+
+    client.add_reporter(&admin, &new);
+    assert_eq!(client.get_reporters(), vec![&env, old.clone(), new.clone()]);
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().instance().has(&DataKey::Reporters));
+    });
+}
+
+#[test]
+fn test_audited_report_read_requires_auth_and_emits_event() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let auditor = Address::generate(&env);
+    let agent_id = symbol_short!("agent_1");
+    client.initialize(&admin, &acl_id);
+    client.submit_report(&reporter, &agent_id, &40, &None);
+
+    let accessed: soroban_sdk::Vec<soroban_sdk::Val> =
+        vec![&env, Symbol::new(&env, "data_accessed").into_val(&env), auditor.into_val(&env)];
+    let audited = || env.events().all().iter().any(|(_, topics, _)| topics == accessed);
+
+    // Off by default: auth is still required, nothing is emitted
+    assert_eq!(client.get_reports_audited(&auditor, &agent_id).len(), 1);
+    assert!(env.auths().iter().any(|(address, _)| *address == auditor));
+    assert!(!audited());
+
+    client.set_read_audit(&admin, &true);
+    assert_eq!(client.get_reports(&agent_id).len(), 1);
+    assert!(!audited());
+    assert!(!env.auths().iter().any(|(address, _)| *address == auditor));
+
+    assert_eq!(client.get_reports_audited(&auditor, &agent_id).len(), 1);
+    assert!(audited());
+}
+
+#[test]
+fn test_premium_over_limit_report_queues_and_drains() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(10_000);
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let premium = Address::generate(&env);
+    let basic = Address::generate(&env);
+    client.initialize(&admin, &acl_id);
+    client.set_user_trust_tier(&admin, &premium, &TrustTier::Premium);
+    client.set_grace_queue_len(&admin, &2);
+    assert_eq!(
+        client.try_set_grace_queue_len(&admin, &(MAX_GRACE_QUEUE_LEN + 1)),
+        Err(Ok(ContractError::InvalidConfiguration))
+    );
+
+    let premium_max = client.get_effective_rate_limit(&premium, &Symbol::new(&env, "submit_rpt")).unwrap().effective_max;
+    for _ in 0..premium_max {
+        client.submit_report(&premium, &symbol_short!("premium"), &40, &None);
+    }
+    for _ in 0..REPORT_RATE_MAX {
+        client.submit_report(&basic, &symbol_short!("basic"), &40, &None);
+    }
+    let total = client.get_total_reports();
+
+    // Over the limit: queued for the premium reporter, refused for the other
+    client.submit_report(&premium, &symbol_short!("late"), &90, &None);
+    assert_eq!(client.get_report_queue(&premium).len(), 1);
+    assert!(client.try_submit_report(&basic, &symbol_short!("basic"), &40, &None).is_err());
+    assert_eq!(client.get_total_reports(), total);
+
+    // The queue is capped at two
+    client.submit_report(&premium, &symbol_short!("late"), &95, &None);
+    assert!(client.try_submit_report(&premium, &symbol_short!("late"), &99, &None).is_err());
+    assert_eq!(client.get_report_queue(&premium).len(), 2);
+
+    // Still inside the window: nothing drains
+    assert_eq!(client.drain_queue(&premium), 0);
+    assert_eq!(client.get_report_queue(&premium).len(), 2);
+
+    env.ledger().set_timestamp(10_000 + 2 * REPORT_RATE_WINDOW);
+    assert_eq!(client.drain_queue(&premium), 2);
+    assert!(client.get_report_queue(&premium).is_empty());
+    assert_eq!(client.get_latest_score(&symbol_short!("late")), 95);
+    assert_eq!(client.get_total_reports(), total + 2);
+}
+
+#[test]
+fn test_grace_queue_only_defers_the_rate_limit() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(10_000);
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let premium = Address::generate(&env);
+    client.initialize(&admin, &acl_id);
+    client.set_user_trust_tier(&admin, &premium, &TrustTier::Premium);
+    client.set_grace_queue_len(&admin, &5);
+
+    let hidden = symbol_short!("hidden");
+    let salt = BytesN::from_array(&env, &[4u8; 32]);
+    let commitment = client.report_commitment(&premium, &hidden, &55, &salt);
+    let committed = client.commit_report(&premium, &hidden, &commitment);
+
+    env.ledger().set_timestamp(committed.reveal_from);
+    let premium_max = client.get_effective_rate_limit(&premium, &Symbol::new(&env, "submit_rpt")).unwrap().effective_max;
+    for _ in 0..premium_max {
+        client.submit_report(&premium, &symbol_short!("premium"), &40, &None);
+    }
+
+    // Checks other than the rate limit still fail at submission
+    assert_eq!(
+        client.try_submit_report(&premium, &symbol_short!("late"), &(DEFAULT_SCORE_SCALE + 1), &None),
+        Err(Ok(ContractError::InvalidInput))
+    );
+    assert!(client.get_report_queue(&premium).is_empty());
+
+    // A reveal is refused over the limit instead of queued
+    assert!(client.try_reveal_report(&premium, &hidden, &55, &salt).is_err());
+    assert!(client.get_report_queue(&premium).is_empty());
+    assert!(client.get_report_commitment(&premium, &hidden).is_some());
+
+    client.submit_report(&premium, &symbol_short!("late"), &60, &None);
+    assert_eq!(client.get_report_queue(&premium).len(), 1);
+}
+
+#[test]
+fn test_stake_deposit_overflow_is_rejected_before_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, reporter) = setup_staking(&env);
+    client.stake_to_report(&reporter, &100);
+    env.as_contract(&client.address, || {
+        let mut stake = stake::get_stake(&env, &reporter);
+        stake.amount = i128::MAX;
+        env.storage()
+            .persistent()
+            .set(&DataKey::ReporterStake(reporter.clone()), &stake);
+    });
+
+    assert_eq!(client.try_stake_to_report(&reporter, &1), Err(Ok(ContractError::InvalidInput)));
+    assert_eq!(client.get_reporter_stake(&reporter).amount, i128::MAX);
+    let token = client.get_config().stake_token.unwrap();
+    assert_eq!(soroban_sdk::token::TokenClient::new(&env, &token).balance(&reporter), 900);
+}
+
+#[test]
+fn test_total_reports_saturates_at_bound() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    client.initialize(&admin, &acl_id);
+    env.as_contract(&contract_id, || {
+        env.storage().instance().set(&DataKey::TotalReports, &u64::MAX);
+    });
+
+    client.submit_report(&reporter, &symbol_short!("agent_1"), &40, &None);
+    assert_eq!(client.get_total_reports(), u64::MAX);
+    assert_eq!(client.get_latest_score(&symbol_short!("agent_1")), 40);
+}
+
+#[test]
+fn test_model_update_counters_saturate_at_bound() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &acl_id);
+    env.as_contract(&contract_id, || {
+        let mut status = FraudDetectContract::get_model_update_status(env.clone());
+        status.total_updates = u64::MAX;
+        status.updates_today = u32::MAX;
+        env.storage().instance().set(&DataKey::ModelUpdates, &status);
+    });
+
+    client.update_model(&admin, &soroban_sdk::Bytes::from_array(&env, &[1]));
+    let status = client.get_model_update_status();
+    assert_eq!(status.total_updates, u64::MAX);
+    assert_eq!(status.updates_today, u32::MAX);
+    assert!(status.last_update.is_some());
+}
+
+#[test]
+fn test_flagged_count_saturates_at_bound() {
+    use common_utils::batch::FraudFlag;
+
+    let env = Env::default();
+    let (client, admin) = setup_batch_limit(&env);
+    env.as_contract(&client.address, || {
+        env.storage().instance().set(&DataKey::FlaggedCount, &u32::MAX);
+    });
+
+    let account = Address::generate(&env);
+    client.batch_flag_fraud(&admin, &vec![&env, FraudFlag { account_id: account.clone(), reason_code: 7 }]);
+    assert_eq!(client.get_global_stats().flagged_count, u32::MAX);
+    assert!(client.is_flagged(&account));
+}
+
+#[test]
+fn test_stake_cooldown_deadline_saturates() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, reporter) = setup_staking(&env);
+    let token = client.get_config().stake_token.unwrap();
+    client.set_reporter_stake_policy(&admin, &token, &100, &u64::MAX);
+    env.ledger().set_timestamp(1_000);
+
+    client.stake_to_report(&reporter, &200);
+    client.submit_report(&reporter, &symbol_short!("agent_1"), &50, &None);
+
+    // 1_000 + u64::MAX would wrap to a deadline in the past
+    env.ledger().set_timestamp(u64::MAX - 1);
+    assert_eq!(
+        client.try_withdraw_stake(&reporter, &200),
+        Err(Ok(ContractError::InvalidState))
+    );
+    assert_eq!(client.get_reporter_stake(&reporter).amount, 200);
+}
+
+#[test]
+fn test_batch_flag_fraud_requires_admin_and_is_logged() {
+    use common_utils::batch::{BatchError, FraudFlag};
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &acl_id);
+
+    let account = Address::generate(&env);
+    let flags = vec![&env, FraudFlag { account_id: account.clone(), reason_code: 7 }];
+    assert_eq!(
+        client.try_batch_flag_fraud(&Address::generate(&env), &flags),
+        Err(Ok(BatchError::InvalidOperation))
+    );
+    assert!(!client.is_flagged(&account));
+
+    client.batch_flag_fraud(&admin, &flags);
+    assert!(client.is_flagged(&account));
+    let log = client.get_admin_log(&10);
+    assert_eq!(log.len(), 1);
+    assert_eq!(log.get_unchecked(0).action, Symbol::new(&env, "batch_flag_fraud"));
+}
+
+#[test]
+fn test_report_over_threshold_opens_disputable_agent_flag() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let disputed = symbol_short!("agent_1");
+    let undisputed = symbol_short!("agent_2");
+    client.initialize(&admin, &acl_id);
+    client.add_reporter(&admin, &reporter);
+    client.set_dispute_window(&admin, &600);
+
+    // Below 70% of the scale nothing is flagged
+    client.submit_report(&reporter, &disputed, &69, &None);
+    assert_eq!(client.agent_flag_pending_until(&disputed), None);
+
+    client.submit_report(&reporter, &disputed, &90, &None);
+    client.submit_report(&reporter, &undisputed, &70, &None);
+    assert_eq!(client.agent_flag_pending_until(&disputed), Some(1_600));
+    assert!(!client.is_agent_flagged(&disputed));
+
+    let outsider = Address::generate(&env);
+    assert_eq!(
+        client.try_uphold_agent_flag_dispute(&outsider, &disputed),
+        Err(Ok(ContractError::Unauthorized))
+    );
+
+    env.ledger().set_timestamp(1_599);
+    client.uphold_agent_flag_dispute(&admin, &disputed);
+
+    env.ledger().set_timestamp(1_600);
+    assert!(!client.is_agent_flagged(&disputed));
+    assert!(client.is_agent_flagged(&undisputed));
+    assert_eq!(
+        client.try_uphold_agent_flag_dispute(&admin, &undisputed),
+        Err(Ok(ContractError::InvalidState))
+    );
+}
+
+#[test]
+fn test_flagging_and_normalization_follow_score_scale() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let low = symbol_short!("agent_1");
+    let high = symbol_short!("agent_2");
+    client.initialize(&admin, &acl_id);
+    client.add_reporter(&admin, &reporter);
+    client.set_score_scale(&admin, &1000);
+
+    // 70% of a 1000 scale is 700, not 70
+    client.submit_report(&reporter, &low, &650, &None);
+    client.submit_report(&reporter, &high, &700, &None);
+    assert!(!client.is_agent_flagged(&low));
+    assert!(client.is_agent_flagged(&high));
+
+    // Lowering the scale below a stored score caps it at the maximum
+    client.set_score_scale(&admin, &500);
+    assert_eq!(client.get_score_normalized(&high), NORMALIZED_SCORE_SCALE);
+}
+
+#[test]
+fn test_reinitialize_drops_queued_reports_and_reporter_records() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    env.ledger().set_timestamp(10_000);
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let premium = Address::generate(&env);
+    let removed = Address::generate(&env);
+    let late = symbol_short!("late");
+    let hidden = symbol_short!("hidden");
+    client.initialize(&admin, &acl_id);
+    client.set_user_trust_tier(&admin, &premium, &TrustTier::Premium);
+    client.set_grace_queue_len(&admin, &2);
+
+    let premium_max = client.get_effective_rate_limit(&premium, &Symbol::new(&env, "submit_rpt")).unwrap().effective_max;
+    for _ in 0..premium_max {
+        client.submit_report(&premium, &symbol_short!("premium"), &40, &None);
+    }
+    client.submit_report(&premium, &late, &90, &None);
+    assert_eq!(client.get_report_queue(&premium).len(), 1);
+
+    let salt = BytesN::from_array(&env, &[4u8; 32]);
+    let commitment = client.report_commitment(&premium, &hidden, &55, &salt);
+    client.commit_report(&premium, &hidden, &commitment);
+    client.set_agent_visibility(&admin, &hidden, &false);
+    client.add_reporter_until(&admin, &premium, &20_000);
+    client.remove_reporter(&admin, &removed, &None);
+
+    client.terminate(&admin);
+    client.reinitialize(&admin, &admin, &acl_id);
+
+    // Past the rate window a surviving queue would drain
+    env.ledger().set_timestamp(10_000 + 2 * REPORT_RATE_WINDOW);
+    assert_eq!(client.drain_queue(&premium), 0);
+    assert!(client.get_report_queue(&premium).is_empty());
+    assert!(client.get_reports(&late).is_empty());
+    assert_eq!(client.get_total_reports(), 0);
+
+    assert_eq!(client.get_report_commitment(&premium, &hidden), None);
+    assert_eq!(client.get_commit_round(&hidden), None);
+    assert!(client.is_agent_public(&hidden));
+    assert_eq!(client.get_reporter_expiry(&premium), None);
+    assert_eq!(client.get_removal_reason(&removed), None);
+}
+
+#[test]
+fn test_fixed_point_precision_rounds_reliability_weights() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let retail = Address::generate(&env);
+    let institution = Address::generate(&env);
+    let agent_id = symbol_short!("agent_1");
+
+    client.initialize(&admin, &acl_id);
+    client.submit_report(&retail, &agent_id, &20, &None);
+    client.submit_report(&institution, &agent_id, &80, &None);
+    client.set_reporter_weight(&admin, &institution, &3);
+    client.uphold_report_dispute(&admin, &institution, &agent_id);
+    client.set_feature(&admin, &FEATURE_RELIABILITY_WEIGHTS, &true);
+    assert_eq!(client.get_aggregate_score(&agent_id), 56);
+
+    // Without fractional bits the institution's 1/2 reliability is 0
+    client.set_fixed_point_precision(&admin, &0);
+    assert_eq!(client.get_aggregate_score(&agent_id), 20);
+
+    // One bit holds 1/2 exactly: 3 * 1/2 = 1.5
+    client.set_fixed_point_precision(&admin, &1);
+    assert_eq!(client.get_aggregate_score(&agent_id), 56);
+
+    assert_eq!(
+        client.try_set_fixed_point_precision(&admin, &65),
+        Err(Ok(ContractError::InvalidConfiguration))
+    );
+}
+
+#[test]
+fn test_breaker_window_end_saturates() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let contract_id = env.register(FraudDetectContract, ());
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+    let acl_id = env.register(allow_all_acl::AllowAllAcl, ());
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    client.initialize(&admin, &acl_id);
+    client.set_circuit_breaker(&admin, &1, &u64::MAX);
+
+    // The window never ends, so the second report trips the breaker
+    client.submit_report(&reporter, &symbol_short!("agent_1"), &10, &None);
+    client.submit_report(&reporter, &symbol_short!("agent_2"), &10, &None);
+    assert!(client.get_contract_state().is_paused());
+}
@@ -1,18 +1,29 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short,
-    Address, Bytes, BytesN, Env, Map, Symbol,
+    contract, contractimpl, contracttype, panic_with_error, symbol_short, vec,
+    Address, Bytes, BytesN, Env, IntoVal, Map, Symbol, Val, Vec,
 };
-use common_utils::error::{AuthorizationError, CryptoError, ValidationError, ContractError, StateError};
-use common_utils::authorization::{IAuthorizable, SignatureBasedAuth, Permission, PermissionCache, CachedAuth};
+use common_utils::admin_log::{self, AdminAction};
+use common_utils::error::{AuthorizationError, CommonError, CryptoError, ValidationError, ContractError, StateError};
+use common_utils::crypto::{encode_message, MessageField};
+use common_utils::authorization::{IAuthorizable, SignatureBasedAuth, SignatureScheme, Permission, PermissionCache, CachedAuth};
 use common_utils::{permission, auth, cached_auth, check_authorization, verify_signature};
-use common_utils::state_machine::{State, StateMachine, RiskEvalState};
+use common_utils::state_machine::{self, State, StateMachine, RiskEvalState};
 use common_utils::{state_guard, transition_to};
 
 mod oracle_cache;
 pub use oracle_cache::OracleCache;
 
+/// Release of this contract, bumped on every meaningful change
+pub const CONTRACT_VERSION: u32 = 1;
+
+/// Release tag reported by `build_id`
+pub const BUILD_ID: Symbol = symbol_short!("v1_0_0");
+
+/// Seconds a signed timestamp may be ahead of the ledger
+pub const MAX_CLOCK_SKEW: u64 = 60;
+
 /// -------------------------
 /// Storage Keys
 /// -------------------------
@@ -22,6 +33,10 @@ pub enum DataKey {
     AclContract,
     Risk(Address),
     ContractState,
+    /// Legacy per-nonce replay key, see `purge_consumed_nonces`
+    ConsumedNonce(u64),
+    /// Highest nonce accepted from an agent by `verify_and_consume`
+    MaxSeenNonce(Address),
 }
 
 /// -------------------------
@@ -40,6 +55,7 @@ pub enum RiskLevel {
 /// Signed Payload
 /// -------------------------
 #[contracttype]
+#[derive(Clone)]
 pub struct RiskAttestation {
     pub agent: Address,
     pub risk: RiskLevel,
@@ -72,12 +88,34 @@ impl StateMachine<RiskEvalState> for RiskEvaluationContract {
 impl RiskEvaluationContract {
     /// Initialize contract with AI bridge public key and ACL contract
     pub fn init(env: Env, bridge_pubkey: BytesN<32>, acl_contract: Address) -> Result<(), StateError> {
+        Self::init_with_scheme(env, SignatureScheme::Ed25519, bridge_pubkey.into(), acl_contract)
+    }
+
+    /// `init` for a bridge that signs with `scheme`: `bridge_key` is a
+    /// 32-byte Ed25519 key or a 65-byte uncompressed secp256k1 key. Every
+    /// bridge signature is then verified with that scheme. The state's
+    /// `bridge_pubkey` holds the Ed25519 key itself, or the SHA-256 of a
+    /// secp256k1 key.
+    pub fn init_with_scheme(
+        env: Env,
+        scheme: SignatureScheme,
+        bridge_key: Bytes,
+        acl_contract: Address,
+    ) -> Result<(), StateError> {
         // Ensure contract is uninitialized
         let current_state = Self::get_state(&env);
         if !current_state.is_uninitialized() {
             return Err(StateError::AlreadyInitialized);
         }
 
+        Self::bridge_auth(&env)
+            .register_key(&env, scheme, &bridge_key)
+            .map_err(|_| StateError::InvalidConfiguration)?;
+        let bridge_pubkey: BytesN<32> = match scheme {
+            SignatureScheme::Ed25519 => bridge_key.try_into().map_err(|_| StateError::InvalidConfiguration)?,
+            SignatureScheme::Secp256k1 => env.crypto().sha256(&bridge_key).into(),
+        };
+
         // Transition to Active state
         let initial_state = State::Active(RiskEvalState {
             bridge_pubkey: bridge_pubkey.clone(),
@@ -127,14 +165,13 @@ impl RiskEvaluationContract {
             return Err(CryptoError::InvalidSignature);
         }
 
-        // Verify signature
-        let valid = env.crypto().ed25519_verify(
-            &state_data.bridge_pubkey,
-            &payload,
-            &signature,
-        );
+        // The signed payload must be the canonical encoding of the attestation
+        if payload != Self::attestation_message(&env, &attestation) {
+            return Err(CryptoError::HashMismatch);
+        }
 
-        if !valid {
+        // Verify signature
+        if !Self::verify_bridge(&env, state_data, &payload, &signature) {
             return Err(CryptoError::SignatureVerificationFailed);
         }
         
@@ -143,7 +180,7 @@ impl RiskEvaluationContract {
 
         // Optional replay protection (basic)
         let now = env.ledger().timestamp();
-        if attestation.timestamp > now + 60 {
+        if attestation.timestamp > now + MAX_CLOCK_SKEW {
             return Err(CryptoError::InvalidSignature);
         }
 
@@ -191,6 +228,17 @@ impl RiskEvaluationContract {
         Ok(())
     }
 
+    /// Seconds guarded writes keep succeeding after `pause`, 0 to pause
+    /// immediately. Only while active.
+    pub fn set_pause_grace(env: Env, admin: Address, secs: u64) -> Result<(), CommonError> {
+        Self::require_admin_action(&env, &admin, "set_pause_grace", vec![&env, secs.into_val(&env)])?;
+        if !Self::get_state(&env).is_active() {
+            return Err(CommonError::InvalidState);
+        }
+        state_machine::set_pause_grace(&env, secs);
+        Ok(())
+    }
+
     /// Resume the contract from paused state
     pub fn resume(env: Env) -> Result<(), StateError> {
         let state = Self::get_state(&env);
@@ -211,6 +259,19 @@ impl RiskEvaluationContract {
         Self::get_state(&env)
     }
 
+    pub fn version(_env: Env) -> u32 {
+        CONTRACT_VERSION
+    }
+
+    pub fn build_id(_env: Env) -> Symbol {
+        BUILD_ID
+    }
+
+    /// The newest `limit` admin actions, oldest first
+    pub fn get_admin_log(env: Env, limit: u32) -> Vec<AdminAction> {
+        admin_log::get_admin_log(&env, limit)
+    }
+
     /// Get total evaluations count
     pub fn get_total_evaluations(env: Env) -> Result<u64, StateError> {
         state_guard!(Self, &env, initialized);
@@ -220,17 +281,203 @@ impl RiskEvaluationContract {
         Ok(state_data.total_evaluations)
     }
     
-    /// Get the authorization instance for this contract
+    /// `admin` must authorise the call and hold `risk/admin` in the ACL
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), CommonError> {
+        admin.require_auth();
+        let acl: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::AclContract)
+            .ok_or(CommonError::NotInitialized)?;
+        if !common_utils::check_permission(
+            env.clone(),
+            acl,
+            admin.clone(),
+            symbol_short!("risk"),
+            symbol_short!("admin"),
+        ) {
+            return Err(CommonError::NotAuthorized);
+        }
+        Ok(())
+    }
+
+    /// `require_admin`, then record `action` and its parameters in the
+    /// admin action log
+    fn require_admin_action(
+        env: &Env,
+        admin: &Address,
+        action: &str,
+        params: Vec<Val>,
+    ) -> Result<(), CommonError> {
+        Self::require_admin(env, admin)?;
+        admin_log::record_admin_action(env, Symbol::new(env, action), admin, &params);
+        Ok(())
+    }
+
+    /// Get the authorization instance for this contract.
+    ///
+    /// The permission cache is keyed by `(address, permission)` only, never by
+    /// payload, so it caches role-style checks and not signature results:
+    /// every signature is verified in full. Single-use signed payloads should
+    /// go through `verify_and_consume`, which rejects stale nonces before
+    /// doing any verification work.
     fn get_auth(env: &Env) -> CachedAuth<SignatureBasedAuth> {
-        let sig_auth = auth!(SignatureBased, Symbol::new(env, "bridge_pubkey"));
+        let sig_auth = Self::bridge_auth(env);
         let cache = PermissionCache::new(300, Symbol::new(env, "auth_cache"));
         cached_auth!(sig_auth, cache)
     }
     
-    /// Verify a signature directly (utility method)
+    /// The bridge key and the scheme it was registered with by `init`
+    fn bridge_auth(env: &Env) -> SignatureBasedAuth {
+        auth!(SignatureBased, Symbol::new(env, "bridge_pubkey"))
+    }
+
+    /// Check a bridge signature over `message` with the scheme chosen at
+    /// init. An invalid Ed25519 signature traps; a secp256k1 one, high-S
+    /// included, returns `false`. Contracts initialized before schemes
+    /// existed verify with the Ed25519 key kept in their state.
+    fn verify_bridge(env: &Env, state_data: &RiskEvalState, message: &Bytes, signature: &BytesN<64>) -> bool {
+        let auth = Self::bridge_auth(env);
+        match auth.get_scheme(env) {
+            SignatureScheme::Ed25519 => {
+                env.crypto().ed25519_verify(&state_data.bridge_pubkey, message, signature);
+                true
+            }
+            SignatureScheme::Secp256k1 => auth.verify_signature(env, message, signature).unwrap_or(false),
+        }
+    }
+
+    /// Canonical message the AI bridge must sign for an attestation
+    pub fn create_message(env: Env, attestation: RiskAttestation) -> Bytes {
+        Self::attestation_message(&env, &attestation)
+    }
+
+    fn attestation_message(env: &Env, attestation: &RiskAttestation) -> Bytes {
+        encode_message(
+            env,
+            &[
+                MessageField::Address(attestation.agent.clone()),
+                MessageField::U32(attestation.risk as u32),
+                MessageField::U64(attestation.timestamp),
+            ],
+        )
+        .unwrap_or_else(|err| panic_with_error!(env, err))
+    }
+
+    /// Canonical message the AI bridge must sign for `verify_and_consume`
+    pub fn create_nonce_message(env: Env, agent: Address, payload: Bytes, nonce: u64) -> Bytes {
+        Self::nonce_message(&env, &agent, &payload, nonce)
+    }
+
+    fn nonce_message(env: &Env, agent: &Address, payload: &Bytes, nonce: u64) -> Bytes {
+        encode_message(
+            env,
+            &[
+                MessageField::Address(agent.clone()),
+                MessageField::Bytes(payload.clone()),
+                MessageField::U64(nonce),
+            ],
+        )
+        .unwrap_or_else(|err| panic_with_error!(env, err))
+    }
+
+    /// Verify a bridge signature over
+    /// `create_nonce_message(agent, payload, nonce)` and advance `agent`'s
+    /// nonce mark to `nonce`. Nonces must increase per agent: anything at or
+    /// below the mark is rejected with `CryptoError::InvalidNonce` without
+    /// re-verifying, even when the signature itself is valid. Only the mark
+    /// is stored, so storage grows with agents, not submissions.
+    pub fn verify_and_consume(
+        env: Env,
+        agent: Address,
+        payload: Bytes,
+        signature: BytesN<64>,
+        nonce: u64,
+    ) -> Result<(), CryptoError> {
+        let state = Self::get_state(&env);
+        let state_data = state.get_data().ok_or(CryptoError::InvalidPublicKey)?;
+
+        if Self::is_nonce_consumed(env.clone(), agent.clone(), nonce) {
+            return Err(CryptoError::InvalidNonce);
+        }
+
+        // Traps on an invalid Ed25519 signature
+        let message = Self::nonce_message(&env, &agent, &payload, nonce);
+        if !Self::verify_bridge(&env, state_data, &message, &signature) {
+            return Err(CryptoError::SignatureVerificationFailed);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::MaxSeenNonce(agent.clone()), &nonce);
+        env.events().publish((symbol_short!("nonce_use"), agent), nonce);
+        Ok(())
+    }
+
+    /// Highest nonce accepted from `agent`, if any
+    pub fn get_max_seen_nonce(env: Env, agent: Address) -> Option<u64> {
+        env.storage().persistent().get(&DataKey::MaxSeenNonce(agent))
+    }
+
+    /// Whether `verify_and_consume` would reject `nonce` for `agent`
+    pub fn is_nonce_consumed(env: Env, agent: Address, nonce: u64) -> bool {
+        matches!(Self::get_max_seen_nonce(env, agent), Some(max) if nonce <= max)
+    }
+
+    /// Remove per-nonce keys left by the earlier replay protection and
+    /// return how many existed. Signatures made for those nonces do not
+    /// name an agent, so they can no longer verify and the keys only hold
+    /// storage; anyone may clear them.
+    pub fn purge_consumed_nonces(env: Env, nonces: Vec<u64>) -> u32 {
+        let mut removed = 0;
+        for nonce in nonces.iter() {
+            let key = DataKey::ConsumedNonce(nonce);
+            if env.storage().persistent().has(&key) {
+                env.storage().persistent().remove(&key);
+                removed += 1;
+            }
+        }
+        removed
+    }
+
+    /// Verify a bridge signature over `payload` directly (utility method)
     pub fn verify_signature_direct(env: Env, payload: Bytes, signature: BytesN<64>) -> Result<bool, CryptoError> {
-        let auth = Self::get_auth(&env);
-        auth.verify_signature(&env, &payload, &signature)
+        let state = Self::get_state(&env);
+        let state_data = state.get_data().ok_or(CryptoError::InvalidPublicKey)?;
+        Ok(Self::verify_bridge(&env, state_data, &payload, &signature))
+    }
+
+    /// Canonical message the AI bridge must sign for `verify_signature_fresh`
+    pub fn create_fresh_message(env: Env, payload: Bytes, signed_at: u64) -> Bytes {
+        Self::fresh_message(&env, &payload, signed_at)
+    }
+
+    fn fresh_message(env: &Env, payload: &Bytes, signed_at: u64) -> Bytes {
+        encode_message(env, &[MessageField::Bytes(payload.clone()), MessageField::U64(signed_at)])
+            .unwrap_or_else(|err| panic_with_error!(env, err))
+    }
+
+    /// `verify_signature_direct` bound to time: the signature must be over
+    /// `create_fresh_message(payload, signed_at)`, so signers have to include
+    /// `signed_at` in what they sign, and `signed_at` must be at most
+    /// `max_age` seconds old. Dates more than `MAX_CLOCK_SKEW` ahead of the
+    /// ledger are rejected too. Both fail with `CryptoError::SignatureExpired`
+    /// before the signature is checked.
+    pub fn verify_signature_fresh(
+        env: Env,
+        payload: Bytes,
+        signature: BytesN<64>,
+        signed_at: u64,
+        max_age: u64,
+    ) -> Result<bool, CryptoError> {
+        let now = env.ledger().timestamp();
+        if signed_at > now.saturating_add(MAX_CLOCK_SKEW) || now.saturating_sub(signed_at) > max_age {
+            return Err(CryptoError::SignatureExpired);
+        }
+        let message = Self::fresh_message(&env, &payload, signed_at);
+        Self::verify_signature_direct(env, message, signature)
     }
 }
 
+#[cfg(test)]
+mod test;
@@ -0,0 +1,256 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::testutils::{ed25519::Sign, Address as _, Ledger};
+use soroban_sdk::Address;
+
+fn sign(signer: &impl Sign<[u8], Signature = [u8; 64]>, message: &Bytes) -> [u8; 64] {
+    let mut raw = [0u8; 256];
+    let raw = &mut raw[..message.len() as usize];
+    message.copy_into_slice(raw);
+    signer.sign(&*raw)
+}
+
+#[test]
+fn test_verify_and_consume_rejects_replayed_nonce() {
+    let env = Env::default();
+    let contract_id = env.register(RiskEvaluationContract, ());
+    let client = RiskEvaluationContractClient::new(&env, &contract_id);
+
+    let signer = soroban_sdk::testutils::ed25519::generate(&env);
+    let acl = Address::generate(&env);
+    client.init(&BytesN::from_array(&env, signer.public_key()), &acl);
+
+    let agent = Address::generate(&env);
+    let payload = Bytes::from_slice(&env, b"risk:agent_1:high");
+    let message = client.create_nonce_message(&agent, &payload, &7);
+    let signature = BytesN::from_array(&env, &sign(&signer, &message));
+
+    assert!(!client.is_nonce_consumed(&agent, &7));
+    client.verify_and_consume(&agent, &payload, &signature, &7);
+    assert!(client.is_nonce_consumed(&agent, &7));
+
+    // Same valid signature, same nonce
+    assert_eq!(
+        client.try_verify_and_consume(&agent, &payload, &signature, &7),
+        Err(Ok(CryptoError::InvalidNonce))
+    );
+
+    // A fresh nonce needs its own signature
+    let message = client.create_nonce_message(&agent, &payload, &8);
+    let signature = BytesN::from_array(&env, &sign(&signer, &message));
+    client.verify_and_consume(&agent, &payload, &signature, &8);
+}
+
+#[test]
+fn test_nonce_high_water_mark_is_per_agent_and_bounded() {
+    let env = Env::default();
+    let contract_id = env.register(RiskEvaluationContract, ());
+    let client = RiskEvaluationContractClient::new(&env, &contract_id);
+
+    let signer = soroban_sdk::testutils::ed25519::generate(&env);
+    client.init(&BytesN::from_array(&env, signer.public_key()), &Address::generate(&env));
+    let payload = Bytes::from_slice(&env, b"risk:agent_1:low");
+    let consume = |agent: &Address, nonce: u64| {
+        let message = client.create_nonce_message(agent, &payload, &nonce);
+        let signature = BytesN::from_array(&env, &sign(&signer, &message));
+        client.try_verify_and_consume(agent, &payload, &signature, &nonce)
+    };
+
+    let agent = Address::generate(&env);
+    for nonce in [1u64, 2, 10, 11] {
+        assert_eq!(consume(&agent, nonce), Ok(Ok(())));
+    }
+    assert_eq!(client.get_max_seen_nonce(&agent), Some(11));
+    assert_eq!(consume(&agent, 11), Err(Ok(CryptoError::InvalidNonce)));
+    assert_eq!(consume(&agent, 5), Err(Ok(CryptoError::InvalidNonce)));
+
+    // Marks are independent per agent
+    let other = Address::generate(&env);
+    assert_eq!(consume(&other, 5), Ok(Ok(())));
+
+    // One key per agent, none per nonce
+    env.as_contract(&contract_id, || {
+        for nonce in [1u64, 2, 10, 11] {
+            assert!(!env.storage().persistent().has(&DataKey::ConsumedNonce(nonce)));
+        }
+        assert!(env.storage().persistent().has(&DataKey::MaxSeenNonce(agent.clone())));
+    });
+}
+
+#[test]
+fn test_purge_consumed_nonces_removes_legacy_keys() {
+    let env = Env::default();
+    let contract_id = env.register(RiskEvaluationContract, ());
+    let client = RiskEvaluationContractClient::new(&env, &contract_id);
+
+    env.as_contract(&contract_id, || {
+        for nonce in [3u64, 4] {
+            env.storage().persistent().set(&DataKey::ConsumedNonce(nonce), &0u64);
+        }
+    });
+    let nonces = soroban_sdk::vec![&env, 3u64, 4, 5];
+    assert_eq!(client.purge_consumed_nonces(&nonces), 2);
+    assert_eq!(client.purge_consumed_nonces(&nonces), 0);
+}
+
+#[test]
+fn test_version_and_build_id() {
+    let env = Env::default();
+    let contract_id = env.register(RiskEvaluationContract, ());
+    let client = RiskEvaluationContractClient::new(&env, &contract_id);
+
+    assert_eq!(client.version(), CONTRACT_VERSION);
+    assert_eq!(client.build_id(), BUILD_ID);
+}
+
+#[test]
+fn test_verify_signature_fresh_accepts_recent_and_rejects_expired() {
+    let env = Env::default();
+    env.ledger().set_timestamp(10_000);
+    let contract_id = env.register(RiskEvaluationContract, ());
+    let client = RiskEvaluationContractClient::new(&env, &contract_id);
+
+    let signer = soroban_sdk::testutils::ed25519::generate(&env);
+    let acl = Address::generate(&env);
+    client.init(&BytesN::from_array(&env, signer.public_key()), &acl);
+
+    let payload = Bytes::from_slice(&env, b"gate:agent_1");
+    let signed_at = 9_700;
+    let message = client.create_fresh_message(&payload, &signed_at);
+    let signature = BytesN::from_array(&env, &sign(&signer, &message));
+
+    // 300 seconds old
+    assert!(client.verify_signature_fresh(&payload, &signature, &signed_at, &300));
+    assert_eq!(
+        client.try_verify_signature_fresh(&payload, &signature, &signed_at, &299),
+        Err(Ok(CryptoError::SignatureExpired))
+    );
+
+    env.ledger().set_timestamp(20_000);
+    assert_eq!(
+        client.try_verify_signature_fresh(&payload, &signature, &signed_at, &300),
+        Err(Ok(CryptoError::SignatureExpired))
+    );
+
+    // Too far ahead of the ledger
+    let future = 20_000 + MAX_CLOCK_SKEW + 1;
+    let message = client.create_fresh_message(&payload, &future);
+    let signature = BytesN::from_array(&env, &sign(&signer, &message));
+    assert_eq!(
+        client.try_verify_signature_fresh(&payload, &signature, &future, &300),
+        Err(Ok(CryptoError::SignatureExpired))
+    );
+}
+
+mod admin_acl {
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env, Symbol};
+
+    /// Grants `risk/admin` to the address passed to `set_admin` only
+    #[contract]
+    pub struct AdminAcl;
+
+    #[contractimpl]
+    impl AdminAcl {
+        pub fn set_admin(env: Env, admin: Address) {
+            env.storage().instance().set(&symbol_short!("admin"), &admin);
+        }
+
+        pub fn has_permission(env: Env, user: Address, resource: Symbol, action: Symbol) -> bool {
+            let admin: Option<Address> = env.storage().instance().get(&symbol_short!("admin"));
+            admin == Some(user) && resource == symbol_short!("risk") && action == symbol_short!("admin")
+        }
+    }
+}
+
+#[test]
+fn test_set_pause_grace_requires_admin_and_active_state() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(RiskEvaluationContract, ());
+    let client = RiskEvaluationContractClient::new(&env, &contract_id);
+    let acl = env.register(admin_acl::AdminAcl, ());
+    let admin = Address::generate(&env);
+    admin_acl::AdminAclClient::new(&env, &acl).set_admin(&admin);
+    client.init(&BytesN::from_array(&env, &[7u8; 32]), &acl);
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_set_pause_grace(&stranger, &60),
+        Err(Ok(CommonError::NotAuthorized))
+    );
+
+    client.set_pause_grace(&admin, &60);
+    let log = client.get_admin_log(&10);
+    assert_eq!(log.len(), 1);
+    assert_eq!(log.get(0).unwrap().action, Symbol::new(&env, "set_pause_grace"));
+    assert_eq!(log.get(0).unwrap().admin, admin);
+
+    client.pause();
+    assert_eq!(
+        client.try_set_pause_grace(&admin, &0),
+        Err(Ok(CommonError::InvalidState))
+    );
+}
+
+/// Uncompressed secp256k1 key that signed `SECP_MESSAGE` as `SECP_SIGNATURE`
+const SECP_PUBLIC_KEY: [u8; 65] = [
+    0x04, 0x89, 0x51, 0x26, 0x62, 0xb5, 0x90, 0x2e, 0xb4, 0x66, 0x3d,
+    0xbf, 0x42, 0x8c, 0x62, 0xf4, 0x5c, 0xe5, 0x2c, 0xd3, 0x17, 0x24,
+    0xc9, 0x79, 0x20, 0x56, 0xb9, 0x62, 0xd8, 0x6c, 0x3e, 0x25, 0x1a,
+    0x22, 0x55, 0x1d, 0x77, 0x2e, 0x86, 0x10, 0x1f, 0x38, 0x57, 0x14,
+    0xeb, 0x74, 0xab, 0x27, 0x05, 0x7a, 0x63, 0x92, 0xfe, 0x9e, 0x05,
+    0xb9, 0x2d, 0x98, 0x77, 0xec, 0xf3, 0x89, 0x1f, 0xb3, 0x72,
+];
+
+const SECP_SIGNATURE: [u8; 64] = [
+    0xff, 0x6f, 0x47, 0x35, 0xad, 0x58, 0x53, 0x90, 0x9b, 0x4d, 0x90,
+    0xb2, 0x77, 0x98, 0x57, 0x75, 0x3d, 0xc5, 0x98, 0xe6, 0xc3, 0xad,
+    0x6c, 0xee, 0x55, 0xb6, 0x04, 0xb7, 0x22, 0x2f, 0x48, 0xd4, 0x2b,
+    0xdd, 0xe5, 0x1e, 0x81, 0x8f, 0x31, 0x1c, 0x87, 0x4f, 0xd8, 0x2a,
+    0x38, 0xed, 0x03, 0x9f, 0x77, 0x23, 0xc8, 0x97, 0xbb, 0x38, 0x13,
+    0x2c, 0x1c, 0x74, 0x20, 0xde, 0xbc, 0xb9, 0xf8, 0x53,
+];
+
+/// `SECP_SIGNATURE` with `s` replaced by `n - s`, its malleated twin
+const SECP_HIGH_S_SIGNATURE: [u8; 64] = [
+    0xff, 0x6f, 0x47, 0x35, 0xad, 0x58, 0x53, 0x90, 0x9b, 0x4d, 0x90,
+    0xb2, 0x77, 0x98, 0x57, 0x75, 0x3d, 0xc5, 0x98, 0xe6, 0xc3, 0xad,
+    0x6c, 0xee, 0x55, 0xb6, 0x04, 0xb7, 0x22, 0x2f, 0x48, 0xd4, 0xd4,
+    0x22, 0x1a, 0xe1, 0x7e, 0x70, 0xce, 0xe3, 0x78, 0xb0, 0x27, 0xd5,
+    0xc7, 0x12, 0xfc, 0x5f, 0x43, 0x8b, 0x14, 0x4e, 0xf4, 0x10, 0x8d,
+    0x0f, 0xa3, 0x5e, 0x3d, 0xae, 0x13, 0x7c, 0x48, 0xee,
+];
+
+const SECP_MESSAGE: &[u8] = b"luminarytrade secp256k1 vector";
+
+#[test]
+fn test_secp256k1_bridge_verifies_low_s_only() {
+    let env = Env::default();
+    let contract_id = env.register(RiskEvaluationContract, ());
+    let client = RiskEvaluationContractClient::new(&env, &contract_id);
+    let key = Bytes::from_array(&env, &SECP_PUBLIC_KEY);
+
+    // A secp256k1 key is not an Ed25519 key
+    assert!(client
+        .try_init_with_scheme(&SignatureScheme::Ed25519, &key, &Address::generate(&env))
+        .is_err());
+    client.init_with_scheme(&SignatureScheme::Secp256k1, &key, &Address::generate(&env));
+    let fingerprint: BytesN<32> = env.crypto().sha256(&key).into();
+    assert_eq!(client.get_contract_state().get_data().unwrap().bridge_pubkey, fingerprint);
+
+    let payload = Bytes::from_slice(&env, SECP_MESSAGE);
+    assert!(client.verify_signature_direct(&payload, &BytesN::from_array(&env, &SECP_SIGNATURE)));
+    let other = Bytes::from_slice(&env, b"some other payload");
+    assert!(!client.verify_signature_direct(&other, &BytesN::from_array(&env, &SECP_SIGNATURE)));
+
+    let twin = SECP_HIGH_S_SIGNATURE;
+    assert!(!client.verify_signature_direct(&payload, &BytesN::from_array(&env, &twin)));
+
+    let agent = Address::generate(&env);
+    assert_eq!(
+        client.try_verify_and_consume(&agent, &payload, &BytesN::from_array(&env, &twin), &1),
+        Err(Ok(CryptoError::SignatureVerificationFailed))
+    );
+    assert!(!client.is_nonce_consumed(&agent, &1));
+}